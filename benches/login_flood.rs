@@ -0,0 +1,207 @@
+// Carga sintetica: varios clientes disparam logins (Argon2 com senha errada) enquanto uma sonda
+// mede a latencia de `/health`. Compara o hash feito dentro do handler com o `HashingPool`.
+//
+//     cargo bench --bench login_flood
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::runtime::Runtime;
+use webrust::shared::security::hashing_pool::{HashingPool, HashingPoolSettings};
+use webrust::shared::security::password::{self, Argon2Settings};
+use webrust::telemetry::AppMetrics;
+
+// Poucas threads no servidor deixam o efeito visivel em qualquer maquina.
+const SERVER_WORKERS: usize = 2;
+const FLOOD_CLIENTS: usize = 32;
+const FLOOD_DURATION: Duration = Duration::from_secs(3);
+const PROBE_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Inline,
+    Pooled,
+}
+
+impl Mode {
+    fn label(self) -> &'static str {
+        match self {
+            Mode::Inline => "inline",
+            Mode::Pooled => "pool",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct BenchState {
+    mode: Mode,
+    stored_hash: Arc<String>,
+    hasher: HashingPool,
+}
+
+fn main() {
+    let stored_hash = Arc::new(
+        password::hash_password(&Argon2Settings::default(), "correct horse battery staple")
+            .expect("hashing should work"),
+    );
+    let client = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .enable_all()
+        .build()
+        .expect("client runtime");
+
+    println!(
+        "{:<8} {:>8} {:>6} {:>12} {:>12} {:>12}",
+        "mode", "logins", "shed", "health p50", "health p99", "health max"
+    );
+    for mode in [Mode::Inline, Mode::Pooled] {
+        let state = BenchState {
+            mode,
+            stored_hash: stored_hash.clone(),
+            hasher: HashingPool::new(
+                HashingPoolSettings {
+                    workers: SERVER_WORKERS,
+                    queue_capacity: 16,
+                    retry_after_seconds: 1,
+                    argon2: Argon2Settings::default(),
+                },
+                AppMetrics::new(),
+            ),
+        };
+        let (server, addr) = start_server(state);
+        let report = client.block_on(flood(addr));
+        report.print(mode);
+        server.shutdown_background();
+    }
+}
+
+fn start_server(state: BenchState) -> (Runtime, SocketAddr) {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(SERVER_WORKERS)
+        .enable_all()
+        .build()
+        .expect("server runtime");
+    let listener = runtime
+        .block_on(TcpListener::bind("127.0.0.1:0"))
+        .expect("bind a local port");
+    let addr = listener.local_addr().expect("local address");
+    let app = Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .route("/auth/login", post(login))
+        .with_state(state);
+    runtime.spawn(async move { axum::serve(listener, app).await });
+    (runtime, addr)
+}
+
+async fn login(State(state): State<BenchState>) -> Response {
+    let outcome = match state.mode {
+        Mode::Inline => Ok(password::verify_password(
+            &Argon2Settings::default(),
+            &state.stored_hash,
+            "wrong password",
+        )),
+        Mode::Pooled => {
+            state
+                .hasher
+                .verify_password(&state.stored_hash, "wrong password")
+                .await
+        }
+    };
+    match outcome {
+        Ok(_) => StatusCode::UNAUTHORIZED.into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+struct Report {
+    logins: usize,
+    shed: usize,
+    health: Vec<Duration>,
+}
+
+impl Report {
+    fn print(mut self, mode: Mode) {
+        self.health.sort();
+        let percentile = |p: f64| {
+            let index = ((self.health.len() as f64 - 1.0) * p).round() as usize;
+            self.health[index]
+        };
+        println!(
+            "{:<8} {:>8} {:>6} {:>12?} {:>12?} {:>12?}",
+            mode.label(),
+            self.logins,
+            self.shed,
+            percentile(0.5),
+            percentile(0.99),
+            percentile(1.0)
+        );
+    }
+}
+
+async fn flood(addr: SocketAddr) -> Report {
+    let deadline = Instant::now() + FLOOD_DURATION;
+    let logins = Arc::new(AtomicUsize::new(0));
+    let shed = Arc::new(AtomicUsize::new(0));
+
+    let mut clients = Vec::with_capacity(FLOOD_CLIENTS);
+    for _ in 0..FLOOD_CLIENTS {
+        let logins = logins.clone();
+        let shed = shed.clone();
+        clients.push(tokio::spawn(async move {
+            while Instant::now() < deadline {
+                match request(addr, "POST", "/auth/login").await {
+                    503 => {
+                        shed.fetch_add(1, Ordering::Relaxed);
+                        // Um cliente bem comportado respeitaria o Retry-After; aqui so aliviamos.
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                    }
+                    _ => {
+                        logins.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }));
+    }
+
+    let mut health = Vec::new();
+    while Instant::now() < deadline {
+        let started = Instant::now();
+        assert_eq!(request(addr, "GET", "/health").await, 200);
+        health.push(started.elapsed());
+        tokio::time::sleep(PROBE_INTERVAL).await;
+    }
+    for client in clients {
+        client.await.expect("flood client");
+    }
+
+    Report {
+        logins: logins.load(Ordering::Relaxed),
+        shed: shed.load(Ordering::Relaxed),
+        health,
+    }
+}
+
+// HTTP/1.1 minimo, uma conexao por requisicao; devolve o status.
+async fn request(addr: SocketAddr, method: &str, path: &str) -> u16 {
+    let mut stream = TcpStream::connect(addr).await.expect("connect");
+    let head = format!(
+        "{method} {path} HTTP/1.1\r\nHost: {addr}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+    );
+    stream.write_all(head.as_bytes()).await.expect("write");
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await.expect("read");
+    let response = String::from_utf8_lossy(&response);
+    response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .expect("status line")
+}
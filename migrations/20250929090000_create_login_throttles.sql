@@ -0,0 +1,11 @@
+-- Contadores de falhas de login por conta e por IP de origem. A chave e um hash SHA-256
+-- (`account:<email>` ou `ip:<endereco>`) para nao guardar emails inexistentes em claro.
+CREATE TABLE IF NOT EXISTS login_throttles (
+    key_hash TEXT PRIMARY KEY,
+    failure_count INTEGER NOT NULL,
+    window_started_at TIMESTAMPTZ NOT NULL,
+    last_failure_at TIMESTAMPTZ NOT NULL,
+    locked_until TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS login_throttles_last_failure_at_idx ON login_throttles (last_failure_at);
@@ -0,0 +1,19 @@
+-- Tokens de acesso pessoal (API keys) para clientes nao interativos. O valor `wr_pat_...` e
+-- exibido uma unica vez; persistimos apenas o hash SHA-256 e um prefixo para identificacao.
+CREATE TABLE IF NOT EXISTS personal_access_tokens (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    token_prefix TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS personal_access_tokens_user_id_idx ON personal_access_tokens (user_id);
+-- Nomes identificam o token na listagem: unicos por usuario entre os ainda nao revogados.
+CREATE UNIQUE INDEX IF NOT EXISTS personal_access_tokens_active_name_idx
+    ON personal_access_tokens (user_id, name) WHERE revoked_at IS NULL;
@@ -0,0 +1,54 @@
+-- Papeis definidos em tempo de execucao, cada um com uma lista de permissoes do catalogo fixo
+-- da aplicacao. `admin` e `viewer` substituem o antigo enum e nao podem ser alterados pela API.
+CREATE TABLE IF NOT EXISTS roles (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    permissions TEXT[] NOT NULL DEFAULT '{}',
+    built_in BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO roles (id, name, description, permissions, built_in)
+VALUES
+    (
+        '00000000-0000-4000-8000-000000000001',
+        'admin',
+        'Full access to users and roles',
+        ARRAY['users:read', 'users:write', 'users:delete', 'roles:read', 'roles:write', 'audit:read'],
+        TRUE
+    ),
+    (
+        '00000000-0000-4000-8000-000000000002',
+        'viewer',
+        'Access to the own account only',
+        '{}',
+        TRUE
+    )
+ON CONFLICT (name) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS user_roles (
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    -- RESTRICT: a API recusa remover papeis ainda atribuidos.
+    role_id UUID NOT NULL REFERENCES roles (id) ON DELETE RESTRICT,
+    assigned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, role_id)
+);
+
+CREATE INDEX IF NOT EXISTS user_roles_role_id_idx ON user_roles (role_id);
+
+-- Cada usuario recebe o papel que tinha na coluna `users.role`.
+INSERT INTO user_roles (user_id, role_id)
+SELECT u.id, r.id
+FROM users u
+JOIN roles r ON r.name = u.role
+ON CONFLICT DO NOTHING;
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_role_check;
+ALTER TABLE users DROP COLUMN IF EXISTS role;
+
+-- Politicas de MFA passam a referenciar papeis existentes e somem junto com eles.
+ALTER TABLE mfa_role_policies
+    ADD CONSTRAINT mfa_role_policies_role_fkey
+    FOREIGN KEY (role) REFERENCES roles (name) ON DELETE CASCADE;
@@ -0,0 +1,6 @@
+-- Paginacao por cursor (keyset) em `GET /users`: cada ordenacao suportada usa a coluna ordenada
+-- mais o id como desempate, e o filtro por dominio compara a parte apos o `@` sem caixa.
+CREATE INDEX IF NOT EXISTS users_created_at_id_idx ON users (created_at, id);
+CREATE INDEX IF NOT EXISTS users_name_id_idx ON users (name, id);
+CREATE INDEX IF NOT EXISTS users_email_id_idx ON users (email, id);
+CREATE INDEX IF NOT EXISTS users_email_domain_idx ON users (lower(split_part(email, '@', 2)));
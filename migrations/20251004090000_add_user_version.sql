@@ -0,0 +1,3 @@
+-- Controle de concorrencia otimista: toda alteracao incrementa `version`, exposta como ETag e
+-- conferida atomicamente contra o `If-Match` no UPDATE/DELETE.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1;
@@ -0,0 +1,5 @@
+-- Exclusao logica de usuarios: `deleted_at` preenchido tira o usuario de login e listagens, e
+-- um job expurga definitivamente os registros mais antigos que a retencao configurada.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS users_deleted_at_idx ON users (deleted_at) WHERE deleted_at IS NOT NULL;
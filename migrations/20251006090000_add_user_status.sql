@@ -0,0 +1,7 @@
+-- Ciclo de vida da conta: apenas usuarios `active` autenticam. `status_reason` guarda o motivo
+-- informado pelo administrador na ultima transicao.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'active'
+        CHECK (status IN ('active', 'suspended', 'pending', 'locked')),
+    ADD COLUMN IF NOT EXISTS status_reason TEXT,
+    ADD COLUMN IF NOT EXISTS status_changed_at TIMESTAMPTZ;
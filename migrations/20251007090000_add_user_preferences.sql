@@ -0,0 +1,4 @@
+-- Preferencias de exibicao editaveis pelo proprio usuario em `PATCH /users/me`.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS locale TEXT,
+    ADD COLUMN IF NOT EXISTS timezone TEXT;
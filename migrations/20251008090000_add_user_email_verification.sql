@@ -0,0 +1,5 @@
+-- Confirmacao de posse do email. Contas existentes ficam como nao verificadas: ative
+-- `auth.email_verification.require_verified_login` apenas depois que elas confirmarem.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS email_verified_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS pending_email TEXT;
@@ -0,0 +1,17 @@
+-- Convites: o usuario e criado como `pending` com uma senha aleatoria que ninguem conhece, e so
+-- passa a `active` quando o convidado escolhe a propria senha pelo link. Apenas o hash SHA-256
+-- do token e persistido; reenviar troca o hash e invalida o link anterior.
+CREATE TABLE IF NOT EXISTS user_invitations (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL UNIQUE REFERENCES users (id) ON DELETE CASCADE,
+    email TEXT NOT NULL,
+    invited_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    accepted_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS user_invitations_pending_idx ON user_invitations (created_at)
+    WHERE accepted_at IS NULL AND revoked_at IS NULL;
@@ -0,0 +1,15 @@
+-- Fila de aprovacao do autocadastro: quando `users.registration.mode` e `approval` o usuario
+-- nasce `pending` e ganha uma linha aqui ate um admin aprovar ou rejeitar. Nos demais modos a
+-- conta ja nasce ativa e nada e gravado.
+CREATE TABLE IF NOT EXISTS user_registrations (
+    user_id UUID PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+    email TEXT NOT NULL,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    reviewed_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    approved_at TIMESTAMPTZ,
+    rejected_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS user_registrations_pending_idx ON user_registrations (created_at)
+    WHERE approved_at IS NULL AND rejected_at IS NULL;
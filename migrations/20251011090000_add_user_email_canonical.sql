@@ -0,0 +1,40 @@
+-- Identidade do email sem diferenciar caixa: `email` guarda o endereco como informado e
+-- `email_canonical` a forma calculada por `EmailAddress` (parte local em NFC e minusculas,
+-- dominio em punycode minusculo), que passa a ser a chave unica e de busca.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email_canonical TEXT;
+
+-- Ate aqui os emails aceitos eram apenas ASCII, entao `lower` produz exatamente a forma canonica.
+UPDATE users SET email_canonical = lower(email) WHERE email_canonical IS NULL;
+
+-- Contas que so diferem pela caixa do email precisam ser unificadas manualmente antes de
+-- aplicar esta migracao; falhar aqui e preferivel a escolher uma delas automaticamente.
+DO $$
+DECLARE
+    duplicates TEXT;
+BEGIN
+    SELECT string_agg(email_canonical || ' (' || total || ' accounts)', ', ')
+      INTO duplicates
+      FROM (
+          SELECT email_canonical, COUNT(*) AS total
+            FROM users
+           GROUP BY email_canonical
+          HAVING COUNT(*) > 1
+      ) AS conflicting;
+
+    IF duplicates IS NOT NULL THEN
+        RAISE EXCEPTION 'users with emails that differ only by case: %', duplicates
+            USING HINT = 'merge or rename these accounts, then rerun the migrations';
+    END IF;
+END
+$$;
+
+ALTER TABLE users ALTER COLUMN email_canonical SET NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS users_email_canonical_key ON users (email_canonical);
+
+-- A unicidade antiga diferenciava caixa e fica redundante com o indice acima.
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_email_key;
+
+-- O filtro por dominio de `GET /users` passa a usar a forma canonica.
+DROP INDEX IF EXISTS users_email_domain_idx;
+CREATE INDEX IF NOT EXISTS users_email_canonical_domain_idx
+    ON users (split_part(email_canonical, '@', 2));
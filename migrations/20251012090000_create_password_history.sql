@@ -0,0 +1,15 @@
+-- Hashes das senhas substituidas, para recusar a reutilizacao das ultimas
+-- `auth.password_history.remember`. Apenas as mais recentes sao mantidas por usuario.
+CREATE TABLE IF NOT EXISTS password_history (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    password_hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS password_history_user_id_idx ON password_history (user_id, created_at DESC);
+
+-- Base da validade maxima da senha (`auth.password_history.max_age_days`). Contas existentes
+-- comecam a contar a partir da migracao para nao expirarem todas de uma vez.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS password_changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
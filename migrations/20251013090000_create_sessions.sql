@@ -0,0 +1,24 @@
+-- Inventario de sessoes: um registro por login, com o mesmo id da familia de refresh tokens e
+-- do claim `sid` dos access tokens. `last_seen_at` e atualizado com granularidade de um minuto.
+CREATE TABLE IF NOT EXISTS sessions (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    auth_method TEXT NOT NULL,
+    user_agent TEXT,
+    ip_address TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS sessions_user_id_idx ON sessions (user_id, last_seen_at DESC);
+
+-- Familias de refresh tokens ainda ativas viram sessoes para que ninguem seja deslogado; o
+-- cliente e o metodo de autenticacao originais nao foram guardados.
+INSERT INTO sessions (id, user_id, auth_method, created_at, last_seen_at, expires_at)
+SELECT family_id, user_id, 'password', MIN(created_at), MAX(created_at), MAX(expires_at)
+FROM refresh_tokens
+WHERE revoked_at IS NULL
+GROUP BY family_id, user_id
+ON CONFLICT (id) DO NOTHING;
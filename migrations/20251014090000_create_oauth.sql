@@ -0,0 +1,60 @@
+-- Servidor de autorizacao OAuth 2.0. Clientes confidenciais guardam apenas o hash SHA-256 do
+-- segredo; clientes publicos nao tem segredo e dependem do PKCE.
+CREATE TABLE IF NOT EXISTS oauth_clients (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    client_type TEXT NOT NULL CHECK (client_type IN ('confidential', 'public')),
+    secret_hash TEXT,
+    redirect_uris TEXT[] NOT NULL DEFAULT '{}',
+    grant_types TEXT[] NOT NULL,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    owner_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    disabled_at TIMESTAMPTZ,
+    CHECK ((client_type = 'confidential') = (secret_hash IS NOT NULL))
+);
+
+-- Codigos de autorizacao de uso unico, guardados como hash. `session_id` e o id reservado para a
+-- sessao aberta na troca do codigo.
+CREATE TABLE IF NOT EXISTS oauth_authorization_codes (
+    id UUID PRIMARY KEY,
+    code_hash TEXT NOT NULL UNIQUE,
+    client_id UUID NOT NULL REFERENCES oauth_clients (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    session_id UUID NOT NULL,
+    redirect_uri TEXT NOT NULL,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    code_challenge TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    consumed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS oauth_authorization_codes_expires_at_idx
+    ON oauth_authorization_codes (expires_at);
+
+CREATE TABLE IF NOT EXISTS oauth_consents (
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    client_id UUID NOT NULL REFERENCES oauth_clients (id) ON DELETE CASCADE,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    granted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, client_id)
+);
+
+-- Sessoes abertas por um cliente OAuth guardam o cliente e os escopos concedidos.
+ALTER TABLE sessions
+    ADD COLUMN IF NOT EXISTS oauth_client_id UUID REFERENCES oauth_clients (id) ON DELETE CASCADE,
+    ADD COLUMN IF NOT EXISTS oauth_scopes TEXT[];
+
+CREATE INDEX IF NOT EXISTS sessions_oauth_client_id_idx
+    ON sessions (oauth_client_id) WHERE oauth_client_id IS NOT NULL;
+
+-- O admin embutido recebe as permissoes novas do catalogo.
+UPDATE roles
+SET permissions = ARRAY(
+        SELECT DISTINCT unnest(permissions || ARRAY['clients:read', 'clients:write'])
+        ORDER BY 1
+    ),
+    updated_at = NOW()
+WHERE name = 'admin' AND built_in;
@@ -0,0 +1,7 @@
+-- Usuarios excluidos logicamente deixam de reservar o email: convites revogados e cadastros
+-- rejeitados sao excluidos assim, e o endereco precisa poder ser usado de novo antes do expurgo.
+-- Restaurar um usuario cujo email ja voltou a ser usado e recusado pela aplicacao.
+DROP INDEX IF EXISTS users_email_canonical_key;
+CREATE UNIQUE INDEX IF NOT EXISTS users_email_canonical_active_key
+    ON users (email_canonical)
+    WHERE deleted_at IS NULL;
@@ -0,0 +1,5 @@
+-- Partes locais entre aspas podem conter `@`, entao o dominio e o que vem depois do ultimo `@`
+-- (como em `EmailAddress::domain`), e nao o segundo campo de `split_part`.
+DROP INDEX IF EXISTS users_email_canonical_domain_idx;
+CREATE INDEX IF NOT EXISTS users_email_canonical_domain_idx
+    ON users (substring(email_canonical from '@([^@]*)$'));
@@ -0,0 +1,4 @@
+-- Indica se a autorizacao trouxe o `redirect_uri`, caso em que a troca do codigo precisa
+-- repeti-lo. Codigos ja emitidos mantem a regra anterior, que sempre o exigia.
+ALTER TABLE oauth_authorization_codes
+    ADD COLUMN IF NOT EXISTS redirect_uri_supplied BOOLEAN NOT NULL DEFAULT TRUE;
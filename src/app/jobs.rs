@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::application::services::email_outbox_service::EmailOutboxService;
+use crate::application::services::login_throttle_service::LoginThrottleService;
+use crate::application::services::oauth_service::OAuthService;
+use crate::application::services::password_reset_service::PasswordResetService;
+use crate::application::services::token_revocation_service::TokenRevocationService;
+use crate::application::services::user_service::UserService;
+
+// Sincroniza o cache da lista de revogacao com o Postgres e expurga entradas cujos tokens
+// ja expiraram. Falhas sao apenas logadas; a proxima rodada tenta novamente.
+pub fn spawn_revocation_maintenance(
+    revocations: TokenRevocationService,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match revocations.purge_expired().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "expired token revocations purged"),
+                Err(err) => tracing::warn!(error = %err, "failed to purge token revocations"),
+            }
+
+            if let Err(err) = revocations.sync().await {
+                tracing::warn!(error = %err, "failed to sync token revocation cache");
+            }
+        }
+    })
+}
+
+// Remove contadores de falha de login cuja janela (e eventual bloqueio) ja terminou.
+pub fn spawn_login_throttle_maintenance(
+    throttle: LoginThrottleService,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match throttle.purge_stale().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "stale login throttles purged"),
+                Err(err) => tracing::warn!(error = %err, "failed to purge login throttles"),
+            }
+        }
+    })
+}
+
+// Entrega as mensagens pendentes do outbox. Erros de envio ficam registrados na propria
+// mensagem; aqui so logamos falhas de acesso ao repositorio.
+pub fn spawn_email_outbox_dispatcher(
+    outbox: EmailOutboxService,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match outbox.dispatch_pending().await {
+                Ok(0) => {}
+                Ok(delivered) => tracing::info!(delivered, "outbox emails delivered"),
+                Err(err) => tracing::warn!(error = %err, "failed to dispatch email outbox"),
+            }
+        }
+    })
+}
+
+// Remove tokens de redefinicao de senha expirados ou ja utilizados.
+pub fn spawn_password_reset_maintenance(
+    password_reset: PasswordResetService,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match password_reset.purge_expired().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "password reset tokens purged"),
+                Err(err) => tracing::warn!(error = %err, "failed to purge password reset tokens"),
+            }
+        }
+    })
+}
+
+// Expurga definitivamente usuarios excluidos logicamente ha mais tempo que a retencao.
+pub fn spawn_deleted_user_purge(
+    users: UserService,
+    retention: chrono::Duration,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match users.purge_deleted_users(retention).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "deleted users purged"),
+                Err(err) => tracing::warn!(error = %err, "failed to purge deleted users"),
+            }
+        }
+    })
+}
+
+// Remove codigos de autorizacao OAuth vencidos, usados ou nao.
+pub fn spawn_oauth_code_maintenance(oauth: OAuthService, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match oauth.purge_expired_codes().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "expired authorization codes purged"),
+                Err(err) => tracing::warn!(error = %err, "failed to purge authorization codes"),
+            }
+        }
+    })
+}
@@ -1,7 +1,12 @@
+mod jobs;
 mod rate_limit;
 mod router;
 mod state;
 
-pub use rate_limit::{build_rate_limiter, RateLimiterLayer};
+pub use jobs::{
+    spawn_deleted_user_purge, spawn_email_outbox_dispatcher, spawn_login_throttle_maintenance,
+    spawn_oauth_code_maintenance, spawn_password_reset_maintenance, spawn_revocation_maintenance,
+};
+pub use rate_limit::{build_rate_limiter, build_registration_rate_limiter, RateLimiterLayer};
 pub use router::build_router;
 pub use state::AppState;
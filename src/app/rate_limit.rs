@@ -1,4 +1,5 @@
-﻿use std::sync::Arc;
+﻿use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
 
 use anyhow::anyhow;
 use axum::http::Request;
@@ -8,30 +9,67 @@ use tower_governor::{
     GovernorLayer,
 };
 
-use crate::config::RateLimitConfig;
+use crate::config::{RateLimitConfig, RegistrationRateLimitConfig};
+use crate::presentation::http::client_ip;
 
-// Extrator que trata todas as requisições como pertencentes ao mesmo balde.
-// Trocar por um extrator por IP (PeerIpKeyExtractor) é fácil caso o ambiente forneça IPs confiáveis.
+// Um balde por IP de origem, resolvido da mesma forma que o `ClientIp` usado no login. Um balde
+// global deixaria um unico cliente abusivo esgotar a cota de todos os outros.
+// Requisicoes sem endereco conhecido compartilham o balde de `0.0.0.0`.
 #[derive(Clone, Default)]
-pub struct GlobalKeyExtractor;
+pub struct ClientIpKeyExtractor {
+    trust_forwarded_for: bool,
+}
 
-impl KeyExtractor for GlobalKeyExtractor {
-    type Key = ();
+impl KeyExtractor for ClientIpKeyExtractor {
+    type Key = IpAddr;
 
-    fn extract<T>(&self, _req: &Request<T>) -> Result<Self::Key, GovernorError> {
-        Ok(())
+    fn extract<T>(&self, req: &Request<T>) -> Result<Self::Key, GovernorError> {
+        Ok(
+            client_ip::resolve(req.headers(), req.extensions(), self.trust_forwarded_for)
+                .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+        )
     }
 }
 
-pub type RateLimiterLayer = GovernorLayer<GlobalKeyExtractor, NoOpMiddleware>;
+pub type RateLimiterLayer = GovernorLayer<ClientIpKeyExtractor, NoOpMiddleware>;
 
 // Constrói a camada de rate limit usando os parâmetros definidos em configuração.
-pub fn build_rate_limiter(config: &RateLimitConfig) -> anyhow::Result<RateLimiterLayer> {
+pub fn build_rate_limiter(
+    config: &RateLimitConfig,
+    trust_forwarded_for: bool,
+) -> anyhow::Result<RateLimiterLayer> {
+    governor_layer(
+        config.requests_per_second,
+        config.burst_capacity,
+        trust_forwarded_for,
+    )
+}
+
+// Limite adicional do autocadastro, bem mais restrito que o geral: cada conta criada dispara
+// emails e ocupa um email, entao um unico IP nao pode cria-las em rajada.
+pub fn build_registration_rate_limiter(
+    config: &RegistrationRateLimitConfig,
+    trust_forwarded_for: bool,
+) -> anyhow::Result<RateLimiterLayer> {
+    governor_layer(
+        config.period_seconds,
+        config.burst_capacity,
+        trust_forwarded_for,
+    )
+}
+
+fn governor_layer(
+    period_seconds: u64,
+    burst_capacity: u32,
+    trust_forwarded_for: bool,
+) -> anyhow::Result<RateLimiterLayer> {
     let mut builder = GovernorConfigBuilder::default();
-    let mut builder = builder.key_extractor(GlobalKeyExtractor::default());
+    let mut builder = builder.key_extractor(ClientIpKeyExtractor {
+        trust_forwarded_for,
+    });
 
-    builder.per_second(config.requests_per_second.max(1));
-    builder.burst_size(config.burst_capacity.max(1));
+    builder.per_second(period_seconds.max(1));
+    builder.burst_size(burst_capacity.max(1));
 
     let cfg = builder
         .finish()
@@ -14,13 +14,21 @@ pub fn build_router(
     state: AppState,
     metrics_layer: MetricsLayer,
     rate_limiter_layer: RateLimiterLayer,
+    registration_limiter_layer: RateLimiterLayer,
 ) -> Router {
     let openapi = ApiDoc::openapi();
     let swagger_ui = SwaggerUi::new("/docs").url("/docs/openapi.json", openapi);
 
     Router::new()
         .merge(routes::auth_routes())
+        .merge(routes::registration_routes(registration_limiter_layer))
         .merge(routes::user_routes())
+        .merge(routes::mfa_routes())
+        .merge(routes::role_routes())
+        .merge(routes::token_routes())
+        .merge(routes::session_routes())
+        .merge(routes::invitation_routes())
+        .merge(routes::oauth_routes())
         .route("/health", get(health_check))
         .route("/metrics", get(metrics_handler))
         .merge(swagger_ui)
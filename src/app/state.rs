@@ -1,4 +1,11 @@
 ﻿use crate::application::services::auth_service::AuthService;
+use crate::application::services::invitation_service::InvitationService;
+use crate::application::services::mfa_service::MfaService;
+use crate::application::services::oauth_service::OAuthService;
+use crate::application::services::password_reset_service::PasswordResetService;
+use crate::application::services::personal_access_token_service::PersonalAccessTokenService;
+use crate::application::services::registration_service::RegistrationService;
+use crate::application::services::role_service::RoleService;
 use crate::application::services::user_service::UserService;
 use crate::telemetry::{AppMetrics, AuditLogger, MetricsHandle};
 
@@ -6,25 +13,50 @@ use crate::telemetry::{AppMetrics, AuditLogger, MetricsHandle};
 pub struct AppState {
     user_service: UserService,
     auth_service: AuthService,
+    mfa_service: MfaService,
+    password_reset_service: PasswordResetService,
+    personal_access_token_service: PersonalAccessTokenService,
+    invitation_service: InvitationService,
+    registration_service: RegistrationService,
+    role_service: RoleService,
+    oauth_service: OAuthService,
     metrics_handle: MetricsHandle,
     app_metrics: AppMetrics,
     audit_logger: AuditLogger,
+    trust_forwarded_for: bool,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_service: UserService,
         auth_service: AuthService,
+        mfa_service: MfaService,
+        password_reset_service: PasswordResetService,
+        personal_access_token_service: PersonalAccessTokenService,
+        invitation_service: InvitationService,
+        registration_service: RegistrationService,
+        role_service: RoleService,
+        oauth_service: OAuthService,
         metrics_handle: MetricsHandle,
         app_metrics: AppMetrics,
         audit_logger: AuditLogger,
+        trust_forwarded_for: bool,
     ) -> Self {
         Self {
             user_service,
             auth_service,
+            mfa_service,
+            password_reset_service,
+            personal_access_token_service,
+            invitation_service,
+            registration_service,
+            role_service,
+            oauth_service,
             metrics_handle,
             app_metrics,
             audit_logger,
+            trust_forwarded_for,
         }
     }
 
@@ -36,6 +68,34 @@ impl AppState {
         &self.auth_service
     }
 
+    pub fn mfa_service(&self) -> &MfaService {
+        &self.mfa_service
+    }
+
+    pub fn password_reset_service(&self) -> &PasswordResetService {
+        &self.password_reset_service
+    }
+
+    pub fn personal_access_token_service(&self) -> &PersonalAccessTokenService {
+        &self.personal_access_token_service
+    }
+
+    pub fn invitation_service(&self) -> &InvitationService {
+        &self.invitation_service
+    }
+
+    pub fn registration_service(&self) -> &RegistrationService {
+        &self.registration_service
+    }
+
+    pub fn role_service(&self) -> &RoleService {
+        &self.role_service
+    }
+
+    pub fn oauth_service(&self) -> &OAuthService {
+        &self.oauth_service
+    }
+
     pub fn metrics_handle(&self) -> &MetricsHandle {
         &self.metrics_handle
     }
@@ -47,4 +107,8 @@ impl AppState {
     pub fn audit(&self) -> &AuditLogger {
         &self.audit_logger
     }
+
+    pub fn trust_forwarded_for(&self) -> bool {
+        self.trust_forwarded_for
+    }
 }
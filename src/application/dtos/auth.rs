@@ -11,10 +11,24 @@ pub struct LoginRequestDto {
     pub password: String,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RefreshRequestDto {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct LogoutRequestDto {
+    /// When provided, the refresh token family is revoked together with the access token.
+    pub refresh_token: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, ToSchema)]
 pub struct LoginResponseDto {
     pub access_token: String,
     pub expires_at: DateTime<Utc>,
+    /// Opaque single-use token; exchange it at `POST /auth/refresh` for a new pair.
+    pub refresh_token: String,
+    pub refresh_expires_at: DateTime<Utc>,
     pub user: AuthenticatedUserDto,
 }
 
@@ -22,7 +36,9 @@ pub struct LoginResponseDto {
 pub struct AuthenticatedUserDto {
     pub id: Uuid,
     pub email: String,
-    pub role: String,
+    pub roles: Vec<String>,
+    /// Effective permissions granted by the roles.
+    pub permissions: Vec<String>,
 }
 
 impl From<AuthSession> for LoginResponseDto {
@@ -30,11 +46,48 @@ impl From<AuthSession> for LoginResponseDto {
         Self {
             access_token: session.token,
             expires_at: session.expires_at,
+            refresh_token: session.refresh_token,
+            refresh_expires_at: session.refresh_expires_at,
             user: AuthenticatedUserDto {
                 id: session.user.id,
                 email: session.user.email,
-                role: session.user.role.as_str().to_string(),
+                roles: session
+                    .user
+                    .roles
+                    .iter()
+                    .map(|role| role.as_str().to_string())
+                    .collect(),
+                permissions: session
+                    .user
+                    .permissions
+                    .iter()
+                    .map(|permission| permission.as_str().to_string())
+                    .collect(),
             },
         }
     }
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ForgotPasswordRequestDto {
+    pub email: String,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ForgotPasswordResponseDto {
+    /// Always the same text, whether or not the email belongs to an account.
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResetPasswordRequestDto {
+    /// Single-use token delivered by email.
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerifyEmailRequestDto {
+    /// Signed token from the confirmation link.
+    pub token: String,
+}
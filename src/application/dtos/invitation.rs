@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::invitation::Invitation;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateInvitationDto {
+    #[schema(example = "Grace Hopper")]
+    pub name: String,
+    #[schema(example = "grace@example.com")]
+    pub email: String,
+    #[schema(example = json!(["viewer"]))]
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AcceptInvitationDto {
+    /// The invitee's own password; the inviter never sees it.
+    #[schema(example = "N3wPassw0rd!")]
+    pub password: String,
+}
+
+/// Pending invitation; the link itself is only ever sent to the invitee.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct InvitationDto {
+    pub id: Uuid,
+    /// The invited user, kept in `pending` status until the invitation is accepted.
+    pub user_id: Uuid,
+    pub email: String,
+    pub invited_by: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    /// Expired invitations can still be resent.
+    pub expired: bool,
+}
+
+impl From<Invitation> for InvitationDto {
+    fn from(invitation: Invitation) -> Self {
+        Self {
+            expired: invitation.expires_at <= Utc::now(),
+            id: invitation.id,
+            user_id: invitation.user_id,
+            email: invitation.email,
+            invited_by: invitation.invited_by,
+            expires_at: invitation.expires_at,
+            created_at: invitation.created_at,
+        }
+    }
+}
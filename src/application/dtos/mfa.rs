@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::application::services::auth_service::MfaChallenge;
+use crate::application::services::mfa_service::TotpEnrollment;
+use crate::domain::entities::mfa::MfaPolicy;
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct MfaChallengeResponseDto {
+    /// `mfa_required`, `mfa_enrollment_required` or `password_change_required`.
+    pub status: String,
+    /// Short-lived token; it is not accepted as an access token.
+    pub challenge_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl MfaChallengeResponseDto {
+    pub fn new(status: &str, challenge: MfaChallenge) -> Self {
+        Self {
+            status: status.to_string(),
+            challenge_token: challenge.token,
+            expires_at: challenge.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MfaVerifyRequestDto {
+    pub challenge_token: String,
+    /// Current TOTP code or one unused recovery code.
+    pub code: String,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct TotpEnrollmentResponseDto {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+impl From<TotpEnrollment> for TotpEnrollmentResponseDto {
+    fn from(enrollment: TotpEnrollment) -> Self {
+        Self {
+            secret: enrollment.secret,
+            otpauth_uri: enrollment.otpauth_uri,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TotpConfirmRequestDto {
+    pub code: String,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct RecoveryCodesResponseDto {
+    /// Shown only once; each code can replace a TOTP code a single time.
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MfaPolicyRequestDto {
+    pub required: bool,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct MfaPolicyResponseDto {
+    pub role: String,
+    pub required: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<MfaPolicy> for MfaPolicyResponseDto {
+    fn from(policy: MfaPolicy) -> Self {
+        Self {
+            role: policy.role.as_str().to_string(),
+            required: policy.required,
+            updated_at: policy.updated_at,
+        }
+    }
+}
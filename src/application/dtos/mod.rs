@@ -1,2 +1,9 @@
 ﻿pub mod auth;
+pub mod invitation;
+pub mod mfa;
+pub mod oauth;
+pub mod personal_access_token;
+pub mod registration;
+pub mod role;
+pub mod session;
 pub mod user;
@@ -0,0 +1,218 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::services::oauth_service::{
+    ClientConsent, IssuedOAuthClient, IssuedOAuthTokens,
+};
+use crate::domain::entities::oauth::OAuthClient;
+use crate::domain::entities::permission::{format_scope, Permission};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateOAuthClientDto {
+    pub name: String,
+    /// `confidential` (server-side apps that keep a secret) or `public` (SPA, mobile).
+    #[schema(example = "public")]
+    pub client_type: String,
+    /// Exact URIs allowed in `redirect_uri`: `https`, `http` on loopback only, or a private-use
+    /// scheme such as `com.example.app:/callback`.
+    #[serde(default)]
+    pub redirect_uris: Vec<String>,
+    /// Any of `authorization_code`, `refresh_token` and `client_credentials` (confidential only).
+    pub grant_types: Vec<String>,
+    /// Upper bound of what the client may request, e.g. `users:read`.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct OAuthClientDto {
+    /// The OAuth `client_id`.
+    pub id: Uuid,
+    pub name: String,
+    pub client_type: String,
+    pub redirect_uris: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub scopes: Vec<String>,
+    pub owner_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<OAuthClient> for OAuthClientDto {
+    fn from(client: OAuthClient) -> Self {
+        Self {
+            id: client.id,
+            name: client.name,
+            client_type: client.client_type.as_str().to_string(),
+            redirect_uris: client.redirect_uris,
+            grant_types: client
+                .grant_types
+                .iter()
+                .map(|grant| grant.as_str().to_string())
+                .collect(),
+            scopes: scope_names(&client.scopes),
+            owner_id: client.owner_id,
+            created_at: client.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct CreatedOAuthClientDto {
+    /// Confidential clients only; shown once, authenticate at `POST /oauth/token` with it.
+    pub client_secret: Option<String>,
+    #[serde(flatten)]
+    pub details: OAuthClientDto,
+}
+
+impl From<IssuedOAuthClient> for CreatedOAuthClientDto {
+    fn from(issued: IssuedOAuthClient) -> Self {
+        Self {
+            client_secret: issued.secret,
+            details: issued.client.into(),
+        }
+    }
+}
+
+/// Authorization request (RFC 6749 section 4.1.1 with PKCE, RFC 7636).
+#[derive(Clone, Debug, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuthorizationRequestDto {
+    /// Must be `code`.
+    pub response_type: String,
+    pub client_id: String,
+    /// Optional only when the client has a single registered redirect URI.
+    pub redirect_uri: Option<String>,
+    /// Space separated permissions; defaults to every scope of the client.
+    pub scope: Option<String>,
+    /// Returned untouched in the redirect.
+    pub state: Option<String>,
+    /// BASE64URL(SHA-256(code_verifier)).
+    pub code_challenge: Option<String>,
+    /// Must be `S256`.
+    pub code_challenge_method: Option<String>,
+}
+
+impl AuthorizationRequestDto {
+    /// Parameters present in the request, to carry it across the sign-in and consent pages.
+    pub fn params(&self) -> Vec<(&'static str, &str)> {
+        let optional = [
+            ("redirect_uri", &self.redirect_uri),
+            ("scope", &self.scope),
+            ("state", &self.state),
+            ("code_challenge", &self.code_challenge),
+            ("code_challenge_method", &self.code_challenge_method),
+        ];
+        let mut params = vec![
+            ("response_type", self.response_type.as_str()),
+            ("client_id", self.client_id.as_str()),
+        ];
+        params.extend(
+            optional
+                .into_iter()
+                .filter_map(|(name, value)| value.as_deref().map(|value| (name, value))),
+        );
+        params
+    }
+}
+
+/// Sign-in form of the authorization page, sent as `application/x-www-form-urlencoded`.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct OAuthSignInDto {
+    #[serde(flatten)]
+    pub request: AuthorizationRequestDto,
+    pub email: Option<String>,
+    pub password: Option<String>,
+    /// Returned by the page when the account has TOTP; sent back with `code`.
+    pub challenge_token: Option<String>,
+    pub code: Option<String>,
+}
+
+/// Consent form of the authorization page, sent as `application/x-www-form-urlencoded`.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct AuthorizationDecisionDto {
+    #[serde(flatten)]
+    pub request: AuthorizationRequestDto,
+    /// `approve`; anything else sends `error=access_denied` back to the client.
+    pub decision: String,
+    /// Anti-CSRF token embedded in the consent page.
+    pub csrf_token: String,
+}
+
+/// Token request (RFC 6749 section 4), sent as `application/x-www-form-urlencoded`.
+#[derive(Clone, Debug, Default, Deserialize, ToSchema)]
+pub struct TokenRequestDto {
+    /// `authorization_code`, `refresh_token` or `client_credentials`.
+    pub grant_type: Option<String>,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+    /// Public clients, or confidential clients not using HTTP Basic authentication.
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct TokenResponseDto {
+    pub access_token: String,
+    #[schema(example = "Bearer")]
+    pub token_type: String,
+    /// Seconds until the access token expires.
+    pub expires_in: i64,
+    /// Single use; present when the client may use the `refresh_token` grant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// Scopes granted, space separated.
+    pub scope: String,
+}
+
+impl From<IssuedOAuthTokens> for TokenResponseDto {
+    fn from(issued: IssuedOAuthTokens) -> Self {
+        Self {
+            access_token: issued.access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: (issued.expires_at - Utc::now()).num_seconds().max(0),
+            refresh_token: issued.refresh_token,
+            scope: format_scope(&issued.scopes),
+        }
+    }
+}
+
+/// Error body of the token endpoint (RFC 6749 section 5.2).
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct OAuthErrorDto {
+    #[schema(example = "invalid_grant")]
+    pub error: String,
+    pub error_description: String,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct OAuthConsentDto {
+    pub client_id: Uuid,
+    pub client_name: String,
+    pub scopes: Vec<String>,
+    pub granted_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ClientConsent> for OAuthConsentDto {
+    fn from(entry: ClientConsent) -> Self {
+        Self {
+            client_id: entry.client.id,
+            client_name: entry.client.name,
+            scopes: scope_names(&entry.consent.scopes),
+            granted_at: entry.consent.granted_at,
+            updated_at: entry.consent.updated_at,
+        }
+    }
+}
+
+fn scope_names(scopes: &[Permission]) -> Vec<String> {
+    scopes
+        .iter()
+        .map(|scope| scope.as_str().to_string())
+        .collect()
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::services::personal_access_token_service::IssuedPersonalAccessToken;
+use crate::domain::entities::personal_access_token::PersonalAccessToken;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePersonalAccessTokenDto {
+    pub name: String,
+    /// Permissions the token may use, e.g. `users:read`. The owner's roles still apply.
+    pub scopes: Vec<String>,
+    /// Defaults to the server configured lifetime when omitted.
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct PersonalAccessTokenDto {
+    pub id: Uuid,
+    pub name: String,
+    /// First characters of the token, to recognise it without exposing the secret.
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<PersonalAccessToken> for PersonalAccessTokenDto {
+    fn from(token: PersonalAccessToken) -> Self {
+        Self {
+            id: token.id,
+            name: token.name,
+            token_prefix: token.token_prefix,
+            scopes: token
+                .scopes
+                .iter()
+                .map(|scope| scope.as_str().to_string())
+                .collect(),
+            expires_at: token.expires_at,
+            created_at: token.created_at,
+            last_used_at: token.last_used_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct CreatedPersonalAccessTokenDto {
+    /// Shown only once; send it as `Authorization: Bearer <token>` or `X-API-Key: <token>`.
+    pub token: String,
+    #[serde(flatten)]
+    pub details: PersonalAccessTokenDto,
+}
+
+impl From<IssuedPersonalAccessToken> for CreatedPersonalAccessTokenDto {
+    fn from(issued: IssuedPersonalAccessToken) -> Self {
+        Self {
+            token: issued.token,
+            details: issued.record.into(),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::registration::Registration;
+
+/// Self-service signup; the account always gets the `viewer` role.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RegisterRequestDto {
+    #[schema(example = "Ada Lovelace")]
+    pub name: String,
+    #[schema(example = "ada@example.com")]
+    pub email: String,
+    #[schema(example = "Sup3rSecure!")]
+    pub password: String,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct RegisterResponseDto {
+    /// Always the same text, whether or not the email already belongs to an account.
+    pub message: String,
+}
+
+/// Signup waiting for an administrator to approve or reject it.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct RegistrationDto {
+    /// The registered user, kept in `pending` status until a decision is made.
+    pub user_id: Uuid,
+    pub email: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Registration> for RegistrationDto {
+    fn from(registration: Registration) -> Self {
+        Self {
+            user_id: registration.user_id,
+            email: registration.email,
+            name: registration.name,
+            created_at: registration.created_at,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::role::Role;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateRoleDto {
+    /// Lowercase identifier, e.g. `support-agent`. It cannot be changed later.
+    #[schema(example = "support-agent")]
+    pub name: String,
+    #[schema(example = "Reads accounts and unlocks users")]
+    pub description: Option<String>,
+    /// Permissions from `GET /permissions`.
+    #[schema(example = json!(["users:read", "users:write"]))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRoleDto {
+    pub description: Option<String>,
+    /// Replaces the full permission list when present.
+    pub permissions: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct RoleResponseDto {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+    /// Built-in roles (`admin`, `viewer`) cannot be changed or deleted.
+    pub built_in: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Role> for RoleResponseDto {
+    fn from(role: Role) -> Self {
+        Self {
+            id: role.id,
+            name: role.name.as_str().to_string(),
+            description: role.description,
+            permissions: role
+                .permissions
+                .iter()
+                .map(|permission| permission.as_str().to_string())
+                .collect(),
+            built_in: role.built_in,
+            created_at: role.created_at,
+            updated_at: role.updated_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct PermissionDto {
+    #[schema(example = "users:read")]
+    pub name: String,
+    pub description: String,
+}
+
+impl From<Permission> for PermissionDto {
+    fn from(permission: Permission) -> Self {
+        Self {
+            name: permission.as_str().to_string(),
+            description: permission.description().to_string(),
+        }
+    }
+}
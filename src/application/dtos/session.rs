@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::session::Session;
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct SessionDto {
+    pub id: Uuid,
+    /// How the session was opened: `password` or `password+totp`.
+    #[schema(example = "password")]
+    pub auth_method: String,
+    /// `User-Agent` sent with the login, when present.
+    pub user_agent: Option<String>,
+    /// Address the login came from, when known.
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Last request made with the session, updated at most once per minute.
+    pub last_seen_at: DateTime<Utc>,
+    /// When the session ends unless its refresh token is used again.
+    pub expires_at: DateTime<Utc>,
+    /// Whether this is the session of the token making the request.
+    pub current: bool,
+}
+
+impl SessionDto {
+    pub fn new(session: Session, current_session: Option<Uuid>) -> Self {
+        Self {
+            current: current_session == Some(session.id),
+            id: session.id,
+            auth_method: session.auth_method.as_str().to_string(),
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
@@ -1,9 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use crate::domain::entities::user::User;
+use crate::domain::entities::user::{User, UserPreferences};
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateUserDto {
@@ -13,8 +13,9 @@ pub struct CreateUserDto {
     pub email: String,
     #[schema(example = "Sup3rSecure!")]
     pub password: String,
-    #[schema(example = "admin")]
-    pub role: String,
+    /// Names of existing roles; at least one is required.
+    #[schema(example = json!(["viewer"]))]
+    pub roles: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -25,8 +26,66 @@ pub struct UpdateUserDto {
     pub email: Option<String>,
     #[schema(example = "N3wPassw0rd!")]
     pub password: Option<String>,
-    #[schema(example = "viewer")]
+    /// Replaces all assigned roles when present.
+    #[schema(example = json!(["viewer"]))]
+    pub roles: Option<Vec<String>>,
+}
+
+/// Fields a user may change on their own account; email and roles stay admin-only.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateProfileDto {
+    #[schema(example = "Grace Hopper")]
+    pub name: Option<String>,
+    /// BCP 47 tag such as `en` or `pt-BR`; an empty string clears it.
+    #[schema(example = "pt-BR")]
+    pub locale: Option<String>,
+    /// IANA time zone such as `America/Sao_Paulo`; an empty string clears it.
+    #[schema(example = "America/Sao_Paulo")]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangePasswordDto {
+    pub current_password: String,
+    #[schema(example = "N3wPassw0rd!")]
+    pub new_password: String,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListUsersQueryDto {
+    /// Page size, from 1 to 100 (default 50).
+    pub limit: Option<u32>,
+    /// Opaque `next_cursor` returned by the previous page.
+    pub cursor: Option<String>,
+    /// Only users holding this role.
     pub role: Option<String>,
+    /// Only users whose email belongs to this domain, e.g. `example.com`.
+    pub email_domain: Option<String>,
+    /// Only users created at or after this instant (RFC 3339).
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only users created before this instant (RFC 3339).
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only users in this status: `active`, `suspended`, `pending` or `locked`.
+    pub status: Option<String>,
+    /// `name`, `email` or `created_at` (default).
+    pub sort: Option<String>,
+    /// `asc` or `desc`; defaults to `desc` for `created_at` and `asc` otherwise.
+    pub order: Option<String>,
+    /// Also count every user matching the filters.
+    pub include_total: Option<bool>,
+    /// Also list soft-deleted users; requires `users:delete`.
+    pub include_deleted: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct UserPageDto {
+    pub items: Vec<UserResponseDto>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page.
+    pub next_cursor: Option<String>,
+    /// Present only when `include_total=true`.
+    pub total_count: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, ToSchema)]
@@ -34,9 +93,59 @@ pub struct UserResponseDto {
     pub id: Uuid,
     pub name: String,
     pub email: String,
-    pub role: String,
+    pub roles: Vec<String>,
+    /// Incremented on every change; also sent as the `ETag` header.
+    pub version: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the user is soft-deleted and can still be restored.
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// `active`, `suspended`, `pending` or `locked`; only active users can sign in.
+    #[schema(example = "active")]
+    pub status: String,
+    /// Reason given on the last status change.
+    pub status_reason: Option<String>,
+    pub preferences: UserPreferencesDto,
+    /// When the owner confirmed `email`; `null` until the emailed link is opened.
+    pub email_verified_at: Option<DateTime<Utc>>,
+    /// New address awaiting confirmation; `email` changes only once it is verified.
+    #[schema(example = "grace@new.example.com")]
+    pub pending_email: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, ToSchema)]
+pub struct UserPreferencesDto {
+    pub locale: Option<String>,
+    pub timezone: Option<String>,
+}
+
+impl From<&UserPreferences> for UserPreferencesDto {
+    fn from(preferences: &UserPreferences) -> Self {
+        Self {
+            locale: preferences
+                .locale
+                .as_ref()
+                .map(|locale| locale.as_str().to_string()),
+            timezone: preferences
+                .timezone
+                .as_ref()
+                .map(|timezone| timezone.as_str().to_string()),
+        }
+    }
+}
+
+/// New address for the signed-in user; it takes effect only after confirmation.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangeEmailDto {
+    #[schema(example = "grace@new.example.com")]
+    pub email: String,
+}
+
+/// Reason recorded with a suspend, lock or reactivate request.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangeUserStatusDto {
+    #[schema(example = "Chargeback under investigation")]
+    pub reason: String,
 }
 
 impl From<User> for UserResponseDto {
@@ -45,9 +154,20 @@ impl From<User> for UserResponseDto {
             id: user.id(),
             name: user.name().as_str().to_string(),
             email: user.email().as_str().to_string(),
-            role: user.role().as_str().to_string(),
+            roles: user
+                .roles()
+                .iter()
+                .map(|role| role.as_str().to_string())
+                .collect(),
+            version: user.version(),
             created_at: user.created_at(),
             updated_at: user.updated_at(),
+            deleted_at: user.deleted_at(),
+            status: user.status().as_str().to_string(),
+            status_reason: user.status_reason().map(str::to_string),
+            preferences: user.preferences().into(),
+            email_verified_at: user.email_verified_at(),
+            pending_email: user.pending_email().map(|email| email.as_str().to_string()),
         }
     }
 }
@@ -65,7 +185,27 @@ impl UserResponseDto {
         &self.email
     }
 
-    pub fn role(&self) -> &str {
-        &self.role
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn preferences(&self) -> &UserPreferencesDto {
+        &self.preferences
+    }
+
+    pub fn email_verified_at(&self) -> Option<DateTime<Utc>> {
+        self.email_verified_at
+    }
+
+    pub fn pending_email(&self) -> Option<&str> {
+        self.pending_email.as_deref()
     }
 }
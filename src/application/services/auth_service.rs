@@ -1,76 +1,811 @@
-use std::str::FromStr;
+use std::net::IpAddr;
 use std::sync::Arc;
 
 use anyhow::anyhow;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::jwk::JwkSet;
 use uuid::Uuid;
 
-use crate::domain::entities::user::UserRole;
+use crate::application::services::login_throttle_service::LoginThrottleService;
+use crate::application::services::mfa_service::MfaService;
+use crate::application::services::password_history_service::PasswordHistoryService;
+use crate::application::services::role_service::RoleService;
+use crate::application::services::session_service::SessionService;
+use crate::application::services::token_revocation_service::TokenRevocationService;
+use crate::domain::entities::permission::{format_scope, parse_scope, Permission};
+use crate::domain::entities::refresh_token::NewRefreshToken;
+use crate::domain::entities::session::{SessionAuthMethod, SessionClient, SessionGrant};
+use crate::domain::entities::user::{UpdateUser, User};
+use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
 use crate::domain::repositories::user_repository::UserRepository;
+use crate::domain::value_objects::{EmailAddress, PasswordHash, RoleName};
 use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::hashing_pool::HashingPool;
 use crate::shared::security::password::PasswordError;
+use crate::shared::security::password_policy::PasswordPolicy;
 use crate::shared::security::{
-    password,
-    token::{Claims, JwtManager, TokenError},
+    opaque_token,
+    token::{Delegation, JwtManager, TokenDetails, TokenError, TokenPurpose},
 };
 
 #[derive(Clone)]
 pub struct AuthService {
     repository: Arc<dyn UserRepository>,
+    refresh_tokens: Arc<dyn RefreshTokenRepository>,
+    sessions: SessionService,
+    revocations: TokenRevocationService,
+    mfa: MfaService,
+    roles: RoleService,
+    throttle: LoginThrottleService,
+    history: PasswordHistoryService,
+    hasher: HashingPool,
     jwt: JwtManager,
+    settings: AuthSettings,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthSettings {
+    pub refresh_token_ttl: Duration,
+    // Validade dos tokens intermediarios: o desafio de `POST /auth/mfa/verify` e o de senha
+    // vencida.
+    pub mfa_challenge_ttl: Duration,
+    // Recusa o login de contas que ainda nao confirmaram o email.
+    pub require_verified_email: bool,
+    // Regras para qualquer senha nova, compartilhadas com os servicos que definem senhas.
+    pub password_policy: PasswordPolicy,
+    // Depois desse prazo desde a ultima troca, o login so emite um token para trocar a senha.
+    pub password_max_age: Option<Duration>,
+}
+
+impl Default for AuthSettings {
+    fn default() -> Self {
+        Self {
+            refresh_token_ttl: Duration::days(14),
+            mfa_challenge_ttl: Duration::minutes(5),
+            require_verified_email: false,
+            password_policy: PasswordPolicy::default(),
+            password_max_age: None,
+        }
+    }
 }
 
 impl AuthService {
-    pub fn new(repository: Arc<dyn UserRepository>, jwt: JwtManager) -> Self {
-        Self { repository, jwt }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repository: Arc<dyn UserRepository>,
+        refresh_tokens: Arc<dyn RefreshTokenRepository>,
+        sessions: SessionService,
+        revocations: TokenRevocationService,
+        mfa: MfaService,
+        roles: RoleService,
+        throttle: LoginThrottleService,
+        history: PasswordHistoryService,
+        hasher: HashingPool,
+        jwt: JwtManager,
+        settings: AuthSettings,
+    ) -> Self {
+        Self {
+            repository,
+            refresh_tokens,
+            sessions,
+            revocations,
+            mfa,
+            roles,
+            throttle,
+            history,
+            hasher,
+            jwt,
+            settings,
+        }
+    }
+
+    // Senha correta nao basta quando o usuario tem TOTP ou o papel exige MFA: nesses casos
+    // devolvemos apenas um token de desafio, sem refresh token nem acesso a API. O mesmo vale
+    // para senhas vencidas, verificadas so depois do segundo fator.
+    pub async fn authenticate(
+        &self,
+        email: &str,
+        password_input: &str,
+        client: &SessionClient,
+    ) -> AppResult<LoginOutcome> {
+        let source_ip = client.ip_address;
+        self.throttle.check(email, source_ip).await?;
+
+        // Um email malformado nunca corresponde a uma conta e recebe a mesma resposta. Sem conta,
+        // a senha e verificada contra um hash descartavel: o tempo de resposta e o mesmo de uma
+        // senha errada e nao revela quais emails estao cadastrados.
+        let user = match EmailAddress::parse(email) {
+            Ok(address) => self.repository.find_by_email(&address).await?,
+            Err(_) => None,
+        };
+        let Some(user) = user else {
+            self.hasher.verify_dummy(password_input).await?;
+            return Err(self.login_failure(email, source_ip).await);
+        };
+
+        match self
+            .hasher
+            .verify_password(user.password_hash().as_str(), password_input)
+            .await?
+        {
+            Ok(()) => {}
+            Err(PasswordError::InvalidPassword) => {
+                return Err(self.login_failure(email, source_ip).await)
+            }
+            Err(PasswordError::Hash(_)) => {
+                return Err(AppError::Unexpected(anyhow!(
+                    "failed to verify stored password hash"
+                )))
+            }
+        }
+
+        // Verificado apos a senha para nao revelar o status da conta a quem nao a conhece.
+        ensure_can_sign_in(&user)?;
+        if self.settings.require_verified_email && !user.is_email_verified() {
+            return Err(AppError::Forbidden(
+                "email address is not verified".to_string(),
+            ));
+        }
+
+        // O login e o unico momento com a senha em claro; uma falha aqui nao impede a entrada.
+        if self.hasher.needs_rehash(user.password_hash().as_str()) {
+            if let Err(err) = self.upgrade_password_hash(&user, password_input).await {
+                tracing::warn!(user_id = %user.id(), error = %err, "failed to upgrade password hash");
+            }
+        }
+
+        if self.mfa.is_enrolled(user.id()).await? {
+            let challenge = self.issue_challenge(&user, TokenPurpose::MfaChallenge)?;
+            return Ok(LoginOutcome::MfaRequired(challenge));
+        }
+
+        if self.mfa.is_required_for(user.roles()).await? {
+            let challenge = self.issue_challenge(&user, TokenPurpose::MfaEnrollment)?;
+            return Ok(LoginOutcome::MfaEnrollmentRequired(challenge));
+        }
+
+        // So zeramos o contador quando a sessao e de fato emitida: acertar a senha e errar o
+        // segundo fator continua contando para o bloqueio.
+        self.throttle.record_success(email).await?;
+        self.complete_login(&user, SessionAuthMethod::Password, client)
+            .await
     }
 
-    pub async fn authenticate(&self, email: &str, password_input: &str) -> AppResult<AuthSession> {
+    // Conclui o login iniciado em `authenticate`. O token de desafio e de uso unico: e revogado
+    // assim que o segundo fator e aceito.
+    pub async fn verify_mfa(
+        &self,
+        challenge_token: &str,
+        code: &str,
+        client: &SessionClient,
+    ) -> AppResult<LoginOutcome> {
+        let source_ip = client.ip_address;
+        let challenge = self
+            .verify_with_purpose(challenge_token, &[TokenPurpose::MfaChallenge])
+            .await
+            .map_err(|_| AppError::Unauthorized("invalid mfa challenge".to_string()))?;
+
+        self.throttle.check(&challenge.email, source_ip).await?;
+
+        if let Err(err) = self.mfa.verify_second_factor(challenge.id, code).await {
+            if let AppError::Unauthorized(_) = err {
+                if let Some(lockout) = self
+                    .throttle
+                    .record_failure(&challenge.email, source_ip)
+                    .await?
+                {
+                    return Err(lockout);
+                }
+            }
+            return Err(err);
+        }
+
+        self.revocations
+            .revoke_token(challenge.token_id, challenge.id, challenge.token_expires_at)
+            .await?;
+
         let user = self
             .repository
-            .find_by_email(email)
+            .find_by_id(challenge.id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("invalid mfa challenge".to_string()))?;
+
+        self.throttle.record_success(&challenge.email).await?;
+        self.complete_login(&user, SessionAuthMethod::PasswordAndTotp, client)
+            .await
+    }
+
+    // Troca um refresh token valido por um novo par de tokens. Apresentar um token ja
+    // rotacionado indica vazamento: toda a sessao e revogada e o cliente precisa logar de novo.
+    // Sessoes de clientes OAuth so renovam em `/oauth/token` (`refresh_delegated`).
+    pub async fn refresh(&self, raw_token: &str) -> AppResult<AuthSession> {
+        self.rotate_refresh_token(raw_token, None, None).await
+    }
+
+    // Renovacao pelo grant `refresh_token`: a sessao precisa ser do mesmo cliente. `scopes`
+    // restringe apenas o novo access token; a sessao mantem o que foi concedido.
+    pub async fn refresh_delegated(
+        &self,
+        raw_token: &str,
+        client_id: Uuid,
+        scopes: Option<&[Permission]>,
+    ) -> AppResult<AuthSession> {
+        self.rotate_refresh_token(raw_token, Some(client_id), scopes)
+            .await
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        raw_token: &str,
+        client_id: Option<Uuid>,
+        scopes: Option<&[Permission]>,
+    ) -> AppResult<AuthSession> {
+        let token_hash = opaque_token::hash(raw_token.trim());
+        let stored = self
+            .refresh_tokens
+            .find_by_hash(&token_hash)
             .await?
-            .ok_or_else(|| AppError::Unauthorized("invalid credentials".to_string()))?;
+            .ok_or_else(invalid_refresh_token)?;
+
+        if stored.is_rotated() {
+            self.sessions
+                .end(stored.user_id(), stored.family_id())
+                .await?;
+            return Err(AppError::Unauthorized(
+                "refresh token reuse detected".to_string(),
+            ));
+        }
+
+        if stored.is_revoked() {
+            return Err(invalid_refresh_token());
+        }
 
-        password::verify_password(user.password_hash().as_str(), password_input).map_err(
-            |err| match err {
-                PasswordError::InvalidPassword => {
-                    AppError::Unauthorized("invalid credentials".to_string())
+        if stored.is_expired(Utc::now()) {
+            return Err(AppError::Unauthorized("refresh token expired".to_string()));
+        }
+
+        let session = self
+            .sessions
+            .ensure_active(stored.family_id(), stored.user_id())
+            .await
+            .map_err(|err| match err {
+                AppError::Unauthorized(_) => invalid_refresh_token(),
+                other => other,
+            })?;
+        // Conferido antes da rotacao: o token apresentado no endpoint errado continua valido.
+        if session.grant.as_ref().map(|grant| grant.client_id) != client_id {
+            return Err(invalid_refresh_token());
+        }
+        let grant = match (session.grant, scopes) {
+            (Some(grant), Some(scopes)) => {
+                if let Some(scope) = scopes.iter().find(|scope| !grant.scopes.contains(scope)) {
+                    return Err(AppError::Validation(format!(
+                        "scope {scope} was not granted to this session"
+                    )));
                 }
-                PasswordError::Hash(_) => {
-                    AppError::Unexpected(anyhow!("failed to verify stored password hash"))
+                Some(SessionGrant {
+                    client_id: grant.client_id,
+                    scopes: scopes.to_vec(),
+                })
+            }
+            (grant, _) => grant,
+        };
+
+        // Duas requisicoes concorrentes com o mesmo token: apenas uma vence a rotacao.
+        if !self.refresh_tokens.mark_rotated(stored.id()).await? {
+            self.sessions
+                .end(stored.user_id(), stored.family_id())
+                .await?;
+            return Err(AppError::Unauthorized(
+                "refresh token reuse detected".to_string(),
+            ));
+        }
+
+        let user = self
+            .repository
+            .find_by_id(stored.user_id())
+            .await?
+            .ok_or_else(invalid_refresh_token)?;
+        ensure_can_sign_in(&user)?;
+
+        let issued = self
+            .issue_session(&user, stored.family_id(), grant.as_ref())
+            .await?;
+        if !self
+            .sessions
+            .renew(stored.family_id(), issued.refresh_expires_at)
+            .await?
+        {
+            return Err(invalid_refresh_token());
+        }
+        Ok(issued)
+    }
+
+    // Revoga o access token atual, a sessao dele e, se informada, a sessao do refresh token do
+    // mesmo usuario.
+    pub async fn logout(
+        &self,
+        actor: &AuthenticatedUser,
+        refresh_token: Option<&str>,
+    ) -> AppResult<()> {
+        actor.require_interactive_session()?;
+        self.revocations
+            .revoke_token(actor.token_id, actor.id, actor.token_expires_at)
+            .await?;
+        if let Some(session_id) = actor.session_id {
+            self.sessions.end(actor.id, session_id).await?;
+        }
+
+        if let Some(raw_token) = refresh_token {
+            let token_hash = opaque_token::hash(raw_token.trim());
+            if let Some(stored) = self.refresh_tokens.find_by_hash(&token_hash).await? {
+                if stored.user_id() == actor.id {
+                    self.sessions.end(actor.id, stored.family_id()).await?;
                 }
-            },
-        )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn revoke_all_sessions(
+        &self,
+        actor: &AuthenticatedUser,
+        user_id: Uuid,
+    ) -> AppResult<()> {
+        actor.require_permission(Permission::UsersWrite)?;
+
+        let user = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("user {user_id} not found")))?;
+        self.roles.ensure_can_manage_user(actor, &user).await?;
+
+        self.revoke_user_sessions(user_id).await
+    }
+
+    // Invalida todas as sessoes, com os refresh tokens, e os access tokens ja emitidos para o
+    // usuario.
+    pub async fn revoke_user_sessions(&self, user_id: Uuid) -> AppResult<()> {
+        let now = Utc::now();
+        let expires_at = now
+            .checked_add_signed(self.jwt.ttl())
+            .ok_or_else(|| AppError::Unexpected(anyhow!("invalid access token ttl")))?;
+
+        self.revocations.revoke_user(user_id, now, expires_at).await
+    }
+
+    // Exige a senha atual, contando erros no mesmo bloqueio do login para que um token roubado
+    // nao sirva para adivinha-la. Todas as sessoes sao revogadas, inclusive a atual; quem trocou
+    // a senha recebe uma sessao nova na resposta, com o mesmo metodo de autenticacao da atual.
+    // Aceita tambem o token de senha vencida.
+    pub async fn change_password(
+        &self,
+        actor: &AuthenticatedUser,
+        current_password: &str,
+        new_password: &str,
+        client: &SessionClient,
+    ) -> AppResult<AuthSession> {
+        actor.require_interactive_session()?;
+        let source_ip = client.ip_address;
+        self.throttle.check(&actor.email, source_ip).await?;
+
+        let user = self
+            .repository
+            .find_by_id(actor.id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("user {} not found", actor.id)))?;
+
+        match self
+            .hasher
+            .verify_password(user.password_hash().as_str(), current_password)
+            .await?
+        {
+            Ok(()) => {}
+            Err(PasswordError::InvalidPassword) => {
+                return Err(
+                    match self
+                        .throttle
+                        .record_failure(&actor.email, source_ip)
+                        .await?
+                    {
+                        Some(lockout) => lockout,
+                        None => AppError::Forbidden("current password is incorrect".to_string()),
+                    },
+                );
+            }
+            Err(PasswordError::Hash(_)) => {
+                return Err(AppError::Unexpected(anyhow!(
+                    "failed to verify stored password hash"
+                )))
+            }
+        }
+        self.throttle.record_success(&actor.email).await?;
+
+        let plain_password = self
+            .settings
+            .password_policy
+            .validate(new_password, &[user.name().as_str(), user.email().as_str()])?;
+        if plain_password.as_str() == current_password {
+            return Err(AppError::Validation(
+                "new password must differ from the current one".to_string(),
+            ));
+        }
+        self.history
+            .ensure_not_reused(&user, &plain_password)
+            .await?;
+        let password_hash_raw = self
+            .hasher
+            .hash_password(plain_password.as_str())
+            .await?
+            .map_err(|err| AppError::Unexpected(anyhow!("failed to hash password: {err}")))?;
+        let password_hash = PasswordHash::new(&password_hash_raw)?;
+
+        let previous = user;
+        let user = self
+            .repository
+            .update(
+                previous.id(),
+                UpdateUser::default().apply_password_hash(password_hash),
+                None,
+            )
+            .await?;
+        self.history.remember_current(&previous).await?;
+
+        let auth_method = match actor.session_id {
+            Some(session_id) => self
+                .sessions
+                .find(session_id)
+                .await?
+                .map_or(SessionAuthMethod::Password, |session| session.auth_method),
+            None => SessionAuthMethod::Password,
+        };
+        self.revoke_user_sessions(user.id()).await?;
+        self.open_session(&user, auth_method, client, None).await
+    }
+
+    pub async fn unlock_account(&self, actor: &AuthenticatedUser, user_id: Uuid) -> AppResult<()> {
+        actor.require_permission(Permission::UsersWrite)?;
+
+        let user = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("user {user_id} not found")))?;
+
+        self.throttle.unlock_account(user.email().as_str()).await
+    }
+
+    pub fn jwks(&self) -> &JwkSet {
+        self.jwt.jwks()
+    }
+
+    pub fn password_policy(&self) -> &PasswordPolicy {
+        &self.settings.password_policy
+    }
+
+    pub fn password_history(&self) -> &PasswordHistoryService {
+        &self.history
+    }
+
+    pub fn hasher(&self) -> &HashingPool {
+        &self.hasher
+    }
+
+    pub fn sessions(&self) -> &SessionService {
+        &self.sessions
+    }
+
+    // Sessao aberta na troca de um codigo de autorizacao. O id vem reservado no codigo para que
+    // um codigo reapresentado revogue exatamente a sessao que originou.
+    pub async fn open_delegated_session(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+        grant: SessionGrant,
+        client: &SessionClient,
+    ) -> AppResult<AuthSession> {
+        let user = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("invalid authorization code".to_string()))?;
+        ensure_can_sign_in(&user)?;
+
+        let issued = self.issue_session(&user, session_id, Some(&grant)).await?;
+        self.sessions
+            .start(
+                session_id,
+                user.id(),
+                SessionAuthMethod::OAuth,
+                client,
+                Some(grant),
+                issued.refresh_expires_at,
+            )
+            .await?;
+        Ok(issued)
+    }
+
+    // Access token do grant `client_credentials`: age em nome de quem registrou o cliente, sem
+    // sessao nem refresh token. O escopo fica limitado ao que os papeis do dono concedem hoje.
+    pub async fn issue_client_token(
+        &self,
+        owner_id: Uuid,
+        client_id: Uuid,
+        scopes: &[Permission],
+    ) -> AppResult<ClientToken> {
+        let owner = self
+            .repository
+            .find_by_id(owner_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("client owner not found".to_string()))?;
+        ensure_can_sign_in(&owner)?;
+
+        let permissions = self.roles.permissions_for(owner.roles()).await?;
+        let scopes: Vec<Permission> = scopes
+            .iter()
+            .copied()
+            .filter(|scope| permissions.contains(scope))
+            .collect();
 
         let token = self
             .jwt
-            .generate(user.id(), user.email().as_str(), user.role().as_str())
+            .generate_delegated(
+                owner.id(),
+                owner.email().as_str(),
+                &role_names(owner.roles()),
+                None,
+                &Delegation {
+                    client_id,
+                    scope: format_scope(&scopes),
+                },
+            )
             .map_err(|err| AppError::Unexpected(anyhow!("failed to issue token: {err}")))?;
+        Ok(ClientToken { token, scopes })
+    }
 
-        Ok(AuthSession {
+    pub async fn verify(&self, token: &str) -> AppResult<AuthenticatedUser> {
+        self.verify_with_purpose(token, &[TokenPurpose::Access])
+            .await
+    }
+
+    // Usado apenas pelas rotas de cadastro de MFA, que tambem aceitam o token emitido quando
+    // a politica do papel exige MFA e o usuario ainda nao configurou o segundo fator.
+    pub async fn verify_for_mfa_enrollment(&self, token: &str) -> AppResult<AuthenticatedUser> {
+        self.verify_with_purpose(token, &[TokenPurpose::Access, TokenPurpose::MfaEnrollment])
+            .await
+    }
+
+    // Usado apenas pela troca da propria senha, unica rota liberada para quem tem a senha vencida.
+    pub async fn verify_for_password_change(&self, token: &str) -> AppResult<AuthenticatedUser> {
+        self.verify_with_purpose(token, &[TokenPurpose::Access, TokenPurpose::PasswordChange])
+            .await
+    }
+
+    async fn verify_with_purpose(
+        &self,
+        token: &str,
+        accepted: &[TokenPurpose],
+    ) -> AppResult<AuthenticatedUser> {
+        let claims = self.jwt.verify(token).map_err(map_token_error)?;
+        if !accepted.contains(&claims.purpose) {
+            return Err(AppError::Unauthorized("invalid token".to_string()));
+        }
+
+        let issued_at = claims
+            .issued_at()
+            .ok_or_else(|| AppError::Unauthorized("invalid token".to_string()))?;
+
+        // A sessao vem antes da lista: logout e revogacao em massa encerram a sessao, e o corte
+        // por horario so vale para tokens sem sessao (desafios de MFA, `client_credentials`).
+        let session_active = match claims.sid {
+            Some(session_id) => {
+                self.sessions.ensure_active(session_id, claims.sub).await?;
+                true
+            }
+            None => false,
+        };
+        if self
+            .revocations
+            .is_revoked(claims.jti, claims.sub, issued_at, session_active)
+        {
+            return Err(AppError::Unauthorized("token revoked".to_string()));
+        }
+
+        let token_expires_at = DateTime::from_timestamp(claims.exp, 0)
+            .ok_or_else(|| AppError::Unauthorized("invalid token".to_string()))?;
+
+        // Papeis, permissoes e status vem do cadastro atual; os do token sao apenas
+        // informativos. Assim uma suspensao vale de imediato para tokens ja emitidos.
+        let user = self
+            .repository
+            .find_by_id(claims.sub)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("invalid token".to_string()))?;
+        ensure_can_sign_in(&user)?;
+        let permissions = self.roles.permissions_for(user.roles()).await?;
+        let scopes = claims
+            .scope
+            .as_deref()
+            .map(parse_scope)
+            .transpose()
+            .map_err(|_| AppError::Unauthorized("invalid token".to_string()))?;
+
+        Ok(
+            AuthenticatedUser::new(&user, permissions, claims.jti, token_expires_at, scopes)
+                .with_session(claims.sid),
+        )
+    }
+
+    async fn login_failure(&self, email: &str, source_ip: Option<IpAddr>) -> AppError {
+        match self.throttle.record_failure(email, source_ip).await {
+            Ok(Some(lockout)) => lockout,
+            Ok(None) => AppError::Unauthorized("invalid credentials".to_string()),
+            Err(err) => err,
+        }
+    }
+
+    // Refaz o hash com os parametros e o pepper atuais. Se a senha mudou nesse meio tempo, o
+    // repositorio nao grava nada.
+    async fn upgrade_password_hash(&self, user: &User, password_input: &str) -> AppResult<()> {
+        let password_hash_raw = self
+            .hasher
+            .hash_password(password_input)
+            .await?
+            .map_err(|err| AppError::Unexpected(anyhow!("failed to hash password: {err}")))?;
+        let password_hash = PasswordHash::new(&password_hash_raw)?;
+        self.repository
+            .replace_password_hash(user.id(), user.password_hash(), &password_hash)
+            .await?;
+        Ok(())
+    }
+
+    // Ultima etapa de um login aceito (senha e, se houver, segundo fator).
+    async fn complete_login(
+        &self,
+        user: &User,
+        auth_method: SessionAuthMethod,
+        client: &SessionClient,
+    ) -> AppResult<LoginOutcome> {
+        if self.is_password_expired(user) {
+            let challenge = self.issue_challenge(user, TokenPurpose::PasswordChange)?;
+            return Ok(LoginOutcome::PasswordChangeRequired(challenge));
+        }
+
+        self.open_session(user, auth_method, client, None)
+            .await
+            .map(LoginOutcome::Authenticated)
+    }
+
+    // Registra a sessao no inventario e emite os tokens dela.
+    async fn open_session(
+        &self,
+        user: &User,
+        auth_method: SessionAuthMethod,
+        client: &SessionClient,
+        grant: Option<SessionGrant>,
+    ) -> AppResult<AuthSession> {
+        let session_id = Uuid::new_v4();
+        let session = self.issue_session(user, session_id, grant.as_ref()).await?;
+        self.sessions
+            .start(
+                session_id,
+                user.id(),
+                auth_method,
+                client,
+                grant,
+                session.refresh_expires_at,
+            )
+            .await?;
+        Ok(session)
+    }
+
+    fn is_password_expired(&self, user: &User) -> bool {
+        self.settings
+            .password_max_age
+            .is_some_and(|max_age| user.password_changed_at() + max_age <= Utc::now())
+    }
+
+    fn issue_challenge(&self, user: &User, purpose: TokenPurpose) -> AppResult<MfaChallenge> {
+        let token = self
+            .jwt
+            .generate_for(
+                user.id(),
+                user.email().as_str(),
+                &role_names(user.roles()),
+                purpose,
+                self.settings.mfa_challenge_ttl,
+            )
+            .map_err(|err| AppError::Unexpected(anyhow!("failed to issue token: {err}")))?;
+
+        Ok(MfaChallenge {
             token: token.token,
             expires_at: token.expires_at,
-            user: AuthenticatedUser {
-                id: user.id(),
-                email: user.email().as_str().to_string(),
-                role: user.role(),
-            },
         })
     }
 
-    pub fn verify(&self, token: &str) -> AppResult<AuthenticatedUser> {
-        let claims = self.jwt.verify(token).map_err(map_token_error)?;
+    // A familia dos refresh tokens e a propria sessao. Sessoes de clientes OAuth emitem tokens
+    // limitados aos escopos concedidos.
+    async fn issue_session(
+        &self,
+        user: &User,
+        session_id: Uuid,
+        grant: Option<&SessionGrant>,
+    ) -> AppResult<AuthSession> {
+        let roles = role_names(user.roles());
+        let token = match grant {
+            Some(grant) => self.jwt.generate_delegated(
+                user.id(),
+                user.email().as_str(),
+                &roles,
+                Some(session_id),
+                &Delegation {
+                    client_id: grant.client_id,
+                    scope: format_scope(&grant.scopes),
+                },
+            ),
+            None => self
+                .jwt
+                .generate(user.id(), user.email().as_str(), &roles, session_id),
+        }
+        .map_err(|err| AppError::Unexpected(anyhow!("failed to issue token: {err}")))?;
+
+        let refresh_token = opaque_token::generate();
+        let refresh_expires_at = Utc::now()
+            .checked_add_signed(self.settings.refresh_token_ttl)
+            .ok_or_else(|| AppError::Unexpected(anyhow!("invalid refresh token ttl")))?;
 
-        claims.try_into()
+        let permissions = self.roles.permissions_for(user.roles()).await?;
+
+        self.refresh_tokens
+            .create(NewRefreshToken::build(
+                user.id(),
+                session_id,
+                opaque_token::hash(&refresh_token),
+                refresh_expires_at,
+            ))
+            .await?;
+
+        Ok(AuthSession {
+            token: token.token,
+            expires_at: token.expires_at,
+            refresh_token,
+            refresh_expires_at,
+            user: AuthenticatedUser::new(
+                user,
+                permissions,
+                token.jti,
+                token.expires_at,
+                grant.map(|grant| grant.scopes.clone()),
+            )
+            .with_session(Some(session_id)),
+        })
     }
 }
 
+// Apenas contas ativas autenticam, seja por senha, refresh token, JWT ou token pessoal.
+pub fn ensure_can_sign_in(user: &User) -> AppResult<()> {
+    if user.status().can_sign_in() {
+        return Ok(());
+    }
+    Err(AppError::Forbidden(format!("account is {}", user.status())))
+}
+
+fn role_names(roles: &[RoleName]) -> Vec<String> {
+    roles.iter().map(|role| role.as_str().to_string()).collect()
+}
+
+fn invalid_refresh_token() -> AppError {
+    AppError::Unauthorized("invalid refresh token".to_string())
+}
+
 fn map_token_error(err: TokenError) -> AppError {
     match err {
         TokenError::Expired => AppError::Unauthorized("token expired".to_string()),
         TokenError::Invalid(_) => AppError::Unauthorized("invalid token".to_string()),
         TokenError::InvalidTtl => AppError::Unexpected(anyhow!("token generated with invalid ttl")),
+        TokenError::Key(detail) => AppError::Unexpected(anyhow!("signing key error: {detail}")),
     }
 }
 
@@ -78,32 +813,76 @@ fn map_token_error(err: TokenError) -> AppError {
 pub struct AuthenticatedUser {
     pub id: Uuid,
     pub email: String,
-    pub role: UserRole,
+    pub roles: Vec<RoleName>,
+    // Uniao das permissoes dos papeis no momento da requisicao.
+    pub permissions: Vec<Permission>,
+    pub token_id: Uuid,
+    pub token_expires_at: DateTime<Utc>,
+    // `None` para sessoes interativas (JWT); tokens pessoais e de clientes OAuth carregam os
+    // escopos concedidos.
+    pub scopes: Option<Vec<Permission>>,
+    // Sessao do inventario de onde veio o access token; ausente para tokens pessoais e de desafio.
+    pub session_id: Option<Uuid>,
 }
 
-impl TryFrom<Claims> for AuthenticatedUser {
-    type Error = AppError;
-
-    fn try_from(value: Claims) -> Result<Self, Self::Error> {
-        let role = UserRole::from_str(&value.role)
-            .map_err(|err| AppError::Unexpected(anyhow!("invalid role in token: {err}")))?;
+#[derive(Debug, Clone)]
+pub struct AuthSession {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+    pub refresh_token: String,
+    pub refresh_expires_at: DateTime<Utc>,
+    pub user: AuthenticatedUser,
+}
 
-        Ok(Self {
-            id: value.sub,
-            email: value.email,
-            role,
-        })
-    }
+#[derive(Debug, Clone)]
+pub struct ClientToken {
+    pub token: TokenDetails,
+    // Escopos efetivamente emitidos, ja cruzados com os papeis do dono do cliente.
+    pub scopes: Vec<Permission>,
 }
 
 #[derive(Debug, Clone)]
-pub struct AuthSession {
+pub struct MfaChallenge {
     pub token: String,
     pub expires_at: DateTime<Utc>,
-    pub user: AuthenticatedUser,
+}
+
+#[derive(Debug, Clone)]
+pub enum LoginOutcome {
+    Authenticated(AuthSession),
+    // Usuario com TOTP confirmado: troque o desafio + codigo em `POST /auth/mfa/verify`.
+    MfaRequired(MfaChallenge),
+    // O papel exige MFA e ainda nao ha TOTP: o desafio so serve para as rotas de cadastro.
+    MfaEnrollmentRequired(MfaChallenge),
+    // A senha venceu: o desafio so serve para `POST /users/me/password`.
+    PasswordChangeRequired(MfaChallenge),
 }
 
 impl AuthenticatedUser {
+    pub fn new(
+        user: &User,
+        permissions: Vec<Permission>,
+        token_id: Uuid,
+        token_expires_at: DateTime<Utc>,
+        scopes: Option<Vec<Permission>>,
+    ) -> Self {
+        Self {
+            id: user.id(),
+            email: user.email().as_str().to_string(),
+            roles: user.roles().to_vec(),
+            permissions,
+            token_id,
+            token_expires_at,
+            scopes,
+            session_id: None,
+        }
+    }
+
+    pub fn with_session(mut self, session_id: Option<Uuid>) -> Self {
+        self.session_id = session_id;
+        self
+    }
+
     pub fn id(&self) -> Uuid {
         self.id
     }
@@ -112,7 +891,69 @@ impl AuthenticatedUser {
         &self.email
     }
 
-    pub fn role(&self) -> &UserRole {
-        &self.role
+    pub fn roles(&self) -> &[RoleName] {
+        &self.roles
+    }
+
+    // Representacao usada na auditoria, ex.: "admin,support".
+    pub fn roles_label(&self) -> String {
+        self.roles
+            .iter()
+            .map(RoleName::as_str)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    // Vale o que os papeis concedem e, para tokens pessoais, o que o escopo do token permite.
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions.contains(&permission)
+            && self
+                .scopes
+                .as_ref()
+                .is_none_or(|scopes| scopes.contains(&permission))
+    }
+
+    pub fn require_permission(&self, permission: Permission) -> AppResult<()> {
+        self.require_scope(permission)?;
+        if !self.permissions.contains(&permission) {
+            return Err(AppError::Forbidden(format!(
+                "permission {permission} required"
+            )));
+        }
+        Ok(())
+    }
+
+    // Ninguem concede permissoes que nao tem, seja em um papel ou ao atribuir papeis.
+    pub fn ensure_can_grant(&self, permissions: &[Permission]) -> AppResult<()> {
+        match permissions
+            .iter()
+            .find(|permission| !self.has_permission(**permission))
+        {
+            Some(permission) => Err(AppError::Forbidden(format!(
+                "cannot grant permission {permission}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    // O escopo so restringe tokens pessoais e de clientes OAuth; sessoes interativas dependem
+    // apenas dos papeis.
+    pub fn require_scope(&self, scope: Permission) -> AppResult<()> {
+        match &self.scopes {
+            Some(scopes) if !scopes.contains(&scope) => {
+                Err(AppError::Forbidden(format!("token scope {scope} required")))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Operacoes sobre credenciais (logout, tokens pessoais) nao aceitam tokens pessoais.
+    pub fn require_interactive_session(&self) -> AppResult<()> {
+        if self.scopes.is_some() {
+            return Err(AppError::Forbidden(
+                "operation requires an interactive session".to_string(),
+            ));
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use crate::domain::mailer::{EmailMessage, Mailer};
+use crate::domain::repositories::email_outbox_repository::EmailOutboxRepository;
+use crate::shared::error::AppResult;
+
+const DISPATCH_BATCH_SIZE: u32 = 50;
+// Tempo que uma mensagem reservada fica invisivel para outras instancias enquanto e enviada.
+const CLAIM_LEASE_SECONDS: i64 = 300;
+const RETRY_BASE_SECONDS: i64 = 30;
+const MAX_RETRY_DOUBLINGS: u32 = 6;
+
+// Desacopla as requisicoes da entrega: `enqueue` so grava no outbox e `dispatch_pending`
+// (chamado pelo job periodico) entrega pelo `Mailer` configurado.
+#[derive(Clone)]
+pub struct EmailOutboxService {
+    repository: Arc<dyn EmailOutboxRepository>,
+    mailer: Arc<dyn Mailer>,
+    max_attempts: u32,
+}
+
+impl EmailOutboxService {
+    pub fn new(
+        repository: Arc<dyn EmailOutboxRepository>,
+        mailer: Arc<dyn Mailer>,
+        max_attempts: u32,
+    ) -> Self {
+        Self {
+            repository,
+            mailer,
+            max_attempts,
+        }
+    }
+
+    pub async fn enqueue(&self, message: EmailMessage) -> AppResult<()> {
+        self.repository.enqueue(&message).await?;
+        Ok(())
+    }
+
+    // Retorna quantas mensagens foram entregues; falhas ficam registradas para nova tentativa.
+    pub async fn dispatch_pending(&self) -> AppResult<usize> {
+        let now = Utc::now();
+        let pending = self
+            .repository
+            .claim_due(
+                now,
+                now + Duration::seconds(CLAIM_LEASE_SECONDS),
+                self.max_attempts,
+                DISPATCH_BATCH_SIZE,
+            )
+            .await?;
+
+        let mut delivered = 0;
+        for email in pending {
+            let message = EmailMessage {
+                to: email.recipient.clone(),
+                subject: email.subject.clone(),
+                body: email.body.clone(),
+            };
+
+            match self.mailer.send(&message).await {
+                Ok(()) => {
+                    self.repository.mark_sent(email.id, Utc::now()).await?;
+                    delivered += 1;
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        email_id = %email.id,
+                        attempts = email.attempts,
+                        error = %err,
+                        "email delivery failed"
+                    );
+                    let retry_at = Utc::now() + retry_delay(email.attempts);
+                    self.repository
+                        .mark_failed(email.id, &err.to_string(), retry_at)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(delivered)
+    }
+}
+
+fn retry_delay(attempts: u32) -> Duration {
+    let doublings = attempts.saturating_sub(1).min(MAX_RETRY_DOUBLINGS);
+    Duration::seconds(RETRY_BASE_SECONDS) * 2i32.pow(doublings)
+}
@@ -0,0 +1,241 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::{Duration, Utc};
+
+use crate::application::services::email_outbox_service::EmailOutboxService;
+use crate::domain::entities::user::{UpdateUser, User};
+use crate::domain::mailer::EmailMessage;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::domain::value_objects::EmailAddress;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::token::{JwtManager, TokenPurpose};
+
+const TOKEN_PLACEHOLDER: &str = "{token}";
+
+// Os links sao JWTs assinados com as chaves da aplicacao: nada e persistido alem do proprio
+// estado do usuario, e um link so vale enquanto o endereco nele ainda for o atual ou o pendente.
+#[derive(Clone)]
+pub struct EmailVerificationService {
+    users: Arc<dyn UserRepository>,
+    outbox: EmailOutboxService,
+    jwt: JwtManager,
+    settings: EmailVerificationSettings,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailVerificationSettings {
+    pub token_ttl: Duration,
+    // URL enviada por email; `{token}` e substituido pelo token assinado.
+    pub link_template: String,
+}
+
+impl Default for EmailVerificationSettings {
+    fn default() -> Self {
+        Self {
+            token_ttl: Duration::hours(48),
+            link_template: "http://localhost:8080/verify-email?token={token}".to_string(),
+        }
+    }
+}
+
+impl EmailVerificationService {
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        outbox: EmailOutboxService,
+        jwt: JwtManager,
+        settings: EmailVerificationSettings,
+    ) -> Self {
+        Self {
+            users,
+            outbox,
+            jwt,
+            settings,
+        }
+    }
+
+    pub async fn send_verification(&self, user: &User) -> AppResult<()> {
+        let link = self.link_for(user, user.email())?;
+        self.outbox
+            .enqueue(EmailMessage {
+                to: user.email().as_str().to_string(),
+                subject: "Confirm your email address".to_string(),
+                body: format!(
+                    "Hello {name},\n\n\
+                     Please confirm that this is your email address by opening the link below:\n\n\
+                     {link}\n\n\
+                     The link expires in {hours} hours.\n",
+                    name = user.name().as_str(),
+                    hours = self.settings.token_ttl.num_hours()
+                ),
+            })
+            .await?;
+        Ok(())
+    }
+
+    // O endereco atual continua valendo ate o link enviado ao novo ser aberto; o antigo recebe
+    // um aviso para que o dono perceba uma troca que nao pediu. Um endereco de outra conta recebe
+    // a mesma resposta, para nao revelar a conta: o link nao sai e o dono dele e avisado.
+    pub async fn request_email_change(
+        &self,
+        user: &User,
+        new_email: EmailAddress,
+        expected_versions: Option<Vec<i64>>,
+    ) -> AppResult<User> {
+        if new_email == *user.email() {
+            return Err(AppError::Validation(
+                "new email must differ from the current one".to_string(),
+            ));
+        }
+        let updated = self
+            .users
+            .update(
+                user.id(),
+                UpdateUser::default().apply_pending_email(Some(new_email.clone())),
+                expected_versions,
+            )
+            .await?;
+
+        self.send_change_link(&updated, &new_email).await?;
+        self.outbox
+            .enqueue(EmailMessage {
+                to: updated.email().as_str().to_string(),
+                subject: "Your email address is being changed".to_string(),
+                body: format!(
+                    "Hello {name},\n\n\
+                     A change of the email address on your account to {new_email} was \
+                     requested. It takes effect only after the new address is confirmed. \
+                     If you did not ask for this, change your password and contact support.\n",
+                    name = updated.name().as_str(),
+                    new_email = new_email.as_str()
+                ),
+            })
+            .await?;
+
+        Ok(updated)
+    }
+
+    // Reenvia o link pendente: o do novo endereco, se houver troca em andamento.
+    pub async fn resend(&self, user: &User) -> AppResult<()> {
+        match user.pending_email() {
+            Some(pending) => self.send_change_link(user, pending).await,
+            None if user.is_email_verified() => {
+                Err(AppError::Conflict("email already verified".to_string()))
+            }
+            None => self.send_verification(user).await,
+        }
+    }
+
+    // Confirma o endereco atual ou conclui a troca pendente, conforme o email do token.
+    pub async fn verify(&self, token: &str) -> AppResult<User> {
+        let claims = self
+            .jwt
+            .verify(token.trim())
+            .map_err(|_| invalid_verification_link())?;
+        if claims.purpose != TokenPurpose::EmailVerification {
+            return Err(invalid_verification_link());
+        }
+
+        let user = self
+            .users
+            .find_by_id(claims.sub)
+            .await?
+            .ok_or_else(invalid_verification_link)?;
+        let now = Utc::now();
+
+        if claims.email == user.email().as_str() {
+            if user.is_email_verified() {
+                return Ok(user);
+            }
+            return self
+                .users
+                .update(
+                    user.id(),
+                    UpdateUser::default().apply_email_verified(now),
+                    None,
+                )
+                .await;
+        }
+
+        match user.pending_email() {
+            Some(pending) if pending.as_str() == claims.email => {
+                let update = UpdateUser::default()
+                    .apply_email(pending.clone())
+                    .apply_email_verified(now)
+                    .apply_pending_email(None);
+                self.users.update(user.id(), update, None).await
+            }
+            _ => Err(invalid_verification_link()),
+        }
+    }
+
+    async fn send_change_link(&self, user: &User, new_email: &EmailAddress) -> AppResult<()> {
+        match self.users.find_by_email(new_email).await? {
+            Some(owner) if owner.id() != user.id() => self.notify_address_owner(&owner).await,
+            _ => self.send_change_confirmation(user, new_email).await,
+        }
+    }
+
+    async fn notify_address_owner(&self, owner: &User) -> AppResult<()> {
+        self.outbox
+            .enqueue(EmailMessage {
+                to: owner.email().as_str().to_string(),
+                subject: "Someone tried to use your email address".to_string(),
+                body: format!(
+                    "Hello {name},\n\n\
+                     Someone asked to move another account to this email address. Nothing was \
+                     changed on your account and you can ignore this message.\n",
+                    name = owner.name().as_str()
+                ),
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn send_change_confirmation(
+        &self,
+        user: &User,
+        new_email: &EmailAddress,
+    ) -> AppResult<()> {
+        let link = self.link_for(user, new_email)?;
+        self.outbox
+            .enqueue(EmailMessage {
+                to: new_email.as_str().to_string(),
+                subject: "Confirm your new email address".to_string(),
+                body: format!(
+                    "Hello {name},\n\n\
+                     Open the link below to start using this address for your account:\n\n\
+                     {link}\n\n\
+                     The link expires in {hours} hours. Until then you keep signing in with your \
+                     current address.\n",
+                    name = user.name().as_str(),
+                    hours = self.settings.token_ttl.num_hours()
+                ),
+            })
+            .await?;
+        Ok(())
+    }
+
+    fn link_for(&self, user: &User, email: &EmailAddress) -> AppResult<String> {
+        let token = self
+            .jwt
+            .generate_for(
+                user.id(),
+                email.as_str(),
+                &[],
+                TokenPurpose::EmailVerification,
+                self.settings.token_ttl,
+            )
+            .map_err(|err| AppError::Unexpected(anyhow!("failed to issue token: {err}")))?;
+
+        Ok(self
+            .settings
+            .link_template
+            .replace(TOKEN_PLACEHOLDER, &token.token))
+    }
+}
+
+// Link expirado, adulterado ou superado por outra troca recebem a mesma resposta.
+fn invalid_verification_link() -> AppError {
+    AppError::Validation("invalid or expired verification link".to_string())
+}
@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::application::dtos::invitation::CreateInvitationDto;
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::application::services::email_outbox_service::EmailOutboxService;
+use crate::application::services::role_service::RoleService;
+use crate::domain::entities::invitation::{Invitation, NewInvitation};
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::user::{NewUser, UpdateUser, User, UserStatus};
+use crate::domain::mailer::EmailMessage;
+use crate::domain::repositories::invitation_repository::InvitationRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::domain::value_objects::{EmailAddress, PasswordHash, UserName};
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::hashing_pool::HashingPool;
+use crate::shared::security::opaque_token;
+use crate::shared::security::password_policy::PasswordPolicy;
+
+const TOKEN_PLACEHOLDER: &str = "{token}";
+
+// Substitui a criacao com senha definida pelo admin: o convidado nasce `pending`, com uma senha
+// aleatoria descartada, e escolhe a propria ao aceitar o link.
+#[derive(Clone)]
+pub struct InvitationService {
+    users: Arc<dyn UserRepository>,
+    invitations: Arc<dyn InvitationRepository>,
+    roles: RoleService,
+    outbox: EmailOutboxService,
+    hasher: HashingPool,
+    password_policy: PasswordPolicy,
+    settings: InvitationSettings,
+}
+
+#[derive(Debug, Clone)]
+pub struct InvitationSettings {
+    pub token_ttl: Duration,
+    // URL enviada por email; `{token}` e substituido pelo token em claro.
+    pub link_template: String,
+}
+
+impl Default for InvitationSettings {
+    fn default() -> Self {
+        Self {
+            token_ttl: Duration::hours(72),
+            link_template: "http://localhost:8080/accept-invitation?token={token}".to_string(),
+        }
+    }
+}
+
+impl InvitationService {
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        invitations: Arc<dyn InvitationRepository>,
+        roles: RoleService,
+        outbox: EmailOutboxService,
+        hasher: HashingPool,
+        password_policy: PasswordPolicy,
+        settings: InvitationSettings,
+    ) -> Self {
+        Self {
+            users,
+            invitations,
+            roles,
+            outbox,
+            hasher,
+            password_policy,
+            settings,
+        }
+    }
+
+    pub async fn invite(
+        &self,
+        actor: &AuthenticatedUser,
+        dto: CreateInvitationDto,
+    ) -> AppResult<Invitation> {
+        actor.require_permission(Permission::UsersWrite)?;
+
+        let roles = self
+            .roles
+            .resolve_assignable(Some(actor), &dto.roles)
+            .await?;
+        let name = UserName::parse(&dto.name)?;
+        let email = EmailAddress::parse(&dto.email)?;
+        let placeholder_hash = self
+            .hasher
+            .hash_password(&opaque_token::generate())
+            .await?
+            .map_err(|err| AppError::Unexpected(anyhow!("failed to hash password: {err}")))?;
+        let password_hash = PasswordHash::new(&placeholder_hash)?;
+
+        let token = opaque_token::generate();
+        let token_hash = opaque_token::hash(&token);
+        let user = self
+            .users
+            .create(
+                NewUser::build(name, email, password_hash, roles)
+                    .with_status(UserStatus::Pending)
+                    .with_invitation(NewInvitation {
+                        invited_by: Some(actor.id),
+                        token_hash: token_hash.clone(),
+                        expires_at: self.expires_at()?,
+                    }),
+            )
+            .await?;
+        let invitation = self
+            .invitations
+            .find_by_hash(&token_hash)
+            .await?
+            .ok_or_else(|| {
+                AppError::Unexpected(anyhow!("invitation of user {} not found", user.id()))
+            })?;
+
+        self.send_invitation(&user, &token).await?;
+        Ok(invitation)
+    }
+
+    // Inclui convites expirados, que continuam pendentes ate serem reenviados ou revogados.
+    pub async fn list_pending(&self, actor: &AuthenticatedUser) -> AppResult<Vec<Invitation>> {
+        actor.require_permission(Permission::UsersRead)?;
+        self.invitations.find_pending().await
+    }
+
+    // Gera um novo link com validade renovada; o anterior deixa de funcionar.
+    pub async fn resend(&self, actor: &AuthenticatedUser, id: Uuid) -> AppResult<Invitation> {
+        actor.require_permission(Permission::UsersWrite)?;
+        let invitation = self.find_pending(id).await?;
+
+        let token = opaque_token::generate();
+        let invitation = self
+            .invitations
+            .replace_token(
+                invitation.id,
+                &opaque_token::hash(&token),
+                self.expires_at()?,
+            )
+            .await?
+            .ok_or_else(|| invitation_not_found(id))?;
+        let user = self
+            .users
+            .find_by_id(invitation.user_id)
+            .await?
+            .ok_or_else(|| invitation_not_found(id))?;
+
+        self.send_invitation(&user, &token).await?;
+        Ok(invitation)
+    }
+
+    // O usuario pendente e excluido logicamente, como qualquer exclusao, e sai das listagens.
+    pub async fn revoke(&self, actor: &AuthenticatedUser, id: Uuid) -> AppResult<Invitation> {
+        actor.require_permission(Permission::UsersWrite)?;
+        let invitation = self.find_pending(id).await?;
+
+        if !self.invitations.revoke(invitation.id, Utc::now()).await? {
+            return Err(invitation_not_found(id));
+        }
+        match self.users.delete(invitation.user_id, None).await {
+            Ok(()) | Err(AppError::NotFound(_)) => Ok(invitation),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Publico: o token do email autentica o convidado. Ele so e consumido depois que a senha
+    // passa na validacao, para que um erro de digitacao nao obrigue a pedir outro convite.
+    pub async fn accept(&self, token: &str, new_password: &str) -> AppResult<User> {
+        let now = Utc::now();
+        let invitation = self
+            .invitations
+            .find_by_hash(&opaque_token::hash(token.trim()))
+            .await?
+            .filter(|invitation| invitation.is_usable(now))
+            .ok_or_else(invalid_invitation)?;
+        let user = self
+            .users
+            .find_by_id(invitation.user_id)
+            .await?
+            .filter(|user| user.status() == UserStatus::Pending)
+            .ok_or_else(invalid_invitation)?;
+        let next = user.status().transition_to(UserStatus::Active)?;
+
+        let plain_password = self
+            .password_policy
+            .validate(new_password, &[user.name().as_str(), user.email().as_str()])?;
+        let password_hash_raw = self
+            .hasher
+            .hash_password(plain_password.as_str())
+            .await?
+            .map_err(|err| AppError::Unexpected(anyhow!("failed to hash password: {err}")))?;
+        let password_hash = PasswordHash::new(&password_hash_raw)?;
+
+        if !self.invitations.accept(invitation.id, now).await? {
+            return Err(invalid_invitation());
+        }
+
+        // Abrir o link recebido por email tambem comprova a posse do endereco.
+        let update = UpdateUser::default()
+            .apply_password_hash(password_hash)
+            .apply_status(next, None)
+            .apply_email_verified(now);
+        self.users
+            .update(user.id(), update, Some(vec![user.version()]))
+            .await
+    }
+
+    async fn find_pending(&self, id: Uuid) -> AppResult<Invitation> {
+        self.invitations
+            .find_by_id(id)
+            .await?
+            .filter(Invitation::is_pending)
+            .ok_or_else(|| invitation_not_found(id))
+    }
+
+    async fn send_invitation(&self, user: &User, token: &str) -> AppResult<()> {
+        let link = self
+            .settings
+            .link_template
+            .replace(TOKEN_PLACEHOLDER, token);
+        self.outbox
+            .enqueue(EmailMessage {
+                to: user.email().as_str().to_string(),
+                subject: "You have been invited".to_string(),
+                body: format!(
+                    "Hello {name},\n\n\
+                     An account was created for you. Open the link below to choose your password \
+                     and activate it:\n\n\
+                     {link}\n\n\
+                     The link expires in {hours} hours and can be used only once.\n",
+                    name = user.name().as_str(),
+                    hours = self.settings.token_ttl.num_hours()
+                ),
+            })
+            .await?;
+        Ok(())
+    }
+
+    fn expires_at(&self) -> AppResult<DateTime<Utc>> {
+        Utc::now()
+            .checked_add_signed(self.settings.token_ttl)
+            .ok_or_else(|| AppError::Unexpected(anyhow!("invalid invitation ttl")))
+    }
+}
+
+fn invitation_not_found(id: Uuid) -> AppError {
+    AppError::NotFound(format!("invitation {id} not found"))
+}
+
+// Token inexistente, expirado, revogado ou ja usado recebem a mesma resposta.
+fn invalid_invitation() -> AppError {
+    AppError::Validation("invalid or expired invitation".to_string())
+}
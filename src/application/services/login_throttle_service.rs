@@ -0,0 +1,202 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::entities::login_throttle::LoginThrottle;
+use crate::domain::repositories::login_throttle_repository::LoginThrottleRepository;
+use crate::domain::value_objects::EmailAddress;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::opaque_token;
+
+// Limite para o multiplicador do bloqueio progressivo (2^6 = 64x a duracao base).
+const MAX_LOCKOUT_DOUBLINGS: u32 = 6;
+
+// Tetos de `lockout_minutes` e `backoff_max_seconds` validados na subida; com eles o bloqueio
+// mais longo (64x a duracao base) continua cabendo em um `DateTime`.
+pub const MAX_LOCKOUT_MINUTES: i64 = 7 * 24 * 60;
+pub const MAX_BACKOFF_SECONDS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone)]
+pub struct LockoutPolicy {
+    pub max_account_failures: u32,
+    pub max_ip_failures: u32,
+    pub failure_window: Duration,
+    pub lockout_duration: Duration,
+    pub backoff_base: Duration,
+    pub backoff_max: Duration,
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self {
+            max_account_failures: 5,
+            max_ip_failures: 20,
+            failure_window: Duration::minutes(15),
+            lockout_duration: Duration::minutes(15),
+            backoff_base: Duration::seconds(1),
+            backoff_max: Duration::seconds(30),
+        }
+    }
+}
+
+// Conta falhas de login por email informado e por IP de origem. A chave e o email digitado (e
+// nao o id do usuario) para que contas inexistentes sejam bloqueadas exatamente como as reais.
+#[derive(Clone)]
+pub struct LoginThrottleService {
+    repository: Arc<dyn LoginThrottleRepository>,
+    policy: LockoutPolicy,
+}
+
+impl LoginThrottleService {
+    pub fn new(repository: Arc<dyn LoginThrottleRepository>, policy: LockoutPolicy) -> Self {
+        Self { repository, policy }
+    }
+
+    // Recusa a tentativa antes de tocar na senha quando a conta ou o IP estao bloqueados, ou
+    // quando o back-off exponencial da ultima falha da conta ainda nao passou.
+    pub async fn check(&self, email: &str, source_ip: Option<IpAddr>) -> AppResult<()> {
+        let now = Utc::now();
+
+        if let Some(throttle) = self.repository.find(&account_key(email)).await? {
+            if throttle.is_locked(now) {
+                return Err(throttled(now, throttle.locked_until));
+            }
+
+            if !self.window_expired(&throttle, now) && throttle.failure_count > 0 {
+                let retry_at = saturating_add(
+                    throttle.last_failure_at,
+                    self.backoff(throttle.failure_count),
+                );
+                if retry_at > now {
+                    return Err(throttled(now, Some(retry_at)));
+                }
+            }
+        }
+
+        if let Some(ip) = source_ip {
+            if let Some(throttle) = self.repository.find(&ip_key(ip)).await? {
+                if throttle.is_locked(now) {
+                    return Err(throttled(now, throttle.locked_until));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Registra a falha e, se algum limite foi atingido, devolve o erro de bloqueio a ser
+    // propagado no lugar de "invalid credentials".
+    pub async fn record_failure(
+        &self,
+        email: &str,
+        source_ip: Option<IpAddr>,
+    ) -> AppResult<Option<AppError>> {
+        let now = Utc::now();
+        let window_start = now - self.policy.failure_window;
+        let mut locked_until = None;
+
+        let mut keys = vec![(account_key(email), self.policy.max_account_failures)];
+        if let Some(ip) = source_ip {
+            keys.push((ip_key(ip), self.policy.max_ip_failures));
+        }
+
+        for (key, limit) in keys {
+            let throttle = self
+                .repository
+                .record_failure(&key, now, window_start)
+                .await?;
+
+            if let Some(until) = self.lockout_until(&throttle, limit, now) {
+                self.repository.lock(&key, until).await?;
+                locked_until = locked_until.max(Some(until));
+            }
+        }
+
+        Ok(locked_until.map(|until| throttled(now, Some(until))))
+    }
+
+    pub async fn record_success(&self, email: &str) -> AppResult<()> {
+        self.repository.clear(&account_key(email)).await
+    }
+
+    // Desbloqueio administrativo: limpa apenas o contador da conta, nunca o do IP.
+    pub async fn unlock_account(&self, email: &str) -> AppResult<()> {
+        self.repository.clear(&account_key(email)).await
+    }
+
+    pub async fn purge_stale(&self) -> AppResult<u64> {
+        self.repository
+            .purge_stale(Utc::now() - self.policy.failure_window)
+            .await
+    }
+
+    // Bloqueia a cada multiplo do limite; cada novo bloqueio na mesma janela dobra a duracao.
+    fn lockout_until(
+        &self,
+        throttle: &LoginThrottle,
+        limit: u32,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        if throttle.failure_count == 0 || !throttle.failure_count.is_multiple_of(limit) {
+            return None;
+        }
+
+        let doublings = (throttle.failure_count / limit - 1).min(MAX_LOCKOUT_DOUBLINGS);
+        let duration = self
+            .policy
+            .lockout_duration
+            .checked_mul(2i32.pow(doublings))
+            .unwrap_or(Duration::MAX);
+        Some(saturating_add(now, duration))
+    }
+
+    fn backoff(&self, failures: u32) -> Duration {
+        let doublings = failures.saturating_sub(1).min(16);
+        self.policy
+            .backoff_base
+            .checked_mul(2i32.pow(doublings))
+            .unwrap_or(Duration::MAX)
+            .min(self.policy.backoff_max)
+    }
+
+    fn window_expired(&self, throttle: &LoginThrottle, now: DateTime<Utc>) -> bool {
+        let last_activity = throttle
+            .locked_until
+            .map_or(throttle.last_failure_at, |until| {
+                until.max(throttle.last_failure_at)
+            });
+        last_activity < now - self.policy.failure_window
+    }
+}
+
+// Usa a mesma identidade das buscas de usuario, para que variar caixa ou normalizacao do email
+// nao abra um contador novo.
+fn account_key(email: &str) -> String {
+    let identity = EmailAddress::parse(email)
+        .map(|address| address.canonical().to_string())
+        .unwrap_or_else(|_| email.trim().to_lowercase());
+    opaque_token::hash(&format!("account:{identity}"))
+}
+
+fn ip_key(ip: IpAddr) -> String {
+    opaque_token::hash(&format!("ip:{ip}"))
+}
+
+fn saturating_add(at: DateTime<Utc>, duration: Duration) -> DateTime<Utc> {
+    at.checked_add_signed(duration)
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+// Mesma mensagem para conta, IP e back-off: a resposta nao revela se o email existe.
+fn throttled(now: DateTime<Utc>, until: Option<DateTime<Utc>>) -> AppError {
+    let retry_after_seconds = until
+        .map(|until| ((until - now).num_milliseconds().max(0) as u64).div_ceil(1000))
+        .unwrap_or(1)
+        .max(1);
+
+    AppError::TooManyRequests {
+        message: "too many login attempts, try again later".to_string(),
+        retry_after_seconds,
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt::Write;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::application::services::role_service::parse_role_name;
+use crate::domain::entities::mfa::MfaPolicy;
+use crate::domain::entities::permission::Permission;
+use crate::domain::repositories::mfa_repository::MfaRepository;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::domain::value_objects::RoleName;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::hashing_pool::HashingPool;
+use crate::shared::security::password::PasswordError;
+use crate::shared::security::totp::{self, TotpError};
+
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_BYTES: usize = 5;
+
+#[derive(Clone)]
+pub struct MfaService {
+    repository: Arc<dyn MfaRepository>,
+    roles: Arc<dyn RoleRepository>,
+    hasher: HashingPool,
+    issuer: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TotpEnrollment {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+impl MfaService {
+    pub fn new(
+        repository: Arc<dyn MfaRepository>,
+        roles: Arc<dyn RoleRepository>,
+        hasher: HashingPool,
+        issuer: impl Into<String>,
+    ) -> Self {
+        Self {
+            repository,
+            roles,
+            hasher,
+            issuer: issuer.into(),
+        }
+    }
+
+    // Gera um segredo novo a cada chamada; so passa a valer apos `confirm_totp_enrollment`.
+    pub async fn begin_totp_enrollment(
+        &self,
+        user: &AuthenticatedUser,
+    ) -> AppResult<TotpEnrollment> {
+        let secret = totp::generate_secret();
+
+        if !self.repository.save_pending_totp(user.id, &secret).await? {
+            return Err(AppError::Conflict("mfa already enabled".to_string()));
+        }
+
+        Ok(TotpEnrollment {
+            otpauth_uri: totp::provisioning_uri(&secret, &self.issuer, &user.email),
+            secret,
+        })
+    }
+
+    // Retorna os recovery codes em texto puro uma unica vez; apenas os hashes sao persistidos.
+    pub async fn confirm_totp_enrollment(
+        &self,
+        user_id: Uuid,
+        code: &str,
+    ) -> AppResult<Vec<String>> {
+        let credential = self
+            .repository
+            .find_totp(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("no pending mfa enrollment".to_string()))?;
+
+        if credential.is_confirmed() {
+            return Err(AppError::Conflict("mfa already enabled".to_string()));
+        }
+
+        let now = Utc::now();
+        let step = totp::verify(&credential.secret, code, now)
+            .map_err(map_totp_error)?
+            .ok_or_else(|| AppError::Validation("invalid mfa code".to_string()))?;
+
+        let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+            .map(|_| generate_recovery_code())
+            .collect();
+        let hashes = self
+            .hasher
+            .hash_passwords(
+                codes
+                    .iter()
+                    .map(|code| normalize_recovery_code(code))
+                    .collect(),
+            )
+            .await?
+            .map_err(|err| AppError::Unexpected(anyhow!("failed to hash recovery code: {err}")))?;
+
+        self.repository
+            .confirm_totp(user_id, step, now, hashes)
+            .await?;
+
+        Ok(codes)
+    }
+
+    pub async fn is_enrolled(&self, user_id: Uuid) -> AppResult<bool> {
+        Ok(self
+            .repository
+            .find_totp(user_id)
+            .await?
+            .is_some_and(|credential| credential.is_confirmed()))
+    }
+
+    // Basta um dos papeis do usuario exigir MFA.
+    pub async fn is_required_for(&self, roles: &[RoleName]) -> AppResult<bool> {
+        Ok(self
+            .repository
+            .find_policies()
+            .await?
+            .iter()
+            .any(|policy| policy.required && roles.contains(&policy.role)))
+    }
+
+    // Aceita um codigo TOTP (uma unica vez por passo) ou um recovery code ainda nao usado.
+    pub async fn verify_second_factor(&self, user_id: Uuid, code: &str) -> AppResult<()> {
+        let credential = self
+            .repository
+            .find_totp(user_id)
+            .await?
+            .filter(|credential| credential.is_confirmed())
+            .ok_or_else(invalid_mfa_code)?;
+
+        let now = Utc::now();
+        if let Some(step) = totp::verify(&credential.secret, code, now).map_err(map_totp_error)? {
+            if !self.repository.advance_totp_step(user_id, step).await? {
+                return Err(AppError::Unauthorized("mfa code already used".to_string()));
+            }
+            return Ok(());
+        }
+
+        let candidate = normalize_recovery_code(code);
+        if candidate.is_empty() {
+            return Err(invalid_mfa_code());
+        }
+
+        for recovery_code in self.repository.find_unused_recovery_codes(user_id).await? {
+            match self
+                .hasher
+                .verify_password(&recovery_code.code_hash, &candidate)
+                .await?
+            {
+                Ok(()) => {
+                    if self
+                        .repository
+                        .mark_recovery_code_used(recovery_code.id, now)
+                        .await?
+                    {
+                        return Ok(());
+                    }
+                    return Err(AppError::Unauthorized("mfa code already used".to_string()));
+                }
+                Err(PasswordError::InvalidPassword) => continue,
+                Err(PasswordError::Hash(_)) => {
+                    return Err(AppError::Unexpected(anyhow!(
+                        "failed to verify stored recovery code hash"
+                    )))
+                }
+            }
+        }
+
+        Err(invalid_mfa_code())
+    }
+
+    // Lista todos os papeis; quem nunca teve politica gravada aparece como nao obrigatorio.
+    pub async fn list_policies(&self, actor: &AuthenticatedUser) -> AppResult<Vec<MfaPolicy>> {
+        actor.require_permission(Permission::RolesRead)?;
+        let policies = self.repository.find_policies().await?;
+
+        Ok(self
+            .roles
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|role| {
+                policies
+                    .iter()
+                    .find(|policy| policy.role == role.name)
+                    .cloned()
+                    .unwrap_or(MfaPolicy {
+                        role: role.name,
+                        required: false,
+                        updated_at: role.updated_at,
+                    })
+            })
+            .collect())
+    }
+
+    pub async fn set_role_policy(
+        &self,
+        actor: &AuthenticatedUser,
+        role: &str,
+        required: bool,
+    ) -> AppResult<MfaPolicy> {
+        actor.require_permission(Permission::RolesWrite)?;
+
+        let role = parse_role_name(role)?;
+        if self.roles.find_by_name(&role).await?.is_none() {
+            return Err(AppError::NotFound(format!("role {role} not found")));
+        }
+        self.repository.set_policy(&role, required).await
+    }
+}
+
+fn generate_recovery_code() -> String {
+    let mut bytes = [0u8; RECOVERY_CODE_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    let encoded = bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        });
+    format!("{}-{}", &encoded[..5], &encoded[5..])
+}
+
+// Hifens e espacos sao apenas apresentacao; comparamos o codigo sem eles.
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn invalid_mfa_code() -> AppError {
+    AppError::Unauthorized("invalid mfa code".to_string())
+}
+
+fn map_totp_error(err: TotpError) -> AppError {
+    AppError::Unexpected(anyhow!("failed to evaluate totp code: {err}"))
+}
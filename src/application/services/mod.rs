@@ -1,2 +1,15 @@
-﻿pub mod auth_service;
+pub mod auth_service;
+pub mod email_outbox_service;
+pub mod email_verification_service;
+pub mod invitation_service;
+pub mod login_throttle_service;
+pub mod mfa_service;
+pub mod oauth_service;
+pub mod password_history_service;
+pub mod password_reset_service;
+pub mod personal_access_token_service;
+pub mod registration_service;
+pub mod role_service;
+pub mod session_service;
+pub mod token_revocation_service;
 pub mod user_service;
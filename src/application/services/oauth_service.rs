@@ -0,0 +1,1097 @@
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Duration, Utc};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::application::dtos::oauth::{
+    AuthorizationRequestDto, CreateOAuthClientDto, TokenRequestDto,
+};
+use crate::application::services::auth_service::{AuthService, AuthenticatedUser, LoginOutcome};
+use crate::domain::entities::oauth::{
+    NewAuthorizationCode, NewOAuthClient, OAuthClient, OAuthClientType, OAuthConsent,
+    OAuthGrantType,
+};
+use crate::domain::entities::permission::{parse_scope, Permission};
+use crate::domain::entities::session::{SessionClient, SessionGrant};
+use crate::domain::repositories::oauth_authorization_code_repository::AuthorizationCodeRepository;
+use crate::domain::repositories::oauth_client_repository::OAuthClientRepository;
+use crate::domain::repositories::oauth_consent_repository::OAuthConsentRepository;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::{opaque_token, pkce};
+
+// Mesmo raciocinio do prefixo dos tokens pessoais: facilita a deteccao por secret scanners.
+pub const CLIENT_SECRET_PREFIX: &str = "wr_cs_";
+const MAX_NAME_CHARS: usize = 100;
+const MAX_REDIRECT_URIS: usize = 10;
+const MAX_REDIRECT_URI_CHARS: usize = 2048;
+const MAX_STATE_CHARS: usize = 1024;
+// Hosts aceitos com `http` (RFC 8252, secao 7.3): o redirect nunca sai da maquina.
+const LOOPBACK_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "[::1]"];
+// Parametros do redirect: tudo menos os caracteres nao reservados da RFC 3986.
+const QUERY_VALUE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+// Servidor de autorizacao OAuth 2.0 sobre o `AuthService`: os tokens emitidos sao os mesmos JWT
+// e refresh tokens do login, limitados aos escopos concedidos ao cliente.
+#[derive(Clone)]
+pub struct OAuthService {
+    clients: Arc<dyn OAuthClientRepository>,
+    codes: Arc<dyn AuthorizationCodeRepository>,
+    consents: Arc<dyn OAuthConsentRepository>,
+    auth: AuthService,
+    settings: OAuthSettings,
+}
+
+#[derive(Debug, Clone)]
+pub struct OAuthSettings {
+    // Curto de proposito: o codigo so precisa sobreviver ao redirect e a troca imediata.
+    pub authorization_code_ttl: Duration,
+}
+
+impl Default for OAuthSettings {
+    fn default() -> Self {
+        Self {
+            authorization_code_ttl: Duration::seconds(60),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedOAuthClient {
+    pub client: OAuthClient,
+    // Segredo em claro dos clientes confidenciais, devolvido uma unica vez no registro.
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum AuthorizationOutcome {
+    // Consentimento ja registrado: `redirect_to` leva o codigo de volta ao cliente.
+    Approved {
+        redirect_to: String,
+        scopes: Vec<Permission>,
+    },
+    // Recusa do usuario ou pedido invalido, entregue ao cliente pelo redirect.
+    Rejected {
+        redirect_to: String,
+        error: OAuthErrorCode,
+    },
+    // O usuario precisa aprovar os escopos em `POST /oauth/authorize`.
+    ConsentRequired {
+        client: OAuthClient,
+        scopes: Vec<Permission>,
+    },
+}
+
+// Proximo passo do navegador em `/oauth/authorize`. A sessao vem do cookie aberto pelo login da
+// propria pagina de autorizacao; o cliente nunca ve a senha nem um token do usuario.
+#[derive(Debug, Clone)]
+pub enum BrowserStep {
+    // Sessao valida: o resultado de `authorize`/`decide`. O formulario de consentimento devolve
+    // o `csrf_token`.
+    Authorized {
+        user: Box<AuthenticatedUser>,
+        outcome: Box<AuthorizationOutcome>,
+        csrf_token: String,
+    },
+    // Sem sessao valida: pedir email e senha.
+    SignIn {
+        error: Option<String>,
+    },
+    // Senha aceita para um usuario com TOTP: pedir o codigo junto com o desafio.
+    SecondFactor {
+        challenge_token: String,
+        error: Option<String>,
+    },
+}
+
+// O que o usuario enviou na pagina de login: a senha ou, depois dela, o codigo TOTP.
+#[derive(Debug, Clone)]
+pub enum BrowserCredentials {
+    Password {
+        email: String,
+        password: String,
+    },
+    SecondFactor {
+        challenge_token: String,
+        code: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum BrowserSignIn {
+    // Sessao aberta: o access token vai para o cookie e o navegador volta a `/oauth/authorize`.
+    SignedIn {
+        session_token: String,
+        expires_at: DateTime<Utc>,
+    },
+    // Login incompleto ou recusado: mostrar a pagina de novo.
+    Retry(BrowserStep),
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedOAuthTokens {
+    pub access_token: String,
+    pub expires_at: DateTime<Utc>,
+    pub refresh_token: Option<String>,
+    pub scopes: Vec<Permission>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClientConsent {
+    pub consent: OAuthConsent,
+    pub client: OAuthClient,
+}
+
+// Credenciais enviadas no header `Authorization: Basic` (RFC 6749, secao 2.3.1).
+#[derive(Debug, Clone)]
+pub struct ClientCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+// Codigos de erro da RFC 6749 (secoes 4.1.2.1 e 5.2).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OAuthErrorCode {
+    InvalidRequest,
+    InvalidClient,
+    InvalidGrant,
+    UnauthorizedClient,
+    UnsupportedGrantType,
+    UnsupportedResponseType,
+    InvalidScope,
+    AccessDenied,
+}
+
+impl OAuthErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidRequest => "invalid_request",
+            Self::InvalidClient => "invalid_client",
+            Self::InvalidGrant => "invalid_grant",
+            Self::UnauthorizedClient => "unauthorized_client",
+            Self::UnsupportedGrantType => "unsupported_grant_type",
+            Self::UnsupportedResponseType => "unsupported_response_type",
+            Self::InvalidScope => "invalid_scope",
+            Self::AccessDenied => "access_denied",
+        }
+    }
+}
+
+impl fmt::Display for OAuthErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Erros do endpoint de token. Falhas de infraestrutura seguem como `AppError` para manter o
+// status (500, 503) e o log de sempre.
+#[derive(Debug, Error)]
+pub enum OAuthError {
+    #[error("{code}: {description}")]
+    Protocol {
+        code: OAuthErrorCode,
+        description: String,
+    },
+    #[error(transparent)]
+    Server(AppError),
+}
+
+impl OAuthError {
+    fn new(code: OAuthErrorCode, description: impl Into<String>) -> Self {
+        Self::Protocol {
+            code,
+            description: description.into(),
+        }
+    }
+}
+
+// Falhas de credencial do usuario (conta suspensa, sessao revogada) invalidam o grant; o resto
+// nao e problema do cliente.
+impl From<AppError> for OAuthError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::Validation(message) => Self::new(OAuthErrorCode::InvalidRequest, message),
+            AppError::Unauthorized(message)
+            | AppError::Forbidden(message)
+            | AppError::NotFound(message) => Self::new(OAuthErrorCode::InvalidGrant, message),
+            other => Self::Server(other),
+        }
+    }
+}
+
+impl OAuthService {
+    pub fn new(
+        clients: Arc<dyn OAuthClientRepository>,
+        codes: Arc<dyn AuthorizationCodeRepository>,
+        consents: Arc<dyn OAuthConsentRepository>,
+        auth: AuthService,
+        settings: OAuthSettings,
+    ) -> Self {
+        Self {
+            clients,
+            codes,
+            consents,
+            auth,
+            settings,
+        }
+    }
+
+    // O escopo do cliente e limitado ao que o proprio admin pode conceder.
+    pub async fn register_client(
+        &self,
+        actor: &AuthenticatedUser,
+        payload: CreateOAuthClientDto,
+    ) -> AppResult<IssuedOAuthClient> {
+        actor.require_permission(Permission::ClientsWrite)?;
+
+        let name = payload.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(AppError::Validation(format!(
+                "client name must have between 1 and {MAX_NAME_CHARS} characters"
+            )));
+        }
+
+        let client_type = payload
+            .client_type
+            .trim()
+            .parse::<OAuthClientType>()
+            .map_err(|_| {
+                AppError::Validation("client_type must be confidential or public".to_string())
+            })?;
+        let grant_types = parse_grant_types(&payload.grant_types, client_type)?;
+        let redirect_uris = parse_redirect_uris(&payload.redirect_uris, &grant_types)?;
+
+        let mut scopes = Vec::new();
+        for value in &payload.scopes {
+            let value = value.trim();
+            let scope = value
+                .parse::<Permission>()
+                .map_err(|_| AppError::Validation(format!("invalid scope: {value}")))?;
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        scopes.sort();
+        actor.ensure_can_grant(&scopes)?;
+
+        let secret = match client_type {
+            OAuthClientType::Confidential => Some(format!(
+                "{CLIENT_SECRET_PREFIX}{}",
+                opaque_token::generate()
+            )),
+            OAuthClientType::Public => None,
+        };
+
+        let client = self
+            .clients
+            .create(NewOAuthClient {
+                name: name.to_string(),
+                client_type,
+                secret_hash: secret.as_deref().map(opaque_token::hash),
+                redirect_uris,
+                grant_types,
+                scopes,
+                owner_id: actor.id,
+            })
+            .await?;
+
+        Ok(IssuedOAuthClient { client, secret })
+    }
+
+    pub async fn list_clients(&self, actor: &AuthenticatedUser) -> AppResult<Vec<OAuthClient>> {
+        actor.require_permission(Permission::ClientsRead)?;
+        self.clients.list_active().await
+    }
+
+    pub async fn get_client(&self, actor: &AuthenticatedUser, id: Uuid) -> AppResult<OAuthClient> {
+        actor.require_permission(Permission::ClientsRead)?;
+        self.clients
+            .find_by_id(id)
+            .await?
+            .filter(OAuthClient::is_active)
+            .ok_or_else(|| AppError::NotFound(format!("client {id} not found")))
+    }
+
+    // Desativar o cliente encerra todas as sessoes abertas por ele; access tokens de
+    // `client_credentials` nao tem sessao e valem ate expirar.
+    pub async fn disable_client(&self, actor: &AuthenticatedUser, id: Uuid) -> AppResult<()> {
+        actor.require_permission(Permission::ClientsWrite)?;
+
+        if !self.clients.disable(id, Utc::now()).await? {
+            return Err(AppError::NotFound(format!("client {id} not found")));
+        }
+        self.auth.sessions().end_for_client(id, None).await?;
+        Ok(())
+    }
+
+    // Cliente ou `redirect_uri` invalidos nao podem ser devolvidos por redirect (RFC 6749,
+    // secao 4.1.2.1) e viram erro da propria API. Os demais erros vao para o cliente.
+    pub async fn authorize(
+        &self,
+        actor: &AuthenticatedUser,
+        request: &AuthorizationRequestDto,
+    ) -> AppResult<AuthorizationOutcome> {
+        actor.require_interactive_session()?;
+        let (client, redirect_uri) = self.resolve_redirect(request).await?;
+
+        let scopes = match check_request(&client, request, actor) {
+            Ok(scopes) => scopes,
+            Err(error) => return Ok(reject(&redirect_uri, request, error)),
+        };
+
+        let consent = self.consents.find(actor.id, client.id).await?;
+        if consent.is_some_and(|consent| consent.covers(&scopes)) {
+            return self
+                .issue_code(actor, &client, redirect_uri, request, scopes)
+                .await;
+        }
+        Ok(AuthorizationOutcome::ConsentRequired { client, scopes })
+    }
+
+    // Resposta do usuario a tela de consentimento. A aprovacao soma os escopos aos ja
+    // consentidos para o cliente.
+    pub async fn decide(
+        &self,
+        actor: &AuthenticatedUser,
+        request: &AuthorizationRequestDto,
+        approve: bool,
+    ) -> AppResult<AuthorizationOutcome> {
+        actor.require_interactive_session()?;
+        let (client, redirect_uri) = self.resolve_redirect(request).await?;
+
+        let scopes = match check_request(&client, request, actor) {
+            Ok(scopes) => scopes,
+            Err(error) => return Ok(reject(&redirect_uri, request, error)),
+        };
+        if !approve {
+            return Ok(reject(
+                &redirect_uri,
+                request,
+                (
+                    OAuthErrorCode::AccessDenied,
+                    "the user denied the request".to_string(),
+                ),
+            ));
+        }
+
+        self.consents.grant(actor.id, client.id, &scopes).await?;
+        self.issue_code(actor, &client, redirect_uri, request, scopes)
+            .await
+    }
+
+    // `GET /oauth/authorize` no navegador. Cliente e `redirect_uri` sao conferidos antes do login
+    // para nao pedir a senha em nome de um cliente invalido.
+    pub async fn authorize_in_browser(
+        &self,
+        session_token: Option<&str>,
+        request: &AuthorizationRequestDto,
+    ) -> AppResult<BrowserStep> {
+        self.resolve_redirect(request).await?;
+        let (Some(token), Some(actor)) = (session_token, self.browser_user(session_token).await?)
+        else {
+            return Ok(BrowserStep::SignIn { error: None });
+        };
+
+        let outcome = self.authorize(&actor, request).await?;
+        Ok(BrowserStep::Authorized {
+            user: Box::new(actor),
+            outcome: Box::new(outcome),
+            csrf_token: csrf_token(token),
+        })
+    }
+
+    // Resposta a tela de consentimento. O `csrf_token` so sai da pagina renderizada para a
+    // sessao do cookie, entao outro site nao consegue aprovar em nome do usuario.
+    pub async fn decide_in_browser(
+        &self,
+        session_token: Option<&str>,
+        csrf: &str,
+        request: &AuthorizationRequestDto,
+        approve: bool,
+    ) -> AppResult<BrowserStep> {
+        self.resolve_redirect(request).await?;
+        let (Some(token), Some(actor)) = (session_token, self.browser_user(session_token).await?)
+        else {
+            return Ok(BrowserStep::SignIn { error: None });
+        };
+        if csrf != csrf_token(token) {
+            return Err(AppError::Forbidden("invalid csrf token".to_string()));
+        }
+
+        let outcome = self.decide(&actor, request, approve).await?;
+        Ok(BrowserStep::Authorized {
+            user: Box::new(actor),
+            outcome: Box::new(outcome),
+            csrf_token: csrf_token(token),
+        })
+    }
+
+    // Login da pagina de autorizacao, com as mesmas regras de `POST /auth/login` (bloqueio,
+    // MFA, status da conta). Recusas voltam como mensagem na pagina.
+    pub async fn sign_in_in_browser(
+        &self,
+        credentials: BrowserCredentials,
+        client: &SessionClient,
+    ) -> AppResult<BrowserSignIn> {
+        let (result, challenge) = match credentials {
+            BrowserCredentials::Password { email, password } => (
+                self.auth.authenticate(&email, &password, client).await,
+                None,
+            ),
+            BrowserCredentials::SecondFactor {
+                challenge_token,
+                code,
+            } => (
+                self.auth.verify_mfa(&challenge_token, &code, client).await,
+                Some(challenge_token),
+            ),
+        };
+
+        let retry = |error: String| match &challenge {
+            Some(challenge_token) => BrowserStep::SecondFactor {
+                challenge_token: challenge_token.clone(),
+                error: Some(error),
+            },
+            None => BrowserStep::SignIn { error: Some(error) },
+        };
+        let step = match result {
+            Ok(LoginOutcome::Authenticated(session)) => {
+                return Ok(BrowserSignIn::SignedIn {
+                    session_token: session.token,
+                    expires_at: session.expires_at,
+                })
+            }
+            Ok(LoginOutcome::MfaRequired(challenge)) => BrowserStep::SecondFactor {
+                challenge_token: challenge.token,
+                error: None,
+            },
+            Ok(LoginOutcome::MfaEnrollmentRequired(_)) => BrowserStep::SignIn {
+                error: Some(
+                    "set up two-factor authentication in the app before signing in here"
+                        .to_string(),
+                ),
+            },
+            Ok(LoginOutcome::PasswordChangeRequired(_)) => BrowserStep::SignIn {
+                error: Some(
+                    "your password has expired; change it in the app before signing in here"
+                        .to_string(),
+                ),
+            },
+            Err(AppError::Unauthorized(message) | AppError::Forbidden(message)) => retry(message),
+            Err(AppError::TooManyRequests { message, .. }) => retry(message),
+            Err(err) => return Err(err),
+        };
+        Ok(BrowserSignIn::Retry(step))
+    }
+
+    // Credenciais do cliente no header Basic ou no corpo, nunca nos dois (RFC 6749, secao 2.3).
+    pub async fn token(
+        &self,
+        request: TokenRequestDto,
+        credentials: Option<ClientCredentials>,
+        session_client: &SessionClient,
+    ) -> Result<IssuedOAuthTokens, OAuthError> {
+        let client = self.authenticate_client(&request, credentials).await?;
+
+        match request.grant_type.as_deref().map(str::trim) {
+            Some("authorization_code") => {
+                self.exchange_code(&client, &request, session_client).await
+            }
+            Some("refresh_token") => self.exchange_refresh_token(&client, &request).await,
+            Some("client_credentials") => self.exchange_client_credentials(&client, &request).await,
+            Some(other) => Err(OAuthError::new(
+                OAuthErrorCode::UnsupportedGrantType,
+                format!("unsupported grant_type: {other}"),
+            )),
+            None => Err(OAuthError::new(
+                OAuthErrorCode::InvalidRequest,
+                "grant_type is required",
+            )),
+        }
+    }
+
+    pub async fn list_consents(&self, actor: &AuthenticatedUser) -> AppResult<Vec<ClientConsent>> {
+        actor.require_interactive_session()?;
+
+        let mut entries = Vec::new();
+        for consent in self.consents.find_by_user(actor.id).await? {
+            if let Some(client) = self.clients.find_by_id(consent.client_id).await? {
+                entries.push(ClientConsent { consent, client });
+            }
+        }
+        Ok(entries)
+    }
+
+    // Revogar o consentimento tambem encerra as sessoes que o cliente tem em nome do usuario.
+    pub async fn revoke_consent(
+        &self,
+        actor: &AuthenticatedUser,
+        client_id: Uuid,
+    ) -> AppResult<()> {
+        actor.require_interactive_session()?;
+
+        if !self.consents.revoke(actor.id, client_id).await? {
+            return Err(AppError::NotFound(format!(
+                "consent for client {client_id} not found"
+            )));
+        }
+        self.auth
+            .sessions()
+            .end_for_client(client_id, Some(actor.id))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn purge_expired_codes(&self) -> AppResult<u64> {
+        self.codes.purge_expired(Utc::now()).await
+    }
+
+    // Cookie ausente, expirado ou revogado pede um novo login; tokens com escopo (de clientes
+    // OAuth) nao valem como sessao do navegador.
+    async fn browser_user(
+        &self,
+        session_token: Option<&str>,
+    ) -> AppResult<Option<AuthenticatedUser>> {
+        let Some(token) = session_token else {
+            return Ok(None);
+        };
+        match self.auth.verify(token).await {
+            Ok(user) if user.scopes.is_none() => Ok(Some(user)),
+            Ok(_) | Err(AppError::Unauthorized(_) | AppError::Forbidden(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn resolve_redirect(
+        &self,
+        request: &AuthorizationRequestDto,
+    ) -> AppResult<(OAuthClient, String)> {
+        let client = match Uuid::parse_str(request.client_id.trim()) {
+            Ok(id) => self.clients.find_by_id(id).await?,
+            Err(_) => None,
+        }
+        .filter(OAuthClient::is_active)
+        .ok_or_else(|| AppError::Validation("unknown client_id".to_string()))?;
+
+        let redirect_uri = match request.redirect_uri.as_deref() {
+            Some(uri) if client.redirect_uris.iter().any(|allowed| allowed == uri) => {
+                uri.to_string()
+            }
+            Some(_) => {
+                return Err(AppError::Validation(
+                    "redirect_uri is not registered for this client".to_string(),
+                ))
+            }
+            None => match client.redirect_uris.as_slice() {
+                [only] => only.clone(),
+                _ => {
+                    return Err(AppError::Validation(
+                        "redirect_uri is required for this client".to_string(),
+                    ))
+                }
+            },
+        };
+
+        Ok((client, redirect_uri))
+    }
+
+    async fn issue_code(
+        &self,
+        actor: &AuthenticatedUser,
+        client: &OAuthClient,
+        redirect_uri: String,
+        request: &AuthorizationRequestDto,
+        scopes: Vec<Permission>,
+    ) -> AppResult<AuthorizationOutcome> {
+        let code = opaque_token::generate();
+        let expires_at = Utc::now()
+            .checked_add_signed(self.settings.authorization_code_ttl)
+            .ok_or_else(|| AppError::Unexpected(anyhow!("invalid authorization code ttl")))?;
+
+        let mut params = vec![("code", code.as_str())];
+        if let Some(state) = request.state.as_deref() {
+            params.push(("state", state));
+        }
+        let redirect_to = append_query(&redirect_uri, &params);
+
+        self.codes
+            .create(NewAuthorizationCode {
+                code_hash: opaque_token::hash(&code),
+                client_id: client.id,
+                user_id: actor.id,
+                session_id: Uuid::new_v4(),
+                redirect_uri,
+                redirect_uri_supplied: request.redirect_uri.is_some(),
+                scopes: scopes.clone(),
+                // Ja validado em `check_request`.
+                code_challenge: request.code_challenge.clone().unwrap_or_default(),
+                expires_at,
+            })
+            .await?;
+
+        Ok(AuthorizationOutcome::Approved {
+            redirect_to,
+            scopes,
+        })
+    }
+
+    async fn authenticate_client(
+        &self,
+        request: &TokenRequestDto,
+        credentials: Option<ClientCredentials>,
+    ) -> Result<OAuthClient, OAuthError> {
+        let (client_id, secret) = match credentials {
+            Some(_) if request.client_secret.is_some() => {
+                return Err(OAuthError::new(
+                    OAuthErrorCode::InvalidRequest,
+                    "use a single client authentication method",
+                ))
+            }
+            Some(credentials) => (credentials.client_id, Some(credentials.client_secret)),
+            None => (
+                request.client_id.clone().ok_or_else(|| {
+                    OAuthError::new(
+                        OAuthErrorCode::InvalidClient,
+                        "client authentication failed",
+                    )
+                })?,
+                request.client_secret.clone(),
+            ),
+        };
+
+        let invalid_client = || {
+            OAuthError::new(
+                OAuthErrorCode::InvalidClient,
+                "client authentication failed",
+            )
+        };
+        let client = match Uuid::parse_str(client_id.trim()) {
+            Ok(id) => self.clients.find_by_id(id).await?,
+            Err(_) => None,
+        }
+        .filter(OAuthClient::is_active)
+        .ok_or_else(invalid_client)?;
+
+        // Cliente publico que envia segredo esta mal configurado; nao ignoramos em silencio.
+        let authenticated = match (&client.secret_hash, secret) {
+            (Some(expected), Some(secret)) => opaque_token::hash(&secret) == *expected,
+            (None, None) => true,
+            _ => false,
+        };
+        if !authenticated {
+            return Err(invalid_client());
+        }
+        Ok(client)
+    }
+
+    // Um codigo reapresentado indica vazamento: a sessao aberta com ele e encerrada (RFC 6749,
+    // secao 4.1.2).
+    async fn exchange_code(
+        &self,
+        client: &OAuthClient,
+        request: &TokenRequestDto,
+        session_client: &SessionClient,
+    ) -> Result<IssuedOAuthTokens, OAuthError> {
+        ensure_grant(client, OAuthGrantType::AuthorizationCode)?;
+        let code = required(request.code.as_deref(), "code")?;
+        let verifier = required(request.code_verifier.as_deref(), "code_verifier")?;
+
+        let invalid_code =
+            || OAuthError::new(OAuthErrorCode::InvalidGrant, "invalid authorization code");
+        let stored = self
+            .codes
+            .find_by_hash(&opaque_token::hash(code))
+            .await?
+            .filter(|stored| stored.client_id == client.id)
+            .ok_or_else(invalid_code)?;
+
+        let now = Utc::now();
+        if stored.consumed_at.is_some() {
+            self.auth
+                .sessions()
+                .end(stored.user_id, stored.session_id)
+                .await?;
+            return Err(OAuthError::new(
+                OAuthErrorCode::InvalidGrant,
+                "authorization code already used",
+            ));
+        }
+        if stored.is_expired(now) {
+            return Err(OAuthError::new(
+                OAuthErrorCode::InvalidGrant,
+                "authorization code expired",
+            ));
+        }
+        if stored.redirect_uri_supplied {
+            let redirect_uri = required(request.redirect_uri.as_deref(), "redirect_uri")?;
+            if stored.redirect_uri != redirect_uri {
+                return Err(OAuthError::new(
+                    OAuthErrorCode::InvalidGrant,
+                    "redirect_uri does not match the authorization request",
+                ));
+            }
+        }
+        if !pkce::verify_s256(verifier, &stored.code_challenge) {
+            return Err(OAuthError::new(
+                OAuthErrorCode::InvalidGrant,
+                "code_verifier does not match the code_challenge",
+            ));
+        }
+
+        // Duas trocas concorrentes com o mesmo codigo: apenas uma vence.
+        if !self.codes.mark_consumed(stored.id, now).await? {
+            self.auth
+                .sessions()
+                .end(stored.user_id, stored.session_id)
+                .await?;
+            return Err(OAuthError::new(
+                OAuthErrorCode::InvalidGrant,
+                "authorization code already used",
+            ));
+        }
+
+        let session = self
+            .auth
+            .open_delegated_session(
+                stored.user_id,
+                stored.session_id,
+                SessionGrant {
+                    client_id: client.id,
+                    scopes: stored.scopes.clone(),
+                },
+                session_client,
+            )
+            .await?;
+
+        Ok(IssuedOAuthTokens {
+            access_token: session.token,
+            expires_at: session.expires_at,
+            refresh_token: client
+                .allows(OAuthGrantType::RefreshToken)
+                .then_some(session.refresh_token),
+            scopes: stored.scopes,
+        })
+    }
+
+    async fn exchange_refresh_token(
+        &self,
+        client: &OAuthClient,
+        request: &TokenRequestDto,
+    ) -> Result<IssuedOAuthTokens, OAuthError> {
+        ensure_grant(client, OAuthGrantType::RefreshToken)?;
+        let refresh_token = required(request.refresh_token.as_deref(), "refresh_token")?;
+        let scopes = request
+            .scope
+            .as_deref()
+            .map(parse_requested_scope)
+            .transpose()?;
+
+        let session = self
+            .auth
+            .refresh_delegated(refresh_token, client.id, scopes.as_deref())
+            .await
+            .map_err(|err| match err {
+                AppError::Validation(message) => {
+                    OAuthError::new(OAuthErrorCode::InvalidScope, message)
+                }
+                other => other.into(),
+            })?;
+
+        Ok(IssuedOAuthTokens {
+            access_token: session.token,
+            expires_at: session.expires_at,
+            refresh_token: Some(session.refresh_token),
+            scopes: session.user.scopes.unwrap_or_default(),
+        })
+    }
+
+    async fn exchange_client_credentials(
+        &self,
+        client: &OAuthClient,
+        request: &TokenRequestDto,
+    ) -> Result<IssuedOAuthTokens, OAuthError> {
+        ensure_grant(client, OAuthGrantType::ClientCredentials)?;
+        let scopes = match request.scope.as_deref() {
+            Some(scope) => parse_requested_scope(scope)?,
+            None => client.scopes.clone(),
+        };
+        ensure_within_client(client, &scopes)?;
+
+        let issued = self
+            .auth
+            .issue_client_token(client.owner_id, client.id, &scopes)
+            .await?;
+
+        Ok(IssuedOAuthTokens {
+            access_token: issued.token.token,
+            expires_at: issued.token.expires_at,
+            refresh_token: None,
+            scopes: issued.scopes,
+        })
+    }
+}
+
+// Valida o restante do pedido de autorizacao e devolve os escopos concedidos: os pedidos que
+// os papeis do usuario permitem. Pedir acima do teto do cliente e erro; acima dos papeis, nao.
+fn check_request(
+    client: &OAuthClient,
+    request: &AuthorizationRequestDto,
+    actor: &AuthenticatedUser,
+) -> Result<Vec<Permission>, (OAuthErrorCode, String)> {
+    if request.response_type.trim() != "code" {
+        return Err((
+            OAuthErrorCode::UnsupportedResponseType,
+            "response_type must be code".to_string(),
+        ));
+    }
+    if !client.allows(OAuthGrantType::AuthorizationCode) {
+        return Err((
+            OAuthErrorCode::UnauthorizedClient,
+            "client may not use the authorization_code grant".to_string(),
+        ));
+    }
+    if request
+        .state
+        .as_ref()
+        .is_some_and(|state| state.chars().count() > MAX_STATE_CHARS)
+    {
+        return Err((
+            OAuthErrorCode::InvalidRequest,
+            format!("state must have at most {MAX_STATE_CHARS} characters"),
+        ));
+    }
+    if request.code_challenge_method.as_deref() != Some(pkce::METHOD_S256) {
+        return Err((
+            OAuthErrorCode::InvalidRequest,
+            "code_challenge_method must be S256".to_string(),
+        ));
+    }
+    if !request
+        .code_challenge
+        .as_deref()
+        .is_some_and(pkce::is_valid_challenge)
+    {
+        return Err((
+            OAuthErrorCode::InvalidRequest,
+            "code_challenge must be a BASE64URL encoded SHA-256 digest".to_string(),
+        ));
+    }
+
+    let requested = match request.scope.as_deref() {
+        Some(scope) => {
+            parse_scope(scope).map_err(|err| (OAuthErrorCode::InvalidScope, err.to_string()))?
+        }
+        None => client.scopes.clone(),
+    };
+    if let Some(scope) = requested
+        .iter()
+        .find(|scope| !client.scopes.contains(scope))
+    {
+        return Err((
+            OAuthErrorCode::InvalidScope,
+            format!("scope {scope} is not allowed for this client"),
+        ));
+    }
+
+    Ok(requested
+        .into_iter()
+        .filter(|scope| actor.has_permission(*scope))
+        .collect())
+}
+
+fn reject(
+    redirect_uri: &str,
+    request: &AuthorizationRequestDto,
+    (error, description): (OAuthErrorCode, String),
+) -> AuthorizationOutcome {
+    let mut params = vec![
+        ("error", error.as_str()),
+        ("error_description", description.as_str()),
+    ];
+    if let Some(state) = request.state.as_deref() {
+        params.push(("state", state));
+    }
+
+    AuthorizationOutcome::Rejected {
+        redirect_to: append_query(redirect_uri, &params),
+        error,
+    }
+}
+
+// Amarrado ao token da sessao: so quem recebeu a pagina com o cookie consegue calcula-lo.
+fn csrf_token(session_token: &str) -> String {
+    opaque_token::hash(&format!("csrf:{session_token}"))
+}
+
+pub(crate) fn append_query(uri: &str, params: &[(&str, &str)]) -> String {
+    let mut result = uri.to_string();
+    for (index, (name, value)) in params.iter().enumerate() {
+        let separator = if index == 0 && !uri.contains('?') {
+            '?'
+        } else {
+            '&'
+        };
+        result.push(separator);
+        result.push_str(name);
+        result.push('=');
+        result.push_str(&utf8_percent_encode(value, QUERY_VALUE).to_string());
+    }
+    result
+}
+
+fn ensure_grant(client: &OAuthClient, grant_type: OAuthGrantType) -> Result<(), OAuthError> {
+    if client.allows(grant_type) {
+        return Ok(());
+    }
+    Err(OAuthError::new(
+        OAuthErrorCode::UnauthorizedClient,
+        format!("client may not use the {grant_type} grant"),
+    ))
+}
+
+fn ensure_within_client(client: &OAuthClient, scopes: &[Permission]) -> Result<(), OAuthError> {
+    match scopes.iter().find(|scope| !client.scopes.contains(scope)) {
+        Some(scope) => Err(OAuthError::new(
+            OAuthErrorCode::InvalidScope,
+            format!("scope {scope} is not allowed for this client"),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn parse_requested_scope(value: &str) -> Result<Vec<Permission>, OAuthError> {
+    parse_scope(value).map_err(|err| OAuthError::new(OAuthErrorCode::InvalidScope, err.to_string()))
+}
+
+fn required<'a>(value: Option<&'a str>, name: &str) -> Result<&'a str, OAuthError> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            OAuthError::new(
+                OAuthErrorCode::InvalidRequest,
+                format!("{name} is required"),
+            )
+        })
+}
+
+fn parse_grant_types(
+    values: &[String],
+    client_type: OAuthClientType,
+) -> AppResult<Vec<OAuthGrantType>> {
+    let mut grant_types = Vec::new();
+    for value in values {
+        let value = value.trim();
+        let grant_type = value
+            .parse::<OAuthGrantType>()
+            .map_err(|_| AppError::Validation(format!("invalid grant type: {value}")))?;
+        if !grant_types.contains(&grant_type) {
+            grant_types.push(grant_type);
+        }
+    }
+    grant_types.sort();
+
+    if grant_types.is_empty() {
+        return Err(AppError::Validation(
+            "at least one grant type is required".to_string(),
+        ));
+    }
+    if grant_types.contains(&OAuthGrantType::RefreshToken)
+        && !grant_types.contains(&OAuthGrantType::AuthorizationCode)
+    {
+        return Err(AppError::Validation(
+            "refresh_token requires the authorization_code grant".to_string(),
+        ));
+    }
+    // Sem segredo nao ha como autenticar o cliente sozinho.
+    if client_type == OAuthClientType::Public
+        && grant_types.contains(&OAuthGrantType::ClientCredentials)
+    {
+        return Err(AppError::Validation(
+            "public clients cannot use the client_credentials grant".to_string(),
+        ));
+    }
+    Ok(grant_types)
+}
+
+fn parse_redirect_uris(
+    values: &[String],
+    grant_types: &[OAuthGrantType],
+) -> AppResult<Vec<String>> {
+    let mut redirect_uris: Vec<String> = Vec::new();
+    for value in values {
+        let value = value.trim();
+        validate_redirect_uri(value)?;
+        if !redirect_uris.iter().any(|uri| uri == value) {
+            redirect_uris.push(value.to_string());
+        }
+    }
+
+    let needs_redirect = grant_types.contains(&OAuthGrantType::AuthorizationCode);
+    if needs_redirect && redirect_uris.is_empty() {
+        return Err(AppError::Validation(
+            "the authorization_code grant requires at least one redirect URI".to_string(),
+        ));
+    }
+    if !needs_redirect && !redirect_uris.is_empty() {
+        return Err(AppError::Validation(
+            "redirect URIs are only used by the authorization_code grant".to_string(),
+        ));
+    }
+    if redirect_uris.len() > MAX_REDIRECT_URIS {
+        return Err(AppError::Validation(format!(
+            "at most {MAX_REDIRECT_URIS} redirect URIs are allowed"
+        )));
+    }
+    Ok(redirect_uris)
+}
+
+// `https` em qualquer host, `http` apenas em loopback e esquemas privados no formato de dominio
+// reverso (RFC 8252, secao 7.1), como `com.example.app:/callback`. Fragmentos nao sao permitidos.
+fn validate_redirect_uri(uri: &str) -> AppResult<()> {
+    let invalid = || AppError::Validation(format!("invalid redirect URI: {uri}"));
+
+    if uri.is_empty()
+        || uri.len() > MAX_REDIRECT_URI_CHARS
+        || uri.contains('#')
+        || uri.chars().any(|ch| ch.is_whitespace() || ch.is_control())
+    {
+        return Err(invalid());
+    }
+
+    let (scheme, rest) = uri.split_once(':').ok_or_else(invalid)?;
+    let valid_scheme = scheme.starts_with(|ch: char| ch.is_ascii_lowercase())
+        && scheme
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || "+-.".contains(ch));
+    if !valid_scheme {
+        return Err(invalid());
+    }
+
+    match scheme {
+        "https" | "http" => {
+            let authority = rest
+                .strip_prefix("//")
+                .and_then(|rest| rest.split(['/', '?']).next())
+                .filter(|authority| !authority.is_empty() && !authority.contains('@'))
+                .ok_or_else(invalid)?;
+            let host = match authority.rfind(']') {
+                Some(end) => &authority[..=end],
+                None => authority.split(':').next().unwrap_or_default(),
+            };
+            if host.is_empty() || (scheme == "http" && !LOOPBACK_HOSTS.contains(&host)) {
+                return Err(invalid());
+            }
+            Ok(())
+        }
+        _ if scheme.contains('.') && !rest.is_empty() => Ok(()),
+        _ => Err(invalid()),
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use crate::domain::entities::user::User;
+use crate::domain::repositories::password_history_repository::PasswordHistoryRepository;
+use crate::domain::value_objects::PlainPassword;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::hashing_pool::HashingPool;
+use crate::shared::security::password::PasswordError;
+
+// Recusa as ultimas `remember` senhas de cada usuario, contando a atual. A tabela guarda apenas
+// as substituidas; a atual vem do proprio cadastro. Com `remember` 0 nada e verificado.
+#[derive(Clone)]
+pub struct PasswordHistoryService {
+    repository: Arc<dyn PasswordHistoryRepository>,
+    hasher: HashingPool,
+    remember: usize,
+}
+
+impl PasswordHistoryService {
+    pub fn new(
+        repository: Arc<dyn PasswordHistoryRepository>,
+        hasher: HashingPool,
+        remember: usize,
+    ) -> Self {
+        Self {
+            repository,
+            hasher,
+            remember,
+        }
+    }
+
+    // Cada hash Argon2 tem sal proprio, entao a comparacao e uma verificacao completa por senha
+    // lembrada; mantenha `remember` pequeno.
+    pub async fn ensure_not_reused(
+        &self,
+        user: &User,
+        new_password: &PlainPassword,
+    ) -> AppResult<()> {
+        if self.remember == 0 {
+            return Ok(());
+        }
+
+        let mut hashes = vec![user.password_hash().clone()];
+        hashes.extend(self.repository.recent(user.id(), self.remember - 1).await?);
+
+        for hash in &hashes {
+            match self
+                .hasher
+                .verify_password(hash.as_str(), new_password.as_str())
+                .await?
+            {
+                Ok(()) => return Err(reused_password(self.remember)),
+                Err(PasswordError::InvalidPassword) => {}
+                Err(PasswordError::Hash(_)) => {
+                    return Err(AppError::Unexpected(anyhow!(
+                        "failed to verify stored password hash"
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Chame depois de gravar a senha nova, com o cadastro anterior: guarda o hash substituido.
+    // Assim uma escrita recusada (versao divergente, por exemplo) nao entra no historico.
+    pub async fn remember_current(&self, user: &User) -> AppResult<()> {
+        if self.remember <= 1 {
+            return Ok(());
+        }
+        self.repository
+            .record(user.id(), user.password_hash(), self.remember - 1)
+            .await
+    }
+}
+
+fn reused_password(remember: usize) -> AppError {
+    AppError::Validation(if remember == 1 {
+        "new password must differ from the current one".to_string()
+    } else {
+        format!("new password must differ from the last {remember} passwords")
+    })
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::application::services::auth_service::AuthService;
+use crate::application::services::email_outbox_service::EmailOutboxService;
+use crate::domain::entities::user::UpdateUser;
+use crate::domain::mailer::EmailMessage;
+use crate::domain::repositories::password_reset_repository::PasswordResetRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::domain::value_objects::{EmailAddress, PasswordHash};
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::opaque_token;
+
+const TOKEN_PLACEHOLDER: &str = "{token}";
+
+#[derive(Clone)]
+pub struct PasswordResetService {
+    users: Arc<dyn UserRepository>,
+    tokens: Arc<dyn PasswordResetRepository>,
+    outbox: EmailOutboxService,
+    auth: AuthService,
+    settings: PasswordResetSettings,
+}
+
+#[derive(Debug, Clone)]
+pub struct PasswordResetSettings {
+    pub token_ttl: Duration,
+    // URL enviada por email; `{token}` e substituido pelo token em claro.
+    pub link_template: String,
+}
+
+impl Default for PasswordResetSettings {
+    fn default() -> Self {
+        Self {
+            token_ttl: Duration::minutes(30),
+            link_template: "http://localhost:8080/reset-password?token={token}".to_string(),
+        }
+    }
+}
+
+impl PasswordResetService {
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        tokens: Arc<dyn PasswordResetRepository>,
+        outbox: EmailOutboxService,
+        auth: AuthService,
+        settings: PasswordResetSettings,
+    ) -> Self {
+        Self {
+            users,
+            tokens,
+            outbox,
+            auth,
+            settings,
+        }
+    }
+
+    // Retorna `Ok(())` exista ou nao a conta, para que a resposta nao revele emails cadastrados.
+    // O email em si sai pelo outbox, fora do caminho da requisicao.
+    pub async fn request_reset(&self, email: &str) -> AppResult<Option<Uuid>> {
+        let email = EmailAddress::parse(email)?;
+        let Some(user) = self.users.find_by_email(&email).await? else {
+            return Ok(None);
+        };
+
+        let token = opaque_token::generate();
+        let expires_at = Utc::now()
+            .checked_add_signed(self.settings.token_ttl)
+            .ok_or_else(|| AppError::Unexpected(anyhow!("invalid password reset ttl")))?;
+        self.tokens
+            .replace_for_user(user.id(), &opaque_token::hash(&token), expires_at)
+            .await?;
+
+        self.outbox
+            .enqueue(EmailMessage {
+                to: user.email().as_str().to_string(),
+                subject: "Reset your password".to_string(),
+                body: self.reset_email_body(user.name().as_str(), &token),
+            })
+            .await?;
+
+        Ok(Some(user.id()))
+    }
+
+    // Troca a senha e derruba todas as sessoes do usuario. O token so e consumido depois que a
+    // nova senha passa na validacao, para que um erro de digitacao nao obrigue a pedir outro.
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> AppResult<Uuid> {
+        let now = Utc::now();
+        let stored = self
+            .tokens
+            .find_by_hash(&opaque_token::hash(token.trim()))
+            .await?
+            .filter(|stored| stored.is_usable(now))
+            .ok_or_else(invalid_reset_token)?;
+
+        let user = self
+            .users
+            .find_by_id(stored.user_id)
+            .await?
+            .ok_or_else(invalid_reset_token)?;
+        let plain_password = self
+            .auth
+            .password_policy()
+            .validate(new_password, &[user.name().as_str(), user.email().as_str()])?;
+        let history = self.auth.password_history();
+        history.ensure_not_reused(&user, &plain_password).await?;
+        let password_hash_raw = self
+            .auth
+            .hasher()
+            .hash_password(plain_password.as_str())
+            .await?
+            .map_err(|err| AppError::Unexpected(anyhow!("failed to hash password: {err}")))?;
+        let password_hash = PasswordHash::new(&password_hash_raw)?;
+
+        if !self.tokens.consume(stored.id, now).await? {
+            return Err(invalid_reset_token());
+        }
+
+        self.users
+            .update(
+                stored.user_id,
+                UpdateUser::default().apply_password_hash(password_hash),
+                None,
+            )
+            .await?;
+        history.remember_current(&user).await?;
+        self.auth.revoke_user_sessions(stored.user_id).await?;
+
+        Ok(stored.user_id)
+    }
+
+    pub async fn purge_expired(&self) -> AppResult<u64> {
+        self.tokens.purge_expired(Utc::now()).await
+    }
+
+    fn reset_email_body(&self, name: &str, token: &str) -> String {
+        let link = self
+            .settings
+            .link_template
+            .replace(TOKEN_PLACEHOLDER, token);
+        format!(
+            "Hello {name},\n\n\
+             We received a request to reset your password. Use the link below to choose a new one:\n\n\
+             {link}\n\n\
+             The link expires in {minutes} minutes and can be used only once. \
+             If you did not request a reset, you can ignore this email.\n",
+            minutes = self.settings.token_ttl.num_minutes()
+        )
+    }
+}
+
+// Token inexistente, expirado ou ja usado recebem a mesma resposta.
+fn invalid_reset_token() -> AppError {
+    AppError::Validation("invalid or expired reset token".to_string())
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::application::services::auth_service::{ensure_can_sign_in, AuthenticatedUser};
+use crate::application::services::role_service::RoleService;
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::personal_access_token::{NewPersonalAccessToken, PersonalAccessToken};
+use crate::domain::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::shared::activity;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::opaque_token;
+
+// Prefixo fixo: permite distinguir o token de um JWT e facilita a deteccao por secret scanners.
+pub const TOKEN_PREFIX: &str = "wr_pat_";
+const DISPLAY_PREFIX_CHARS: usize = 8;
+const MAX_NAME_CHARS: usize = 100;
+
+#[derive(Clone)]
+pub struct PersonalAccessTokenService {
+    repository: Arc<dyn PersonalAccessTokenRepository>,
+    users: Arc<dyn UserRepository>,
+    roles: RoleService,
+    settings: PersonalAccessTokenSettings,
+}
+
+#[derive(Debug, Clone)]
+pub struct PersonalAccessTokenSettings {
+    pub default_ttl: Duration,
+    pub max_ttl: Duration,
+}
+
+impl Default for PersonalAccessTokenSettings {
+    fn default() -> Self {
+        Self {
+            default_ttl: Duration::days(90),
+            max_ttl: Duration::days(365),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedPersonalAccessToken {
+    // Valor em claro, devolvido uma unica vez na criacao.
+    pub token: String,
+    pub record: PersonalAccessToken,
+}
+
+impl PersonalAccessTokenService {
+    pub fn new(
+        repository: Arc<dyn PersonalAccessTokenRepository>,
+        users: Arc<dyn UserRepository>,
+        roles: RoleService,
+        settings: PersonalAccessTokenSettings,
+    ) -> Self {
+        Self {
+            repository,
+            users,
+            roles,
+            settings,
+        }
+    }
+
+    pub fn is_personal_access_token(token: &str) -> bool {
+        token.starts_with(TOKEN_PREFIX)
+    }
+
+    // Um token pessoal nao pode criar outros tokens: a operacao exige sessao interativa.
+    pub async fn create(
+        &self,
+        actor: &AuthenticatedUser,
+        name: &str,
+        scopes: &[String],
+        expires_in_days: Option<i64>,
+    ) -> AppResult<IssuedPersonalAccessToken> {
+        actor.require_interactive_session()?;
+
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(AppError::Validation(format!(
+                "token name must have between 1 and {MAX_NAME_CHARS} characters"
+            )));
+        }
+
+        let scopes = parse_scopes(scopes)?;
+        let too_long = || {
+            AppError::Validation(format!(
+                "expires_in_days must be at most {}",
+                self.settings.max_ttl.num_days()
+            ))
+        };
+        let ttl = match expires_in_days {
+            Some(days) if days <= 0 => {
+                return Err(AppError::Validation(
+                    "expires_in_days must be greater than zero".to_string(),
+                ))
+            }
+            Some(days) => Duration::try_days(days).ok_or_else(too_long)?,
+            None => self.settings.default_ttl,
+        };
+        if ttl > self.settings.max_ttl {
+            return Err(too_long());
+        }
+        let expires_at = Utc::now()
+            .checked_add_signed(ttl)
+            .ok_or_else(|| AppError::Unexpected(anyhow!("invalid personal access token ttl")))?;
+
+        let secret = opaque_token::generate();
+        let token = format!("{TOKEN_PREFIX}{secret}");
+        let record = self
+            .repository
+            .create(NewPersonalAccessToken {
+                user_id: actor.id,
+                name: name.to_string(),
+                token_prefix: token[..TOKEN_PREFIX.len() + DISPLAY_PREFIX_CHARS].to_string(),
+                token_hash: opaque_token::hash(&token),
+                scopes,
+                expires_at,
+            })
+            .await
+            .map_err(|err| match err {
+                AppError::Conflict(_) => {
+                    AppError::Conflict(format!("a token named '{name}' already exists"))
+                }
+                other => other,
+            })?;
+
+        Ok(IssuedPersonalAccessToken { token, record })
+    }
+
+    pub async fn list(&self, actor: &AuthenticatedUser) -> AppResult<Vec<PersonalAccessToken>> {
+        actor.require_interactive_session()?;
+        self.repository.find_by_user(actor.id).await
+    }
+
+    pub async fn revoke(&self, actor: &AuthenticatedUser, id: Uuid) -> AppResult<()> {
+        actor.require_interactive_session()?;
+
+        if !self.repository.revoke(id, actor.id, Utc::now()).await? {
+            return Err(AppError::NotFound(format!("token {id} not found")));
+        }
+        Ok(())
+    }
+
+    // Resolve o usuario dono do token. Papeis e email vem do cadastro atual, nao da criacao.
+    pub async fn authenticate(&self, raw_token: &str) -> AppResult<AuthenticatedUser> {
+        let raw_token = raw_token.trim();
+        if !Self::is_personal_access_token(raw_token) {
+            return Err(invalid_token());
+        }
+
+        let now = Utc::now();
+        let token = self
+            .repository
+            .find_by_hash(&opaque_token::hash(raw_token))
+            .await?
+            .filter(|token| token.is_active(now))
+            .ok_or_else(invalid_token)?;
+
+        let user = self
+            .users
+            .find_by_id(token.user_id)
+            .await?
+            .ok_or_else(invalid_token)?;
+        ensure_can_sign_in(&user)?;
+
+        if activity::is_stale(token.last_used_at, now) {
+            self.repository.record_usage(token.id, now).await?;
+        }
+
+        let permissions = self.roles.permissions_for(user.roles()).await?;
+
+        Ok(AuthenticatedUser::new(
+            &user,
+            permissions,
+            token.id,
+            token.expires_at,
+            Some(token.scopes),
+        ))
+    }
+}
+
+fn parse_scopes(values: &[String]) -> AppResult<Vec<Permission>> {
+    let mut scopes = Vec::new();
+    for value in values {
+        let value = value.trim();
+        let scope = value
+            .parse::<Permission>()
+            .map_err(|_| AppError::Validation(format!("invalid scope: {value}")))?;
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+
+    if scopes.is_empty() {
+        return Err(AppError::Validation(
+            "at least one scope is required".to_string(),
+        ));
+    }
+    Ok(scopes)
+}
+
+fn invalid_token() -> AppError {
+    AppError::Unauthorized("invalid token".to_string())
+}
@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::application::dtos::registration::RegisterRequestDto;
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::application::services::email_outbox_service::EmailOutboxService;
+use crate::application::services::email_verification_service::EmailVerificationService;
+use crate::application::services::role_service::parse_role_name;
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::registration::Registration;
+use crate::domain::entities::role::VIEWER_ROLE;
+use crate::domain::entities::user::{NewUser, UpdateUser, User, UserStatus};
+use crate::domain::mailer::EmailMessage;
+use crate::domain::repositories::registration_repository::RegistrationRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::domain::value_objects::{normalize_email_domain, EmailAddress, PasswordHash, UserName};
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::hashing_pool::HashingPool;
+use crate::shared::security::password_policy::PasswordPolicy;
+
+// Quem pode se cadastrar sozinho. Fica desligado por padrao: abrir o cadastro e uma decisao
+// explicita de quem opera o servidor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RegistrationMode {
+    #[default]
+    Disabled,
+    Open,
+    // Apenas emails dos dominios em `allowed_domains`.
+    AllowedDomains,
+    // Qualquer um, mas a conta fica `pending` ate um admin aprovar.
+    Approval,
+}
+
+impl RegistrationMode {
+    pub const ALL: [RegistrationMode; 4] = [
+        RegistrationMode::Disabled,
+        RegistrationMode::Open,
+        RegistrationMode::AllowedDomains,
+        RegistrationMode::Approval,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegistrationMode::Disabled => "disabled",
+            RegistrationMode::Open => "open",
+            RegistrationMode::AllowedDomains => "domains",
+            RegistrationMode::Approval => "approval",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == value.trim().to_ascii_lowercase())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RegistrationSettings {
+    pub mode: RegistrationMode,
+    pub allowed_domains: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct RegistrationService {
+    users: Arc<dyn UserRepository>,
+    registrations: Arc<dyn RegistrationRepository>,
+    verification: EmailVerificationService,
+    outbox: EmailOutboxService,
+    hasher: HashingPool,
+    password_policy: PasswordPolicy,
+    settings: RegistrationSettings,
+}
+
+impl RegistrationService {
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        registrations: Arc<dyn RegistrationRepository>,
+        verification: EmailVerificationService,
+        outbox: EmailOutboxService,
+        hasher: HashingPool,
+        password_policy: PasswordPolicy,
+        settings: RegistrationSettings,
+    ) -> Self {
+        Self {
+            users,
+            registrations,
+            verification,
+            outbox,
+            hasher,
+            password_policy,
+            settings,
+        }
+    }
+
+    // Publico. O papel e sempre `viewer`: permissoes so vem de um admin. Um email ja cadastrado
+    // devolve `None` em vez de erro, para a resposta nao revelar a conta; o dono e avisado.
+    pub async fn register(&self, dto: RegisterRequestDto) -> AppResult<Option<User>> {
+        let mode = self.settings.mode;
+        if mode == RegistrationMode::Disabled {
+            return Err(AppError::Forbidden(
+                "self-registration is disabled".to_string(),
+            ));
+        }
+
+        let name = UserName::parse(&dto.name)?;
+        let email = EmailAddress::parse(&dto.email)?;
+        if mode == RegistrationMode::AllowedDomains && !self.is_allowed_domain(&email) {
+            return Err(AppError::Forbidden(
+                "registration is not open to this email domain".to_string(),
+            ));
+        }
+        let plain_password = self
+            .password_policy
+            .validate(&dto.password, &[name.as_str(), email.as_str()])?;
+        let password_hash_raw = self
+            .hasher
+            .hash_password(plain_password.as_str())
+            .await?
+            .map_err(|err| AppError::Unexpected(anyhow!("failed to hash password: {err}")))?;
+        let password_hash = PasswordHash::new(&password_hash_raw)?;
+        let roles = vec![parse_role_name(VIEWER_ROLE)?];
+
+        let new_user = NewUser::build(name, email.clone(), password_hash, roles);
+        let new_user = match mode {
+            RegistrationMode::Approval => new_user
+                .with_status(UserStatus::Pending)
+                .with_registration(),
+            _ => new_user,
+        };
+        let user = match self.users.create(new_user).await {
+            Ok(user) => user,
+            Err(AppError::Conflict(_)) => {
+                self.notify_existing_owner(&email).await?;
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+
+        self.verification.send_verification(&user).await?;
+        Ok(Some(user))
+    }
+
+    pub async fn list_pending(&self, actor: &AuthenticatedUser) -> AppResult<Vec<Registration>> {
+        actor.require_permission(Permission::UsersRead)?;
+        self.registrations.find_pending().await
+    }
+
+    pub async fn approve(&self, actor: &AuthenticatedUser, user_id: Uuid) -> AppResult<User> {
+        actor.require_permission(Permission::UsersWrite)?;
+        let user = self.pending_user(user_id).await?;
+        let next = user.status().transition_to(UserStatus::Active)?;
+
+        if !self
+            .registrations
+            .approve(user_id, actor.id, Utc::now())
+            .await?
+        {
+            return Err(registration_not_found(user_id));
+        }
+        let user = self
+            .users
+            .update(
+                user_id,
+                UpdateUser::default().apply_status(next, None),
+                Some(vec![user.version()]),
+            )
+            .await?;
+
+        self.notify(
+            &user,
+            "Your account has been approved",
+            "Your registration was approved. You can now sign in.",
+        )
+        .await?;
+        Ok(user)
+    }
+
+    // O usuario rejeitado e excluido logicamente, o que libera o email para um novo cadastro.
+    pub async fn reject(&self, actor: &AuthenticatedUser, user_id: Uuid) -> AppResult<()> {
+        actor.require_permission(Permission::UsersWrite)?;
+        let user = self.pending_user(user_id).await?;
+
+        if !self
+            .registrations
+            .reject(user_id, actor.id, Utc::now())
+            .await?
+        {
+            return Err(registration_not_found(user_id));
+        }
+        self.users.delete(user_id, None).await?;
+
+        self.notify(
+            &user,
+            "Your registration was not approved",
+            "Your registration was reviewed and not approved.",
+        )
+        .await
+    }
+
+    async fn pending_user(&self, user_id: Uuid) -> AppResult<User> {
+        self.registrations
+            .find_by_user(user_id)
+            .await?
+            .filter(Registration::is_pending)
+            .ok_or_else(|| registration_not_found(user_id))?;
+        self.users
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| registration_not_found(user_id))
+    }
+
+    // Compara na forma ASCII, assim `bücher.example` e `xn--bcher-kva.example` sao o mesmo dominio.
+    fn is_allowed_domain(&self, email: &EmailAddress) -> bool {
+        self.settings
+            .allowed_domains
+            .iter()
+            .filter_map(|allowed| normalize_email_domain(allowed).ok())
+            .any(|allowed| allowed == email.domain())
+    }
+
+    // A conta pode ter sido excluida entre o conflito e a busca; nesse caso ninguem e avisado.
+    async fn notify_existing_owner(&self, email: &EmailAddress) -> AppResult<()> {
+        let Some(owner) = self.users.find_by_email(email).await? else {
+            return Ok(());
+        };
+        self.notify(
+            &owner,
+            "Someone tried to register with your email",
+            "Someone tried to create a new account with this email address. If it was you, \
+             sign in or reset your password instead. Otherwise you can ignore this message.",
+        )
+        .await
+    }
+
+    async fn notify(&self, user: &User, subject: &str, text: &str) -> AppResult<()> {
+        self.outbox
+            .enqueue(EmailMessage {
+                to: user.email().as_str().to_string(),
+                subject: subject.to_string(),
+                body: format!("Hello {},\n\n{text}\n", user.name().as_str()),
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+fn registration_not_found(user_id: Uuid) -> AppError {
+    AppError::NotFound(format!("no pending registration for user {user_id}"))
+}
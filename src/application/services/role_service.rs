@@ -0,0 +1,249 @@
+use std::sync::Arc;
+
+use crate::application::dtos::role::{CreateRoleDto, RoleResponseDto, UpdateRoleDto};
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::role::{NewRole, Role, UpdateRole};
+use crate::domain::entities::user::User;
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::domain::value_objects::RoleName;
+use crate::shared::error::{AppError, AppResult};
+
+const MAX_DESCRIPTION_CHARS: usize = 255;
+
+#[derive(Clone)]
+pub struct RoleService {
+    repository: Arc<dyn RoleRepository>,
+    users: Arc<dyn UserRepository>,
+}
+
+impl RoleService {
+    pub fn new(repository: Arc<dyn RoleRepository>, users: Arc<dyn UserRepository>) -> Self {
+        Self { repository, users }
+    }
+
+    pub async fn list_roles(&self, actor: &AuthenticatedUser) -> AppResult<Vec<RoleResponseDto>> {
+        actor.require_permission(Permission::RolesRead)?;
+        let roles = self.repository.find_all().await?;
+        Ok(roles.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn get_role(
+        &self,
+        actor: &AuthenticatedUser,
+        name: &str,
+    ) -> AppResult<RoleResponseDto> {
+        actor.require_permission(Permission::RolesRead)?;
+        let name = parse_role_name(name)?;
+        Ok(self.find_existing(&name).await?.into())
+    }
+
+    pub async fn create_role(
+        &self,
+        actor: &AuthenticatedUser,
+        dto: CreateRoleDto,
+    ) -> AppResult<RoleResponseDto> {
+        actor.require_permission(Permission::RolesWrite)?;
+
+        let name = parse_role_name(&dto.name)?;
+        let description = parse_description(dto.description)?;
+        let permissions = parse_permissions(&dto.permissions)?;
+        actor.ensure_can_grant(&permissions)?;
+
+        let role = self
+            .repository
+            .create(NewRole {
+                name: name.clone(),
+                description,
+                permissions,
+            })
+            .await
+            .map_err(|err| match err {
+                AppError::Conflict(_) => AppError::Conflict(format!("role {name} already exists")),
+                other => other,
+            })?;
+
+        Ok(role.into())
+    }
+
+    pub async fn update_role(
+        &self,
+        actor: &AuthenticatedUser,
+        name: &str,
+        dto: UpdateRoleDto,
+    ) -> AppResult<RoleResponseDto> {
+        actor.require_permission(Permission::RolesWrite)?;
+
+        let name = parse_role_name(name)?;
+        ensure_mutable(&self.find_existing(&name).await?)?;
+
+        let mut update = UpdateRole {
+            description: parse_description(dto.description)?,
+            permissions: None,
+        };
+        if let Some(permissions) = dto.permissions {
+            let permissions = parse_permissions(&permissions)?;
+            actor.ensure_can_grant(&permissions)?;
+            update.permissions = Some(permissions);
+        }
+
+        if update.is_empty() {
+            return Err(AppError::Validation(
+                "at least one field must be provided".to_string(),
+            ));
+        }
+
+        self.repository
+            .update(&name, update)
+            .await?
+            .map(Into::into)
+            .ok_or_else(|| role_not_found(&name))
+    }
+
+    // Papeis ainda atribuidos nao podem sumir: os usuarios perderiam acesso sem aviso.
+    pub async fn delete_role(&self, actor: &AuthenticatedUser, name: &str) -> AppResult<()> {
+        actor.require_permission(Permission::RolesWrite)?;
+
+        let name = parse_role_name(name)?;
+        ensure_mutable(&self.find_existing(&name).await?)?;
+
+        let assigned = self.users.count_with_role(&name).await?;
+        if assigned > 0 {
+            return Err(AppError::Conflict(format!(
+                "role {name} is still assigned to {assigned} user(s)"
+            )));
+        }
+
+        if !self.repository.delete(&name).await? {
+            return Err(role_not_found(&name));
+        }
+        Ok(())
+    }
+
+    // Usado ao atribuir papeis a um usuario: todos os nomes precisam existir.
+    pub async fn resolve(&self, names: &[RoleName]) -> AppResult<Vec<Role>> {
+        let roles = self.repository.find_by_names(names).await?;
+
+        if let Some(missing) = names
+            .iter()
+            .find(|name| !roles.iter().any(|role| &role.name == *name))
+        {
+            return Err(AppError::Validation(format!("unknown role: {missing}")));
+        }
+        Ok(roles)
+    }
+
+    // Papeis pedidos para um usuario: validados, sem repeticao e limitados ao que o ator pode
+    // conceder. Sem `actor` (bootstrap) qualquer papel existente pode ser atribuido.
+    pub async fn resolve_assignable(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        raw: &[String],
+    ) -> AppResult<Vec<RoleName>> {
+        let mut names = raw
+            .iter()
+            .map(|name| parse_role_name(name))
+            .collect::<AppResult<Vec<_>>>()?;
+        names.sort();
+        names.dedup();
+
+        if names.is_empty() {
+            return Err(AppError::Validation(
+                "at least one role is required".to_string(),
+            ));
+        }
+
+        let roles = self.resolve(&names).await?;
+        if let Some(actor) = actor {
+            actor.ensure_can_grant(&merge_permissions(&roles))?;
+        }
+        Ok(names)
+    }
+
+    // Uniao das permissoes dos papeis, resolvida a cada requisicao para que mudancas em um
+    // papel valham imediatamente, sem esperar o access token expirar.
+    pub async fn permissions_for(&self, names: &[RoleName]) -> AppResult<Vec<Permission>> {
+        let roles = self.repository.find_by_names(names).await?;
+        Ok(merge_permissions(&roles))
+    }
+
+    // Agir sobre alguem com mais permissoes equivaleria a assumir essas permissoes.
+    pub async fn ensure_can_manage_user(
+        &self,
+        actor: &AuthenticatedUser,
+        user: &User,
+    ) -> AppResult<()> {
+        let permissions = self.permissions_for(user.roles()).await?;
+
+        match permissions
+            .iter()
+            .find(|permission| !actor.has_permission(**permission))
+        {
+            Some(permission) => Err(AppError::Forbidden(format!(
+                "cannot manage a user holding permission {permission}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    async fn find_existing(&self, name: &RoleName) -> AppResult<Role> {
+        self.repository
+            .find_by_name(name)
+            .await?
+            .ok_or_else(|| role_not_found(name))
+    }
+}
+
+pub fn merge_permissions(roles: &[Role]) -> Vec<Permission> {
+    let mut permissions: Vec<Permission> = roles
+        .iter()
+        .flat_map(|role| role.permissions.iter().copied())
+        .collect();
+    permissions.sort();
+    permissions.dedup();
+    permissions
+}
+
+pub fn parse_role_name(raw: &str) -> AppResult<RoleName> {
+    RoleName::parse(raw).map_err(AppError::from)
+}
+
+fn parse_description(raw: Option<String>) -> AppResult<Option<String>> {
+    let Some(description) = raw else {
+        return Ok(None);
+    };
+
+    let description = description.trim();
+    if description.chars().count() > MAX_DESCRIPTION_CHARS {
+        return Err(AppError::Validation(format!(
+            "description must be at most {MAX_DESCRIPTION_CHARS} characters"
+        )));
+    }
+    Ok(Some(description.to_string()))
+}
+
+fn parse_permissions(values: &[String]) -> AppResult<Vec<Permission>> {
+    let mut permissions = values
+        .iter()
+        .map(|value| value.trim().parse::<Permission>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| AppError::Validation(err.to_string()))?;
+    permissions.sort();
+    permissions.dedup();
+    Ok(permissions)
+}
+
+fn ensure_mutable(role: &Role) -> AppResult<()> {
+    if role.built_in {
+        return Err(AppError::Conflict(format!(
+            "built-in role {} cannot be changed",
+            role.name
+        )));
+    }
+    Ok(())
+}
+
+fn role_not_found(name: &RoleName) -> AppError {
+    AppError::NotFound(format!("role {name} not found"))
+}
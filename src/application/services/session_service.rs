@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::application::services::role_service::RoleService;
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::session::{
+    NewSession, Session, SessionAuthMethod, SessionClient, SessionGrant,
+};
+use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+use crate::domain::repositories::session_repository::SessionRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::shared::activity;
+use crate::shared::error::{AppError, AppResult};
+
+const MAX_USER_AGENT_CHARS: usize = 256;
+
+// Inventario de sessoes (um registro por login). Cada sessao e a familia dos seus refresh
+// tokens, entao revoga-la tambem impede a renovacao; os access tokens carregam o id em `sid` e
+// sao recusados assim que a sessao deixa de estar ativa.
+#[derive(Clone)]
+pub struct SessionService {
+    repository: Arc<dyn SessionRepository>,
+    refresh_tokens: Arc<dyn RefreshTokenRepository>,
+    users: Arc<dyn UserRepository>,
+    roles: RoleService,
+}
+
+impl SessionService {
+    pub fn new(
+        repository: Arc<dyn SessionRepository>,
+        refresh_tokens: Arc<dyn RefreshTokenRepository>,
+        users: Arc<dyn UserRepository>,
+        roles: RoleService,
+    ) -> Self {
+        Self {
+            repository,
+            refresh_tokens,
+            users,
+            roles,
+        }
+    }
+
+    pub async fn start(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        auth_method: SessionAuthMethod,
+        client: &SessionClient,
+        grant: Option<SessionGrant>,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<Session> {
+        self.repository
+            .create(NewSession {
+                id,
+                user_id,
+                auth_method,
+                user_agent: client.user_agent.as_deref().and_then(normalize_user_agent),
+                ip_address: client.ip_address.map(|ip| ip.to_string()),
+                expires_at,
+                grant,
+            })
+            .await
+    }
+
+    pub async fn find(&self, id: Uuid) -> AppResult<Option<Session>> {
+        self.repository.find_by_id(id).await
+    }
+
+    // Chamado a cada uso de um token ligado a sessao: recusa sessoes revogadas ou vencidas e
+    // registra a atividade.
+    pub async fn ensure_active(&self, id: Uuid, user_id: Uuid) -> AppResult<Session> {
+        let now = Utc::now();
+        let session = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .filter(|session| session.user_id == user_id && session.is_active(now))
+            .ok_or_else(|| AppError::Unauthorized("session revoked".to_string()))?;
+
+        if activity::is_stale(Some(session.last_seen_at), now) {
+            self.repository.record_activity(id, now).await?;
+        }
+        Ok(session)
+    }
+
+    // Acompanha a validade do refresh token recem-emitido; `false` se a sessao foi revogada.
+    pub async fn renew(&self, id: Uuid, expires_at: DateTime<Utc>) -> AppResult<bool> {
+        self.repository.renew(id, Utc::now(), expires_at).await
+    }
+
+    pub async fn list(&self, actor: &AuthenticatedUser) -> AppResult<Vec<Session>> {
+        actor.require_interactive_session()?;
+        self.repository
+            .find_active_by_user(actor.id, Utc::now())
+            .await
+    }
+
+    pub async fn list_for_user(
+        &self,
+        actor: &AuthenticatedUser,
+        user_id: Uuid,
+    ) -> AppResult<Vec<Session>> {
+        actor.require_permission(Permission::UsersRead)?;
+        self.ensure_can_manage(actor, user_id).await?;
+        self.repository
+            .find_active_by_user(user_id, Utc::now())
+            .await
+    }
+
+    // Vale tambem para a sessao atual, como um logout.
+    pub async fn revoke(&self, actor: &AuthenticatedUser, id: Uuid) -> AppResult<()> {
+        actor.require_interactive_session()?;
+        if !self.end(actor.id, id).await? {
+            return Err(session_not_found(id));
+        }
+        Ok(())
+    }
+
+    pub async fn revoke_for_user(
+        &self,
+        actor: &AuthenticatedUser,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> AppResult<()> {
+        actor.require_permission(Permission::UsersWrite)?;
+        self.ensure_can_manage(actor, user_id).await?;
+        if !self.end(user_id, id).await? {
+            return Err(session_not_found(id));
+        }
+        Ok(())
+    }
+
+    // Revoga a sessao e a familia de refresh tokens. `false` se a sessao nao era do usuario ou
+    // ja estava revogada.
+    pub async fn end(&self, user_id: Uuid, id: Uuid) -> AppResult<bool> {
+        let revoked = self.repository.revoke(id, user_id, Utc::now()).await?;
+        if revoked {
+            self.refresh_tokens.revoke_family(id).await?;
+        }
+        Ok(revoked)
+    }
+
+    pub async fn end_all(&self, user_id: Uuid) -> AppResult<()> {
+        self.repository
+            .revoke_all_for_user(user_id, Utc::now())
+            .await?;
+        self.refresh_tokens.revoke_all_for_user(user_id).await?;
+        Ok(())
+    }
+
+    // Sessoes de um cliente OAuth, de um usuario ou de todos. Os refresh tokens delas deixam de
+    // valer porque a renovacao exige a sessao ativa.
+    pub async fn end_for_client(&self, client_id: Uuid, user_id: Option<Uuid>) -> AppResult<u64> {
+        self.repository
+            .revoke_all_for_client(client_id, user_id, Utc::now())
+            .await
+    }
+
+    async fn ensure_can_manage(&self, actor: &AuthenticatedUser, user_id: Uuid) -> AppResult<()> {
+        let user = self
+            .users
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("user {user_id} not found")))?;
+        self.roles.ensure_can_manage_user(actor, &user).await
+    }
+}
+
+// O header e livre: guardamos so texto imprimivel e limitado, suficiente para reconhecer o
+// navegador ou o app.
+fn normalize_user_agent(value: &str) -> Option<String> {
+    let normalized = value
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_USER_AGENT_CHARS)
+        .collect::<String>();
+    let normalized = normalized.trim();
+    (!normalized.is_empty()).then(|| normalized.to_string())
+}
+
+fn session_not_found(id: Uuid) -> AppError {
+    AppError::NotFound(format!("session {id} not found"))
+}
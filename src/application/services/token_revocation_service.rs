@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::application::services::session_service::SessionService;
+use crate::domain::entities::token_revocation::{
+    RevocationSnapshot, RevokedToken, UserTokenRevocation,
+};
+use crate::domain::repositories::token_revocation_repository::TokenRevocationRepository;
+use crate::shared::error::AppResult;
+
+// Lista de revogacao de access tokens. O Postgres e a fonte da verdade; o cache em memoria
+// evita uma consulta por requisicao e e sincronizado periodicamente para refletir revogacoes
+// feitas por outras instancias (ver `auth.revocation_sync_seconds`).
+#[derive(Clone)]
+pub struct TokenRevocationService {
+    repository: Arc<dyn TokenRevocationRepository>,
+    sessions: SessionService,
+    cache: Arc<RwLock<RevocationCache>>,
+}
+
+#[derive(Default)]
+struct RevocationCache {
+    tokens: HashMap<Uuid, DateTime<Utc>>,
+    users: HashMap<Uuid, UserTokenRevocation>,
+}
+
+impl RevocationCache {
+    fn merge(&mut self, snapshot: RevocationSnapshot) {
+        for token in snapshot.tokens {
+            self.tokens.insert(token.jti, token.expires_at);
+        }
+        for revocation in snapshot.users {
+            self.merge_user(revocation);
+        }
+    }
+
+    fn merge_user(&mut self, revocation: UserTokenRevocation) {
+        match self.users.get_mut(&revocation.user_id) {
+            Some(existing) => {
+                existing.revoked_before = existing.revoked_before.max(revocation.revoked_before);
+                existing.expires_at = existing.expires_at.max(revocation.expires_at);
+            }
+            None => {
+                self.users.insert(revocation.user_id, revocation);
+            }
+        }
+    }
+
+    fn prune(&mut self, now: DateTime<Utc>) {
+        self.tokens.retain(|_, expires_at| *expires_at > now);
+        self.users
+            .retain(|_, revocation| revocation.expires_at > now);
+    }
+}
+
+impl TokenRevocationService {
+    pub fn new(repository: Arc<dyn TokenRevocationRepository>, sessions: SessionService) -> Self {
+        Self {
+            repository,
+            sessions,
+            cache: Arc::new(RwLock::new(RevocationCache::default())),
+        }
+    }
+
+    pub async fn revoke_token(
+        &self,
+        jti: Uuid,
+        user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<()> {
+        let token = RevokedToken {
+            jti,
+            user_id,
+            expires_at,
+        };
+        self.repository.revoke_token(token.clone()).await?;
+        self.write_cache()
+            .tokens
+            .insert(token.jti, token.expires_at);
+        Ok(())
+    }
+
+    // Invalida todo token do usuario emitido ate `revoked_before`; a entrada so precisa viver
+    // enquanto algum desses tokens ainda puder estar dentro da validade. Encerra tambem todas as
+    // sessoes: `is_revoked` dispensa o corte para tokens de sessao ativa, entao as duas coisas
+    // precisam andar juntas.
+    pub async fn revoke_user(
+        &self,
+        user_id: Uuid,
+        revoked_before: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<()> {
+        self.sessions.end_all(user_id).await?;
+        let revocation = UserTokenRevocation {
+            user_id,
+            revoked_before,
+            expires_at,
+        };
+        self.repository.revoke_user(revocation.clone()).await?;
+        self.write_cache().merge_user(revocation);
+        Ok(())
+    }
+
+    // `issued_at` vem do jti, em milissegundos: um token emitido logo depois da revogacao, ainda
+    // no mesmo segundo, continua valendo. Quem tem uma sessao ativa fica de fora: a revogacao em
+    // massa encerra todas as sessoes, entao a dele foi aberta depois.
+    pub fn is_revoked(
+        &self,
+        jti: Uuid,
+        user_id: Uuid,
+        issued_at: DateTime<Utc>,
+        session_active: bool,
+    ) -> bool {
+        let cache = self
+            .cache
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if cache.tokens.contains_key(&jti) {
+            return true;
+        }
+        if session_active {
+            return false;
+        }
+
+        cache
+            .users
+            .get(&user_id)
+            .is_some_and(|revocation| issued_at < revocation.revoked_before)
+    }
+
+    // Mescla (em vez de substituir) para nao perder revogacoes locais gravadas durante a leitura.
+    pub async fn sync(&self) -> AppResult<()> {
+        let now = Utc::now();
+        let snapshot = self.repository.find_active(now).await?;
+
+        let mut cache = self.write_cache();
+        cache.merge(snapshot);
+        cache.prune(now);
+        Ok(())
+    }
+
+    pub async fn purge_expired(&self) -> AppResult<u64> {
+        let now = Utc::now();
+        let purged = self.repository.purge_expired(now).await?;
+        self.write_cache().prune(now);
+        Ok(purged)
+    }
+
+    fn write_cache(&self) -> std::sync::RwLockWriteGuard<'_, RevocationCache> {
+        self.cache
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
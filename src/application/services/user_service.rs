@@ -1,26 +1,63 @@
-use std::str::FromStr;
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use uuid::Uuid;
 
-use crate::application::dtos::user::{CreateUserDto, UpdateUserDto, UserResponseDto};
-use crate::application::services::auth_service::AuthenticatedUser;
-use crate::domain::entities::user::{NewUser, UpdateUser, UserRole};
+use crate::application::dtos::user::{
+    ChangeEmailDto, ChangeUserStatusDto, CreateUserDto, ListUsersQueryDto, UpdateProfileDto,
+    UpdateUserDto, UserPageDto, UserResponseDto,
+};
+use crate::application::services::auth_service::{AuthService, AuthenticatedUser};
+use crate::application::services::email_verification_service::EmailVerificationService;
+use crate::application::services::role_service::{parse_role_name, RoleService};
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::role::ADMIN_ROLE;
+use crate::domain::entities::user::{
+    NewUser, SortDirection, UpdateUser, User, UserCursor, UserFilter, UserQuery, UserSort,
+    UserSortField, UserSortKey, UserStatus,
+};
 use crate::domain::errors::DomainError;
 use crate::domain::repositories::user_repository::UserRepository;
-use crate::domain::value_objects::{EmailAddress, PasswordHash, PlainPassword, UserName};
+use crate::domain::value_objects::{
+    normalize_email_domain, EmailAddress, Locale, PasswordHash, PlainPassword, TimeZoneName,
+    UserName,
+};
 use crate::shared::error::{AppError, AppResult};
-use crate::shared::security::password;
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 100;
+const MAX_STATUS_REASON_LENGTH: usize = 500;
 
 #[derive(Clone)]
 pub struct UserService {
     repository: Arc<dyn UserRepository>,
+    roles: RoleService,
+    auth: AuthService,
+    verification: EmailVerificationService,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserStatusChange {
+    pub previous: UserStatus,
+    pub user: UserResponseDto,
 }
 
 impl UserService {
-    pub fn new(repository: Arc<dyn UserRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn UserRepository>,
+        roles: RoleService,
+        auth: AuthService,
+        verification: EmailVerificationService,
+    ) -> Self {
+        Self {
+            repository,
+            roles,
+            auth,
+            verification,
+        }
     }
 
     pub async fn create_user(
@@ -28,20 +65,56 @@ impl UserService {
         actor: &AuthenticatedUser,
         dto: CreateUserDto,
     ) -> AppResult<UserResponseDto> {
-        ensure_admin(actor)?;
-        self.create_user_internal(dto).await
+        actor.require_permission(Permission::UsersWrite)?;
+        let user = self.create_user_internal(Some(actor), dto).await?;
+        self.verification.send_verification(&user).await?;
+        Ok(user.into())
     }
 
-    pub async fn list_users(&self, actor: &AuthenticatedUser) -> AppResult<Vec<UserResponseDto>> {
-        if actor.role == UserRole::Admin {
-            let users = self.repository.find_all().await?;
-            return Ok(users.into_iter().map(Into::into).collect());
+    pub async fn list_users(
+        &self,
+        actor: &AuthenticatedUser,
+        dto: ListUsersQueryDto,
+    ) -> AppResult<UserPageDto> {
+        actor.require_scope(Permission::UsersRead)?;
+        if dto.include_deleted == Some(true) {
+            actor.require_permission(Permission::UsersDelete)?;
         }
+        let query = build_user_query(dto)?;
 
-        match self.repository.find_by_id(actor.id).await? {
-            Some(user) => Ok(vec![user.into()]),
-            None => Err(AppError::NotFound(format!("user {} not found", actor.id))),
+        // Sem `users:read` a listagem se resume a propria conta, se ela passar pelos filtros.
+        if !actor.has_permission(Permission::UsersRead) {
+            let user = self
+                .repository
+                .find_by_id(actor.id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("user {} not found", actor.id)))?;
+            let items: Vec<UserResponseDto> =
+                if query.after.is_none() && query.filter.matches(&user) {
+                    vec![user.into()]
+                } else {
+                    Vec::new()
+                };
+
+            return Ok(UserPageDto {
+                total_count: query.include_total.then_some(items.len() as u64),
+                items,
+                next_cursor: None,
+            });
         }
+
+        let page = self.repository.find_page(&query).await?;
+        let next_cursor = page
+            .users
+            .last()
+            .filter(|_| page.has_more)
+            .map(|user| encode_cursor(&UserCursor::after(user, query.sort)));
+
+        Ok(UserPageDto {
+            items: page.users.into_iter().map(Into::into).collect(),
+            next_cursor,
+            total_count: page.total,
+        })
     }
 
     pub async fn get_user(
@@ -49,7 +122,8 @@ impl UserService {
         actor: &AuthenticatedUser,
         id: Uuid,
     ) -> AppResult<UserResponseDto> {
-        if actor.role != UserRole::Admin && actor.id != id {
+        actor.require_scope(Permission::UsersRead)?;
+        if !actor.has_permission(Permission::UsersRead) && actor.id != id {
             return Err(AppError::Forbidden("insufficient privileges".to_string()));
         }
 
@@ -59,19 +133,175 @@ impl UserService {
         }
     }
 
+    // Perfil da propria conta: qualquer usuario autenticado, independentemente dos papeis.
+    pub async fn get_profile(&self, actor: &AuthenticatedUser) -> AppResult<UserResponseDto> {
+        actor.require_scope(Permission::UsersRead)?;
+        Ok(self.current_user(actor).await?.into())
+    }
+
+    // So nome e preferencias: email e papeis continuam restritos a quem administra usuarios.
+    pub async fn update_profile(
+        &self,
+        actor: &AuthenticatedUser,
+        dto: UpdateProfileDto,
+        expected_versions: Option<Vec<i64>>,
+    ) -> AppResult<UserResponseDto> {
+        actor.require_scope(Permission::UsersWrite)?;
+        let mut update = UpdateUser::default();
+
+        if let Some(name) = dto.name {
+            update = update.apply_name(UserName::parse(&name)?);
+        }
+
+        if dto.locale.is_some() || dto.timezone.is_some() {
+            let mut preferences = self.current_user(actor).await?.preferences().clone();
+            if let Some(locale) = dto.locale {
+                preferences.locale = parse_preference(&locale, |raw| Locale::parse(raw))?;
+            }
+            if let Some(timezone) = dto.timezone {
+                preferences.timezone = parse_preference(&timezone, |raw| TimeZoneName::parse(raw))?;
+            }
+            update = update.apply_preferences(preferences);
+        }
+
+        if update.is_empty() {
+            return Err(AppError::Validation(
+                "at least one field must be provided".to_string(),
+            ));
+        }
+
+        let user = self
+            .repository
+            .update(actor.id, update, expected_versions)
+            .await?;
+        Ok(user.into())
+    }
+
+    // O endereco atual continua valendo ate o novo ser confirmado pelo link enviado a ele.
+    pub async fn change_email(
+        &self,
+        actor: &AuthenticatedUser,
+        dto: ChangeEmailDto,
+        expected_versions: Option<Vec<i64>>,
+    ) -> AppResult<UserResponseDto> {
+        actor.require_scope(Permission::UsersWrite)?;
+        let email = EmailAddress::parse(&dto.email)?;
+        let user = self.current_user(actor).await?;
+
+        Ok(self
+            .verification
+            .request_email_change(&user, email, expected_versions)
+            .await?
+            .into())
+    }
+
+    pub async fn resend_email_verification(&self, actor: &AuthenticatedUser) -> AppResult<()> {
+        actor.require_scope(Permission::UsersWrite)?;
+        let user = self.current_user(actor).await?;
+        self.verification.resend(&user).await
+    }
+
+    // Publico: quem abre o link prova o acesso a caixa de entrada, nao precisa estar logado.
+    pub async fn verify_email(&self, token: &str) -> AppResult<UserResponseDto> {
+        Ok(self.verification.verify(token).await?.into())
+    }
+
     pub async fn update_user(
         &self,
         actor: &AuthenticatedUser,
         id: Uuid,
         dto: UpdateUserDto,
+        expected_versions: Option<Vec<i64>>,
+    ) -> AppResult<UserResponseDto> {
+        actor.require_permission(Permission::UsersWrite)?;
+        self.ensure_can_manage(actor, id).await?;
+        self.update_user_internal(actor, id, dto, expected_versions)
+            .await
+    }
+
+    pub async fn delete_user(
+        &self,
+        actor: &AuthenticatedUser,
+        id: Uuid,
+        expected_versions: Option<Vec<i64>>,
+    ) -> AppResult<()> {
+        actor.require_permission(Permission::UsersDelete)?;
+        self.ensure_can_manage(actor, id).await?;
+        self.repository.delete(id, expected_versions).await
+    }
+
+    pub async fn restore_user(
+        &self,
+        actor: &AuthenticatedUser,
+        id: Uuid,
     ) -> AppResult<UserResponseDto> {
-        ensure_admin(actor)?;
-        self.update_user_internal(id, dto).await
+        actor.require_permission(Permission::UsersDelete)?;
+        let user = self
+            .repository
+            .find_deleted_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("deleted user {id} not found")))?;
+        self.roles.ensure_can_manage_user(actor, &user).await?;
+
+        Ok(self.repository.restore(id).await?.into())
+    }
+
+    // A transicao e validada contra o status lido e gravada apenas se a versao nao mudou, para
+    // que duas mudancas concorrentes nao pulem etapas do ciclo de vida.
+    pub async fn change_status(
+        &self,
+        actor: &AuthenticatedUser,
+        id: Uuid,
+        target: UserStatus,
+        dto: ChangeUserStatusDto,
+    ) -> AppResult<UserStatusChange> {
+        actor.require_permission(Permission::UsersWrite)?;
+        if actor.id == id {
+            return Err(AppError::Forbidden(
+                "cannot change the status of your own account".to_string(),
+            ));
+        }
+
+        let reason = dto.reason.trim();
+        if reason.is_empty() {
+            return Err(AppError::Validation("reason is required".to_string()));
+        }
+        if reason.chars().count() > MAX_STATUS_REASON_LENGTH {
+            return Err(AppError::Validation(format!(
+                "reason must be at most {MAX_STATUS_REASON_LENGTH} characters"
+            )));
+        }
+
+        let user = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("user {id} not found")))?;
+        self.roles.ensure_can_manage_user(actor, &user).await?;
+
+        let previous = user.status();
+        let next = previous.transition_to(target)?;
+        let update = UpdateUser::default().apply_status(next, Some(reason.to_string()));
+        let updated = self
+            .repository
+            .update(id, update, Some(vec![user.version()]))
+            .await?;
+
+        // Tokens ja emitidos sao recusados pelo status; revogar garante que uma reativacao
+        // posterior nao devolva o acesso a sessoes antigas.
+        if !next.can_sign_in() {
+            self.auth.revoke_user_sessions(id).await?;
+        }
+
+        Ok(UserStatusChange {
+            previous,
+            user: updated.into(),
+        })
     }
 
-    pub async fn delete_user(&self, actor: &AuthenticatedUser, id: Uuid) -> AppResult<()> {
-        ensure_admin(actor)?;
-        self.repository.delete(id).await
+    // Chamado pelo job de manutencao; nao ha ator porque a retencao e configuracao do servidor.
+    pub async fn purge_deleted_users(&self, retention: Duration) -> AppResult<u64> {
+        self.repository.purge_deleted(Utc::now() - retention).await
     }
 
     pub async fn ensure_admin_account(
@@ -80,7 +310,8 @@ impl UserService {
         email: &str,
         password: &str,
     ) -> AppResult<bool> {
-        if self.repository.find_by_email(email).await?.is_some() {
+        let address = EmailAddress::parse(email)?;
+        if self.repository.find_by_email(&address).await?.is_some() {
             return Ok(false);
         }
 
@@ -88,93 +319,279 @@ impl UserService {
             name: name.to_string(),
             email: email.to_string(),
             password: password.to_string(),
-            role: "admin".to_string(),
+            roles: vec![ADMIN_ROLE.to_string()],
         };
 
-        match self.create_user_internal(dto).await {
-            Ok(_) => Ok(true),
+        match self.create_user_internal(None, dto).await {
+            // O email do bootstrap vem da configuracao do servidor, que ja e confiavel.
+            Ok(user) => {
+                self.repository
+                    .update(
+                        user.id(),
+                        UpdateUser::default().apply_email_verified(Utc::now()),
+                        None,
+                    )
+                    .await?;
+                Ok(true)
+            }
             Err(AppError::Conflict(_)) => Ok(false),
             Err(err) => Err(err),
         }
     }
 
-    async fn create_user_internal(&self, dto: CreateUserDto) -> AppResult<UserResponseDto> {
+    async fn current_user(&self, actor: &AuthenticatedUser) -> AppResult<User> {
+        self.repository
+            .find_by_id(actor.id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("user {} not found", actor.id)))
+    }
+
+    async fn ensure_can_manage(&self, actor: &AuthenticatedUser, id: Uuid) -> AppResult<()> {
+        let user = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("user {id} not found")))?;
+        self.roles.ensure_can_manage_user(actor, &user).await
+    }
+
+    async fn create_user_internal(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        dto: CreateUserDto,
+    ) -> AppResult<User> {
         let CreateUserDto {
             name,
             email,
             password,
-            role,
+            roles,
         } = dto;
 
-        let role = parse_role(&role)?;
-        let user_name = UserName::parse(&name).map_err(map_domain_error)?;
-        let email_address = EmailAddress::parse(&email).map_err(map_domain_error)?;
-        let plain_password = PlainPassword::parse(&password).map_err(map_domain_error)?;
-        let password_hash_raw = password::hash_password(plain_password.as_str())
+        let roles = self.roles.resolve_assignable(actor, &roles).await?;
+        let user_name = UserName::parse(&name)?;
+        let email_address = EmailAddress::parse(&email)?;
+        // Sem ator e o bootstrap: a senha vem da configuracao do servidor e recusa-la impediria a
+        // subida, entao `main` apenas avisa quando ela nao atende a politica.
+        let plain_password = match actor {
+            Some(_) => self
+                .auth
+                .password_policy()
+                .validate(&password, &[user_name.as_str(), email_address.as_str()]),
+            None => PlainPassword::parse(&password),
+        }?;
+        let password_hash_raw = self
+            .auth
+            .hasher()
+            .hash_password(plain_password.as_str())
+            .await?
             .map_err(|err| AppError::Unexpected(anyhow!("failed to hash password: {err}")))?;
-        let password_hash = PasswordHash::new(&password_hash_raw).map_err(map_domain_error)?;
+        let password_hash = PasswordHash::new(&password_hash_raw)?;
 
-        let new_user = NewUser::build(user_name, email_address, password_hash, role);
-        let user = self.repository.create(new_user).await?;
-        Ok(user.into())
+        let new_user = NewUser::build(user_name, email_address, password_hash, roles);
+        self.repository.create(new_user).await
     }
 
     async fn update_user_internal(
         &self,
+        actor: &AuthenticatedUser,
         id: Uuid,
         dto: UpdateUserDto,
+        expected_versions: Option<Vec<i64>>,
     ) -> AppResult<UserResponseDto> {
         let mut update = UpdateUser::default();
 
         if let Some(name) = dto.name {
-            let parsed = UserName::parse(&name).map_err(map_domain_error)?;
+            let parsed = UserName::parse(&name)?;
             update = update.apply_name(parsed);
         }
 
-        if let Some(email) = dto.email {
-            let parsed = EmailAddress::parse(&email).map_err(map_domain_error)?;
-            update = update.apply_email(parsed);
-        }
+        let new_email = dto
+            .email
+            .map(|email| EmailAddress::parse(&email))
+            .transpose()?;
 
+        let mut replaced = None;
         if let Some(password) = dto.password {
-            let plain_password = PlainPassword::parse(&password).map_err(map_domain_error)?;
-            let password_hash_raw = password::hash_password(plain_password.as_str())
+            let target = self
+                .repository
+                .find_by_id(id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("user {id} not found")))?;
+            let plain_password = self.auth.password_policy().validate(
+                &password,
+                &[target.name().as_str(), target.email().as_str()],
+            )?;
+            let history = self.auth.password_history();
+            history.ensure_not_reused(&target, &plain_password).await?;
+            let password_hash_raw = self
+                .auth
+                .hasher()
+                .hash_password(plain_password.as_str())
+                .await?
                 .map_err(|err| AppError::Unexpected(anyhow!("failed to hash password: {err}")))?;
-            let password_hash = PasswordHash::new(&password_hash_raw).map_err(map_domain_error)?;
+            let password_hash = PasswordHash::new(&password_hash_raw)?;
             update = update.apply_password_hash(password_hash);
+            replaced = Some(target);
         }
 
-        if let Some(role) = dto.role {
-            update = update.apply_role(parse_role(&role)?);
+        if let Some(roles) = dto.roles {
+            update = update.apply_roles(self.roles.resolve_assignable(Some(actor), &roles).await?);
         }
 
-        if update.is_empty() {
+        if update.is_empty() && new_email.is_none() {
             return Err(AppError::Validation(
                 "at least one field must be provided".to_string(),
             ));
         }
 
-        let user = self.repository.update(id, update).await?;
-        Ok(user.into())
+        let (user, expected_versions) = if update.is_empty() {
+            let user = self
+                .repository
+                .find_by_id(id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("user {id} not found")))?;
+            (user, expected_versions)
+        } else {
+            let user = self
+                .repository
+                .update(id, update, expected_versions)
+                .await?;
+            if let Some(previous) = &replaced {
+                self.auth
+                    .password_history()
+                    .remember_current(previous)
+                    .await?;
+                // Como na troca pelo proprio usuario: quem tinha a senha antiga perde as sessoes.
+                self.auth.revoke_user_sessions(id).await?;
+            }
+            let version = user.version();
+            (user, Some(vec![version]))
+        };
+
+        // Nem administradores trocam o email direto: o novo fica pendente ate o dono confirmar.
+        match new_email.filter(|email| email != user.email()) {
+            Some(email) => Ok(self
+                .verification
+                .request_email_change(&user, email, expected_versions)
+                .await?
+                .into()),
+            None => Ok(user.into()),
+        }
     }
 }
 
-fn parse_role(raw: &str) -> AppResult<UserRole> {
-    let normalized = raw.trim().to_lowercase();
-
-    UserRole::from_str(&normalized).map_err(|err| AppError::Validation(err.to_string()))
+// String vazia limpa a preferencia.
+fn parse_preference<T>(
+    raw: &str,
+    parse: impl Fn(&str) -> Result<T, DomainError>,
+) -> AppResult<Option<T>> {
+    if raw.trim().is_empty() {
+        return Ok(None);
+    }
+    parse(raw).map(Some).map_err(AppError::from)
 }
 
-fn ensure_admin(actor: &AuthenticatedUser) -> AppResult<()> {
-    if actor.role == UserRole::Admin {
-        Ok(())
-    } else {
-        Err(AppError::Forbidden("admin role required".to_string()))
+fn build_user_query(dto: ListUsersQueryDto) -> AppResult<UserQuery> {
+    let limit = dto.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(AppError::Validation(format!(
+            "limit must be between 1 and {MAX_PAGE_SIZE}"
+        )));
     }
-}
 
-fn map_domain_error(error: DomainError) -> AppError {
-    match error {
-        DomainError::Validation(message) => AppError::Validation(message),
+    let field = dto
+        .sort
+        .as_deref()
+        .map(UserSortField::parse)
+        .transpose()?
+        .unwrap_or_default();
+    let direction = match dto.order.as_deref() {
+        Some(order) => SortDirection::parse(order)?,
+        None => field.default_direction(),
+    };
+    let sort = UserSort { field, direction };
+
+    let after = dto.cursor.as_deref().map(decode_cursor).transpose()?;
+    if after.as_ref().is_some_and(|cursor| cursor.sort() != sort) {
+        return Err(AppError::Validation(
+            "cursor does not match the requested sort".to_string(),
+        ));
     }
+
+    if let (Some(after), Some(before)) = (dto.created_after, dto.created_before) {
+        if after >= before {
+            return Err(AppError::Validation(
+                "created_after must be before created_before".to_string(),
+            ));
+        }
+    }
+
+    Ok(UserQuery {
+        filter: UserFilter {
+            role: dto.role.as_deref().map(parse_role_name).transpose()?,
+            email_domain: dto
+                .email_domain
+                .as_deref()
+                .map(parse_email_domain)
+                .transpose()?,
+            created_after: dto.created_after,
+            created_before: dto.created_before,
+            status: dto.status.as_deref().map(UserStatus::parse).transpose()?,
+            include_deleted: dto.include_deleted.unwrap_or(false),
+        },
+        sort,
+        after,
+        limit,
+        include_total: dto.include_total.unwrap_or(false),
+    })
+}
+
+fn parse_email_domain(raw: &str) -> AppResult<String> {
+    normalize_email_domain(raw.trim().trim_start_matches('@')).map_err(AppError::from)
+}
+
+// Cursor opaco `campo|ordem|id|valor` em base64 URL-safe; o valor fica por ultimo porque nomes
+// podem conter `|`. Guardar campo e ordem permite recusar cursores de outra ordenacao.
+fn encode_cursor(cursor: &UserCursor) -> String {
+    let value = match &cursor.key {
+        UserSortKey::Name(value) | UserSortKey::Email(value) => value.clone(),
+        UserSortKey::CreatedAt(at) => at.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+    };
+
+    URL_SAFE_NO_PAD.encode(format!(
+        "{}|{}|{}|{}",
+        cursor.key.field().as_str(),
+        cursor.direction.as_str(),
+        cursor.id,
+        value
+    ))
+}
+
+fn decode_cursor(raw: &str) -> AppResult<UserCursor> {
+    let invalid = || AppError::Validation("invalid cursor".to_string());
+
+    let bytes = URL_SAFE_NO_PAD.decode(raw.trim()).map_err(|_| invalid())?;
+    let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+    let mut parts = text.splitn(4, '|');
+    let (Some(field), Some(direction), Some(id), Some(value)) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+
+    let key = match UserSortField::parse(field).map_err(|_| invalid())? {
+        UserSortField::Name => UserSortKey::Name(value.to_string()),
+        UserSortField::Email => UserSortKey::Email(value.to_string()),
+        UserSortField::CreatedAt => UserSortKey::CreatedAt(
+            DateTime::parse_from_rfc3339(value)
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+        ),
+    };
+
+    Ok(UserCursor {
+        direction: SortDirection::parse(direction).map_err(|_| invalid())?,
+        key,
+        id: Uuid::parse_str(id).map_err(|_| invalid())?,
+    })
 }
@@ -1,8 +1,11 @@
-﻿mod settings;
+mod settings;
 
 pub use settings::{
-    AppConfig, AuthConfig, BootstrapConfig, DatabaseConfig, RateLimitConfig, ServerConfig,
-    TelemetryConfig,
+    AppConfig, AuthConfig, BootstrapConfig, DatabaseConfig, EmailVerificationConfig,
+    InvitationConfig, JwtKeyConfig, LockoutConfig, MailConfig, OAuthConfig, PasswordHashingConfig,
+    PasswordHistoryConfig, PasswordPolicyConfig, PasswordResetConfig, PepperConfig,
+    PersonalAccessTokenConfig, RateLimitConfig, RegistrationConfig, RegistrationRateLimitConfig,
+    ServerConfig, SmtpConfig, TelemetryConfig, UsersConfig,
 };
 
 use anyhow::Context;
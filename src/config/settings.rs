@@ -8,6 +8,8 @@ pub struct AppConfig {
     pub rate_limit: RateLimitConfig,
     pub auth: AuthConfig,
     pub bootstrap: BootstrapConfig,
+    pub mail: MailConfig,
+    pub users: UsersConfig,
 }
 
 impl AppConfig {
@@ -20,6 +22,8 @@ impl AppConfig {
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    #[serde(default)]
+    pub trust_forwarded_for: bool,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -38,12 +42,140 @@ pub struct TelemetryConfig {
 pub struct RateLimitConfig {
     pub requests_per_second: u64,
     pub burst_capacity: u32,
+    pub registration: RegistrationRateLimitConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RegistrationRateLimitConfig {
+    pub period_seconds: u64,
+    pub burst_capacity: u32,
 }
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct AuthConfig {
     pub jwt_secret: String,
     pub jwt_ttl_minutes: i64,
+    pub jwt_algorithm: String,
+    #[serde(default)]
+    pub jwt_active_kid: Option<String>,
+    #[serde(default)]
+    pub jwt_keys: Vec<JwtKeyConfig>,
+    pub refresh_token_ttl_days: i64,
+    pub revocation_sync_seconds: u64,
+    pub mfa_issuer: String,
+    pub mfa_challenge_ttl_minutes: i64,
+    pub lockout: LockoutConfig,
+    pub password_reset: PasswordResetConfig,
+    pub email_verification: EmailVerificationConfig,
+    pub personal_access_tokens: PersonalAccessTokenConfig,
+    pub oauth: OAuthConfig,
+    pub password_policy: PasswordPolicyConfig,
+    pub password_history: PasswordHistoryConfig,
+    pub password_hashing: PasswordHashingConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LockoutConfig {
+    pub max_account_failures: u32,
+    pub max_ip_failures: u32,
+    pub failure_window_minutes: i64,
+    pub lockout_minutes: i64,
+    pub backoff_base_seconds: i64,
+    pub backoff_max_seconds: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PasswordResetConfig {
+    pub token_ttl_minutes: i64,
+    pub link_template: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct EmailVerificationConfig {
+    pub token_ttl_hours: i64,
+    pub link_template: String,
+    pub require_verified_login: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PersonalAccessTokenConfig {
+    pub default_ttl_days: i64,
+    pub max_ttl_days: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct OAuthConfig {
+    pub authorization_code_ttl_seconds: i64,
+    pub code_purge_interval_minutes: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PasswordPolicyConfig {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub min_strength_score: u8,
+    pub reject_personal_info: bool,
+    #[serde(default)]
+    pub breached_passwords_file: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PasswordHistoryConfig {
+    pub remember: usize,
+    pub max_age_days: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PasswordHashingConfig {
+    pub workers: usize,
+    pub queue_capacity: usize,
+    pub retry_after_seconds: u64,
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    #[serde(default)]
+    pub active_pepper_id: Option<String>,
+    #[serde(default)]
+    pub peppers: Vec<PepperConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PepperConfig {
+    pub id: String,
+    pub secret: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct JwtKeyConfig {
+    pub kid: String,
+    pub public_key_path: String,
+    #[serde(default)]
+    pub private_key_path: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UsersConfig {
+    pub deleted_retention_days: i64,
+    pub purge_interval_minutes: u64,
+    pub invitations: InvitationConfig,
+    pub registration: RegistrationConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct InvitationConfig {
+    pub token_ttl_hours: i64,
+    pub link_template: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RegistrationConfig {
+    pub mode: String,
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -53,3 +185,24 @@ pub struct BootstrapConfig {
     pub admin_email: String,
     pub admin_password: String,
 }
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MailConfig {
+    pub transport: String,
+    pub from: String,
+    pub file_directory: String,
+    pub smtp: SmtpConfig,
+    pub dispatch_interval_seconds: u64,
+    pub max_attempts: u32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    pub starttls: bool,
+}
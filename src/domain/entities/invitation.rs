@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// Convite de um usuario `pending`; o token em claro so existe no email enviado ao convidado.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invitation {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub email: String,
+    // `None` quando o convite veio do bootstrap ou o autor ja foi removido.
+    pub invited_by: Option<Uuid>,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Invitation {
+    // Ainda nao aceito nem revogado; pode estar expirado e ser reenviado.
+    pub fn is_pending(&self) -> bool {
+        self.accepted_at.is_none() && self.revoked_at.is_none()
+    }
+
+    pub fn is_usable(&self, now: DateTime<Utc>) -> bool {
+        self.is_pending() && self.expires_at > now
+    }
+}
+
+// Convite gravado junto com o usuario convidado (ver `NewUser::with_invitation`).
+#[derive(Clone, Debug)]
+pub struct NewInvitation {
+    pub invited_by: Option<Uuid>,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+
+// Falhas de login acumuladas para uma chave (conta ou IP) dentro da janela corrente.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoginThrottle {
+    pub key_hash: String,
+    pub failure_count: u32,
+    pub window_started_at: DateTime<Utc>,
+    pub last_failure_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+impl LoginThrottle {
+    pub fn is_locked(&self, now: DateTime<Utc>) -> bool {
+        self.locked_until.is_some_and(|until| until > now)
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::value_objects::RoleName;
+
+// Segredo TOTP do usuario. Enquanto `confirmed_at` for nulo o cadastro esta pendente e o
+// segundo fator ainda nao e exigido no login.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TotpCredential {
+    pub user_id: Uuid,
+    pub secret: String,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    // Ultimo passo de 30s aceito; codigos do mesmo passo (ou anteriores) sao recusados.
+    pub last_used_step: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TotpCredential {
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryCode {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub code_hash: String,
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MfaPolicy {
+    pub role: RoleName,
+    pub required: bool,
+    pub updated_at: DateTime<Utc>,
+}
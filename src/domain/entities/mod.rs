@@ -1 +1,14 @@
-﻿pub mod user;
+pub mod email_outbox;
+pub mod invitation;
+pub mod login_throttle;
+pub mod mfa;
+pub mod oauth;
+pub mod password_reset;
+pub mod permission;
+pub mod personal_access_token;
+pub mod refresh_token;
+pub mod registration;
+pub mod role;
+pub mod session;
+pub mod token_revocation;
+pub mod user;
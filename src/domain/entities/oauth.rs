@@ -0,0 +1,175 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::permission::Permission;
+
+// Aplicativo registrado no servidor de autorizacao. Clientes confidenciais se autenticam em
+// `/oauth/token` com o segredo; clientes publicos (SPA, mobile) nao guardam segredo e dependem
+// apenas do PKCE.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OAuthClient {
+    pub id: Uuid,
+    pub name: String,
+    pub client_type: OAuthClientType,
+    // Hash SHA-256 do segredo; ausente em clientes publicos.
+    pub secret_hash: Option<String>,
+    // Comparadas por igualdade exata com o `redirect_uri` da requisicao.
+    pub redirect_uris: Vec<String>,
+    pub grant_types: Vec<OAuthGrantType>,
+    // Teto do que o cliente pode pedir; o concedido ainda depende dos papeis do usuario.
+    pub scopes: Vec<Permission>,
+    // Quem registrou o cliente; tokens de `client_credentials` agem em nome dele.
+    pub owner_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub disabled_at: Option<DateTime<Utc>>,
+}
+
+impl OAuthClient {
+    pub fn is_active(&self) -> bool {
+        self.disabled_at.is_none()
+    }
+
+    pub fn allows(&self, grant_type: OAuthGrantType) -> bool {
+        self.grant_types.contains(&grant_type)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NewOAuthClient {
+    pub name: String,
+    pub client_type: OAuthClientType,
+    pub secret_hash: Option<String>,
+    pub redirect_uris: Vec<String>,
+    pub grant_types: Vec<OAuthGrantType>,
+    pub scopes: Vec<Permission>,
+    pub owner_id: Uuid,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OAuthClientType {
+    Confidential,
+    Public,
+}
+
+impl OAuthClientType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Confidential => "confidential",
+            Self::Public => "public",
+        }
+    }
+}
+
+impl fmt::Display for OAuthClientType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OAuthClientType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "confidential" => Ok(Self::Confidential),
+            "public" => Ok(Self::Public),
+            other => Err(format!("unknown client type: {other}")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OAuthGrantType {
+    AuthorizationCode,
+    RefreshToken,
+    ClientCredentials,
+}
+
+impl OAuthGrantType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AuthorizationCode => "authorization_code",
+            Self::RefreshToken => "refresh_token",
+            Self::ClientCredentials => "client_credentials",
+        }
+    }
+}
+
+impl fmt::Display for OAuthGrantType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OAuthGrantType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "authorization_code" => Ok(Self::AuthorizationCode),
+            "refresh_token" => Ok(Self::RefreshToken),
+            "client_credentials" => Ok(Self::ClientCredentials),
+            other => Err(format!("unsupported grant type: {other}")),
+        }
+    }
+}
+
+// Codigo de autorizacao de uso unico, persistido apenas como hash. `session_id` e reservado na
+// emissao: a sessao aberta na troca recebe esse id, e reapresentar o codigo a revoga.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthorizationCode {
+    pub id: Uuid,
+    pub code_hash: String,
+    pub client_id: Uuid,
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+    pub redirect_uri: String,
+    // Se o `redirect_uri` veio na autorizacao; so entao a troca exige o mesmo valor (RFC 6749,
+    // secao 4.1.3).
+    pub redirect_uri_supplied: bool,
+    pub scopes: Vec<Permission>,
+    // BASE64URL(SHA-256(code_verifier)); so aceitamos o metodo S256.
+    pub code_challenge: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+}
+
+impl AuthorizationCode {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NewAuthorizationCode {
+    pub code_hash: String,
+    pub client_id: Uuid,
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+    pub redirect_uri: String,
+    pub redirect_uri_supplied: bool,
+    pub scopes: Vec<Permission>,
+    pub code_challenge: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+// Escopos que o usuario ja aprovou para o cliente; pedidos cobertos por eles dispensam a tela
+// de consentimento.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OAuthConsent {
+    pub user_id: Uuid,
+    pub client_id: Uuid,
+    pub scopes: Vec<Permission>,
+    pub granted_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OAuthConsent {
+    pub fn covers(&self, scopes: &[Permission]) -> bool {
+        scopes.iter().all(|scope| self.scopes.contains(scope))
+    }
+}
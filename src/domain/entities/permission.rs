@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// Catalogo fixo de permissoes. Papeis sao criados em tempo de execucao, mas so podem combinar
+// itens desta lista; o mesmo vocabulario define os escopos dos tokens pessoais e do OAuth.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Permission {
+    UsersRead,
+    UsersWrite,
+    UsersDelete,
+    RolesRead,
+    RolesWrite,
+    AuditRead,
+    ClientsRead,
+    ClientsWrite,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 8] = [
+        Permission::UsersRead,
+        Permission::UsersWrite,
+        Permission::UsersDelete,
+        Permission::RolesRead,
+        Permission::RolesWrite,
+        Permission::AuditRead,
+        Permission::ClientsRead,
+        Permission::ClientsWrite,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UsersRead => "users:read",
+            Self::UsersWrite => "users:write",
+            Self::UsersDelete => "users:delete",
+            Self::RolesRead => "roles:read",
+            Self::RolesWrite => "roles:write",
+            Self::AuditRead => "audit:read",
+            Self::ClientsRead => "clients:read",
+            Self::ClientsWrite => "clients:write",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::UsersRead => "Read any user account",
+            Self::UsersWrite => {
+                "Create and update user accounts, revoke their sessions and unlock them"
+            }
+            Self::UsersDelete => "Delete user accounts",
+            Self::RolesRead => "Read roles and MFA policies",
+            Self::RolesWrite => "Create, update and delete roles and MFA policies",
+            Self::AuditRead => "Read the audit trail",
+            Self::ClientsRead => "Read registered OAuth clients",
+            Self::ClientsWrite => "Register and disable OAuth clients",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PermissionParseError(pub String);
+
+impl fmt::Display for PermissionParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for PermissionParseError {}
+
+impl FromStr for Permission {
+    type Err = PermissionParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == value)
+            .ok_or_else(|| PermissionParseError(format!("invalid permission: {value}")))
+    }
+}
+
+// Escopo OAuth: permissoes separadas por espaco (RFC 6749, secao 3.3). Repeticoes sao ignoradas
+// e o resultado segue a ordem do catalogo.
+pub fn parse_scope(value: &str) -> Result<Vec<Permission>, PermissionParseError> {
+    let mut scopes = value
+        .split(' ')
+        .filter(|item| !item.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<Permission>, _>>()?;
+    scopes.sort();
+    scopes.dedup();
+    Ok(scopes)
+}
+
+pub fn format_scope(scopes: &[Permission]) -> String {
+    scopes
+        .iter()
+        .map(Permission::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// Pedido de autocadastro aguardando um admin; so existe no modo que exige aprovacao.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Registration {
+    pub user_id: Uuid,
+    pub email: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    // `None` enquanto pendente ou se o revisor ja foi removido.
+    pub reviewed_by: Option<Uuid>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub rejected_at: Option<DateTime<Utc>>,
+}
+
+impl Registration {
+    pub fn is_pending(&self) -> bool {
+        self.approved_at.is_none() && self.rejected_at.is_none()
+    }
+}
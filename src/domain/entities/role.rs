@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::permission::Permission;
+use crate::domain::value_objects::RoleName;
+
+// Papeis criados pela migration e que nao podem ser alterados nem removidos pela API.
+pub const ADMIN_ROLE: &str = "admin";
+pub const VIEWER_ROLE: &str = "viewer";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Role {
+    pub id: Uuid,
+    pub name: RoleName,
+    pub description: Option<String>,
+    pub permissions: Vec<Permission>,
+    pub built_in: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Role {
+    pub fn grants(&self, permission: Permission) -> bool {
+        self.permissions.contains(&permission)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NewRole {
+    pub name: RoleName,
+    pub description: Option<String>,
+    pub permissions: Vec<Permission>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UpdateRole {
+    pub description: Option<String>,
+    pub permissions: Option<Vec<Permission>>,
+}
+
+impl UpdateRole {
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none() && self.permissions.is_none()
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::permission::Permission;
+
+// Um login e tudo o que ele originou. O `id` e o `family_id` dos refresh tokens e vai no claim
+// `sid` dos access tokens; revogar a sessao derruba os dois.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub auth_method: SessionAuthMethod,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    // Validade do refresh token mais recente; avanca a cada renovacao.
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    // Presente quando a sessao foi aberta por um cliente OAuth em nome do usuario.
+    pub grant: Option<SessionGrant>,
+}
+
+impl Session {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NewSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub auth_method: SessionAuthMethod,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub grant: Option<SessionGrant>,
+}
+
+// Cliente OAuth dono da sessao e os escopos concedidos a ele. Os tokens da sessao so valem
+// dentro desses escopos e so sao renovados pelo mesmo cliente em `/oauth/token`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionGrant {
+    pub client_id: Uuid,
+    pub scopes: Vec<Permission>,
+}
+
+// Como o usuario provou a identidade ao abrir a sessao.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionAuthMethod {
+    Password,
+    // Senha seguida de TOTP ou recovery code.
+    PasswordAndTotp,
+    // Codigo de autorizacao OAuth trocado por um cliente registrado.
+    OAuth,
+}
+
+impl SessionAuthMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Password => "password",
+            Self::PasswordAndTotp => "password+totp",
+            Self::OAuth => "oauth",
+        }
+    }
+}
+
+impl fmt::Display for SessionAuthMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SessionAuthMethod {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "password" => Ok(Self::Password),
+            "password+totp" => Ok(Self::PasswordAndTotp),
+            "oauth" => Ok(Self::OAuth),
+            other => Err(format!("unknown session auth method: {other}")),
+        }
+    }
+}
+
+// Dados do cliente que abriu a sessao, vindos da requisicao de login.
+#[derive(Clone, Debug, Default)]
+pub struct SessionClient {
+    pub ip_address: Option<IpAddr>,
+    pub user_agent: Option<String>,
+}
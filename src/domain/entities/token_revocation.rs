@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// Access token revogado individualmente (logout). Mantido ate `expires_at`, quando o
+// proprio JWT deixa de ser aceito e a entrada pode ser expurgada.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevokedToken {
+    pub jti: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+// Revogacao de todas as sessoes de um usuario: tokens emitidos antes de `revoked_before` (pelo
+// instante do jti) sao rejeitados, exceto os de uma sessao (`sid`) ainda ativa.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserTokenRevocation {
+    pub user_id: Uuid,
+    pub revoked_before: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RevocationSnapshot {
+    pub tokens: Vec<RevokedToken>,
+    pub users: Vec<UserTokenRevocation>,
+}
@@ -1,66 +1,124 @@
-use std::error::Error;
-use std::fmt;
-use std::str::FromStr;
-
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::domain::entities::invitation::NewInvitation;
 use crate::domain::errors::DomainError;
-use crate::domain::value_objects::{EmailAddress, PasswordHash, PlainPassword, UserName};
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub enum UserRole {
-    Admin,
-    Viewer,
+use crate::domain::value_objects::{
+    EmailAddress, Locale, PasswordHash, PlainPassword, RoleName, TimeZoneName, UserName,
+};
+
+// Ciclo de vida da conta. Apenas `Active` autentica; as transicoes permitidas ficam em
+// `can_transition_to` para que nenhuma camada acima consiga pular etapas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UserStatus {
+    #[default]
+    Active,
+    // Bloqueio administrativo (politica, desligamento).
+    Suspended,
+    // Conta criada mas ainda nao ativada.
+    Pending,
+    // Bloqueio de seguranca, ex.: credencial comprometida; derruba as sessoes abertas.
+    Locked,
 }
 
-impl UserRole {
+impl UserStatus {
+    pub const ALL: [UserStatus; 4] = [
+        UserStatus::Active,
+        UserStatus::Suspended,
+        UserStatus::Pending,
+        UserStatus::Locked,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
-            Self::Admin => "admin",
-            Self::Viewer => "viewer",
+            UserStatus::Active => "active",
+            UserStatus::Suspended => "suspended",
+            UserStatus::Pending => "pending",
+            UserStatus::Locked => "locked",
         }
     }
-}
 
-impl fmt::Display for UserRole {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(self.as_str())
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == value.trim().to_ascii_lowercase())
+            .ok_or_else(|| DomainError::validation(format!("invalid user status: {value}")))
     }
-}
-
-#[derive(Debug, Clone)]
-pub struct UserRoleParseError(pub String);
 
-impl fmt::Display for UserRoleParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.0)
+    pub fn can_sign_in(&self) -> bool {
+        matches!(self, UserStatus::Active)
     }
-}
-
-impl Error for UserRoleParseError {}
 
-impl FromStr for UserRole {
-    type Err = UserRoleParseError;
+    pub fn can_transition_to(&self, next: UserStatus) -> bool {
+        use UserStatus::*;
+
+        matches!(
+            (self, next),
+            (Pending, Active)
+                | (Pending, Suspended)
+                | (Active, Suspended)
+                | (Active, Locked)
+                | (Suspended, Active)
+                | (Suspended, Locked)
+                | (Locked, Active)
+                | (Locked, Suspended)
+        )
+    }
 
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        match value {
-            "admin" => Ok(Self::Admin),
-            "viewer" => Ok(Self::Viewer),
-            _ => Err(UserRoleParseError(format!("invalid role: {value}"))),
+    pub fn transition_to(&self, next: UserStatus) -> Result<UserStatus, DomainError> {
+        if *self == next {
+            return Err(DomainError::InvalidTransition(format!(
+                "user is already {}",
+                next.as_str()
+            )));
+        }
+        if !self.can_transition_to(next) {
+            return Err(DomainError::InvalidTransition(format!(
+                "cannot change user status from {} to {}",
+                self.as_str(),
+                next.as_str()
+            )));
         }
+        Ok(next)
+    }
+}
+
+impl std::fmt::Display for UserStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
+// Preferencias editaveis pelo proprio usuario; ausentes significam o padrao do cliente.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserPreferences {
+    pub locale: Option<Locale>,
+    pub timezone: Option<TimeZoneName>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct User {
     id: Uuid,
     name: UserName,
     email: EmailAddress,
-    role: UserRole,
+    roles: Vec<RoleName>,
     password_hash: PasswordHash,
+    // Incrementada a cada alteracao; base do controle de concorrencia otimista.
+    version: i64,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    // Exclusao logica: o registro fica restauravel ate ser expurgado pela retencao.
+    deleted_at: Option<DateTime<Utc>>,
+    status: UserStatus,
+    // Motivo informado na ultima mudanca de status, exibido para administradores.
+    status_reason: Option<String>,
+    preferences: UserPreferences,
+    // `None` ate o dono confirmar o endereco pelo link enviado por email.
+    email_verified_at: Option<DateTime<Utc>>,
+    // Novo endereco aguardando confirmacao; `email` so muda quando o link e aberto.
+    pending_email: Option<EmailAddress>,
+    // Base da validade maxima da senha; comeca na criacao da conta.
+    password_changed_at: DateTime<Utc>,
 }
 
 impl User {
@@ -69,40 +127,94 @@ impl User {
         id: Uuid,
         name: UserName,
         email: EmailAddress,
-        role: UserRole,
+        roles: Vec<RoleName>,
         password_hash: PasswordHash,
+        version: i64,
         created_at: DateTime<Utc>,
         updated_at: DateTime<Utc>,
+        deleted_at: Option<DateTime<Utc>>,
     ) -> Self {
         Self {
             id,
             name,
             email,
-            role,
+            roles,
             password_hash,
+            version,
             created_at,
             updated_at,
+            deleted_at,
+            status: UserStatus::Active,
+            status_reason: None,
+            preferences: UserPreferences::default(),
+            email_verified_at: None,
+            pending_email: None,
+            password_changed_at: created_at,
         }
     }
 
+    pub fn with_status(mut self, status: UserStatus, reason: Option<String>) -> Self {
+        self.status = status;
+        self.status_reason = reason;
+        self
+    }
+
+    pub fn with_preferences(mut self, preferences: UserPreferences) -> Self {
+        self.preferences = preferences;
+        self
+    }
+
+    pub fn with_email_verification(
+        mut self,
+        verified_at: Option<DateTime<Utc>>,
+        pending_email: Option<EmailAddress>,
+    ) -> Self {
+        self.email_verified_at = verified_at;
+        self.pending_email = pending_email;
+        self
+    }
+
+    pub fn with_password_changed_at(mut self, changed_at: DateTime<Utc>) -> Self {
+        self.password_changed_at = changed_at;
+        self
+    }
+
+    pub fn with_password_hash(mut self, password_hash: PasswordHash) -> Self {
+        self.password_hash = password_hash;
+        self
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn try_new(
         id: Uuid,
         name: &str,
         email: &str,
-        role: UserRole,
+        roles: &[String],
         password_hash: &str,
+        version: i64,
         created_at: DateTime<Utc>,
         updated_at: DateTime<Utc>,
+        deleted_at: Option<DateTime<Utc>>,
     ) -> Result<Self, DomainError> {
         Ok(Self {
             id,
             name: UserName::parse(name)?,
             email: EmailAddress::parse(email)?,
-            role,
+            roles: roles
+                .iter()
+                .map(RoleName::parse)
+                .collect::<Result<_, _>>()?,
             password_hash: PasswordHash::new(password_hash)?,
+            version,
             created_at,
             updated_at,
+            deleted_at,
+            status: UserStatus::Active,
+            status_reason: None,
+            preferences: UserPreferences::default(),
+            email_verified_at: None,
+            pending_email: None,
+            password_changed_at: created_at,
         })
     }
 
@@ -118,14 +230,18 @@ impl User {
         &self.email
     }
 
-    pub fn role(&self) -> UserRole {
-        self.role.clone()
+    pub fn roles(&self) -> &[RoleName] {
+        &self.roles
     }
 
     pub fn password_hash(&self) -> &PasswordHash {
         &self.password_hash
     }
 
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -133,6 +249,42 @@ impl User {
     pub fn updated_at(&self) -> DateTime<Utc> {
         self.updated_at
     }
+
+    pub fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub fn status(&self) -> UserStatus {
+        self.status
+    }
+
+    pub fn status_reason(&self) -> Option<&str> {
+        self.status_reason.as_deref()
+    }
+
+    pub fn preferences(&self) -> &UserPreferences {
+        &self.preferences
+    }
+
+    pub fn email_verified_at(&self) -> Option<DateTime<Utc>> {
+        self.email_verified_at
+    }
+
+    pub fn is_email_verified(&self) -> bool {
+        self.email_verified_at.is_some()
+    }
+
+    pub fn pending_email(&self) -> Option<&EmailAddress> {
+        self.pending_email.as_ref()
+    }
+
+    pub fn password_changed_at(&self) -> DateTime<Utc> {
+        self.password_changed_at
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -140,7 +292,13 @@ pub struct NewUser {
     pub name: UserName,
     pub email: EmailAddress,
     pub password_hash: PasswordHash,
-    pub role: UserRole,
+    pub roles: Vec<RoleName>,
+    // Convidados nascem `pending` e so ativam ao aceitar o convite.
+    pub status: UserStatus,
+    // Autocadastro que espera aprovacao: o pedido entra na fila junto com o usuario.
+    pub registration: bool,
+    // Convite do usuario `pending`, criado na mesma gravacao.
+    pub invitation: Option<NewInvitation>,
 }
 
 impl NewUser {
@@ -148,21 +306,39 @@ impl NewUser {
         name: UserName,
         email: EmailAddress,
         password_hash: PasswordHash,
-        role: UserRole,
+        roles: Vec<RoleName>,
     ) -> Self {
         Self {
             name,
             email,
             password_hash,
-            role,
+            roles,
+            status: UserStatus::Active,
+            registration: false,
+            invitation: None,
         }
     }
 
+    pub fn with_status(mut self, status: UserStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_registration(mut self) -> Self {
+        self.registration = true;
+        self
+    }
+
+    pub fn with_invitation(mut self, invitation: NewInvitation) -> Self {
+        self.invitation = Some(invitation);
+        self
+    }
+
     pub fn try_from_input(
         name: &str,
         email: &str,
         password: &PlainPassword,
-        role: UserRole,
+        roles: Vec<RoleName>,
         hashed_password: &str,
     ) -> Result<Self, DomainError> {
         let _ = password; // ensures password already validated
@@ -170,7 +346,10 @@ impl NewUser {
             name: UserName::parse(name)?,
             email: EmailAddress::parse(email)?,
             password_hash: PasswordHash::new(hashed_password)?,
-            role,
+            roles,
+            status: UserStatus::Active,
+            registration: false,
+            invitation: None,
         })
     }
 
@@ -186,8 +365,20 @@ impl NewUser {
         &self.password_hash
     }
 
-    pub fn role(&self) -> UserRole {
-        self.role.clone()
+    pub fn roles(&self) -> &[RoleName] {
+        &self.roles
+    }
+
+    pub fn status(&self) -> UserStatus {
+        self.status
+    }
+
+    pub fn has_registration(&self) -> bool {
+        self.registration
+    }
+
+    pub fn invitation(&self) -> Option<&NewInvitation> {
+        self.invitation.as_ref()
     }
 }
 
@@ -196,7 +387,17 @@ pub struct UpdateUser {
     pub name: Option<UserName>,
     pub email: Option<EmailAddress>,
     pub password_hash: Option<PasswordHash>,
-    pub role: Option<UserRole>,
+    // Quando presente, substitui todos os papeis atribuidos ao usuario.
+    pub roles: Option<Vec<RoleName>>,
+    // Novo status e o motivo da mudanca; a transicao ja deve ter sido validada.
+    pub status: Option<(UserStatus, Option<String>)>,
+    // Quando presente, substitui todas as preferencias.
+    pub preferences: Option<UserPreferences>,
+    // Marca o email (o novo, se `email` tambem mudar) como confirmado. Trocar o email sem isso
+    // volta a conta para nao verificada.
+    pub email_verified_at: Option<DateTime<Utc>>,
+    // `Some(None)` descarta a troca de email pendente.
+    pub pending_email: Option<Option<EmailAddress>>,
 }
 
 impl UpdateUser {
@@ -215,8 +416,28 @@ impl UpdateUser {
         self
     }
 
-    pub fn apply_role(mut self, role: UserRole) -> Self {
-        self.role = Some(role);
+    pub fn apply_roles(mut self, roles: Vec<RoleName>) -> Self {
+        self.roles = Some(roles);
+        self
+    }
+
+    pub fn apply_status(mut self, status: UserStatus, reason: Option<String>) -> Self {
+        self.status = Some((status, reason));
+        self
+    }
+
+    pub fn apply_preferences(mut self, preferences: UserPreferences) -> Self {
+        self.preferences = Some(preferences);
+        self
+    }
+
+    pub fn apply_email_verified(mut self, verified_at: DateTime<Utc>) -> Self {
+        self.email_verified_at = Some(verified_at);
+        self
+    }
+
+    pub fn apply_pending_email(mut self, email: Option<EmailAddress>) -> Self {
+        self.pending_email = Some(email);
         self
     }
 
@@ -228,18 +449,227 @@ impl UpdateUser {
         self.email.as_ref().map(|value| value.as_str())
     }
 
+    pub fn email_canonical(&self) -> Option<&str> {
+        self.email.as_ref().map(|value| value.canonical())
+    }
+
     pub fn password_hash_str(&self) -> Option<&str> {
         self.password_hash.as_ref().map(|value| value.as_str())
     }
 
-    pub fn role(&self) -> Option<UserRole> {
-        self.role.clone()
+    pub fn roles(&self) -> Option<&[RoleName]> {
+        self.roles.as_deref()
+    }
+
+    pub fn status_str(&self) -> Option<&str> {
+        self.status.as_ref().map(|(status, _)| status.as_str())
+    }
+
+    pub fn status_reason(&self) -> Option<&str> {
+        self.status
+            .as_ref()
+            .and_then(|(_, reason)| reason.as_deref())
+    }
+
+    pub fn pending_email_str(&self) -> Option<&str> {
+        self.pending_email
+            .as_ref()
+            .and_then(|email| email.as_ref())
+            .map(EmailAddress::as_str)
+    }
+
+    pub fn locale_str(&self) -> Option<&str> {
+        self.preferences
+            .as_ref()
+            .and_then(|preferences| preferences.locale.as_ref())
+            .map(Locale::as_str)
+    }
+
+    pub fn timezone_str(&self) -> Option<&str> {
+        self.preferences
+            .as_ref()
+            .and_then(|preferences| preferences.timezone.as_ref())
+            .map(TimeZoneName::as_str)
     }
 
     pub fn is_empty(&self) -> bool {
         self.name.is_none()
             && self.email.is_none()
             && self.password_hash.is_none()
-            && self.role.is_none()
+            && self.roles.is_none()
+            && self.status.is_none()
+            && self.preferences.is_none()
+            && self.email_verified_at.is_none()
+            && self.pending_email.is_none()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UserSortField {
+    Name,
+    Email,
+    #[default]
+    CreatedAt,
+}
+
+impl UserSortField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserSortField::Name => "name",
+            UserSortField::Email => "email",
+            UserSortField::CreatedAt => "created_at",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "name" => Ok(UserSortField::Name),
+            "email" => Ok(UserSortField::Email),
+            "created_at" => Ok(UserSortField::CreatedAt),
+            other => Err(DomainError::validation(format!(
+                "invalid sort field: {other} (use name, email or created_at)"
+            ))),
+        }
+    }
+
+    // Nomes e emails crescem em ordem alfabetica; datas mostram primeiro os mais recentes.
+    pub fn default_direction(&self) -> SortDirection {
+        match self {
+            UserSortField::Name | UserSortField::Email => SortDirection::Asc,
+            UserSortField::CreatedAt => SortDirection::Desc,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
     }
+
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "asc" => Ok(SortDirection::Asc),
+            "desc" => Ok(SortDirection::Desc),
+            other => Err(DomainError::validation(format!(
+                "invalid sort order: {other} (use asc or desc)"
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UserSort {
+    pub field: UserSortField,
+    pub direction: SortDirection,
+}
+
+// Valor da coluna ordenada no ultimo usuario entregue; o id desempata valores iguais.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UserSortKey {
+    Name(String),
+    Email(String),
+    CreatedAt(DateTime<Utc>),
+}
+
+impl UserSortKey {
+    pub fn of(user: &User, field: UserSortField) -> Self {
+        match field {
+            UserSortField::Name => UserSortKey::Name(user.name().as_str().to_string()),
+            UserSortField::Email => UserSortKey::Email(user.email().as_str().to_string()),
+            UserSortField::CreatedAt => UserSortKey::CreatedAt(user.created_at()),
+        }
+    }
+
+    pub fn field(&self) -> UserSortField {
+        match self {
+            UserSortKey::Name(_) => UserSortField::Name,
+            UserSortKey::Email(_) => UserSortField::Email,
+            UserSortKey::CreatedAt(_) => UserSortField::CreatedAt,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserCursor {
+    pub direction: SortDirection,
+    pub key: UserSortKey,
+    pub id: Uuid,
+}
+
+impl UserCursor {
+    pub fn after(user: &User, sort: UserSort) -> Self {
+        Self {
+            direction: sort.direction,
+            key: UserSortKey::of(user, sort.field),
+            id: user.id(),
+        }
+    }
+
+    pub fn sort(&self) -> UserSort {
+        UserSort {
+            field: self.key.field(),
+            direction: self.direction,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UserFilter {
+    pub role: Option<RoleName>,
+    // Dominio canonico (punycode, minusculas), comparado com `EmailAddress::domain`.
+    pub email_domain: Option<String>,
+    // Intervalo semiaberto: `created_after` inclusivo, `created_before` exclusivo.
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub status: Option<UserStatus>,
+    // Por padrao usuarios excluidos logicamente ficam de fora.
+    pub include_deleted: bool,
+}
+
+impl UserFilter {
+    pub fn matches(&self, user: &User) -> bool {
+        (self.include_deleted || !user.is_deleted())
+            && self
+                .role
+                .as_ref()
+                .is_none_or(|role| user.roles().contains(role))
+            && self
+                .email_domain
+                .as_deref()
+                .is_none_or(|domain| user.email().domain() == domain)
+            && self
+                .created_after
+                .is_none_or(|after| user.created_at() >= after)
+            && self
+                .created_before
+                .is_none_or(|before| user.created_at() < before)
+            && self.status.is_none_or(|status| user.status() == status)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UserQuery {
+    pub filter: UserFilter,
+    pub sort: UserSort,
+    pub after: Option<UserCursor>,
+    pub limit: u32,
+    pub include_total: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct UserPage {
+    pub users: Vec<User>,
+    pub has_more: bool,
+    // Total de usuarios que passam pelos filtros, ignorando o cursor.
+    pub total: Option<u64>,
 }
@@ -4,6 +4,8 @@
 pub enum DomainError {
     #[error("validation error: {0}")]
     Validation(String),
+    #[error("invalid state transition: {0}")]
+    InvalidTransition(String),
 }
 
 impl DomainError {
@@ -13,7 +15,7 @@ impl DomainError {
 
     pub fn message(&self) -> &str {
         match self {
-            Self::Validation(msg) => msg,
+            Self::Validation(msg) | Self::InvalidTransition(msg) => msg,
         }
     }
 }
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::shared::error::AppResult;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+// Transporte de email plugavel: SMTP em producao, arquivo ou memoria em desenvolvimento e testes.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()>;
+}
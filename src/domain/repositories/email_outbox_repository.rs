@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::email_outbox::OutboundEmail;
+use crate::domain::mailer::EmailMessage;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait EmailOutboxRepository: Send + Sync {
+    async fn enqueue(&self, message: &EmailMessage) -> RepositoryResult<OutboundEmail>;
+    // Reserva ate `limit` mensagens pendentes com `next_attempt_at <= now`, incrementando
+    // `attempts` e adiando a proxima tentativa para `lease_until` para que outra instancia
+    // nao envie a mesma mensagem em paralelo.
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        max_attempts: u32,
+        limit: u32,
+    ) -> RepositoryResult<Vec<OutboundEmail>>;
+    async fn mark_sent(&self, id: Uuid, sent_at: DateTime<Utc>) -> RepositoryResult<()>;
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> RepositoryResult<()>;
+}
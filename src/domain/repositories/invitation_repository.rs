@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::invitation::Invitation;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait InvitationRepository: Send + Sync {
+    // Os convites nascem com o usuario, em `UserRepository::create`.
+    async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<Invitation>>;
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<Invitation>>;
+    // Convites nao aceitos nem revogados, incluindo os expirados, do mais antigo ao mais novo.
+    async fn find_pending(&self) -> RepositoryResult<Vec<Invitation>>;
+    // Troca o token de um convite pendente; `None` se ele ja foi aceito ou revogado.
+    async fn replace_token(
+        &self,
+        id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<Option<Invitation>>;
+    // Marca como aceito apenas se ainda estiver pendente e valido; `false` indica reuso.
+    async fn accept(&self, id: Uuid, accepted_at: DateTime<Utc>) -> RepositoryResult<bool>;
+    async fn revoke(&self, id: Uuid, revoked_at: DateTime<Utc>) -> RepositoryResult<bool>;
+}
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::login_throttle::LoginThrottle;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait LoginThrottleRepository: Send + Sync {
+    async fn find(&self, key_hash: &str) -> RepositoryResult<Option<LoginThrottle>>;
+    // Incrementa o contador de forma atomica. Se a ultima falha (ou o fim do bloqueio) for anterior
+    // a `window_start`, a contagem recomeca em 1.
+    async fn record_failure(
+        &self,
+        key_hash: &str,
+        now: DateTime<Utc>,
+        window_start: DateTime<Utc>,
+    ) -> RepositoryResult<LoginThrottle>;
+    async fn lock(&self, key_hash: &str, until: DateTime<Utc>) -> RepositoryResult<()>;
+    async fn clear(&self, key_hash: &str) -> RepositoryResult<()>;
+    async fn purge_stale(&self, before: DateTime<Utc>) -> RepositoryResult<u64>;
+}
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::mfa::{MfaPolicy, RecoveryCode, TotpCredential};
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::domain::value_objects::RoleName;
+
+#[async_trait]
+pub trait MfaRepository: Send + Sync {
+    async fn find_totp(&self, user_id: Uuid) -> RepositoryResult<Option<TotpCredential>>;
+    // Substitui um cadastro pendente; nunca sobrescreve um segredo ja confirmado.
+    async fn save_pending_totp(&self, user_id: Uuid, secret: &str) -> RepositoryResult<bool>;
+    // Confirma o segredo e troca o conjunto de recovery codes numa unica operacao.
+    async fn confirm_totp(
+        &self,
+        user_id: Uuid,
+        step: i64,
+        confirmed_at: DateTime<Utc>,
+        recovery_code_hashes: Vec<String>,
+    ) -> RepositoryResult<()>;
+    // Avanca `last_used_step` somente se `step` for maior; `false` indica replay.
+    async fn advance_totp_step(&self, user_id: Uuid, step: i64) -> RepositoryResult<bool>;
+    async fn find_unused_recovery_codes(
+        &self,
+        user_id: Uuid,
+    ) -> RepositoryResult<Vec<RecoveryCode>>;
+    async fn mark_recovery_code_used(
+        &self,
+        id: Uuid,
+        used_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool>;
+    async fn find_policies(&self) -> RepositoryResult<Vec<MfaPolicy>>;
+    async fn set_policy(&self, role: &RoleName, required: bool) -> RepositoryResult<MfaPolicy>;
+}
@@ -1 +1,16 @@
-﻿pub mod user_repository;
+pub mod email_outbox_repository;
+pub mod invitation_repository;
+pub mod login_throttle_repository;
+pub mod mfa_repository;
+pub mod oauth_authorization_code_repository;
+pub mod oauth_client_repository;
+pub mod oauth_consent_repository;
+pub mod password_history_repository;
+pub mod password_reset_repository;
+pub mod personal_access_token_repository;
+pub mod refresh_token_repository;
+pub mod registration_repository;
+pub mod role_repository;
+pub mod session_repository;
+pub mod token_revocation_repository;
+pub mod user_repository;
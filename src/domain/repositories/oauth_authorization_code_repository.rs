@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::{AuthorizationCode, NewAuthorizationCode};
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait AuthorizationCodeRepository: Send + Sync {
+    async fn create(&self, code: NewAuthorizationCode) -> RepositoryResult<AuthorizationCode>;
+    async fn find_by_hash(&self, code_hash: &str) -> RepositoryResult<Option<AuthorizationCode>>;
+    // Marca o codigo como usado apenas se ainda nao estava; `false` indica reuso.
+    async fn mark_consumed(&self, id: Uuid, consumed_at: DateTime<Utc>) -> RepositoryResult<bool>;
+    async fn purge_expired(&self, before: DateTime<Utc>) -> RepositoryResult<u64>;
+}
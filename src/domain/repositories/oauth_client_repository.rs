@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::{NewOAuthClient, OAuthClient};
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait OAuthClientRepository: Send + Sync {
+    async fn create(&self, client: NewOAuthClient) -> RepositoryResult<OAuthClient>;
+    async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<OAuthClient>>;
+    // Clientes ainda habilitados, do mais recente para o mais antigo.
+    async fn list_active(&self) -> RepositoryResult<Vec<OAuthClient>>;
+    // `false` se o cliente nao existe ou ja estava desabilitado.
+    async fn disable(&self, id: Uuid, disabled_at: DateTime<Utc>) -> RepositoryResult<bool>;
+}
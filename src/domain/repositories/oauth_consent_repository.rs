@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::OAuthConsent;
+use crate::domain::entities::permission::Permission;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait OAuthConsentRepository: Send + Sync {
+    async fn find(&self, user_id: Uuid, client_id: Uuid) -> RepositoryResult<Option<OAuthConsent>>;
+    // Soma os escopos aos ja aprovados para o cliente, criando o consentimento se preciso.
+    async fn grant(
+        &self,
+        user_id: Uuid,
+        client_id: Uuid,
+        scopes: &[Permission],
+    ) -> RepositoryResult<OAuthConsent>;
+    async fn find_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<OAuthConsent>>;
+    // `false` se nao havia consentimento.
+    async fn revoke(&self, user_id: Uuid, client_id: Uuid) -> RepositoryResult<bool>;
+}
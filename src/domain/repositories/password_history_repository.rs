@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::domain::value_objects::PasswordHash;
+
+#[async_trait]
+pub trait PasswordHistoryRepository: Send + Sync {
+    // Guarda o hash substituido e descarta os mais antigos alem dos `keep` mais recentes.
+    async fn record(
+        &self,
+        user_id: Uuid,
+        password_hash: &PasswordHash,
+        keep: usize,
+    ) -> RepositoryResult<()>;
+    // Do mais recente para o mais antigo.
+    async fn recent(&self, user_id: Uuid, limit: usize) -> RepositoryResult<Vec<PasswordHash>>;
+}
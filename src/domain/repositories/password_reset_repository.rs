@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::password_reset::PasswordResetToken;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait PasswordResetRepository: Send + Sync {
+    // Grava o novo token e invalida os anteriores ainda pendentes do mesmo usuario.
+    async fn replace_for_user(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<PasswordResetToken>;
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<PasswordResetToken>>;
+    // Marca o token como usado apenas se ainda estiver valido; `false` indica reuso ou expiracao.
+    async fn consume(&self, id: Uuid, used_at: DateTime<Utc>) -> RepositoryResult<bool>;
+    async fn purge_expired(&self, before: DateTime<Utc>) -> RepositoryResult<u64>;
+}
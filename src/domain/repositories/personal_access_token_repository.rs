@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::personal_access_token::{NewPersonalAccessToken, PersonalAccessToken};
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait PersonalAccessTokenRepository: Send + Sync {
+    async fn create(&self, token: NewPersonalAccessToken) -> RepositoryResult<PersonalAccessToken>;
+    async fn find_by_hash(&self, token_hash: &str)
+        -> RepositoryResult<Option<PersonalAccessToken>>;
+    // Lista os tokens nao revogados do usuario, incluindo os ja expirados.
+    async fn find_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<PersonalAccessToken>>;
+    // Revoga apenas se o token pertencer ao usuario e ainda estiver ativo; `false` caso contrario.
+    async fn revoke(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool>;
+    async fn record_usage(&self, id: Uuid, used_at: DateTime<Utc>) -> RepositoryResult<()>;
+}
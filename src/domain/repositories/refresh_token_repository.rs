@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::refresh_token::{NewRefreshToken, RefreshToken};
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    async fn create(&self, token: NewRefreshToken) -> RepositoryResult<RefreshToken>;
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<RefreshToken>>;
+    // Marca o token como rotacionado apenas se ainda estiver ativo; `false` indica reuso.
+    async fn mark_rotated(&self, id: Uuid) -> RepositoryResult<bool>;
+    async fn revoke_family(&self, family_id: Uuid) -> RepositoryResult<u64>;
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> RepositoryResult<u64>;
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::registration::Registration;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait RegistrationRepository: Send + Sync {
+    // Os pedidos nascem com o usuario, em `UserRepository::create`.
+    async fn find_by_user(&self, user_id: Uuid) -> RepositoryResult<Option<Registration>>;
+    // Pedidos ainda sem decisao, do mais antigo ao mais novo.
+    async fn find_pending(&self) -> RepositoryResult<Vec<Registration>>;
+    // Registram a decisao apenas se o pedido ainda estiver pendente; `false` caso contrario.
+    async fn approve(
+        &self,
+        user_id: Uuid,
+        reviewer: Uuid,
+        at: DateTime<Utc>,
+    ) -> RepositoryResult<bool>;
+    async fn reject(
+        &self,
+        user_id: Uuid,
+        reviewer: Uuid,
+        at: DateTime<Utc>,
+    ) -> RepositoryResult<bool>;
+}
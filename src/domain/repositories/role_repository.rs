@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::entities::role::{NewRole, Role, UpdateRole};
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::domain::value_objects::RoleName;
+
+#[async_trait]
+pub trait RoleRepository: Send + Sync {
+    async fn create(&self, role: NewRole) -> RepositoryResult<Role>;
+    async fn find_all(&self) -> RepositoryResult<Vec<Role>>;
+    async fn find_by_name(&self, name: &RoleName) -> RepositoryResult<Option<Role>>;
+    // Nomes desconhecidos sao ignorados; quem precisa deles valida o tamanho do resultado.
+    async fn find_by_names(&self, names: &[RoleName]) -> RepositoryResult<Vec<Role>>;
+    async fn update(&self, name: &RoleName, update: UpdateRole) -> RepositoryResult<Option<Role>>;
+    async fn delete(&self, name: &RoleName) -> RepositoryResult<bool>;
+}
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::session::{NewSession, Session};
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn create(&self, session: NewSession) -> RepositoryResult<Session>;
+    async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<Session>>;
+    // Sessoes nao revogadas e ainda validas, da usada mais recentemente para a mais antiga.
+    async fn find_active_by_user(
+        &self,
+        user_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> RepositoryResult<Vec<Session>>;
+    async fn record_activity(&self, id: Uuid, seen_at: DateTime<Utc>) -> RepositoryResult<()>;
+    // Prorroga a sessao quando o refresh token e rotacionado; `false` se ja estava revogada.
+    async fn renew(
+        &self,
+        id: Uuid,
+        seen_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool>;
+    // Revoga apenas se a sessao pertencer ao usuario e ainda nao estiver revogada.
+    async fn revoke(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool>;
+    async fn revoke_all_for_user(
+        &self,
+        user_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<u64>;
+    // Encerra as sessoes abertas pelo cliente OAuth; com `user_id`, apenas as desse usuario.
+    async fn revoke_all_for_client(
+        &self,
+        client_id: Uuid,
+        user_id: Option<Uuid>,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<u64>;
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::token_revocation::{
+    RevocationSnapshot, RevokedToken, UserTokenRevocation,
+};
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[async_trait]
+pub trait TokenRevocationRepository: Send + Sync {
+    async fn revoke_token(&self, token: RevokedToken) -> RepositoryResult<()>;
+    async fn revoke_user(&self, revocation: UserTokenRevocation) -> RepositoryResult<()>;
+    async fn find_active(&self, now: DateTime<Utc>) -> RepositoryResult<RevocationSnapshot>;
+    async fn purge_expired(&self, now: DateTime<Utc>) -> RepositoryResult<u64>;
+}
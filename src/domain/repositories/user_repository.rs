@@ -1,17 +1,45 @@
 ﻿use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::entities::user::{NewUser, UpdateUser, User};
+use crate::domain::entities::user::{NewUser, UpdateUser, User, UserPage, UserQuery};
+use crate::domain::value_objects::{EmailAddress, PasswordHash, RoleName};
 use crate::shared::error::AppError;
 
 pub type RepositoryResult<T> = Result<T, AppError>;
 
 #[async_trait]
 pub trait UserRepository: Send + Sync {
+    // Com `NewUser::with_registration` ou `NewUser::with_invitation`, grava o pedido de aprovacao
+    // ou o convite na mesma transacao: um usuario `pending` sem eles ficaria sem ninguem para
+    // ativa-lo, ocupando o email.
     async fn create(&self, new_user: NewUser) -> RepositoryResult<User>;
-    async fn find_all(&self) -> RepositoryResult<Vec<User>>;
+    async fn find_page(&self, query: &UserQuery) -> RepositoryResult<UserPage>;
+    // As buscas abaixo ignoram usuarios excluidos logicamente.
     async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<User>>;
-    async fn find_by_email(&self, email: &str) -> RepositoryResult<Option<User>>;
-    async fn update(&self, id: Uuid, update: UpdateUser) -> RepositoryResult<User>;
-    async fn delete(&self, id: Uuid) -> RepositoryResult<()>;
+    // Compara a forma canonica do email, sem diferenciar caixa nem normalizacao Unicode.
+    async fn find_by_email(&self, email: &EmailAddress) -> RepositoryResult<Option<User>>;
+    async fn find_deleted_by_id(&self, id: Uuid) -> RepositoryResult<Option<User>>;
+    // Com `expected_versions`, a escrita so acontece se a versao persistida for uma delas; do
+    // contrario retorna `AppError::PreconditionFailed`.
+    async fn update(
+        &self,
+        id: Uuid,
+        update: UpdateUser,
+        expected_versions: Option<Vec<i64>>,
+    ) -> RepositoryResult<User>;
+    // Regrava o hash da mesma senha com parametros novos. So escreve se o hash ainda for
+    // `current` e nao mexe em `version` nem em `password_changed_at`: a senha nao mudou.
+    async fn replace_password_hash(
+        &self,
+        id: Uuid,
+        current: &PasswordHash,
+        replacement: &PasswordHash,
+    ) -> RepositoryResult<bool>;
+    // Exclusao logica; o registro some de buscas e listagens mas pode ser restaurado.
+    async fn delete(&self, id: Uuid, expected_versions: Option<Vec<i64>>) -> RepositoryResult<()>;
+    async fn restore(&self, id: Uuid) -> RepositoryResult<User>;
+    // Remove definitivamente quem foi excluido antes de `deleted_before`.
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> RepositoryResult<u64>;
+    async fn count_with_role(&self, role: &RoleName) -> RepositoryResult<u64>;
 }
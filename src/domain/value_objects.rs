@@ -1,17 +1,46 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::net::{Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
 
 use crate::domain::errors::DomainError;
 
-static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)^[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}$").expect("invalid email regex")
+static ROLE_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z][a-z0-9_-]{0,63}$").expect("invalid role name regex"));
+
+static LOCALE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z]{2,3}(-[A-Za-z0-9]{2,8})*$").expect("invalid locale regex"));
+
+static TIME_ZONE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(UTC|[A-Za-z]+(/[A-Za-z0-9_+-]+)+)$").expect("invalid time zone regex")
 });
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct EmailAddress(String);
+// Limites da RFC 5321 (secao 4.5.3.1), contados em bytes UTF-8.
+const EMAIL_MAX_LENGTH: usize = 254;
+const EMAIL_LOCAL_MAX_LENGTH: usize = 64;
+const EMAIL_DOMAIN_MAX_LENGTH: usize = 253;
+const EMAIL_LABEL_MAX_LENGTH: usize = 63;
+
+// `atext` da RFC 5322 alem de letras e digitos ASCII.
+const EMAIL_ATEXT_SYMBOLS: &str = "!#$%&'*+-/=?^_`{|}~";
+
+// `as_str` devolve o endereco como informado (apenas aparado e em NFC), usado para exibir e
+// enviar emails. A identidade e `canonical`: parte local em NFC e minusculas, dominio em
+// punycode minusculo. Igualdade e hash usam apenas a forma canonica, entao `Ada@X.com` e
+// `ada@x.com` sao o mesmo endereco em qualquer repositorio.
+//
+// Aceita o que a RFC 5321 com a extensao SMTPUTF8 (RFC 6531) aceita: parte local dot-atom ou
+// entre aspas, com caracteres nao ASCII, e dominio IDN ou literal IP (`[192.0.2.1]`,
+// `[IPv6:2001:db8::1]`). Comentarios e enderecos com rota ficam de fora.
+#[derive(Clone, Debug)]
+pub struct EmailAddress {
+    value: String,
+    canonical: String,
+}
 
 impl EmailAddress {
     pub fn parse<S: AsRef<str>>(value: S) -> Result<Self, DomainError> {
@@ -19,25 +48,254 @@ impl EmailAddress {
         if trimmed.is_empty() {
             return Err(DomainError::validation("email is required"));
         }
-        if trimmed.len() > 190 {
-            return Err(DomainError::validation(
-                "email must be at most 190 characters",
-            ));
+        let value: String = trimmed.nfc().collect();
+        if value.len() > EMAIL_MAX_LENGTH {
+            return Err(email_too_long());
         }
-        if !EMAIL_REGEX.is_match(trimmed) {
-            return Err(DomainError::validation("email has an invalid format"));
+
+        let (local, domain) = split_email(&value)?;
+        let local = canonical_local_part(local)?;
+        let domain = canonical_domain(domain)?;
+        let canonical = format!("{local}@{domain}");
+        if canonical.len() > EMAIL_MAX_LENGTH {
+            return Err(email_too_long());
         }
-        Ok(Self(trimmed.to_owned()))
+        Ok(Self { value, canonical })
     }
 
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.value
+    }
+
+    // Chave de unicidade e de busca; nunca exibida ao usuario.
+    pub fn canonical(&self) -> &str {
+        &self.canonical
+    }
+
+    // Dominio na forma canonica (ASCII em minusculas, ou o literal IP entre colchetes).
+    pub fn domain(&self) -> &str {
+        self.canonical
+            .rsplit_once('@')
+            .map(|(_, domain)| domain)
+            .unwrap_or_default()
+    }
+}
+
+// Converte um dominio (possivelmente internacionalizado) para a forma ASCII em minusculas,
+// com as mesmas regras aplicadas a parte de dominio de um `EmailAddress`.
+pub fn normalize_email_domain(raw: &str) -> Result<String, DomainError> {
+    let domain: String = raw.trim().nfc().collect();
+    canonical_domain(&domain)
+}
+
+fn email_too_long() -> DomainError {
+    DomainError::validation(format!("email must be at most {EMAIL_MAX_LENGTH} bytes"))
+}
+
+// Uma parte local entre aspas pode conter `@`, entao o separador so e procurado depois dela.
+fn split_email(value: &str) -> Result<(&str, &str), DomainError> {
+    let separator = if value.starts_with('"') {
+        let mut escaped = false;
+        let closing = value
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| {
+                let closes = c == '"' && !escaped;
+                escaped = c == '\\' && !escaped;
+                closes
+            })
+            .map(|(index, _)| index)
+            .ok_or_else(|| DomainError::validation("email quoted local part is not closed"))?;
+        match value[closing + 1..].chars().next() {
+            Some('@') => closing + 1,
+            _ => {
+                return Err(DomainError::validation(
+                    "email quoted local part must be followed by @",
+                ))
+            }
+        }
+    } else {
+        value
+            .find('@')
+            .ok_or_else(|| DomainError::validation("email must contain @"))?
+    };
+    Ok((&value[..separator], &value[separator + 1..]))
+}
+
+// Caracteres nao ASCII permitidos pela RFC 6531; controles e espacos continuam proibidos.
+fn is_utf8_email_char(c: char) -> bool {
+    !c.is_ascii() && !c.is_control() && !c.is_whitespace()
+}
+
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || EMAIL_ATEXT_SYMBOLS.contains(c) || is_utf8_email_char(c)
+}
+
+fn is_dot_atom(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .split('.')
+            .all(|atom| !atom.is_empty() && atom.chars().all(is_atext))
+}
+
+fn invalid_local_char(c: char) -> DomainError {
+    DomainError::validation(format!(
+        "email local part contains an invalid character: '{}'",
+        c.escape_default()
+    ))
+}
+
+// Devolve a parte local canonica. Aspas desnecessarias saem (`"ada"` equivale a `ada`, como
+// pede a RFC 5321) e, nas necessarias, so `"` e `\` continuam escapados.
+fn canonical_local_part(local: &str) -> Result<String, DomainError> {
+    if local.is_empty() {
+        return Err(DomainError::validation("email local part is required"));
+    }
+    if local.len() > EMAIL_LOCAL_MAX_LENGTH {
+        return Err(DomainError::validation(format!(
+            "email local part must be at most {EMAIL_LOCAL_MAX_LENGTH} bytes"
+        )));
+    }
+
+    if let Some(quoted) = local.strip_prefix('"') {
+        let inner = quoted.strip_suffix('"').unwrap_or(quoted);
+        let mut content = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(escaped @ ' '..='~') => content.push(escaped),
+                    _ => {
+                        return Err(DomainError::validation(
+                            "email quoted local part has an invalid escape",
+                        ))
+                    }
+                },
+                ' '..='~' => content.push(c),
+                _ if is_utf8_email_char(c) => content.push(c),
+                _ => return Err(invalid_local_char(c)),
+            }
+        }
+        let content = content.to_lowercase();
+        if is_dot_atom(&content) {
+            return Ok(content);
+        }
+        let escaped = content.replace('\\', "\\\\").replace('"', "\\\"");
+        return Ok(format!("\"{escaped}\""));
+    }
+
+    if local.starts_with('.') || local.ends_with('.') {
+        return Err(DomainError::validation(
+            "email local part cannot start or end with a dot",
+        ));
+    }
+    if local.contains("..") {
+        return Err(DomainError::validation(
+            "email local part cannot contain consecutive dots",
+        ));
+    }
+    if let Some(c) = local.chars().find(|&c| c != '.' && !is_atext(c)) {
+        return Err(invalid_local_char(c));
+    }
+    Ok(local.to_lowercase())
+}
+
+fn invalid_domain(message: impl fmt::Display) -> DomainError {
+    DomainError::validation(format!("email domain {message}"))
+}
+
+fn canonical_domain(domain: &str) -> Result<String, DomainError> {
+    if domain.is_empty() {
+        return Err(DomainError::validation("email domain is required"));
+    }
+    if let Some(literal) = domain.strip_prefix('[') {
+        let literal = literal
+            .strip_suffix(']')
+            .ok_or_else(|| invalid_domain("address literal is not closed"))?;
+        return canonical_address_literal(literal);
+    }
+
+    if let Some(c) = domain
+        .chars()
+        .find(|&c| c.is_ascii() && !c.is_ascii_alphanumeric() && c != '-' && c != '.')
+    {
+        return Err(invalid_domain(format_args!(
+            "contains an invalid character: '{}'",
+            c.escape_default()
+        )));
+    }
+    let ascii = idna::domain_to_ascii_cow(domain.as_bytes(), idna::AsciiDenyList::STD3)
+        .map_err(|_| invalid_domain("is not a valid internationalized domain name"))?;
+
+    if ascii.len() > EMAIL_DOMAIN_MAX_LENGTH {
+        return Err(invalid_domain(format_args!(
+            "must be at most {EMAIL_DOMAIN_MAX_LENGTH} characters"
+        )));
+    }
+    let labels: Vec<&str> = ascii.split('.').collect();
+    if labels.iter().any(|label| label.is_empty()) {
+        return Err(invalid_domain(
+            "cannot contain empty labels (check for leading, trailing or consecutive dots)",
+        ));
+    }
+    if labels.len() < 2 {
+        return Err(invalid_domain("must include a top-level domain"));
+    }
+    for label in &labels {
+        if label.len() > EMAIL_LABEL_MAX_LENGTH {
+            return Err(invalid_domain(format_args!(
+                "label must be at most {EMAIL_LABEL_MAX_LENGTH} characters: {label}"
+            )));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(invalid_domain(format_args!(
+                "label cannot start or end with a hyphen: {label}"
+            )));
+        }
+    }
+    if labels
+        .last()
+        .is_some_and(|tld| tld.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Err(DomainError::validation(
+            "email top-level domain cannot be numeric",
+        ));
+    }
+    Ok(ascii.into_owned())
+}
+
+// `[192.0.2.1]` ou `[IPv6:2001:db8::1]`; a forma canonica usa a notacao padrao do endereco.
+fn canonical_address_literal(literal: &str) -> Result<String, DomainError> {
+    let invalid = || invalid_domain("address literal is not a valid IP address");
+    match literal.get(..5) {
+        Some(tag) if tag.eq_ignore_ascii_case("IPv6:") => {
+            let address: Ipv6Addr = literal[5..].parse().map_err(|_| invalid())?;
+            Ok(format!("[IPv6:{address}]"))
+        }
+        _ => {
+            let address: Ipv4Addr = literal.parse().map_err(|_| invalid())?;
+            Ok(format!("[{address}]"))
+        }
+    }
+}
+
+impl PartialEq for EmailAddress {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical == other.canonical
+    }
+}
+
+impl Eq for EmailAddress {}
+
+impl Hash for EmailAddress {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical.hash(state);
     }
 }
 
 impl fmt::Display for EmailAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.0)
+        f.write_str(&self.value)
     }
 }
 
@@ -89,6 +347,119 @@ impl AsRef<str> for UserName {
     }
 }
 
+// Tag BCP 47 simplificada, ex.: "pt-BR". Idioma em minusculas e regiao de duas letras em
+// maiusculas, para que "PT_br" e "pt-BR" sejam a mesma preferencia.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Locale(String);
+
+impl Locale {
+    pub fn parse<S: AsRef<str>>(value: S) -> Result<Self, DomainError> {
+        let trimmed = value.as_ref().trim();
+        if trimmed.len() > 35 {
+            return Err(DomainError::validation(
+                "locale must be at most 35 characters",
+            ));
+        }
+
+        let normalized = trimmed
+            .split(['-', '_'])
+            .enumerate()
+            .map(|(index, part)| match (index, part.len()) {
+                (0, _) => part.to_ascii_lowercase(),
+                (_, 2) => part.to_ascii_uppercase(),
+                _ => part.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("-");
+        if !LOCALE_REGEX.is_match(&normalized) {
+            return Err(DomainError::validation(format!(
+                "invalid locale: {trimmed} (use a tag such as en or pt-BR)"
+            )));
+        }
+        Ok(Self(normalized))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// Nome de fuso da base IANA, ex.: "America/Sao_Paulo". Validamos apenas o formato: a lista de
+// fusos muda com frequencia e quem interpreta a preferencia e o cliente.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimeZoneName(String);
+
+impl TimeZoneName {
+    pub fn parse<S: AsRef<str>>(value: S) -> Result<Self, DomainError> {
+        let trimmed = value.as_ref().trim();
+        if trimmed.len() > 64 || !TIME_ZONE_REGEX.is_match(trimmed) {
+            return Err(DomainError::validation(format!(
+                "invalid timezone: {trimmed} (use an IANA name such as Europe/Lisbon)"
+            )));
+        }
+        Ok(Self(trimmed.to_owned()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TimeZoneName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// Identificador estavel de um papel: usado nas rotas, nos claims do JWT e nas politicas de MFA.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RoleName(String);
+
+impl RoleName {
+    pub fn parse<S: AsRef<str>>(value: S) -> Result<Self, DomainError> {
+        let normalized = value.as_ref().trim().to_lowercase();
+        if normalized.is_empty() {
+            return Err(DomainError::validation("role name is required"));
+        }
+        if !ROLE_NAME_REGEX.is_match(&normalized) {
+            return Err(DomainError::validation(format!(
+                "invalid role: {normalized} (use up to 64 lowercase letters, digits, '-' or '_', starting with a letter)"
+            )));
+        }
+        Ok(Self(normalized))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RoleName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for RoleName {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RoleName::parse(s)
+    }
+}
+
+impl AsRef<str> for RoleName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PasswordHash(String);
 
@@ -116,26 +487,13 @@ impl fmt::Display for PasswordHash {
 pub struct PlainPassword(String);
 
 impl PlainPassword {
+    // So garante que ha uma senha; as regras de tamanho e forca ficam em `PasswordPolicy`,
+    // que e configuravel e precisa do contexto do usuario.
     pub fn parse<S: AsRef<str>>(value: S) -> Result<Self, DomainError> {
         let raw = value.as_ref();
-        if raw.len() < 12 {
-            return Err(DomainError::validation(
-                "password must be at least 12 characters",
-            ));
+        if raw.is_empty() {
+            return Err(DomainError::validation("password is required"));
         }
-        let has_upper = raw.chars().any(|c| c.is_ascii_uppercase());
-        let has_lower = raw.chars().any(|c| c.is_ascii_lowercase());
-        let has_digit = raw.chars().any(|c| c.is_ascii_digit());
-        let has_symbol = raw
-            .chars()
-            .any(|c| !c.is_ascii_alphanumeric() && !c.is_whitespace());
-
-        if !(has_upper && has_lower && has_digit && has_symbol) {
-            return Err(DomainError::validation(
-                "password must include uppercase, lowercase, digit and symbol",
-            ));
-        }
-
         Ok(Self(raw.to_owned()))
     }
 
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::message::Mailbox;
+use uuid::Uuid;
+
+use super::build_message;
+use crate::domain::mailer::{EmailMessage, Mailer};
+use crate::shared::error::{AppError, AppResult};
+
+// Grava cada mensagem como um arquivo `.eml` no diretorio configurado, util em
+// desenvolvimento para abrir o link de redefinicao sem um servidor SMTP.
+#[derive(Clone)]
+pub struct FileMailer {
+    directory: PathBuf,
+    from: Mailbox,
+}
+
+impl FileMailer {
+    pub fn new(directory: impl Into<PathBuf>, from: Mailbox) -> Self {
+        Self {
+            directory: directory.into(),
+            from,
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        let email = build_message(&self.from, message)?;
+        let path = self.directory.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S"),
+            Uuid::new_v4()
+        ));
+
+        tokio::fs::create_dir_all(&self.directory)
+            .await
+            .map_err(|err| {
+                AppError::Unexpected(anyhow!("failed to create mail directory: {err}"))
+            })?;
+        tokio::fs::write(&path, email.formatted())
+            .await
+            .map_err(|err| {
+                AppError::Unexpected(anyhow!("failed to write {}: {err}", path.display()))
+            })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+mod file_mailer;
+mod smtp_mailer;
+
+pub use file_mailer::FileMailer;
+pub use smtp_mailer::SmtpMailer;
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::Message;
+
+use crate::config::MailConfig;
+use crate::domain::mailer::{EmailMessage, Mailer};
+use crate::shared::error::{AppError, AppResult};
+
+pub fn build_mailer(config: &MailConfig) -> AppResult<Arc<dyn Mailer>> {
+    let from = parse_mailbox(&config.from)?;
+
+    match config.transport.trim().to_ascii_lowercase().as_str() {
+        "smtp" => Ok(Arc::new(SmtpMailer::new(&config.smtp, from)?)),
+        "file" => Ok(Arc::new(FileMailer::new(&config.file_directory, from))),
+        other => Err(AppError::Unexpected(anyhow!(
+            "unknown mail transport {other}, expected smtp or file"
+        ))),
+    }
+}
+
+fn parse_mailbox(value: &str) -> AppResult<Mailbox> {
+    value
+        .parse()
+        .map_err(|err| AppError::Unexpected(anyhow!("invalid mail address {value}: {err}")))
+}
+
+// Os dois transportes montam a mensagem RFC 5322 da mesma forma; so muda o destino.
+fn build_message(from: &Mailbox, message: &EmailMessage) -> AppResult<Message> {
+    Message::builder()
+        .from(from.clone())
+        .to(parse_mailbox(&message.to)?)
+        .subject(&message.subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(message.body.clone())
+        .map_err(|err| AppError::Unexpected(anyhow!("failed to build email: {err}")))
+}
@@ -0,0 +1,53 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+use super::build_message;
+use crate::config::SmtpConfig;
+use crate::domain::mailer::{EmailMessage, Mailer};
+use crate::shared::error::{AppError, AppResult};
+
+#[derive(Clone)]
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &SmtpConfig, from: Mailbox) -> AppResult<Self> {
+        // Sem STARTTLS apenas para relays locais (ex.: Mailpit em desenvolvimento).
+        let mut builder = if config.starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host).map_err(|err| {
+                AppError::Unexpected(anyhow!("invalid smtp relay {}: {err}", config.host))
+            })?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+        };
+        builder = builder.port(config.port);
+
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        let email = build_message(&self.from, message)?;
+
+        self.transport
+            .send(email)
+            .await
+            .map_err(|err| AppError::Unexpected(anyhow!("smtp delivery failed: {err}")))?;
+
+        Ok(())
+    }
+}
@@ -1 +1,16 @@
-﻿pub mod postgres_user_repository;
+pub mod postgres_email_outbox_repository;
+pub mod postgres_invitation_repository;
+pub mod postgres_login_throttle_repository;
+pub mod postgres_mfa_repository;
+pub mod postgres_oauth_authorization_code_repository;
+pub mod postgres_oauth_client_repository;
+pub mod postgres_oauth_consent_repository;
+pub mod postgres_password_history_repository;
+pub mod postgres_password_reset_repository;
+pub mod postgres_personal_access_token_repository;
+pub mod postgres_refresh_token_repository;
+pub mod postgres_registration_repository;
+pub mod postgres_role_repository;
+pub mod postgres_session_repository;
+pub mod postgres_token_revocation_repository;
+pub mod postgres_user_repository;
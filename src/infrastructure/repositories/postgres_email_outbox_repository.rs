@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::email_outbox::OutboundEmail;
+use crate::domain::mailer::EmailMessage;
+use crate::domain::repositories::email_outbox_repository::EmailOutboxRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone)]
+pub struct PostgresEmailOutboxRepository {
+    pool: PgPool,
+}
+
+impl PostgresEmailOutboxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct OutboundEmailRecord {
+    id: Uuid,
+    recipient: String,
+    subject: String,
+    body: String,
+    attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    last_error: Option<String>,
+    created_at: DateTime<Utc>,
+    sent_at: Option<DateTime<Utc>>,
+}
+
+impl From<OutboundEmailRecord> for OutboundEmail {
+    fn from(record: OutboundEmailRecord) -> Self {
+        Self {
+            id: record.id,
+            recipient: record.recipient,
+            subject: record.subject,
+            body: record.body,
+            attempts: record.attempts.max(0) as u32,
+            next_attempt_at: record.next_attempt_at,
+            last_error: record.last_error,
+            created_at: record.created_at,
+            sent_at: record.sent_at,
+        }
+    }
+}
+
+#[async_trait]
+impl EmailOutboxRepository for PostgresEmailOutboxRepository {
+    async fn enqueue(&self, message: &EmailMessage) -> RepositoryResult<OutboundEmail> {
+        let record = sqlx::query_as::<_, OutboundEmailRecord>(
+            "INSERT INTO email_outbox (id, recipient, subject, body)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id, recipient, subject, body, attempts, next_attempt_at, last_error,
+                       created_at, sent_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&message.to)
+        .bind(&message.subject)
+        .bind(&message.body)
+        .fetch_one(self.pool())
+        .await?;
+
+        Ok(record.into())
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        max_attempts: u32,
+        limit: u32,
+    ) -> RepositoryResult<Vec<OutboundEmail>> {
+        // SKIP LOCKED permite varias instancias despachando sem disputar as mesmas linhas.
+        let records = sqlx::query_as::<_, OutboundEmailRecord>(
+            "UPDATE email_outbox
+             SET attempts = attempts + 1, next_attempt_at = $2
+             WHERE id IN (
+                 SELECT id FROM email_outbox
+                 WHERE sent_at IS NULL AND next_attempt_at <= $1 AND attempts < $3
+                 ORDER BY next_attempt_at
+                 LIMIT $4
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING id, recipient, subject, body, attempts, next_attempt_at, last_error,
+                       created_at, sent_at",
+        )
+        .bind(now)
+        .bind(lease_until)
+        .bind(max_attempts as i32)
+        .bind(i64::from(limit))
+        .fetch_all(self.pool())
+        .await?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn mark_sent(&self, id: Uuid, sent_at: DateTime<Utc>) -> RepositoryResult<()> {
+        sqlx::query("UPDATE email_outbox SET sent_at = $2, last_error = NULL WHERE id = $1")
+            .bind(id)
+            .bind(sent_at)
+            .execute(self.pool())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        sqlx::query("UPDATE email_outbox SET last_error = $2, next_attempt_at = $3 WHERE id = $1")
+            .bind(id)
+            .bind(error)
+            .bind(retry_at)
+            .execute(self.pool())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::invitation::Invitation;
+use crate::domain::repositories::invitation_repository::InvitationRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+const SELECT_INVITATION: &str = "SELECT id, user_id, email, invited_by, token_hash, expires_at,
+        created_at, accepted_at, revoked_at
+ FROM user_invitations";
+
+#[derive(Clone)]
+pub struct PostgresInvitationRepository {
+    pool: PgPool,
+}
+
+impl PostgresInvitationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct InvitationRecord {
+    id: Uuid,
+    user_id: Uuid,
+    email: String,
+    invited_by: Option<Uuid>,
+    token_hash: String,
+    expires_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    accepted_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<InvitationRecord> for Invitation {
+    fn from(record: InvitationRecord) -> Self {
+        Self {
+            id: record.id,
+            user_id: record.user_id,
+            email: record.email,
+            invited_by: record.invited_by,
+            token_hash: record.token_hash,
+            expires_at: record.expires_at,
+            created_at: record.created_at,
+            accepted_at: record.accepted_at,
+            revoked_at: record.revoked_at,
+        }
+    }
+}
+
+#[async_trait]
+impl InvitationRepository for PostgresInvitationRepository {
+    async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<Invitation>> {
+        let record =
+            sqlx::query_as::<_, InvitationRecord>(&format!("{SELECT_INVITATION} WHERE id = $1"))
+                .bind(id)
+                .fetch_optional(self.pool())
+                .await?;
+
+        Ok(record.map(Into::into))
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<Invitation>> {
+        let record = sqlx::query_as::<_, InvitationRecord>(&format!(
+            "{SELECT_INVITATION} WHERE token_hash = $1"
+        ))
+        .bind(token_hash)
+        .fetch_optional(self.pool())
+        .await?;
+
+        Ok(record.map(Into::into))
+    }
+
+    async fn find_pending(&self) -> RepositoryResult<Vec<Invitation>> {
+        let records = sqlx::query_as::<_, InvitationRecord>(&format!(
+            "{SELECT_INVITATION} WHERE accepted_at IS NULL AND revoked_at IS NULL
+             ORDER BY created_at, id"
+        ))
+        .fetch_all(self.pool())
+        .await?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn replace_token(
+        &self,
+        id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<Option<Invitation>> {
+        let record = sqlx::query_as::<_, InvitationRecord>(
+            "UPDATE user_invitations SET token_hash = $2, expires_at = $3
+             WHERE id = $1 AND accepted_at IS NULL AND revoked_at IS NULL
+             RETURNING id, user_id, email, invited_by, token_hash, expires_at,
+                       created_at, accepted_at, revoked_at",
+        )
+        .bind(id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .fetch_optional(self.pool())
+        .await?;
+
+        Ok(record.map(Into::into))
+    }
+
+    async fn accept(&self, id: Uuid, accepted_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE user_invitations SET accepted_at = $2
+             WHERE id = $1 AND accepted_at IS NULL AND revoked_at IS NULL AND expires_at > $2",
+        )
+        .bind(id)
+        .bind(accepted_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke(&self, id: Uuid, revoked_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE user_invitations SET revoked_at = $2
+             WHERE id = $1 AND accepted_at IS NULL AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .bind(revoked_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
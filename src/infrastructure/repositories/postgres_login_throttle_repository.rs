@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+use crate::domain::entities::login_throttle::LoginThrottle;
+use crate::domain::repositories::login_throttle_repository::LoginThrottleRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone)]
+pub struct PostgresLoginThrottleRepository {
+    pool: PgPool,
+}
+
+impl PostgresLoginThrottleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct LoginThrottleRecord {
+    key_hash: String,
+    failure_count: i32,
+    window_started_at: DateTime<Utc>,
+    last_failure_at: DateTime<Utc>,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+impl From<LoginThrottleRecord> for LoginThrottle {
+    fn from(record: LoginThrottleRecord) -> Self {
+        Self {
+            key_hash: record.key_hash,
+            failure_count: record.failure_count.max(0) as u32,
+            window_started_at: record.window_started_at,
+            last_failure_at: record.last_failure_at,
+            locked_until: record.locked_until,
+        }
+    }
+}
+
+#[async_trait]
+impl LoginThrottleRepository for PostgresLoginThrottleRepository {
+    async fn find(&self, key_hash: &str) -> RepositoryResult<Option<LoginThrottle>> {
+        let record = sqlx::query_as::<_, LoginThrottleRecord>(
+            "SELECT key_hash, failure_count, window_started_at, last_failure_at, locked_until
+             FROM login_throttles WHERE key_hash = $1",
+        )
+        .bind(key_hash)
+        .fetch_optional(self.pool())
+        .await?;
+
+        Ok(record.map(Into::into))
+    }
+
+    async fn record_failure(
+        &self,
+        key_hash: &str,
+        now: DateTime<Utc>,
+        window_start: DateTime<Utc>,
+    ) -> RepositoryResult<LoginThrottle> {
+        let record = sqlx::query_as::<_, LoginThrottleRecord>(
+            "INSERT INTO login_throttles (key_hash, failure_count, window_started_at, last_failure_at)
+             VALUES ($1, 1, $2, $2)
+             ON CONFLICT (key_hash) DO UPDATE
+             SET failure_count = CASE
+                     WHEN GREATEST(login_throttles.last_failure_at, login_throttles.locked_until) < $3 THEN 1
+                     ELSE login_throttles.failure_count + 1
+                 END,
+                 window_started_at = CASE
+                     WHEN GREATEST(login_throttles.last_failure_at, login_throttles.locked_until) < $3 THEN $2
+                     ELSE login_throttles.window_started_at
+                 END,
+                 locked_until = CASE
+                     WHEN GREATEST(login_throttles.last_failure_at, login_throttles.locked_until) < $3 THEN NULL
+                     ELSE login_throttles.locked_until
+                 END,
+                 last_failure_at = $2
+             RETURNING key_hash, failure_count, window_started_at, last_failure_at, locked_until",
+        )
+        .bind(key_hash)
+        .bind(now)
+        .bind(window_start)
+        .fetch_one(self.pool())
+        .await?;
+
+        Ok(record.into())
+    }
+
+    async fn lock(&self, key_hash: &str, until: DateTime<Utc>) -> RepositoryResult<()> {
+        sqlx::query("UPDATE login_throttles SET locked_until = $2 WHERE key_hash = $1")
+            .bind(key_hash)
+            .bind(until)
+            .execute(self.pool())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn clear(&self, key_hash: &str) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM login_throttles WHERE key_hash = $1")
+            .bind(key_hash)
+            .execute(self.pool())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn purge_stale(&self, before: DateTime<Utc>) -> RepositoryResult<u64> {
+        let result = sqlx::query(
+            "DELETE FROM login_throttles
+             WHERE last_failure_at < $1 AND (locked_until IS NULL OR locked_until < $1)",
+        )
+        .bind(before)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
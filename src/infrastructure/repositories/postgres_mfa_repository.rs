@@ -0,0 +1,227 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::mfa::{MfaPolicy, RecoveryCode, TotpCredential};
+use crate::domain::repositories::mfa_repository::MfaRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::domain::value_objects::RoleName;
+use crate::shared::error::AppError;
+
+#[derive(Clone)]
+pub struct PostgresMfaRepository {
+    pool: PgPool,
+}
+
+impl PostgresMfaRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct TotpCredentialRecord {
+    user_id: Uuid,
+    secret: String,
+    confirmed_at: Option<DateTime<Utc>>,
+    last_used_step: Option<i64>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<TotpCredentialRecord> for TotpCredential {
+    fn from(record: TotpCredentialRecord) -> Self {
+        Self {
+            user_id: record.user_id,
+            secret: record.secret,
+            confirmed_at: record.confirmed_at,
+            last_used_step: record.last_used_step,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct RecoveryCodeRecord {
+    id: Uuid,
+    user_id: Uuid,
+    code_hash: String,
+    used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct MfaPolicyRecord {
+    role: String,
+    required: bool,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<MfaPolicyRecord> for MfaPolicy {
+    type Error = AppError;
+
+    fn try_from(record: MfaPolicyRecord) -> Result<Self, Self::Error> {
+        let role = RoleName::parse(&record.role).map_err(|err| {
+            AppError::Unexpected(anyhow!("failed to parse persisted role: {}", err))
+        })?;
+
+        Ok(Self {
+            role,
+            required: record.required,
+            updated_at: record.updated_at,
+        })
+    }
+}
+
+#[async_trait]
+impl MfaRepository for PostgresMfaRepository {
+    async fn find_totp(&self, user_id: Uuid) -> RepositoryResult<Option<TotpCredential>> {
+        let record = sqlx::query_as::<_, TotpCredentialRecord>(
+            "SELECT user_id, secret, confirmed_at, last_used_step, created_at
+             FROM user_totp_credentials WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(self.pool())
+        .await?;
+
+        Ok(record.map(Into::into))
+    }
+
+    async fn save_pending_totp(&self, user_id: Uuid, secret: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "INSERT INTO user_totp_credentials (user_id, secret)
+             VALUES ($1, $2)
+             ON CONFLICT (user_id) DO UPDATE
+             SET secret = EXCLUDED.secret, created_at = NOW(), last_used_step = NULL
+             WHERE user_totp_credentials.confirmed_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(secret)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn confirm_totp(
+        &self,
+        user_id: Uuid,
+        step: i64,
+        confirmed_at: DateTime<Utc>,
+        recovery_code_hashes: Vec<String>,
+    ) -> RepositoryResult<()> {
+        let mut transaction = self.pool().begin().await?;
+
+        sqlx::query(
+            "UPDATE user_totp_credentials
+             SET confirmed_at = $2, last_used_step = $3
+             WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .bind(confirmed_at)
+        .bind(step)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        for code_hash in recovery_code_hashes {
+            sqlx::query(
+                "INSERT INTO user_recovery_codes (id, user_id, code_hash) VALUES ($1, $2, $3)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(user_id)
+            .bind(code_hash)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn advance_totp_step(&self, user_id: Uuid, step: i64) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE user_totp_credentials SET last_used_step = $2
+             WHERE user_id = $1 AND (last_used_step IS NULL OR last_used_step < $2)",
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_unused_recovery_codes(
+        &self,
+        user_id: Uuid,
+    ) -> RepositoryResult<Vec<RecoveryCode>> {
+        let records = sqlx::query_as::<_, RecoveryCodeRecord>(
+            "SELECT id, user_id, code_hash, used_at
+             FROM user_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_all(self.pool())
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| RecoveryCode {
+                id: record.id,
+                user_id: record.user_id,
+                code_hash: record.code_hash,
+                used_at: record.used_at,
+            })
+            .collect())
+    }
+
+    async fn mark_recovery_code_used(
+        &self,
+        id: Uuid,
+        used_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE user_recovery_codes SET used_at = $2 WHERE id = $1 AND used_at IS NULL",
+        )
+        .bind(id)
+        .bind(used_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_policies(&self) -> RepositoryResult<Vec<MfaPolicy>> {
+        let records = sqlx::query_as::<_, MfaPolicyRecord>(
+            "SELECT role, required, updated_at FROM mfa_role_policies ORDER BY role",
+        )
+        .fetch_all(self.pool())
+        .await?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn set_policy(&self, role: &RoleName, required: bool) -> RepositoryResult<MfaPolicy> {
+        let record = sqlx::query_as::<_, MfaPolicyRecord>(
+            "INSERT INTO mfa_role_policies (role, required, updated_at)
+             VALUES ($1, $2, NOW())
+             ON CONFLICT (role) DO UPDATE
+             SET required = EXCLUDED.required, updated_at = NOW()
+             RETURNING role, required, updated_at",
+        )
+        .bind(role.as_str())
+        .bind(required)
+        .fetch_one(self.pool())
+        .await?;
+
+        record.try_into()
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::{AuthorizationCode, NewAuthorizationCode};
+use crate::domain::entities::permission::Permission;
+use crate::domain::repositories::oauth_authorization_code_repository::AuthorizationCodeRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::shared::error::AppError;
+
+#[derive(Clone)]
+pub struct PostgresAuthorizationCodeRepository {
+    pool: PgPool,
+}
+
+impl PostgresAuthorizationCodeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct AuthorizationCodeRecord {
+    id: Uuid,
+    code_hash: String,
+    client_id: Uuid,
+    user_id: Uuid,
+    session_id: Uuid,
+    redirect_uri: String,
+    redirect_uri_supplied: bool,
+    scopes: Vec<String>,
+    code_challenge: String,
+    expires_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    consumed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<AuthorizationCodeRecord> for AuthorizationCode {
+    type Error = AppError;
+
+    fn try_from(record: AuthorizationCodeRecord) -> Result<Self, Self::Error> {
+        let scopes = record
+            .scopes
+            .iter()
+            .map(|scope| scope.parse::<Permission>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| {
+                AppError::Unexpected(anyhow!("failed to parse persisted scope: {}", err))
+            })?;
+
+        Ok(Self {
+            id: record.id,
+            code_hash: record.code_hash,
+            client_id: record.client_id,
+            user_id: record.user_id,
+            session_id: record.session_id,
+            redirect_uri: record.redirect_uri,
+            redirect_uri_supplied: record.redirect_uri_supplied,
+            scopes,
+            code_challenge: record.code_challenge,
+            expires_at: record.expires_at,
+            created_at: record.created_at,
+            consumed_at: record.consumed_at,
+        })
+    }
+}
+
+#[async_trait]
+impl AuthorizationCodeRepository for PostgresAuthorizationCodeRepository {
+    async fn create(&self, code: NewAuthorizationCode) -> RepositoryResult<AuthorizationCode> {
+        let scopes: Vec<&str> = code.scopes.iter().map(Permission::as_str).collect();
+
+        let record = sqlx::query_as::<_, AuthorizationCodeRecord>(
+            "INSERT INTO oauth_authorization_codes
+                 (id, code_hash, client_id, user_id, session_id, redirect_uri,
+                  redirect_uri_supplied, scopes, code_challenge, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             RETURNING id, code_hash, client_id, user_id, session_id, redirect_uri,
+                       redirect_uri_supplied, scopes, code_challenge, expires_at, created_at,
+                       consumed_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&code.code_hash)
+        .bind(code.client_id)
+        .bind(code.user_id)
+        .bind(code.session_id)
+        .bind(&code.redirect_uri)
+        .bind(code.redirect_uri_supplied)
+        .bind(scopes)
+        .bind(&code.code_challenge)
+        .bind(code.expires_at)
+        .fetch_one(self.pool())
+        .await?;
+
+        record.try_into()
+    }
+
+    async fn find_by_hash(&self, code_hash: &str) -> RepositoryResult<Option<AuthorizationCode>> {
+        let record = sqlx::query_as::<_, AuthorizationCodeRecord>(
+            "SELECT id, code_hash, client_id, user_id, session_id, redirect_uri,
+                    redirect_uri_supplied, scopes, code_challenge, expires_at, created_at,
+                    consumed_at
+             FROM oauth_authorization_codes WHERE code_hash = $1",
+        )
+        .bind(code_hash)
+        .fetch_optional(self.pool())
+        .await?;
+
+        record.map(TryInto::try_into).transpose()
+    }
+
+    async fn mark_consumed(&self, id: Uuid, consumed_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE oauth_authorization_codes SET consumed_at = $2
+             WHERE id = $1 AND consumed_at IS NULL",
+        )
+        .bind(id)
+        .bind(consumed_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn purge_expired(&self, before: DateTime<Utc>) -> RepositoryResult<u64> {
+        let result = sqlx::query("DELETE FROM oauth_authorization_codes WHERE expires_at < $1")
+            .bind(before)
+            .execute(self.pool())
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
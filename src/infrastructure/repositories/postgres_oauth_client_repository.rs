@@ -0,0 +1,153 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::{
+    NewOAuthClient, OAuthClient, OAuthClientType, OAuthGrantType,
+};
+use crate::domain::entities::permission::Permission;
+use crate::domain::repositories::oauth_client_repository::OAuthClientRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::shared::error::AppError;
+
+#[derive(Clone)]
+pub struct PostgresOAuthClientRepository {
+    pool: PgPool,
+}
+
+impl PostgresOAuthClientRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct OAuthClientRecord {
+    id: Uuid,
+    name: String,
+    client_type: String,
+    secret_hash: Option<String>,
+    redirect_uris: Vec<String>,
+    grant_types: Vec<String>,
+    scopes: Vec<String>,
+    owner_id: Uuid,
+    created_at: DateTime<Utc>,
+    disabled_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<OAuthClientRecord> for OAuthClient {
+    type Error = AppError;
+
+    fn try_from(record: OAuthClientRecord) -> Result<Self, Self::Error> {
+        let parse_error = |err: String| {
+            AppError::Unexpected(anyhow!("failed to parse persisted client: {}", err))
+        };
+
+        let client_type = record
+            .client_type
+            .parse::<OAuthClientType>()
+            .map_err(parse_error)?;
+        let grant_types = record
+            .grant_types
+            .iter()
+            .map(|grant| grant.parse::<OAuthGrantType>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(parse_error)?;
+        let scopes = record
+            .scopes
+            .iter()
+            .map(|scope| scope.parse::<Permission>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| parse_error(err.to_string()))?;
+
+        Ok(Self {
+            id: record.id,
+            name: record.name,
+            client_type,
+            secret_hash: record.secret_hash,
+            redirect_uris: record.redirect_uris,
+            grant_types,
+            scopes,
+            owner_id: record.owner_id,
+            created_at: record.created_at,
+            disabled_at: record.disabled_at,
+        })
+    }
+}
+
+#[async_trait]
+impl OAuthClientRepository for PostgresOAuthClientRepository {
+    async fn create(&self, client: NewOAuthClient) -> RepositoryResult<OAuthClient> {
+        let grant_types: Vec<&str> = client
+            .grant_types
+            .iter()
+            .map(OAuthGrantType::as_str)
+            .collect();
+        let scopes: Vec<&str> = client.scopes.iter().map(Permission::as_str).collect();
+
+        let record = sqlx::query_as::<_, OAuthClientRecord>(
+            "INSERT INTO oauth_clients
+                 (id, name, client_type, secret_hash, redirect_uris, grant_types, scopes, owner_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING id, name, client_type, secret_hash, redirect_uris, grant_types, scopes,
+                       owner_id, created_at, disabled_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&client.name)
+        .bind(client.client_type.as_str())
+        .bind(&client.secret_hash)
+        .bind(&client.redirect_uris)
+        .bind(grant_types)
+        .bind(scopes)
+        .bind(client.owner_id)
+        .fetch_one(self.pool())
+        .await?;
+
+        record.try_into()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<OAuthClient>> {
+        let record = sqlx::query_as::<_, OAuthClientRecord>(
+            "SELECT id, name, client_type, secret_hash, redirect_uris, grant_types, scopes,
+                    owner_id, created_at, disabled_at
+             FROM oauth_clients WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(self.pool())
+        .await?;
+
+        record.map(TryInto::try_into).transpose()
+    }
+
+    async fn list_active(&self) -> RepositoryResult<Vec<OAuthClient>> {
+        let records = sqlx::query_as::<_, OAuthClientRecord>(
+            "SELECT id, name, client_type, secret_hash, redirect_uris, grant_types, scopes,
+                    owner_id, created_at, disabled_at
+             FROM oauth_clients
+             WHERE disabled_at IS NULL
+             ORDER BY created_at DESC",
+        )
+        .fetch_all(self.pool())
+        .await?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn disable(&self, id: Uuid, disabled_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE oauth_clients SET disabled_at = $2 WHERE id = $1 AND disabled_at IS NULL",
+        )
+        .bind(id)
+        .bind(disabled_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::OAuthConsent;
+use crate::domain::entities::permission::Permission;
+use crate::domain::repositories::oauth_consent_repository::OAuthConsentRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::shared::error::AppError;
+
+#[derive(Clone)]
+pub struct PostgresOAuthConsentRepository {
+    pool: PgPool,
+}
+
+impl PostgresOAuthConsentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct OAuthConsentRecord {
+    user_id: Uuid,
+    client_id: Uuid,
+    scopes: Vec<String>,
+    granted_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<OAuthConsentRecord> for OAuthConsent {
+    type Error = AppError;
+
+    fn try_from(record: OAuthConsentRecord) -> Result<Self, Self::Error> {
+        let scopes = record
+            .scopes
+            .iter()
+            .map(|scope| scope.parse::<Permission>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| {
+                AppError::Unexpected(anyhow!("failed to parse persisted scope: {}", err))
+            })?;
+
+        Ok(Self {
+            user_id: record.user_id,
+            client_id: record.client_id,
+            scopes,
+            granted_at: record.granted_at,
+            updated_at: record.updated_at,
+        })
+    }
+}
+
+#[async_trait]
+impl OAuthConsentRepository for PostgresOAuthConsentRepository {
+    async fn find(&self, user_id: Uuid, client_id: Uuid) -> RepositoryResult<Option<OAuthConsent>> {
+        let record = sqlx::query_as::<_, OAuthConsentRecord>(
+            "SELECT user_id, client_id, scopes, granted_at, updated_at
+             FROM oauth_consents WHERE user_id = $1 AND client_id = $2",
+        )
+        .bind(user_id)
+        .bind(client_id)
+        .fetch_optional(self.pool())
+        .await?;
+
+        record.map(TryInto::try_into).transpose()
+    }
+
+    async fn grant(
+        &self,
+        user_id: Uuid,
+        client_id: Uuid,
+        scopes: &[Permission],
+    ) -> RepositoryResult<OAuthConsent> {
+        let scopes: Vec<&str> = scopes.iter().map(Permission::as_str).collect();
+
+        let record = sqlx::query_as::<_, OAuthConsentRecord>(
+            "INSERT INTO oauth_consents (user_id, client_id, scopes)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (user_id, client_id) DO UPDATE
+             SET scopes = ARRAY(
+                     SELECT DISTINCT unnest(oauth_consents.scopes || EXCLUDED.scopes) ORDER BY 1
+                 ),
+                 updated_at = NOW()
+             RETURNING user_id, client_id, scopes, granted_at, updated_at",
+        )
+        .bind(user_id)
+        .bind(client_id)
+        .bind(scopes)
+        .fetch_one(self.pool())
+        .await?;
+
+        record.try_into()
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<OAuthConsent>> {
+        let records = sqlx::query_as::<_, OAuthConsentRecord>(
+            "SELECT user_id, client_id, scopes, granted_at, updated_at
+             FROM oauth_consents
+             WHERE user_id = $1
+             ORDER BY updated_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(self.pool())
+        .await?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn revoke(&self, user_id: Uuid, client_id: Uuid) -> RepositoryResult<bool> {
+        let result =
+            sqlx::query("DELETE FROM oauth_consents WHERE user_id = $1 AND client_id = $2")
+                .bind(user_id)
+                .bind(client_id)
+                .execute(self.pool())
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
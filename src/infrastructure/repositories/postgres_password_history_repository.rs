@@ -0,0 +1,82 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::repositories::password_history_repository::PasswordHistoryRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::domain::value_objects::PasswordHash;
+use crate::shared::error::AppError;
+
+#[derive(Clone)]
+pub struct PostgresPasswordHistoryRepository {
+    pool: PgPool,
+}
+
+impl PostgresPasswordHistoryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[async_trait]
+impl PasswordHistoryRepository for PostgresPasswordHistoryRepository {
+    async fn record(
+        &self,
+        user_id: Uuid,
+        password_hash: &PasswordHash,
+        keep: usize,
+    ) -> RepositoryResult<()> {
+        let mut transaction = self.pool().begin().await?;
+
+        sqlx::query(
+            "INSERT INTO password_history (id, user_id, password_hash)
+             VALUES ($1, $2, $3)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(password_hash.as_str())
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM password_history
+             WHERE user_id = $1 AND id NOT IN (
+                 SELECT id FROM password_history
+                 WHERE user_id = $1
+                 ORDER BY created_at DESC
+                 LIMIT $2
+             )",
+        )
+        .bind(user_id)
+        .bind(i64::try_from(keep).unwrap_or(i64::MAX))
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn recent(&self, user_id: Uuid, limit: usize) -> RepositoryResult<Vec<PasswordHash>> {
+        let hashes: Vec<String> = sqlx::query_scalar(
+            "SELECT password_hash FROM password_history
+             WHERE user_id = $1
+             ORDER BY created_at DESC
+             LIMIT $2",
+        )
+        .bind(user_id)
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(self.pool())
+        .await?;
+
+        hashes
+            .iter()
+            .map(PasswordHash::new)
+            .collect::<Result<_, _>>()
+            .map_err(|err| AppError::Unexpected(anyhow!("invalid persisted password hash: {err}")))
+    }
+}
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::password_reset::PasswordResetToken;
+use crate::domain::repositories::password_reset_repository::PasswordResetRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone)]
+pub struct PostgresPasswordResetRepository {
+    pool: PgPool,
+}
+
+impl PostgresPasswordResetRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct PasswordResetTokenRecord {
+    id: Uuid,
+    user_id: Uuid,
+    token_hash: String,
+    expires_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    used_at: Option<DateTime<Utc>>,
+}
+
+impl From<PasswordResetTokenRecord> for PasswordResetToken {
+    fn from(record: PasswordResetTokenRecord) -> Self {
+        Self {
+            id: record.id,
+            user_id: record.user_id,
+            token_hash: record.token_hash,
+            expires_at: record.expires_at,
+            created_at: record.created_at,
+            used_at: record.used_at,
+        }
+    }
+}
+
+#[async_trait]
+impl PasswordResetRepository for PostgresPasswordResetRepository {
+    async fn replace_for_user(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<PasswordResetToken> {
+        let mut transaction = self.pool().begin().await?;
+
+        sqlx::query(
+            "UPDATE password_reset_tokens SET used_at = NOW()
+             WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(&mut *transaction)
+        .await?;
+
+        let record = sqlx::query_as::<_, PasswordResetTokenRecord>(
+            "INSERT INTO password_reset_tokens (id, user_id, token_hash, expires_at)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id, user_id, token_hash, expires_at, created_at, used_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(record.into())
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<PasswordResetToken>> {
+        let record = sqlx::query_as::<_, PasswordResetTokenRecord>(
+            "SELECT id, user_id, token_hash, expires_at, created_at, used_at
+             FROM password_reset_tokens WHERE token_hash = $1",
+        )
+        .bind(token_hash)
+        .fetch_optional(self.pool())
+        .await?;
+
+        Ok(record.map(Into::into))
+    }
+
+    async fn consume(&self, id: Uuid, used_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE password_reset_tokens SET used_at = $2
+             WHERE id = $1 AND used_at IS NULL AND expires_at > $2",
+        )
+        .bind(id)
+        .bind(used_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn purge_expired(&self, before: DateTime<Utc>) -> RepositoryResult<u64> {
+        let result =
+            sqlx::query("DELETE FROM password_reset_tokens WHERE expires_at < $1 OR used_at < $1")
+                .bind(before)
+                .execute(self.pool())
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::personal_access_token::{NewPersonalAccessToken, PersonalAccessToken};
+use crate::domain::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::shared::error::AppError;
+
+#[derive(Clone)]
+pub struct PostgresPersonalAccessTokenRepository {
+    pool: PgPool,
+}
+
+impl PostgresPersonalAccessTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct PersonalAccessTokenRecord {
+    id: Uuid,
+    user_id: Uuid,
+    name: String,
+    token_prefix: String,
+    token_hash: String,
+    scopes: Vec<String>,
+    expires_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<PersonalAccessTokenRecord> for PersonalAccessToken {
+    type Error = AppError;
+
+    fn try_from(record: PersonalAccessTokenRecord) -> Result<Self, Self::Error> {
+        let scopes = record
+            .scopes
+            .iter()
+            .map(|scope| scope.parse::<Permission>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| {
+                AppError::Unexpected(anyhow!("failed to parse persisted scope: {}", err))
+            })?;
+
+        Ok(Self {
+            id: record.id,
+            user_id: record.user_id,
+            name: record.name,
+            token_prefix: record.token_prefix,
+            token_hash: record.token_hash,
+            scopes,
+            expires_at: record.expires_at,
+            created_at: record.created_at,
+            last_used_at: record.last_used_at,
+            revoked_at: record.revoked_at,
+        })
+    }
+}
+
+#[async_trait]
+impl PersonalAccessTokenRepository for PostgresPersonalAccessTokenRepository {
+    async fn create(&self, token: NewPersonalAccessToken) -> RepositoryResult<PersonalAccessToken> {
+        let scopes: Vec<&str> = token.scopes.iter().map(Permission::as_str).collect();
+
+        let record = sqlx::query_as::<_, PersonalAccessTokenRecord>(
+            "INSERT INTO personal_access_tokens
+                 (id, user_id, name, token_prefix, token_hash, scopes, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING id, user_id, name, token_prefix, token_hash, scopes, expires_at,
+                       created_at, last_used_at, revoked_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(token.user_id)
+        .bind(&token.name)
+        .bind(&token.token_prefix)
+        .bind(&token.token_hash)
+        .bind(scopes)
+        .bind(token.expires_at)
+        .fetch_one(self.pool())
+        .await?;
+
+        record.try_into()
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<PersonalAccessToken>> {
+        let record = sqlx::query_as::<_, PersonalAccessTokenRecord>(
+            "SELECT id, user_id, name, token_prefix, token_hash, scopes, expires_at,
+                    created_at, last_used_at, revoked_at
+             FROM personal_access_tokens WHERE token_hash = $1",
+        )
+        .bind(token_hash)
+        .fetch_optional(self.pool())
+        .await?;
+
+        record.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<PersonalAccessToken>> {
+        let records = sqlx::query_as::<_, PersonalAccessTokenRecord>(
+            "SELECT id, user_id, name, token_prefix, token_hash, scopes, expires_at,
+                    created_at, last_used_at, revoked_at
+             FROM personal_access_tokens
+             WHERE user_id = $1 AND revoked_at IS NULL
+             ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(self.pool())
+        .await?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn revoke(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE personal_access_tokens SET revoked_at = $3
+             WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(revoked_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_usage(&self, id: Uuid, used_at: DateTime<Utc>) -> RepositoryResult<()> {
+        sqlx::query(
+            "UPDATE personal_access_tokens SET last_used_at = $2
+             WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < $2)",
+        )
+        .bind(id)
+        .bind(used_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(())
+    }
+}
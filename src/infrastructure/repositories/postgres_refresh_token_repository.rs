@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::refresh_token::{NewRefreshToken, RefreshToken};
+use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone)]
+pub struct PostgresRefreshTokenRepository {
+    pool: PgPool,
+}
+
+impl PostgresRefreshTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct RefreshTokenRecord {
+    id: Uuid,
+    user_id: Uuid,
+    family_id: Uuid,
+    token_hash: String,
+    expires_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    rotated_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<RefreshTokenRecord> for RefreshToken {
+    fn from(record: RefreshTokenRecord) -> Self {
+        RefreshToken::new(
+            record.id,
+            record.user_id,
+            record.family_id,
+            record.token_hash,
+            record.expires_at,
+            record.created_at,
+            record.rotated_at,
+            record.revoked_at,
+        )
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for PostgresRefreshTokenRepository {
+    async fn create(&self, token: NewRefreshToken) -> RepositoryResult<RefreshToken> {
+        let record = sqlx::query_as::<_, RefreshTokenRecord>(
+            "INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id, user_id, family_id, token_hash, expires_at, created_at, rotated_at, revoked_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(token.user_id)
+        .bind(token.family_id)
+        .bind(&token.token_hash)
+        .bind(token.expires_at)
+        .fetch_one(self.pool())
+        .await?;
+
+        Ok(record.into())
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<RefreshToken>> {
+        let record = sqlx::query_as::<_, RefreshTokenRecord>(
+            "SELECT id, user_id, family_id, token_hash, expires_at, created_at, rotated_at, revoked_at
+             FROM refresh_tokens WHERE token_hash = $1",
+        )
+        .bind(token_hash)
+        .fetch_optional(self.pool())
+        .await?;
+
+        Ok(record.map(Into::into))
+    }
+
+    async fn mark_rotated(&self, id: Uuid) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens
+             SET rotated_at = NOW()
+             WHERE id = $1 AND rotated_at IS NULL AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn revoke_family(&self, family_id: Uuid) -> RepositoryResult<u64> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens
+             SET revoked_at = NOW()
+             WHERE family_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(family_id)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> RepositoryResult<u64> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens
+             SET revoked_at = NOW()
+             WHERE user_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
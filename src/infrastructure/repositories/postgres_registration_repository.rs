@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::registration::Registration;
+use crate::domain::repositories::registration_repository::RegistrationRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+const SELECT_REGISTRATION: &str =
+    "SELECT user_id, email, name, created_at, reviewed_by, approved_at, rejected_at
+ FROM user_registrations";
+
+#[derive(Clone)]
+pub struct PostgresRegistrationRepository {
+    pool: PgPool,
+}
+
+impl PostgresRegistrationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    async fn review(
+        &self,
+        column: &str,
+        user_id: Uuid,
+        reviewer: Uuid,
+        at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        let result = sqlx::query(&format!(
+            "UPDATE user_registrations SET {column} = $3, reviewed_by = $2
+             WHERE user_id = $1 AND approved_at IS NULL AND rejected_at IS NULL"
+        ))
+        .bind(user_id)
+        .bind(reviewer)
+        .bind(at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct RegistrationRecord {
+    user_id: Uuid,
+    email: String,
+    name: String,
+    created_at: DateTime<Utc>,
+    reviewed_by: Option<Uuid>,
+    approved_at: Option<DateTime<Utc>>,
+    rejected_at: Option<DateTime<Utc>>,
+}
+
+impl From<RegistrationRecord> for Registration {
+    fn from(record: RegistrationRecord) -> Self {
+        Self {
+            user_id: record.user_id,
+            email: record.email,
+            name: record.name,
+            created_at: record.created_at,
+            reviewed_by: record.reviewed_by,
+            approved_at: record.approved_at,
+            rejected_at: record.rejected_at,
+        }
+    }
+}
+
+#[async_trait]
+impl RegistrationRepository for PostgresRegistrationRepository {
+    async fn find_by_user(&self, user_id: Uuid) -> RepositoryResult<Option<Registration>> {
+        let record = sqlx::query_as::<_, RegistrationRecord>(&format!(
+            "{SELECT_REGISTRATION} WHERE user_id = $1"
+        ))
+        .bind(user_id)
+        .fetch_optional(self.pool())
+        .await?;
+
+        Ok(record.map(Into::into))
+    }
+
+    async fn find_pending(&self) -> RepositoryResult<Vec<Registration>> {
+        let records = sqlx::query_as::<_, RegistrationRecord>(&format!(
+            "{SELECT_REGISTRATION} WHERE approved_at IS NULL AND rejected_at IS NULL
+             ORDER BY created_at, user_id"
+        ))
+        .fetch_all(self.pool())
+        .await?;
+
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    async fn approve(
+        &self,
+        user_id: Uuid,
+        reviewer: Uuid,
+        at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        self.review("approved_at", user_id, reviewer, at).await
+    }
+
+    async fn reject(
+        &self,
+        user_id: Uuid,
+        reviewer: Uuid,
+        at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        self.review("rejected_at", user_id, reviewer, at).await
+    }
+}
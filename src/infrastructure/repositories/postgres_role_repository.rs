@@ -0,0 +1,152 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::role::{NewRole, Role, UpdateRole};
+use crate::domain::repositories::role_repository::RoleRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::domain::value_objects::RoleName;
+use crate::shared::error::AppError;
+
+#[derive(Clone)]
+pub struct PostgresRoleRepository {
+    pool: PgPool,
+}
+
+impl PostgresRoleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct RoleRecord {
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    permissions: Vec<String>,
+    built_in: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RoleRecord> for Role {
+    type Error = AppError;
+
+    fn try_from(record: RoleRecord) -> Result<Self, Self::Error> {
+        let name = RoleName::parse(&record.name).map_err(|err| {
+            AppError::Unexpected(anyhow!("failed to parse persisted role name: {}", err))
+        })?;
+        let permissions = record
+            .permissions
+            .iter()
+            .map(|permission| permission.parse::<Permission>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| {
+                AppError::Unexpected(anyhow!("failed to parse persisted permission: {}", err))
+            })?;
+
+        Ok(Self {
+            id: record.id,
+            name,
+            description: record.description,
+            permissions,
+            built_in: record.built_in,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        })
+    }
+}
+
+fn permission_names(permissions: &[Permission]) -> Vec<&'static str> {
+    permissions.iter().map(Permission::as_str).collect()
+}
+
+#[async_trait]
+impl RoleRepository for PostgresRoleRepository {
+    async fn create(&self, role: NewRole) -> RepositoryResult<Role> {
+        let record = sqlx::query_as::<_, RoleRecord>(
+            "INSERT INTO roles (id, name, description, permissions)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id, name, description, permissions, built_in, created_at, updated_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(role.name.as_str())
+        .bind(role.description)
+        .bind(permission_names(&role.permissions))
+        .fetch_one(self.pool())
+        .await?;
+
+        record.try_into()
+    }
+
+    async fn find_all(&self) -> RepositoryResult<Vec<Role>> {
+        let records = sqlx::query_as::<_, RoleRecord>(
+            "SELECT id, name, description, permissions, built_in, created_at, updated_at
+             FROM roles ORDER BY name",
+        )
+        .fetch_all(self.pool())
+        .await?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn find_by_name(&self, name: &RoleName) -> RepositoryResult<Option<Role>> {
+        let record = sqlx::query_as::<_, RoleRecord>(
+            "SELECT id, name, description, permissions, built_in, created_at, updated_at
+             FROM roles WHERE name = $1",
+        )
+        .bind(name.as_str())
+        .fetch_optional(self.pool())
+        .await?;
+
+        record.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_by_names(&self, names: &[RoleName]) -> RepositoryResult<Vec<Role>> {
+        let names: Vec<&str> = names.iter().map(RoleName::as_str).collect();
+        let records = sqlx::query_as::<_, RoleRecord>(
+            "SELECT id, name, description, permissions, built_in, created_at, updated_at
+             FROM roles WHERE name = ANY($1) ORDER BY name",
+        )
+        .bind(names)
+        .fetch_all(self.pool())
+        .await?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn update(&self, name: &RoleName, update: UpdateRole) -> RepositoryResult<Option<Role>> {
+        let record = sqlx::query_as::<_, RoleRecord>(
+            "UPDATE roles
+             SET description = COALESCE($2, description),
+                 permissions = COALESCE($3, permissions),
+                 updated_at = NOW()
+             WHERE name = $1
+             RETURNING id, name, description, permissions, built_in, created_at, updated_at",
+        )
+        .bind(name.as_str())
+        .bind(update.description)
+        .bind(update.permissions.as_deref().map(permission_names))
+        .fetch_optional(self.pool())
+        .await?;
+
+        record.map(TryInto::try_into).transpose()
+    }
+
+    async fn delete(&self, name: &RoleName) -> RepositoryResult<bool> {
+        let result = sqlx::query("DELETE FROM roles WHERE name = $1")
+            .bind(name.as_str())
+            .execute(self.pool())
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,229 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::permission::Permission;
+use crate::domain::entities::session::{NewSession, Session, SessionAuthMethod, SessionGrant};
+use crate::domain::repositories::session_repository::SessionRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+use crate::shared::error::AppError;
+
+#[derive(Clone)]
+pub struct PostgresSessionRepository {
+    pool: PgPool,
+}
+
+impl PostgresSessionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct SessionRecord {
+    id: Uuid,
+    user_id: Uuid,
+    auth_method: String,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    created_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+    oauth_client_id: Option<Uuid>,
+    oauth_scopes: Option<Vec<String>>,
+}
+
+impl TryFrom<SessionRecord> for Session {
+    type Error = AppError;
+
+    fn try_from(record: SessionRecord) -> Result<Self, Self::Error> {
+        let auth_method = record
+            .auth_method
+            .parse::<SessionAuthMethod>()
+            .map_err(|err| {
+                AppError::Unexpected(anyhow!("failed to parse persisted session: {}", err))
+            })?;
+        let grant = match (record.oauth_client_id, record.oauth_scopes) {
+            (Some(client_id), Some(scopes)) => Some(SessionGrant {
+                client_id,
+                scopes: scopes
+                    .iter()
+                    .map(|scope| scope.parse::<Permission>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| {
+                        AppError::Unexpected(anyhow!("failed to parse persisted scope: {}", err))
+                    })?,
+            }),
+            _ => None,
+        };
+
+        Ok(Self {
+            id: record.id,
+            user_id: record.user_id,
+            auth_method,
+            user_agent: record.user_agent,
+            ip_address: record.ip_address,
+            created_at: record.created_at,
+            last_seen_at: record.last_seen_at,
+            expires_at: record.expires_at,
+            revoked_at: record.revoked_at,
+            grant,
+        })
+    }
+}
+
+#[async_trait]
+impl SessionRepository for PostgresSessionRepository {
+    async fn create(&self, session: NewSession) -> RepositoryResult<Session> {
+        let scopes: Option<Vec<&str>> = session
+            .grant
+            .as_ref()
+            .map(|grant| grant.scopes.iter().map(Permission::as_str).collect());
+
+        let record = sqlx::query_as::<_, SessionRecord>(
+            "INSERT INTO sessions
+                 (id, user_id, auth_method, user_agent, ip_address, expires_at, oauth_client_id,
+                  oauth_scopes)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING id, user_id, auth_method, user_agent, ip_address, created_at,
+                       last_seen_at, expires_at, revoked_at, oauth_client_id, oauth_scopes",
+        )
+        .bind(session.id)
+        .bind(session.user_id)
+        .bind(session.auth_method.as_str())
+        .bind(&session.user_agent)
+        .bind(&session.ip_address)
+        .bind(session.expires_at)
+        .bind(session.grant.as_ref().map(|grant| grant.client_id))
+        .bind(scopes)
+        .fetch_one(self.pool())
+        .await?;
+
+        record.try_into()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<Session>> {
+        let record = sqlx::query_as::<_, SessionRecord>(
+            "SELECT id, user_id, auth_method, user_agent, ip_address, created_at, last_seen_at,
+                    expires_at, revoked_at, oauth_client_id, oauth_scopes
+             FROM sessions WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(self.pool())
+        .await?;
+
+        record.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_active_by_user(
+        &self,
+        user_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> RepositoryResult<Vec<Session>> {
+        let records = sqlx::query_as::<_, SessionRecord>(
+            "SELECT id, user_id, auth_method, user_agent, ip_address, created_at, last_seen_at,
+                    expires_at, revoked_at, oauth_client_id, oauth_scopes
+             FROM sessions
+             WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > $2
+             ORDER BY last_seen_at DESC",
+        )
+        .bind(user_id)
+        .bind(now)
+        .fetch_all(self.pool())
+        .await?;
+
+        records.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn record_activity(&self, id: Uuid, seen_at: DateTime<Utc>) -> RepositoryResult<()> {
+        sqlx::query("UPDATE sessions SET last_seen_at = $2 WHERE id = $1 AND last_seen_at < $2")
+            .bind(id)
+            .bind(seen_at)
+            .execute(self.pool())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn renew(
+        &self,
+        id: Uuid,
+        seen_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE sessions
+             SET last_seen_at = GREATEST(last_seen_at, $2), expires_at = $3
+             WHERE id = $1 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .bind(seen_at)
+        .bind(expires_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn revoke(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE sessions SET revoked_at = $3
+             WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(revoked_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_all_for_user(
+        &self,
+        user_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<u64> {
+        let result = sqlx::query(
+            "UPDATE sessions SET revoked_at = $2 WHERE user_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(revoked_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn revoke_all_for_client(
+        &self,
+        client_id: Uuid,
+        user_id: Option<Uuid>,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<u64> {
+        let result = sqlx::query(
+            "UPDATE sessions SET revoked_at = $3
+             WHERE oauth_client_id = $1 AND ($2::uuid IS NULL OR user_id = $2)
+               AND revoked_at IS NULL",
+        )
+        .bind(client_id)
+        .bind(user_id)
+        .bind(revoked_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
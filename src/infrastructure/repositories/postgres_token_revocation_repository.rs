@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::token_revocation::{
+    RevocationSnapshot, RevokedToken, UserTokenRevocation,
+};
+use crate::domain::repositories::token_revocation_repository::TokenRevocationRepository;
+use crate::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone)]
+pub struct PostgresTokenRevocationRepository {
+    pool: PgPool,
+}
+
+impl PostgresTokenRevocationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct RevokedTokenRecord {
+    jti: Uuid,
+    user_id: Uuid,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct UserTokenRevocationRecord {
+    user_id: Uuid,
+    revoked_before: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+#[async_trait]
+impl TokenRevocationRepository for PostgresTokenRevocationRepository {
+    async fn revoke_token(&self, token: RevokedToken) -> RepositoryResult<()> {
+        sqlx::query(
+            "INSERT INTO revoked_tokens (jti, user_id, expires_at)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (jti) DO NOTHING",
+        )
+        .bind(token.jti)
+        .bind(token.user_id)
+        .bind(token.expires_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn revoke_user(&self, revocation: UserTokenRevocation) -> RepositoryResult<()> {
+        sqlx::query(
+            "INSERT INTO user_token_revocations (user_id, revoked_before, expires_at)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (user_id) DO UPDATE
+             SET revoked_before = GREATEST(user_token_revocations.revoked_before, EXCLUDED.revoked_before),
+                 expires_at = GREATEST(user_token_revocations.expires_at, EXCLUDED.expires_at)",
+        )
+        .bind(revocation.user_id)
+        .bind(revocation.revoked_before)
+        .bind(revocation.expires_at)
+        .execute(self.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_active(&self, now: DateTime<Utc>) -> RepositoryResult<RevocationSnapshot> {
+        let tokens = sqlx::query_as::<_, RevokedTokenRecord>(
+            "SELECT jti, user_id, expires_at FROM revoked_tokens WHERE expires_at > $1",
+        )
+        .bind(now)
+        .fetch_all(self.pool())
+        .await?;
+
+        let users = sqlx::query_as::<_, UserTokenRevocationRecord>(
+            "SELECT user_id, revoked_before, expires_at
+             FROM user_token_revocations WHERE expires_at > $1",
+        )
+        .bind(now)
+        .fetch_all(self.pool())
+        .await?;
+
+        Ok(RevocationSnapshot {
+            tokens: tokens
+                .into_iter()
+                .map(|record| RevokedToken {
+                    jti: record.jti,
+                    user_id: record.user_id,
+                    expires_at: record.expires_at,
+                })
+                .collect(),
+            users: users
+                .into_iter()
+                .map(|record| UserTokenRevocation {
+                    user_id: record.user_id,
+                    revoked_before: record.revoked_before,
+                    expires_at: record.expires_at,
+                })
+                .collect(),
+        })
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> RepositoryResult<u64> {
+        let tokens = sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= $1")
+            .bind(now)
+            .execute(self.pool())
+            .await?;
+
+        let users = sqlx::query("DELETE FROM user_token_revocations WHERE expires_at <= $1")
+            .bind(now)
+            .execute(self.pool())
+            .await?;
+
+        Ok(tokens.rows_affected() + users.rows_affected())
+    }
+}
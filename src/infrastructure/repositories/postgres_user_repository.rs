@@ -1,16 +1,25 @@
-use std::str::FromStr;
-
 use anyhow::anyhow;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::{FromRow, PgPool};
+use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
-use crate::domain::entities::user::{NewUser, UpdateUser, User, UserRole};
-use crate::domain::errors::DomainError;
+use crate::domain::entities::user::{
+    NewUser, SortDirection, UpdateUser, User, UserFilter, UserPage, UserPreferences, UserQuery,
+    UserSortField, UserSortKey, UserStatus,
+};
 use crate::domain::repositories::user_repository::{RepositoryResult, UserRepository};
+use crate::domain::value_objects::{EmailAddress, Locale, PasswordHash, RoleName, TimeZoneName};
 use crate::shared::error::AppError;
 
+// Os papeis vem agregados da tabela `user_roles`, em ordem alfabetica.
+const SELECT_USER: &str = "SELECT u.id, u.name, u.email, u.password_hash,
+        ARRAY(SELECT r.name FROM user_roles ur JOIN roles r ON r.id = ur.role_id
+              WHERE ur.user_id = u.id ORDER BY r.name) AS roles,
+        u.version, u.created_at, u.updated_at, u.deleted_at, u.status, u.status_reason,
+        u.locale, u.timezone, u.email_verified_at, u.pending_email, u.password_changed_at
+ FROM users u";
+
 #[derive(Clone)]
 pub struct PostgresUserRepository {
     pool: PgPool,
@@ -24,6 +33,66 @@ impl PostgresUserRepository {
     fn pool(&self) -> &PgPool {
         &self.pool
     }
+
+    async fn fetch_by_id<'e, E>(executor: E, id: Uuid) -> RepositoryResult<Option<User>>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let record = sqlx::query_as::<_, UserRecord>(&format!(
+            "{SELECT_USER} WHERE u.id = $1 AND u.deleted_at IS NULL"
+        ))
+        .bind(id)
+        .fetch_optional(executor)
+        .await?;
+
+        record.map(TryInto::try_into).transpose()
+    }
+
+    async fn assign_roles(
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        roles: &[RoleName],
+    ) -> RepositoryResult<()> {
+        let names: Vec<&str> = roles.iter().map(RoleName::as_str).collect();
+
+        sqlx::query("DELETE FROM user_roles WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut **transaction)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO user_roles (user_id, role_id)
+             SELECT $1, id FROM roles WHERE name = ANY($2)",
+        )
+        .bind(user_id)
+        .bind(names)
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    // Diferencia usuario inexistente de versao divergente depois de uma escrita sem efeito.
+    async fn missed_write<'e, E>(
+        executor: E,
+        id: Uuid,
+        expected_versions: Option<Vec<i64>>,
+    ) -> AppError
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let current: Result<Option<i64>, sqlx::Error> =
+            sqlx::query_scalar("SELECT version FROM users WHERE id = $1 AND deleted_at IS NULL")
+                .bind(id)
+                .fetch_optional(executor)
+                .await;
+
+        match (current, expected_versions) {
+            (Err(err), _) => err.into(),
+            (Ok(Some(_)), Some(expected)) => version_mismatch(id, &expected),
+            _ => AppError::NotFound(format!("user {id} not found")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -32,29 +101,60 @@ struct UserRecord {
     name: String,
     email: String,
     password_hash: String,
-    role: String,
+    roles: Vec<String>,
+    version: i64,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+    status: String,
+    status_reason: Option<String>,
+    locale: Option<String>,
+    timezone: Option<String>,
+    email_verified_at: Option<DateTime<Utc>>,
+    pending_email: Option<String>,
+    password_changed_at: DateTime<Utc>,
 }
 
 impl TryFrom<UserRecord> for User {
     type Error = AppError;
 
     fn try_from(record: UserRecord) -> Result<Self, Self::Error> {
-        let role = UserRole::from_str(&record.role).map_err(|err| {
-            AppError::Unexpected(anyhow!("failed to parse persisted role: {}", err))
+        let status = UserStatus::parse(&record.status).map_err(|err| {
+            AppError::Unexpected(anyhow!("failed to parse persisted user status: {}", err))
         })?;
 
-        User::try_new(
+        let preferences = UserPreferences {
+            locale: record.locale.as_deref().map(Locale::parse).transpose()?,
+            timezone: record
+                .timezone
+                .as_deref()
+                .map(TimeZoneName::parse)
+                .transpose()?,
+        };
+
+        let pending_email = record
+            .pending_email
+            .as_deref()
+            .map(EmailAddress::parse)
+            .transpose()?;
+
+        let user = User::try_new(
             record.id,
             &record.name,
             &record.email,
-            role,
+            &record.roles,
             &record.password_hash,
+            record.version,
             record.created_at,
             record.updated_at,
-        )
-        .map_err(map_domain_error)
+            record.deleted_at,
+        )?;
+
+        Ok(user
+            .with_status(status, record.status_reason)
+            .with_preferences(preferences)
+            .with_email_verification(record.email_verified_at, pending_email)
+            .with_password_changed_at(record.password_changed_at))
     }
 }
 
@@ -62,106 +162,349 @@ impl TryFrom<UserRecord> for User {
 impl UserRepository for PostgresUserRepository {
     async fn create(&self, new_user: NewUser) -> RepositoryResult<User> {
         let id = Uuid::new_v4();
+        let mut transaction = self.pool().begin().await?;
 
-        let record = sqlx::query_as::<_, UserRecord>(
-            "INSERT INTO users (id, name, email, password_hash, role)
-             VALUES ($1, $2, $3, $4, $5)
-             RETURNING id, name, email, password_hash, role, created_at, updated_at",
+        sqlx::query(
+            "INSERT INTO users (id, name, email, email_canonical, password_hash, status)
+             VALUES ($1, $2, $3, $4, $5, $6)",
         )
         .bind(id)
         .bind(new_user.name().as_str())
         .bind(new_user.email().as_str())
+        .bind(new_user.email().canonical())
         .bind(new_user.password_hash().as_str())
-        .bind(new_user.role().as_str())
-        .fetch_one(self.pool())
+        .bind(new_user.status().as_str())
+        .execute(&mut *transaction)
         .await?;
 
-        record.try_into()
+        Self::assign_roles(&mut transaction, id, new_user.roles()).await?;
+
+        if new_user.has_registration() {
+            sqlx::query(
+                "INSERT INTO user_registrations (user_id, email, name) VALUES ($1, $2, $3)",
+            )
+            .bind(id)
+            .bind(new_user.email().as_str())
+            .bind(new_user.name().as_str())
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        if let Some(invitation) = new_user.invitation() {
+            sqlx::query(
+                "INSERT INTO user_invitations (id, user_id, email, invited_by, token_hash, expires_at)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(id)
+            .bind(new_user.email().as_str())
+            .bind(invitation.invited_by)
+            .bind(&invitation.token_hash)
+            .bind(invitation.expires_at)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        let user = Self::fetch_by_id(&mut *transaction, id)
+            .await?
+            .ok_or_else(|| AppError::Unexpected(anyhow!("created user {id} not found")))?;
+
+        transaction.commit().await?;
+        Ok(user)
     }
 
-    async fn find_all(&self) -> RepositoryResult<Vec<User>> {
-        let records = sqlx::query_as::<_, UserRecord>(
-            "SELECT id, name, email, password_hash, role, created_at, updated_at
-             FROM users
-             ORDER BY created_at DESC",
-        )
-        .fetch_all(self.pool())
-        .await?;
+    async fn find_page(&self, query: &UserQuery) -> RepositoryResult<UserPage> {
+        let column = sort_column(query.sort.field);
+        let (direction, comparison) = match query.sort.direction {
+            SortDirection::Asc => ("ASC", ">"),
+            SortDirection::Desc => ("DESC", "<"),
+        };
+
+        let mut builder = QueryBuilder::<Postgres>::new(SELECT_USER);
+        builder.push(" WHERE TRUE");
+        push_filters(&mut builder, &query.filter);
+
+        if let Some(cursor) = &query.after {
+            builder.push(format!(" AND ({column}, u.id) {comparison} ("));
+            match &cursor.key {
+                UserSortKey::Name(value) | UserSortKey::Email(value) => {
+                    builder.push_bind(value.clone())
+                }
+                UserSortKey::CreatedAt(value) => builder.push_bind(*value),
+            };
+            builder.push(", ").push_bind(cursor.id).push(")");
+        }
+
+        // Um registro a mais indica se existe proxima pagina sem precisar de COUNT.
+        builder
+            .push(format!(
+                " ORDER BY {column} {direction}, u.id {direction} LIMIT "
+            ))
+            .push_bind(i64::from(query.limit) + 1);
+
+        let records = builder
+            .build_query_as::<UserRecord>()
+            .fetch_all(self.pool())
+            .await?;
+        let mut users = records
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<RepositoryResult<Vec<User>>>()?;
+
+        let has_more = users.len() > query.limit as usize;
+        users.truncate(query.limit as usize);
+
+        let total = if query.include_total {
+            let mut builder =
+                QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM users u WHERE TRUE");
+            push_filters(&mut builder, &query.filter);
+            let count: i64 = builder.build_query_scalar().fetch_one(self.pool()).await?;
+            Some(count as u64)
+        } else {
+            None
+        };
 
-        records.into_iter().map(TryInto::try_into).collect()
+        Ok(UserPage {
+            users,
+            has_more,
+            total,
+        })
     }
 
     async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<User>> {
-        let record = sqlx::query_as::<_, UserRecord>(
-            "SELECT id, name, email, password_hash, role, created_at, updated_at
-             FROM users WHERE id = $1",
-        )
-        .bind(id)
+        Self::fetch_by_id(self.pool(), id).await
+    }
+
+    async fn find_by_email(&self, email: &EmailAddress) -> RepositoryResult<Option<User>> {
+        let record = sqlx::query_as::<_, UserRecord>(&format!(
+            "{SELECT_USER} WHERE u.email_canonical = $1 AND u.deleted_at IS NULL"
+        ))
+        .bind(email.canonical())
         .fetch_optional(self.pool())
         .await?;
 
-        match record {
-            Some(record) => Ok(Some(record.try_into()?)),
-            None => Ok(None),
-        }
+        record.map(TryInto::try_into).transpose()
     }
 
-    async fn find_by_email(&self, email: &str) -> RepositoryResult<Option<User>> {
-        let record = sqlx::query_as::<_, UserRecord>(
-            "SELECT id, name, email, password_hash, role, created_at, updated_at
-             FROM users WHERE email = $1",
-        )
-        .bind(email)
+    async fn find_deleted_by_id(&self, id: Uuid) -> RepositoryResult<Option<User>> {
+        let record = sqlx::query_as::<_, UserRecord>(&format!(
+            "{SELECT_USER} WHERE u.id = $1 AND u.deleted_at IS NOT NULL"
+        ))
+        .bind(id)
         .fetch_optional(self.pool())
         .await?;
 
-        match record {
-            Some(record) => Ok(Some(record.try_into()?)),
-            None => Ok(None),
-        }
+        record.map(TryInto::try_into).transpose()
     }
 
-    async fn update(&self, id: Uuid, update: UpdateUser) -> RepositoryResult<User> {
-        let record = sqlx::query_as::<_, UserRecord>(
+    async fn update(
+        &self,
+        id: Uuid,
+        update: UpdateUser,
+        expected_versions: Option<Vec<i64>>,
+    ) -> RepositoryResult<User> {
+        let mut transaction = self.pool().begin().await?;
+
+        let result = sqlx::query(
             "UPDATE users
              SET name = COALESCE($2, name),
                  email = COALESCE($3, email),
+                 email_canonical = COALESCE($14, email_canonical),
                  password_hash = COALESCE($4, password_hash),
-                 role = COALESCE($5, role),
+                 password_changed_at = CASE WHEN $4 IS NULL THEN password_changed_at ELSE NOW() END,
+                 status = COALESCE($6, status),
+                 status_reason = CASE WHEN $6 IS NULL THEN status_reason ELSE $7 END,
+                 status_changed_at = CASE WHEN $6 IS NULL THEN status_changed_at ELSE NOW() END,
+                 locale = CASE WHEN $8 THEN $9 ELSE locale END,
+                 timezone = CASE WHEN $8 THEN $10 ELSE timezone END,
+                 email_verified_at = CASE
+                     WHEN $11::TIMESTAMPTZ IS NOT NULL THEN $11
+                     WHEN $3 IS NOT NULL AND $3 <> email THEN NULL
+                     ELSE email_verified_at
+                 END,
+                 pending_email = CASE WHEN $12 THEN $13 ELSE pending_email END,
+                 version = version + 1,
                  updated_at = NOW()
-             WHERE id = $1
-             RETURNING id, name, email, password_hash, role, created_at, updated_at",
+             WHERE id = $1 AND deleted_at IS NULL AND ($5::BIGINT[] IS NULL OR version = ANY($5))",
         )
         .bind(id)
         .bind(update.name_str())
         .bind(update.email_str())
         .bind(update.password_hash_str())
-        .bind(update.role().map(|role| role.as_str().to_string()))
-        .fetch_optional(self.pool())
+        .bind(expected_versions.as_deref())
+        .bind(update.status_str())
+        .bind(update.status_reason())
+        .bind(update.preferences.is_some())
+        .bind(update.locale_str())
+        .bind(update.timezone_str())
+        .bind(update.email_verified_at)
+        .bind(update.pending_email.is_some())
+        .bind(update.pending_email_str())
+        .bind(update.email_canonical())
+        .execute(&mut *transaction)
         .await?;
 
-        match record {
-            Some(record) => record.try_into(),
-            None => Err(AppError::NotFound(format!("user {id} not found"))),
+        if result.rows_affected() == 0 {
+            return Err(Self::missed_write(&mut *transaction, id, expected_versions).await);
         }
+
+        if let Some(roles) = update.roles() {
+            Self::assign_roles(&mut transaction, id, roles).await?;
+        }
+
+        let user = Self::fetch_by_id(&mut *transaction, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("user {id} not found")))?;
+
+        transaction.commit().await?;
+        Ok(user)
     }
 
-    async fn delete(&self, id: Uuid) -> RepositoryResult<()> {
-        let result = sqlx::query("DELETE FROM users WHERE id = $1")
-            .bind(id)
-            .execute(self.pool())
-            .await?;
+    async fn replace_password_hash(
+        &self,
+        id: Uuid,
+        current: &PasswordHash,
+        replacement: &PasswordHash,
+    ) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            "UPDATE users
+             SET password_hash = $3
+             WHERE id = $1 AND password_hash = $2 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .bind(current.as_str())
+        .bind(replacement.as_str())
+        .execute(self.pool())
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn delete(&self, id: Uuid, expected_versions: Option<Vec<i64>>) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            "UPDATE users
+             SET deleted_at = NOW(), version = version + 1, updated_at = NOW()
+             WHERE id = $1 AND deleted_at IS NULL AND ($2::BIGINT[] IS NULL OR version = ANY($2))",
+        )
+        .bind(id)
+        .bind(expected_versions.as_deref())
+        .execute(self.pool())
+        .await?;
 
         if result.rows_affected() == 0 {
-            return Err(AppError::NotFound(format!("user {id} not found")));
+            return Err(Self::missed_write(self.pool(), id, expected_versions).await);
         }
 
         Ok(())
     }
+
+    async fn restore(&self, id: Uuid) -> RepositoryResult<User> {
+        let mut transaction = self.pool().begin().await?;
+
+        // O email pode ter sido reutilizado enquanto o usuario estava excluido.
+        let taken: Option<String> = sqlx::query_scalar(
+            "SELECT deleted.email FROM users deleted
+             JOIN users active ON active.email_canonical = deleted.email_canonical
+             WHERE deleted.id = $1 AND deleted.deleted_at IS NOT NULL
+               AND active.deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+        if let Some(email) = taken {
+            return Err(AppError::Conflict(format!("user {email} already exists")));
+        }
+
+        let result = sqlx::query(
+            "UPDATE users
+             SET deleted_at = NULL, version = version + 1, updated_at = NOW()
+             WHERE id = $1 AND deleted_at IS NOT NULL",
+        )
+        .bind(id)
+        .execute(&mut *transaction)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("deleted user {id} not found")));
+        }
+
+        let user = Self::fetch_by_id(&mut *transaction, id)
+            .await?
+            .ok_or_else(|| AppError::Unexpected(anyhow!("restored user {id} not found")))?;
+
+        transaction.commit().await?;
+        Ok(user)
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> RepositoryResult<u64> {
+        let result = sqlx::query("DELETE FROM users WHERE deleted_at < $1")
+            .bind(deleted_before)
+            .execute(self.pool())
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn count_with_role(&self, role: &RoleName) -> RepositoryResult<u64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM user_roles ur JOIN roles r ON r.id = ur.role_id
+             WHERE r.name = $1",
+        )
+        .bind(role.as_str())
+        .fetch_one(self.pool())
+        .await?;
+
+        Ok(count as u64)
+    }
 }
 
-fn map_domain_error(error: DomainError) -> AppError {
-    match error {
-        DomainError::Validation(message) => AppError::Validation(message),
+fn version_mismatch(id: Uuid, expected: &[i64]) -> AppError {
+    let expected: Vec<String> = expected.iter().map(i64::to_string).collect();
+    AppError::PreconditionFailed(format!(
+        "user {id} has been modified since version {}",
+        expected.join(", ")
+    ))
+}
+
+fn sort_column(field: UserSortField) -> &'static str {
+    match field {
+        UserSortField::Name => "u.name",
+        UserSortField::Email => "u.email",
+        UserSortField::CreatedAt => "u.created_at",
+    }
+}
+
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, filter: &UserFilter) {
+    if !filter.include_deleted {
+        builder.push(" AND u.deleted_at IS NULL");
+    }
+
+    if let Some(role) = &filter.role {
+        builder
+            .push(
+                " AND EXISTS (SELECT 1 FROM user_roles ur JOIN roles r ON r.id = ur.role_id
+                  WHERE ur.user_id = u.id AND r.name = ",
+            )
+            .push_bind(role.as_str().to_string())
+            .push(")");
+    }
+
+    if let Some(domain) = &filter.email_domain {
+        // Mesma expressao do indice `users_email_canonical_domain_idx`.
+        builder
+            .push(" AND substring(u.email_canonical from '@([^@]*)$') = ")
+            .push_bind(domain.clone());
+    }
+
+    if let Some(after) = filter.created_after {
+        builder.push(" AND u.created_at >= ").push_bind(after);
+    }
+
+    if let Some(before) = filter.created_before {
+        builder.push(" AND u.created_at < ").push_bind(before);
+    }
+
+    if let Some(status) = filter.status {
+        builder.push(" AND u.status = ").push_bind(status.as_str());
     }
 }
@@ -1,15 +1,79 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{ensure, Context};
 use tokio::net::TcpListener;
 
-use webrust::app::{build_rate_limiter, build_router, AppState};
-use webrust::application::services::auth_service::AuthService;
+use webrust::app::{
+    build_rate_limiter, build_registration_rate_limiter, build_router, spawn_deleted_user_purge,
+    spawn_email_outbox_dispatcher, spawn_login_throttle_maintenance, spawn_oauth_code_maintenance,
+    spawn_password_reset_maintenance, spawn_revocation_maintenance, AppState,
+};
+use webrust::application::services::auth_service::{AuthService, AuthSettings};
+use webrust::application::services::email_outbox_service::EmailOutboxService;
+use webrust::application::services::email_verification_service::{
+    EmailVerificationService, EmailVerificationSettings,
+};
+use webrust::application::services::invitation_service::{InvitationService, InvitationSettings};
+use webrust::application::services::login_throttle_service::{
+    LockoutPolicy, LoginThrottleService, MAX_BACKOFF_SECONDS, MAX_LOCKOUT_MINUTES,
+};
+use webrust::application::services::mfa_service::MfaService;
+use webrust::application::services::oauth_service::{OAuthService, OAuthSettings};
+use webrust::application::services::password_history_service::PasswordHistoryService;
+use webrust::application::services::password_reset_service::{
+    PasswordResetService, PasswordResetSettings,
+};
+use webrust::application::services::personal_access_token_service::{
+    PersonalAccessTokenService, PersonalAccessTokenSettings,
+};
+use webrust::application::services::registration_service::{
+    RegistrationMode, RegistrationService, RegistrationSettings,
+};
+use webrust::application::services::role_service::RoleService;
+use webrust::application::services::session_service::SessionService;
+use webrust::application::services::token_revocation_service::TokenRevocationService;
 use webrust::application::services::user_service::UserService;
 use webrust::config;
+use webrust::domain::repositories::email_outbox_repository::EmailOutboxRepository;
+use webrust::domain::repositories::invitation_repository::InvitationRepository;
+use webrust::domain::repositories::login_throttle_repository::LoginThrottleRepository;
+use webrust::domain::repositories::mfa_repository::MfaRepository;
+use webrust::domain::repositories::oauth_authorization_code_repository::AuthorizationCodeRepository;
+use webrust::domain::repositories::oauth_client_repository::OAuthClientRepository;
+use webrust::domain::repositories::oauth_consent_repository::OAuthConsentRepository;
+use webrust::domain::repositories::password_history_repository::PasswordHistoryRepository;
+use webrust::domain::repositories::password_reset_repository::PasswordResetRepository;
+use webrust::domain::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+use webrust::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+use webrust::domain::repositories::registration_repository::RegistrationRepository;
+use webrust::domain::repositories::role_repository::RoleRepository;
+use webrust::domain::repositories::session_repository::SessionRepository;
+use webrust::domain::repositories::token_revocation_repository::TokenRevocationRepository;
 use webrust::domain::repositories::user_repository::UserRepository;
 use webrust::infrastructure::database;
+use webrust::infrastructure::mail::build_mailer;
+use webrust::infrastructure::repositories::postgres_email_outbox_repository::PostgresEmailOutboxRepository;
+use webrust::infrastructure::repositories::postgres_invitation_repository::PostgresInvitationRepository;
+use webrust::infrastructure::repositories::postgres_login_throttle_repository::PostgresLoginThrottleRepository;
+use webrust::infrastructure::repositories::postgres_mfa_repository::PostgresMfaRepository;
+use webrust::infrastructure::repositories::postgres_oauth_authorization_code_repository::PostgresAuthorizationCodeRepository;
+use webrust::infrastructure::repositories::postgres_oauth_client_repository::PostgresOAuthClientRepository;
+use webrust::infrastructure::repositories::postgres_oauth_consent_repository::PostgresOAuthConsentRepository;
+use webrust::infrastructure::repositories::postgres_password_history_repository::PostgresPasswordHistoryRepository;
+use webrust::infrastructure::repositories::postgres_password_reset_repository::PostgresPasswordResetRepository;
+use webrust::infrastructure::repositories::postgres_personal_access_token_repository::PostgresPersonalAccessTokenRepository;
+use webrust::infrastructure::repositories::postgres_refresh_token_repository::PostgresRefreshTokenRepository;
+use webrust::infrastructure::repositories::postgres_registration_repository::PostgresRegistrationRepository;
+use webrust::infrastructure::repositories::postgres_role_repository::PostgresRoleRepository;
+use webrust::infrastructure::repositories::postgres_session_repository::PostgresSessionRepository;
+use webrust::infrastructure::repositories::postgres_token_revocation_repository::PostgresTokenRevocationRepository;
 use webrust::infrastructure::repositories::postgres_user_repository::PostgresUserRepository;
+use webrust::shared::security::breached_passwords::BreachedPasswords;
+use webrust::shared::security::hashing_pool::{HashingPool, HashingPoolSettings};
+use webrust::shared::security::password::{Argon2Settings, Pepper};
+use webrust::shared::security::password_policy::PasswordPolicy;
 use webrust::shared::security::token::JwtManager;
 use webrust::telemetry::{init_metrics, init_tracing, AuditLogger};
 
@@ -23,6 +87,134 @@ async fn main() -> anyhow::Result<()> {
         configuration.auth.jwt_ttl_minutes > 0,
         "auth.jwt_ttl_minutes must be greater than zero"
     );
+    ensure!(
+        configuration.auth.refresh_token_ttl_days > 0,
+        "auth.refresh_token_ttl_days must be greater than zero"
+    );
+    ensure!(
+        configuration.auth.revocation_sync_seconds > 0,
+        "auth.revocation_sync_seconds must be greater than zero"
+    );
+    ensure!(
+        configuration.auth.mfa_challenge_ttl_minutes > 0,
+        "auth.mfa_challenge_ttl_minutes must be greater than zero"
+    );
+    let lockout = &configuration.auth.lockout;
+    ensure!(
+        lockout.max_account_failures > 0 && lockout.max_ip_failures > 0,
+        "auth.lockout failure limits must be greater than zero"
+    );
+    ensure!(
+        lockout.failure_window_minutes > 0 && lockout.lockout_minutes > 0,
+        "auth.lockout window and lockout duration must be greater than zero"
+    );
+    ensure!(
+        lockout.backoff_base_seconds >= 0
+            && lockout.backoff_max_seconds >= lockout.backoff_base_seconds,
+        "auth.lockout back-off must satisfy 0 <= backoff_base_seconds <= backoff_max_seconds"
+    );
+    ensure!(
+        lockout.lockout_minutes <= MAX_LOCKOUT_MINUTES
+            && lockout.backoff_max_seconds <= MAX_BACKOFF_SECONDS,
+        "auth.lockout.lockout_minutes must be at most {} and backoff_max_seconds at most {}",
+        MAX_LOCKOUT_MINUTES,
+        MAX_BACKOFF_SECONDS
+    );
+    ensure!(
+        configuration.auth.password_reset.token_ttl_minutes > 0,
+        "auth.password_reset.token_ttl_minutes must be greater than zero"
+    );
+    ensure!(
+        configuration
+            .auth
+            .password_reset
+            .link_template
+            .contains("{token}"),
+        "auth.password_reset.link_template must contain the {{token}} placeholder"
+    );
+    let verification = &configuration.auth.email_verification;
+    ensure!(
+        verification.token_ttl_hours > 0 && verification.link_template.contains("{token}"),
+        "auth.email_verification needs token_ttl_hours > 0 and a link_template with {{token}}"
+    );
+    let policy_config = &configuration.auth.password_policy;
+    ensure!(
+        policy_config.min_length > 0 && policy_config.max_length >= policy_config.min_length,
+        "auth.password_policy must satisfy 0 < min_length <= max_length"
+    );
+    ensure!(
+        policy_config.min_strength_score <= 4,
+        "auth.password_policy.min_strength_score must be between 0 and 4"
+    );
+    let history_config = &configuration.auth.password_history;
+    ensure!(
+        history_config.max_age_days >= 0,
+        "auth.password_history.max_age_days must not be negative (0 disables expiry)"
+    );
+    let hashing_config = &configuration.auth.password_hashing;
+    ensure!(
+        hashing_config.retry_after_seconds > 0,
+        "auth.password_hashing.retry_after_seconds must be greater than zero"
+    );
+    let argon2_settings = Argon2Settings {
+        m_cost: hashing_config.m_cost,
+        t_cost: hashing_config.t_cost,
+        p_cost: hashing_config.p_cost,
+        active_pepper: hashing_config
+            .active_pepper_id
+            .clone()
+            .filter(|id| !id.trim().is_empty()),
+        peppers: hashing_config
+            .peppers
+            .iter()
+            .map(|pepper| Pepper {
+                key_id: pepper.id.clone(),
+                secret: pepper.secret.clone().into_bytes(),
+            })
+            .collect(),
+    };
+    argon2_settings
+        .validate()
+        .map_err(|err| anyhow::anyhow!("invalid auth.password_hashing settings: {}", err))?;
+    let pat_config = &configuration.auth.personal_access_tokens;
+    ensure!(
+        pat_config.default_ttl_days > 0 && pat_config.max_ttl_days >= pat_config.default_ttl_days,
+        "auth.personal_access_tokens must satisfy 0 < default_ttl_days <= max_ttl_days"
+    );
+    let oauth_config = &configuration.auth.oauth;
+    ensure!(
+        oauth_config.authorization_code_ttl_seconds > 0
+            && oauth_config.code_purge_interval_minutes > 0,
+        "auth.oauth.authorization_code_ttl_seconds and auth.oauth.code_purge_interval_minutes must be greater than zero"
+    );
+    ensure!(
+        configuration.mail.dispatch_interval_seconds > 0 && configuration.mail.max_attempts > 0,
+        "mail.dispatch_interval_seconds and mail.max_attempts must be greater than zero"
+    );
+    ensure!(
+        configuration.users.deleted_retention_days > 0
+            && configuration.users.purge_interval_minutes > 0,
+        "users.deleted_retention_days and users.purge_interval_minutes must be greater than zero"
+    );
+    let invitation_config = &configuration.users.invitations;
+    ensure!(
+        invitation_config.token_ttl_hours > 0
+            && invitation_config.link_template.contains("{token}"),
+        "users.invitations needs token_ttl_hours > 0 and a link_template with {{token}}"
+    );
+    let registration_config = &configuration.users.registration;
+    let registration_mode =
+        RegistrationMode::parse(&registration_config.mode).with_context(|| {
+            format!(
+                "users.registration.mode must be disabled, open, domains or approval, got {}",
+                registration_config.mode
+            )
+        })?;
+    ensure!(
+        registration_mode != RegistrationMode::AllowedDomains
+            || !registration_config.allowed_domains.is_empty(),
+        "users.registration.allowed_domains must not be empty when mode is domains"
+    );
 
     // Tracing precisa ser iniciado antes de qualquer log para capturar boot e diagnÃ³sticos.
     init_tracing(
@@ -33,7 +225,56 @@ async fn main() -> anyhow::Result<()> {
 
     // Camada de mÃ©tricas + handle Prometheus e rate limiting sÃ£o construÃ­dos antes da aplicaÃ§Ã£o.
     let (metrics_layer, metrics_handle, app_metrics) = init_metrics();
-    let rate_limiter_layer = build_rate_limiter(&configuration.rate_limit)?;
+    let rate_limiter_layer = build_rate_limiter(
+        &configuration.rate_limit,
+        configuration.server.trust_forwarded_for,
+    )?;
+    let registration_limiter_layer = build_registration_rate_limiter(
+        &configuration.rate_limit.registration,
+        configuration.server.trust_forwarded_for,
+    )?;
+
+    // A lista de senhas vazadas fica em memoria; um arquivo invalido impede a subida.
+    let breached_passwords = match policy_config
+        .breached_passwords_file
+        .as_deref()
+        .filter(|path| !path.trim().is_empty())
+    {
+        Some(path) => {
+            let list = BreachedPasswords::load(path)?;
+            tracing::info!(path, entries = list.len(), "breached password list loaded");
+            Some(Arc::new(list))
+        }
+        None => None,
+    };
+    let password_policy = PasswordPolicy {
+        min_length: policy_config.min_length,
+        max_length: policy_config.max_length,
+        require_uppercase: policy_config.require_uppercase,
+        require_lowercase: policy_config.require_lowercase,
+        require_digit: policy_config.require_digit,
+        require_symbol: policy_config.require_symbol,
+        min_strength_score: policy_config.min_strength_score,
+        reject_personal_info: policy_config.reject_personal_info,
+        breached_passwords,
+    };
+
+    let hasher = HashingPool::new(
+        HashingPoolSettings {
+            workers: match hashing_config.workers {
+                0 => HashingPoolSettings::default().workers,
+                workers => workers,
+            },
+            queue_capacity: hashing_config.queue_capacity,
+            retry_after_seconds: hashing_config.retry_after_seconds,
+            argon2: argon2_settings,
+        },
+        app_metrics.clone(),
+    );
+    hasher
+        .warm_up()
+        .await
+        .map_err(|err| anyhow::anyhow!("failed to prepare password hashing: {}", err))?;
 
     // Conecta ao Postgres e garante que o pool esteja pronto para receber requisiÃ§Ãµes.
     let pool = database::init_pool(&configuration.database)
@@ -48,12 +289,204 @@ async fn main() -> anyhow::Result<()> {
 
     // Ainda ganhamos flexibilidade usando trait objects: Ã© fÃ¡cil trocar o repositÃ³rio por outro backend.
     let repository: Arc<dyn UserRepository> = Arc::new(PostgresUserRepository::new(pool.clone()));
-    let user_service = UserService::new(repository.clone());
-    let jwt_manager = JwtManager::new(
-        &configuration.auth.jwt_secret,
-        configuration.auth.jwt_ttl_minutes,
+    let role_repository: Arc<dyn RoleRepository> =
+        Arc::new(PostgresRoleRepository::new(pool.clone()));
+    let role_service = RoleService::new(role_repository.clone(), repository.clone());
+    let jwt_manager = JwtManager::from_config(&configuration.auth)
+        .context("failed to initialise JWT signing keys")?;
+    let refresh_tokens: Arc<dyn RefreshTokenRepository> =
+        Arc::new(PostgresRefreshTokenRepository::new(pool.clone()));
+    let session_repository: Arc<dyn SessionRepository> =
+        Arc::new(PostgresSessionRepository::new(pool.clone()));
+    let sessions = SessionService::new(
+        session_repository,
+        refresh_tokens.clone(),
+        repository.clone(),
+        role_service.clone(),
+    );
+    let revocation_repository: Arc<dyn TokenRevocationRepository> =
+        Arc::new(PostgresTokenRevocationRepository::new(pool.clone()));
+    let revocations = TokenRevocationService::new(revocation_repository, sessions.clone());
+    revocations
+        .sync()
+        .await
+        .map_err(|err| anyhow::anyhow!("failed to load token revocation list: {}", err))?;
+    spawn_revocation_maintenance(
+        revocations.clone(),
+        Duration::from_secs(configuration.auth.revocation_sync_seconds),
+    );
+    let mfa_repository: Arc<dyn MfaRepository> = Arc::new(PostgresMfaRepository::new(pool.clone()));
+    let mfa_service = MfaService::new(
+        mfa_repository,
+        role_repository,
+        hasher.clone(),
+        configuration.auth.mfa_issuer.clone(),
+    );
+    let throttle_repository: Arc<dyn LoginThrottleRepository> =
+        Arc::new(PostgresLoginThrottleRepository::new(pool.clone()));
+    let failure_window = chrono::Duration::minutes(lockout.failure_window_minutes);
+    let throttle = LoginThrottleService::new(
+        throttle_repository,
+        LockoutPolicy {
+            max_account_failures: lockout.max_account_failures,
+            max_ip_failures: lockout.max_ip_failures,
+            failure_window,
+            lockout_duration: chrono::Duration::minutes(lockout.lockout_minutes),
+            backoff_base: chrono::Duration::seconds(lockout.backoff_base_seconds),
+            backoff_max: chrono::Duration::seconds(lockout.backoff_max_seconds),
+        },
+    );
+    spawn_login_throttle_maintenance(
+        throttle.clone(),
+        failure_window
+            .to_std()
+            .context("auth.lockout.failure_window_minutes is out of range")?,
+    );
+    let password_history_repository: Arc<dyn PasswordHistoryRepository> =
+        Arc::new(PostgresPasswordHistoryRepository::new(pool.clone()));
+    let password_history = PasswordHistoryService::new(
+        password_history_repository,
+        hasher.clone(),
+        history_config.remember,
+    );
+    let auth_service = AuthService::new(
+        repository.clone(),
+        refresh_tokens,
+        sessions,
+        revocations,
+        mfa_service.clone(),
+        role_service.clone(),
+        throttle,
+        password_history,
+        hasher.clone(),
+        jwt_manager.clone(),
+        AuthSettings {
+            refresh_token_ttl: chrono::Duration::days(configuration.auth.refresh_token_ttl_days),
+            mfa_challenge_ttl: chrono::Duration::minutes(
+                configuration.auth.mfa_challenge_ttl_minutes,
+            ),
+            require_verified_email: configuration.auth.email_verification.require_verified_login,
+            password_policy: password_policy.clone(),
+            password_max_age: (history_config.max_age_days > 0)
+                .then(|| chrono::Duration::days(history_config.max_age_days)),
+        },
+    );
+
+    let mailer = build_mailer(&configuration.mail)
+        .map_err(|err| anyhow::anyhow!("failed to initialise mailer: {}", err))?;
+    let outbox_repository: Arc<dyn EmailOutboxRepository> =
+        Arc::new(PostgresEmailOutboxRepository::new(pool.clone()));
+    let outbox =
+        EmailOutboxService::new(outbox_repository, mailer, configuration.mail.max_attempts);
+    spawn_email_outbox_dispatcher(
+        outbox.clone(),
+        Duration::from_secs(configuration.mail.dispatch_interval_seconds),
+    );
+    let verification_service = EmailVerificationService::new(
+        repository.clone(),
+        outbox.clone(),
+        jwt_manager,
+        EmailVerificationSettings {
+            token_ttl: chrono::Duration::hours(
+                configuration.auth.email_verification.token_ttl_hours,
+            ),
+            link_template: configuration.auth.email_verification.link_template.clone(),
+        },
+    );
+    let user_service = UserService::new(
+        repository.clone(),
+        role_service.clone(),
+        auth_service.clone(),
+        verification_service.clone(),
+    );
+    let registration_repository: Arc<dyn RegistrationRepository> =
+        Arc::new(PostgresRegistrationRepository::new(pool.clone()));
+    let registration_service = RegistrationService::new(
+        repository.clone(),
+        registration_repository,
+        verification_service,
+        outbox.clone(),
+        hasher.clone(),
+        password_policy.clone(),
+        RegistrationSettings {
+            mode: registration_mode,
+            allowed_domains: registration_config.allowed_domains.clone(),
+        },
+    );
+    spawn_deleted_user_purge(
+        user_service.clone(),
+        chrono::Duration::days(configuration.users.deleted_retention_days),
+        Duration::from_secs(configuration.users.purge_interval_minutes * 60),
+    );
+
+    let reset_repository: Arc<dyn PasswordResetRepository> =
+        Arc::new(PostgresPasswordResetRepository::new(pool.clone()));
+    let reset_ttl = chrono::Duration::minutes(configuration.auth.password_reset.token_ttl_minutes);
+    let password_reset_service = PasswordResetService::new(
+        repository.clone(),
+        reset_repository,
+        outbox.clone(),
+        auth_service.clone(),
+        PasswordResetSettings {
+            token_ttl: reset_ttl,
+            link_template: configuration.auth.password_reset.link_template.clone(),
+        },
+    );
+    spawn_password_reset_maintenance(
+        password_reset_service.clone(),
+        reset_ttl
+            .to_std()
+            .context("auth.password_reset.token_ttl_minutes is out of range")?,
+    );
+
+    let invitation_repository: Arc<dyn InvitationRepository> =
+        Arc::new(PostgresInvitationRepository::new(pool.clone()));
+    let invitation_service = InvitationService::new(
+        repository.clone(),
+        invitation_repository,
+        role_service.clone(),
+        outbox,
+        hasher,
+        password_policy,
+        InvitationSettings {
+            token_ttl: chrono::Duration::hours(invitation_config.token_ttl_hours),
+            link_template: invitation_config.link_template.clone(),
+        },
+    );
+
+    let pat_repository: Arc<dyn PersonalAccessTokenRepository> =
+        Arc::new(PostgresPersonalAccessTokenRepository::new(pool.clone()));
+    let personal_access_token_service = PersonalAccessTokenService::new(
+        pat_repository,
+        repository,
+        role_service.clone(),
+        PersonalAccessTokenSettings {
+            default_ttl: chrono::Duration::days(pat_config.default_ttl_days),
+            max_ttl: chrono::Duration::days(pat_config.max_ttl_days),
+        },
+    );
+
+    let oauth_clients: Arc<dyn OAuthClientRepository> =
+        Arc::new(PostgresOAuthClientRepository::new(pool.clone()));
+    let oauth_codes: Arc<dyn AuthorizationCodeRepository> =
+        Arc::new(PostgresAuthorizationCodeRepository::new(pool.clone()));
+    let oauth_consents: Arc<dyn OAuthConsentRepository> =
+        Arc::new(PostgresOAuthConsentRepository::new(pool.clone()));
+    let oauth_service = OAuthService::new(
+        oauth_clients,
+        oauth_codes,
+        oauth_consents,
+        auth_service.clone(),
+        OAuthSettings {
+            authorization_code_ttl: chrono::Duration::seconds(
+                oauth_config.authorization_code_ttl_seconds,
+            ),
+        },
+    );
+    spawn_oauth_code_maintenance(
+        oauth_service.clone(),
+        Duration::from_secs(oauth_config.code_purge_interval_minutes * 60),
     );
-    let auth_service = AuthService::new(repository, jwt_manager);
 
     if configuration.bootstrap.enabled {
         match user_service
@@ -64,10 +497,24 @@ async fn main() -> anyhow::Result<()> {
             )
             .await
         {
-            Ok(true) => tracing::info!(
-                email = %configuration.bootstrap.admin_email,
-                "bootstrap admin created"
-            ),
+            Ok(true) => {
+                tracing::info!(
+                    email = %configuration.bootstrap.admin_email,
+                    "bootstrap admin created"
+                );
+                if let Err(err) = auth_service.password_policy().validate(
+                    &configuration.bootstrap.admin_password,
+                    &[
+                        &configuration.bootstrap.admin_name,
+                        &configuration.bootstrap.admin_email,
+                    ],
+                ) {
+                    tracing::warn!(
+                        reason = %err,
+                        "bootstrap admin password does not meet the password policy; change it after the first login"
+                    );
+                }
+            }
             Ok(false) => tracing::info!(
                 email = %configuration.bootstrap.admin_email,
                 "bootstrap admin already present"
@@ -86,11 +533,24 @@ async fn main() -> anyhow::Result<()> {
     let state = AppState::new(
         user_service,
         auth_service,
+        mfa_service,
+        password_reset_service,
+        personal_access_token_service,
+        invitation_service,
+        registration_service,
+        role_service,
+        oauth_service,
         metrics_handle,
         app_metrics,
         audit_logger,
+        configuration.server.trust_forwarded_for,
+    );
+    let router = build_router(
+        state,
+        metrics_layer,
+        rate_limiter_layer,
+        registration_limiter_layer,
     );
-    let router = build_router(state, metrics_layer, rate_limiter_layer);
 
     // Subimos o listener TCP e logamos o endereÃ§o final.
     let address = configuration.address();
@@ -101,7 +561,10 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!(%address, "server started");
 
     // Axum assume o controle do loop de requisiÃ§Ãµes; qualquer erro encerra o processo com contexto.
-    axum::serve(listener, router.into_make_service())
-        .await
-        .context("server error")
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .context("server error")
 }
@@ -7,9 +7,11 @@ use axum::http::request::Parts;
 
 use crate::app::AppState;
 use crate::application::services::auth_service::AuthenticatedUser;
+use crate::application::services::personal_access_token_service::PersonalAccessTokenService;
 use crate::shared::error::AppError;
 
 const BEARER_PREFIX: &str = "Bearer ";
+const API_KEY_HEADER: &str = "x-api-key";
 
 pub struct CurrentUser(pub AuthenticatedUser);
 
@@ -35,18 +37,98 @@ impl FromRequestParts<AppState> for CurrentUser {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        // Clientes de maquina podem mandar o token pessoal em `X-API-Key` ou como Bearer.
+        if let Some(api_key) = parts.headers.get(API_KEY_HEADER) {
+            let api_key = api_key
+                .to_str()
+                .map_err(|_| AppError::Unauthorized("invalid api key header".to_string()))?;
+            let user = state
+                .personal_access_token_service()
+                .authenticate(api_key)
+                .await?;
+            return Ok(CurrentUser(user));
+        }
+
         let header = parts
             .headers
             .get(AUTHORIZATION)
             .ok_or_else(|| AppError::Unauthorized("missing authorization header".to_string()))?;
 
         let token = extract_bearer_token(header)?;
-        let user = state.auth_service().verify(token)?;
+        let user = if PersonalAccessTokenService::is_personal_access_token(token) {
+            state
+                .personal_access_token_service()
+                .authenticate(token)
+                .await?
+        } else {
+            state.auth_service().verify(token).await?
+        };
 
         Ok(CurrentUser(user))
     }
 }
 
+// Aceita tambem o token de desafio emitido quando a politica exige MFA e o usuario ainda nao
+// cadastrou o TOTP. Use apenas nas rotas de cadastro de MFA.
+pub struct MfaEnrollmentUser(pub AuthenticatedUser);
+
+#[async_trait]
+impl FromRequestParts<AppState> for MfaEnrollmentUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let header = parts
+            .headers
+            .get(AUTHORIZATION)
+            .ok_or_else(|| AppError::Unauthorized("missing authorization header".to_string()))?;
+
+        let token = extract_bearer_token(header)?;
+        let user = state
+            .auth_service()
+            .verify_for_mfa_enrollment(token)
+            .await?;
+
+        Ok(MfaEnrollmentUser(user))
+    }
+}
+
+// Aceita tambem o token emitido no login quando a senha venceu. Use apenas na troca da propria
+// senha; tokens pessoais seguem pelo `CurrentUser` para que o servico os recuse como sempre.
+pub struct PasswordChangeUser(pub AuthenticatedUser);
+
+#[async_trait]
+impl FromRequestParts<AppState> for PasswordChangeUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = match parts.headers.get(AUTHORIZATION) {
+            Some(header) if !parts.headers.contains_key(API_KEY_HEADER) => {
+                Some(extract_bearer_token(header)?.to_string())
+            }
+            _ => None,
+        };
+
+        match token {
+            Some(token) if !PersonalAccessTokenService::is_personal_access_token(&token) => {
+                let user = state
+                    .auth_service()
+                    .verify_for_password_change(&token)
+                    .await?;
+                Ok(PasswordChangeUser(user))
+            }
+            _ => CurrentUser::from_request_parts(parts, state)
+                .await
+                .map(|current_user| PasswordChangeUser(current_user.into_inner())),
+        }
+    }
+}
+
 fn extract_bearer_token(value: &HeaderValue) -> Result<&str, AppError> {
     let raw = value
         .to_str()
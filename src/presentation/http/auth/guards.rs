@@ -1,18 +1,18 @@
 ﻿use crate::application::services::auth_service::AuthenticatedUser;
-use crate::domain::entities::user::UserRole;
+use crate::domain::entities::permission::Permission;
 use crate::shared::error::AppError;
 
-pub fn ensure_admin(user: &AuthenticatedUser) -> Result<(), AppError> {
-    match user.role {
-        UserRole::Admin => Ok(()),
-        _ => Err(AppError::Forbidden("admin role required".to_string())),
-    }
+pub fn ensure_permission(user: &AuthenticatedUser, permission: Permission) -> Result<(), AppError> {
+    user.require_permission(permission)
 }
 
-pub fn ensure_any(user: &AuthenticatedUser, allowed_roles: &[UserRole]) -> Result<(), AppError> {
-    if allowed_roles.iter().any(|role| role == &user.role) {
+pub fn ensure_any(user: &AuthenticatedUser, permissions: &[Permission]) -> Result<(), AppError> {
+    if permissions
+        .iter()
+        .any(|permission| user.has_permission(*permission))
+    {
         Ok(())
     } else {
-        Err(AppError::Forbidden("insufficient role".to_string()))
+        Err(AppError::Forbidden("insufficient permissions".to_string()))
     }
 }
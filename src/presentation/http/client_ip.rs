@@ -0,0 +1,83 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::async_trait;
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::header::USER_AGENT;
+use axum::http::request::Parts;
+use axum::http::{Extensions, HeaderMap};
+
+use crate::app::AppState;
+use crate::domain::entities::session::SessionClient;
+
+const FORWARDED_FOR: &str = "x-forwarded-for";
+
+// Endereco de origem da requisicao. `X-Forwarded-For` so e considerado quando
+// `server.trust_forwarded_for` esta ligado (API atras de um proxy confiavel); caso contrario o
+// header e ignorado para que o cliente nao escolha o proprio IP. Vale a entrada mais a direita,
+// a que o proxy acrescentou com o endereco que o conectou: as anteriores vem do cliente.
+pub fn resolve(
+    headers: &HeaderMap,
+    extensions: &Extensions,
+    trust_forwarded_for: bool,
+) -> Option<IpAddr> {
+    if trust_forwarded_for {
+        let forwarded = headers
+            .get_all(FORWARDED_FOR)
+            .iter()
+            .next_back()
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .and_then(|value| value.trim().parse::<IpAddr>().ok());
+        if forwarded.is_some() {
+            return forwarded;
+        }
+    }
+
+    extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip())
+}
+
+pub struct ClientIp(pub Option<IpAddr>);
+
+#[async_trait]
+impl FromRequestParts<AppState> for ClientIp {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(ClientIp(resolve(
+            &parts.headers,
+            &parts.extensions,
+            state.trust_forwarded_for(),
+        )))
+    }
+}
+
+// Endereco e `User-Agent` de quem esta abrindo uma sessao, guardados no inventario.
+pub struct LoginClient(pub SessionClient);
+
+#[async_trait]
+impl FromRequestParts<AppState> for LoginClient {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(LoginClient(SessionClient {
+            ip_address: resolve(
+                &parts.headers,
+                &parts.extensions,
+                state.trust_forwarded_for(),
+            ),
+            user_agent: parts
+                .headers
+                .get(USER_AGENT)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+        }))
+    }
+}
@@ -1,9 +1,23 @@
-﻿use axum::{extract::State, Json};
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
 
 use crate::app::AppState;
-use crate::application::dtos::auth::{LoginRequestDto, LoginResponseDto};
+use crate::application::dtos::auth::{
+    ForgotPasswordRequestDto, ForgotPasswordResponseDto, LoginRequestDto, LoginResponseDto,
+    LogoutRequestDto, RefreshRequestDto, ResetPasswordRequestDto, VerifyEmailRequestDto,
+};
+use crate::application::dtos::mfa::{MfaChallengeResponseDto, MfaVerifyRequestDto};
+use crate::application::dtos::registration::{RegisterRequestDto, RegisterResponseDto};
+use crate::application::services::auth_service::{AuthSession, LoginOutcome};
+use crate::domain::entities::user::User;
+use crate::presentation::http::auth::extractor::CurrentUser;
+use crate::presentation::http::client_ip::{ClientIp, LoginClient};
 #[allow(unused_imports)]
-use crate::shared::error::{AppResult, ErrorResponse};
+use crate::shared::error::{AppError, AppResult, ErrorResponse};
 use crate::shared::validation::sanitize_for_logging;
 use crate::telemetry::{AuditActor, AuditEvent, AuditTarget};
 
@@ -13,53 +27,521 @@ use crate::telemetry::{AuditActor, AuditEvent, AuditTarget};
     request_body = LoginRequestDto,
     responses(
         (status = 200, description = "Authenticated successfully", body = LoginResponseDto),
+        (status = 202, description = "Second factor, MFA enrollment or a password change required", body = MfaChallengeResponseDto),
         (status = 401, description = "Invalid credentials", body = ErrorResponse),
+        (status = 429, description = "Too many failed attempts for this account or address", body = ErrorResponse),
+        (status = 503, description = "Password hashing is saturated; retry after the Retry-After delay", body = ErrorResponse),
         (status = 500, description = "Unexpected error", body = ErrorResponse)
     ),
     tag = "Auth"
 )]
 pub async fn login(
     State(state): State<AppState>,
+    LoginClient(client): LoginClient,
     Json(payload): Json<LoginRequestDto>,
-) -> AppResult<Json<LoginResponseDto>> {
+) -> AppResult<Response> {
     let email = payload.email.clone();
+    let ip = client.ip_address.map(|ip| ip.to_string());
 
     match state
         .auth_service()
-        .authenticate(&payload.email, &payload.password)
+        .authenticate(&payload.email, &payload.password, &client)
         .await
     {
-        Ok(session) => {
+        Ok(outcome) => Ok(outcome_response(
+            &state,
+            "auth.login",
+            Some(&email),
+            outcome,
+            ip,
+        )),
+        Err(err) => {
             let actor = AuditActor {
-                id: Some(session.user.id),
-                email: Some(sanitize_for_logging(&session.user.email)),
-                role: Some(session.user.role.as_str().to_string()),
+                id: None,
+                email: Some(sanitize_for_logging(&email)),
+                role: None,
             };
+            log_lockout(&state, &actor, &err, ip.clone());
+            state.audit().log(AuditEvent::failure(
+                "auth.login",
+                actor,
+                AuditTarget::new("auth", None),
+                Some(sanitize_for_logging(&err.to_string())),
+                ip,
+            ));
+
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/mfa/verify",
+    request_body = MfaVerifyRequestDto,
+    responses(
+        (status = 200, description = "Second factor accepted", body = LoginResponseDto),
+        (status = 202, description = "Password expired; the challenge only allows changing it", body = MfaChallengeResponseDto),
+        (status = 401, description = "Invalid challenge or code", body = ErrorResponse),
+        (status = 429, description = "Too many failed attempts for this account or address", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn verify_mfa(
+    State(state): State<AppState>,
+    LoginClient(client): LoginClient,
+    Json(payload): Json<MfaVerifyRequestDto>,
+) -> AppResult<Response> {
+    let ip = client.ip_address.map(|ip| ip.to_string());
+
+    match state
+        .auth_service()
+        .verify_mfa(&payload.challenge_token, &payload.code, &client)
+        .await
+    {
+        Ok(outcome) => Ok(outcome_response(
+            &state,
+            "auth.mfa.verify",
+            None,
+            outcome,
+            ip,
+        )),
+        Err(err) => {
+            log_lockout(&state, &AuditActor::default(), &err, ip.clone());
+            state.audit().log(AuditEvent::failure(
+                "auth.mfa.verify",
+                AuditActor::default(),
+                AuditTarget::new("auth", None),
+                Some(sanitize_for_logging(&err.to_string())),
+                ip,
+            ));
+
+            Err(err)
+        }
+    }
+}
+
+fn session_response(
+    state: &AppState,
+    action: &str,
+    session: AuthSession,
+    ip: Option<String>,
+) -> Json<LoginResponseDto> {
+    let actor = AuditActor {
+        id: Some(session.user.id),
+        email: Some(sanitize_for_logging(&session.user.email)),
+        role: Some(session.user.roles_label()),
+    };
+
+    state.audit().log(AuditEvent::success(
+        action,
+        actor,
+        AuditTarget::new("auth", Some(session.user.id.to_string())),
+        None,
+        ip,
+    ));
+
+    Json(LoginResponseDto::from(session))
+}
+
+// Sessao completa responde `200`; qualquer desafio intermediario responde `202` com o status.
+fn outcome_response(
+    state: &AppState,
+    action: &str,
+    email: Option<&str>,
+    outcome: LoginOutcome,
+    ip: Option<String>,
+) -> Response {
+    let (status, challenge) = match outcome {
+        LoginOutcome::Authenticated(session) => {
+            return session_response(state, action, session, ip).into_response()
+        }
+        LoginOutcome::MfaRequired(challenge) => ("mfa_required", challenge),
+        LoginOutcome::MfaEnrollmentRequired(challenge) => ("mfa_enrollment_required", challenge),
+        LoginOutcome::PasswordChangeRequired(challenge) => ("password_change_required", challenge),
+    };
+    log_challenge(state, action, email, status, ip);
+
+    (
+        StatusCode::ACCEPTED,
+        Json(MfaChallengeResponseDto::new(status, challenge)),
+    )
+        .into_response()
+}
+
+fn log_challenge(
+    state: &AppState,
+    action: &str,
+    email: Option<&str>,
+    status: &str,
+    ip: Option<String>,
+) {
+    state.audit().log(AuditEvent::success(
+        action,
+        AuditActor {
+            id: None,
+            email: email.map(sanitize_for_logging),
+            role: None,
+        },
+        AuditTarget::new("auth", None),
+        Some(status.to_string()),
+        ip,
+    ));
+}
+
+// Toda tentativa recusada por bloqueio ou back-off gera `auth.lockout`, independentemente de o
+// email existir.
+fn log_lockout(state: &AppState, actor: &AuditActor, err: &AppError, ip: Option<String>) {
+    if let AppError::TooManyRequests {
+        retry_after_seconds,
+        ..
+    } = err
+    {
+        state.audit().log(AuditEvent::failure(
+            "auth.lockout",
+            actor.clone(),
+            AuditTarget::new("auth", None),
+            Some(format!("retry_after_seconds={retry_after_seconds}")),
+            ip,
+        ));
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    request_body = RefreshRequestDto,
+    responses(
+        (status = 200, description = "Tokens rotated", body = LoginResponseDto),
+        (status = 401, description = "Invalid, expired or reused refresh token", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn refresh(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequestDto>,
+) -> AppResult<Json<LoginResponseDto>> {
+    match state.auth_service().refresh(&payload.refresh_token).await {
+        Ok(session) => Ok(session_response(&state, "auth.refresh", session, None)),
+        Err(err) => {
+            state.audit().log(AuditEvent::failure(
+                "auth.refresh",
+                AuditActor::default(),
+                AuditTarget::new("auth", None),
+                Some(sanitize_for_logging(&err.to_string())),
+                None,
+            ));
+
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    request_body(content = Option<LogoutRequestDto>, description = "Optional refresh token to revoke"),
+    responses(
+        (status = 204, description = "Session revoked"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    payload: Option<Json<LogoutRequestDto>>,
+) -> AppResult<StatusCode> {
+    let Json(payload) = payload.unwrap_or_default();
+    let actor = AuditActor {
+        id: Some(current_user.id),
+        email: Some(sanitize_for_logging(&current_user.email)),
+        role: Some(current_user.roles_label()),
+    };
 
+    match state
+        .auth_service()
+        .logout(&current_user, payload.refresh_token.as_deref())
+        .await
+    {
+        Ok(()) => {
             state.audit().log(AuditEvent::success(
-                "auth.login",
+                "auth.logout",
                 actor,
-                AuditTarget::new("auth", Some(session.user.id.to_string())),
+                AuditTarget::new("auth", Some(current_user.id.to_string())),
                 None,
                 None,
             ));
 
-            Ok(Json(LoginResponseDto::from(session)))
+            Ok(StatusCode::NO_CONTENT)
         }
         Err(err) => {
             state.audit().log(AuditEvent::failure(
-                "auth.login",
+                "auth.logout",
+                actor,
+                AuditTarget::new("auth", Some(current_user.id.to_string())),
+                Some(sanitize_for_logging(&err.to_string())),
+                None,
+            ));
+
+            Err(err)
+        }
+    }
+}
+
+const REGISTER_MESSAGE: &str =
+    "Registration received. Check your email to confirm your address and continue.";
+
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    request_body = RegisterRequestDto,
+    responses(
+        (status = 202, description = "Request accepted; the response is identical whether or not the email is already registered. New accounts get the viewer role and stay `pending` until approved when approval is required", body = RegisterResponseDto),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 403, description = "Registration disabled or email domain not allowed", body = ErrorResponse),
+        (status = 429, description = "Too many registrations from this address", body = ErrorResponse),
+        (status = 503, description = "Password hashing is saturated; retry after the Retry-After delay", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn register(
+    State(state): State<AppState>,
+    ClientIp(source_ip): ClientIp,
+    Json(payload): Json<RegisterRequestDto>,
+) -> AppResult<(StatusCode, Json<RegisterResponseDto>)> {
+    let ip = source_ip.map(|ip| ip.to_string());
+    let actor = AuditActor {
+        id: None,
+        email: Some(sanitize_for_logging(&payload.email)),
+        role: None,
+    };
+
+    match state.registration_service().register(payload).await {
+        Ok(user) => {
+            // Como no esqueci a senha, so a auditoria sabe se o email ja estava cadastrado.
+            let details = match &user {
+                Some(user) => format!("status={}", user.status()),
+                None => "email_taken".to_string(),
+            };
+            state.audit().log(AuditEvent::success(
+                "user.register",
                 AuditActor {
-                    id: None,
-                    email: Some(sanitize_for_logging(&email)),
+                    id: user.as_ref().map(User::id),
+                    ..actor
+                },
+                AuditTarget::new("user", user.as_ref().map(|user| user.id().to_string())),
+                Some(details),
+                ip,
+            ));
+
+            Ok((
+                StatusCode::ACCEPTED,
+                Json(RegisterResponseDto {
+                    message: REGISTER_MESSAGE.to_string(),
+                }),
+            ))
+        }
+        Err(err) => {
+            state.audit().log(AuditEvent::failure(
+                "user.register",
+                actor,
+                AuditTarget::new("user", None),
+                Some(sanitize_for_logging(&err.to_string())),
+                ip,
+            ));
+
+            Err(err)
+        }
+    }
+}
+
+const FORGOT_PASSWORD_MESSAGE: &str =
+    "If an account exists for this email, a password reset link has been sent.";
+
+#[utoipa::path(
+    post,
+    path = "/auth/password/forgot",
+    request_body = ForgotPasswordRequestDto,
+    responses(
+        (status = 202, description = "Request accepted; the response is identical whether or not the account exists", body = ForgotPasswordResponseDto),
+        (status = 400, description = "Malformed email", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn forgot_password(
+    State(state): State<AppState>,
+    ClientIp(source_ip): ClientIp,
+    Json(payload): Json<ForgotPasswordRequestDto>,
+) -> AppResult<(StatusCode, Json<ForgotPasswordResponseDto>)> {
+    let ip = source_ip.map(|ip| ip.to_string());
+    let actor = AuditActor {
+        id: None,
+        email: Some(sanitize_for_logging(&payload.email)),
+        role: None,
+    };
+
+    match state
+        .password_reset_service()
+        .request_reset(&payload.email)
+        .await
+    {
+        Ok(user_id) => {
+            // O detalhe fica apenas no log de auditoria; o cliente recebe sempre o mesmo corpo.
+            state.audit().log(AuditEvent::success(
+                "auth.password.forgot",
+                actor,
+                AuditTarget::new("user", user_id.map(|id| id.to_string())),
+                Some(
+                    if user_id.is_some() {
+                        "email_queued"
+                    } else {
+                        "unknown_email"
+                    }
+                    .to_string(),
+                ),
+                ip,
+            ));
+
+            Ok((
+                StatusCode::ACCEPTED,
+                Json(ForgotPasswordResponseDto {
+                    message: FORGOT_PASSWORD_MESSAGE.to_string(),
+                }),
+            ))
+        }
+        Err(err) => {
+            state.audit().log(AuditEvent::failure(
+                "auth.password.forgot",
+                actor,
+                AuditTarget::new("user", None),
+                Some(sanitize_for_logging(&err.to_string())),
+                ip,
+            ));
+
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/password/reset",
+    request_body = ResetPasswordRequestDto,
+    responses(
+        (status = 204, description = "Password changed and all sessions revoked"),
+        (status = 400, description = "Invalid, expired or used token, or weak password", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn reset_password(
+    State(state): State<AppState>,
+    ClientIp(source_ip): ClientIp,
+    Json(payload): Json<ResetPasswordRequestDto>,
+) -> AppResult<StatusCode> {
+    let ip = source_ip.map(|ip| ip.to_string());
+
+    match state
+        .password_reset_service()
+        .reset_password(&payload.token, &payload.new_password)
+        .await
+    {
+        Ok(user_id) => {
+            state.audit().log(AuditEvent::success(
+                "auth.password.reset",
+                AuditActor {
+                    id: Some(user_id),
+                    email: None,
                     role: None,
                 },
-                AuditTarget::new("auth", None),
+                AuditTarget::new("user", Some(user_id.to_string())),
+                None,
+                ip,
+            ));
+
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(err) => {
+            state.audit().log(AuditEvent::failure(
+                "auth.password.reset",
+                AuditActor::default(),
+                AuditTarget::new("user", None),
                 Some(sanitize_for_logging(&err.to_string())),
+                ip,
+            ));
+
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/email/verify",
+    request_body = VerifyEmailRequestDto,
+    responses(
+        (status = 204, description = "Email confirmed; a pending change now replaces the old address"),
+        (status = 400, description = "Invalid, expired or superseded link", body = ErrorResponse),
+        (status = 409, description = "The new address was taken in the meantime", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn verify_email(
+    State(state): State<AppState>,
+    ClientIp(source_ip): ClientIp,
+    Json(payload): Json<VerifyEmailRequestDto>,
+) -> AppResult<StatusCode> {
+    let ip = source_ip.map(|ip| ip.to_string());
+
+    match state.user_service().verify_email(&payload.token).await {
+        Ok(user) => {
+            state.audit().log(AuditEvent::success(
+                "auth.email.verify",
+                AuditActor {
+                    id: Some(user.id()),
+                    email: Some(user.email().to_string()),
+                    role: None,
+                },
+                AuditTarget::new("user", Some(user.id().to_string())),
                 None,
+                ip,
+            ));
+
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(err) => {
+            state.audit().log(AuditEvent::failure(
+                "auth.email.verify",
+                AuditActor::default(),
+                AuditTarget::new("user", None),
+                Some(sanitize_for_logging(&err.to_string())),
+                ip,
             ));
 
             Err(err)
         }
     }
 }
+
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    responses(
+        (status = 200, description = "Public keys used to verify access tokens (empty with HS256)", content_type = "application/json")
+    ),
+    tag = "Auth"
+)]
+pub async fn jwks(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(state.auth_service().jwks().clone()),
+    )
+}
@@ -0,0 +1,243 @@
+use axum::{extract::Path, extract::State, http::StatusCode, Json};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::application::dtos::invitation::{
+    AcceptInvitationDto, CreateInvitationDto, InvitationDto,
+};
+use crate::application::dtos::user::UserResponseDto;
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::presentation::http::auth::extractor::CurrentUser;
+use crate::presentation::http::client_ip::ClientIp;
+#[allow(unused_imports)]
+use crate::shared::error::{AppError, AppResult, ErrorResponse};
+use crate::shared::validation::sanitize_for_logging;
+use crate::telemetry::{AuditActor, AuditEvent, AuditTarget};
+
+#[utoipa::path(
+    post,
+    path = "/invitations",
+    request_body = CreateInvitationDto,
+    responses(
+        (status = 201, description = "Pending user created and invitation emailed", body = InvitationDto),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 409, description = "A user with this email already exists", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Invitations"
+)]
+pub async fn create_invitation(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Json(payload): Json<CreateInvitationDto>,
+) -> AppResult<(StatusCode, Json<InvitationDto>)> {
+    let roles = payload.roles.join(",");
+    let result = state
+        .invitation_service()
+        .invite(&current_user, payload)
+        .await;
+
+    let (target, detail) = match &result {
+        Ok(invitation) => (
+            Some(invitation.id.to_string()),
+            Some(sanitize_for_logging(&format!(
+                "email={} roles={roles}",
+                invitation.email
+            ))),
+        ),
+        Err(_) => (None, None),
+    };
+    log_result(
+        &state,
+        "invitation.create",
+        &current_user,
+        target,
+        detail,
+        &result,
+    );
+
+    result.map(|invitation| (StatusCode::CREATED, Json(invitation.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/invitations",
+    responses(
+        (status = 200, description = "Invitations not yet accepted or revoked, oldest first", body = [InvitationDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Invitations"
+)]
+pub async fn list_invitations(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> AppResult<Json<Vec<InvitationDto>>> {
+    let invitations = state
+        .invitation_service()
+        .list_pending(&current_user)
+        .await?;
+
+    Ok(Json(invitations.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/invitations/{id}/resend",
+    params(
+        ("id" = Uuid, Path, description = "Invitation identifier")
+    ),
+    responses(
+        (status = 200, description = "New link emailed; the previous one no longer works", body = InvitationDto),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "No pending invitation with this id", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Invitations"
+)]
+pub async fn resend_invitation(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<InvitationDto>> {
+    let result = state.invitation_service().resend(&current_user, id).await;
+    log_result(
+        &state,
+        "invitation.resend",
+        &current_user,
+        Some(id.to_string()),
+        None,
+        &result,
+    );
+
+    result.map(|invitation| Json(invitation.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/invitations/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Invitation identifier")
+    ),
+    responses(
+        (status = 204, description = "Invitation revoked and the pending user deleted"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "No pending invitation with this id", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Invitations"
+)]
+pub async fn revoke_invitation(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let result = state.invitation_service().revoke(&current_user, id).await;
+    let detail = result
+        .as_ref()
+        .ok()
+        .map(|invitation| sanitize_for_logging(&format!("email={}", invitation.email)));
+    log_result(
+        &state,
+        "invitation.revoke",
+        &current_user,
+        Some(id.to_string()),
+        detail,
+        &result,
+    );
+
+    result.map(|_| StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/invitations/{token}/accept",
+    params(
+        ("token" = String, Path, description = "Token from the invitation link")
+    ),
+    request_body = AcceptInvitationDto,
+    responses(
+        (status = 200, description = "Password set and account activated", body = UserResponseDto),
+        (status = 400, description = "Invalid, expired or used invitation, or weak password", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "Invitations"
+)]
+pub async fn accept_invitation(
+    State(state): State<AppState>,
+    ClientIp(source_ip): ClientIp,
+    Path(token): Path<String>,
+    Json(payload): Json<AcceptInvitationDto>,
+) -> AppResult<Json<UserResponseDto>> {
+    let ip = source_ip.map(|ip| ip.to_string());
+
+    match state
+        .invitation_service()
+        .accept(&token, &payload.password)
+        .await
+    {
+        Ok(user) => {
+            state.audit().log(AuditEvent::success(
+                "invitation.accept",
+                AuditActor {
+                    id: Some(user.id()),
+                    email: Some(sanitize_for_logging(user.email().as_str())),
+                    role: None,
+                },
+                AuditTarget::new("user", Some(user.id().to_string())),
+                None,
+                ip,
+            ));
+
+            Ok(Json(user.into()))
+        }
+        Err(err) => {
+            state.audit().log(AuditEvent::failure(
+                "invitation.accept",
+                AuditActor::default(),
+                AuditTarget::new("invitation", None),
+                Some(sanitize_for_logging(&err.to_string())),
+                ip,
+            ));
+
+            Err(err)
+        }
+    }
+}
+
+fn log_result<T>(
+    state: &AppState,
+    action: &str,
+    user: &AuthenticatedUser,
+    invitation_id: Option<String>,
+    detail: Option<String>,
+    result: &AppResult<T>,
+) {
+    let actor = AuditActor {
+        id: Some(user.id),
+        email: Some(sanitize_for_logging(&user.email)),
+        role: Some(user.roles_label()),
+    };
+    let target = AuditTarget::new("invitation", invitation_id);
+
+    let event = match result {
+        Ok(_) => AuditEvent::success(action, actor, target, detail, None),
+        Err(err) => AuditEvent::failure(
+            action,
+            actor,
+            target,
+            Some(sanitize_for_logging(&err.to_string())),
+            None,
+        ),
+    };
+    state.audit().log(event);
+}
@@ -0,0 +1,156 @@
+use axum::{extract::Path, extract::State, Json};
+
+use crate::app::AppState;
+use crate::application::dtos::mfa::{
+    MfaPolicyRequestDto, MfaPolicyResponseDto, RecoveryCodesResponseDto, TotpConfirmRequestDto,
+    TotpEnrollmentResponseDto,
+};
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::presentation::http::auth::extractor::{CurrentUser, MfaEnrollmentUser};
+#[allow(unused_imports)]
+use crate::shared::error::{AppError, AppResult, ErrorResponse};
+use crate::shared::validation::sanitize_for_logging;
+use crate::telemetry::{AuditActor, AuditEvent, AuditTarget};
+
+#[utoipa::path(
+    post,
+    path = "/users/me/mfa/totp",
+    responses(
+        (status = 200, description = "Pending TOTP secret created", body = TotpEnrollmentResponseDto),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 409, description = "MFA already enabled", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "MFA"
+)]
+pub async fn enroll_totp(
+    State(state): State<AppState>,
+    MfaEnrollmentUser(current_user): MfaEnrollmentUser,
+) -> AppResult<Json<TotpEnrollmentResponseDto>> {
+    let result = state
+        .mfa_service()
+        .begin_totp_enrollment(&current_user)
+        .await;
+    log_result(&state, "mfa.totp.enroll", &current_user, None, &result);
+
+    result.map(|enrollment| Json(enrollment.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/me/mfa/totp/confirm",
+    request_body = TotpConfirmRequestDto,
+    responses(
+        (status = 200, description = "TOTP enabled; recovery codes are returned once", body = RecoveryCodesResponseDto),
+        (status = 400, description = "Invalid code", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "No pending enrollment", body = ErrorResponse),
+        (status = 409, description = "MFA already enabled", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "MFA"
+)]
+pub async fn confirm_totp(
+    State(state): State<AppState>,
+    MfaEnrollmentUser(current_user): MfaEnrollmentUser,
+    Json(payload): Json<TotpConfirmRequestDto>,
+) -> AppResult<Json<RecoveryCodesResponseDto>> {
+    let result = state
+        .mfa_service()
+        .confirm_totp_enrollment(current_user.id, &payload.code)
+        .await;
+    log_result(&state, "mfa.totp.confirm", &current_user, None, &result);
+
+    result.map(|recovery_codes| Json(RecoveryCodesResponseDto { recovery_codes }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/mfa/policies",
+    responses(
+        (status = 200, description = "MFA requirement per role", body = [MfaPolicyResponseDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "MFA"
+)]
+pub async fn list_policies(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> AppResult<Json<Vec<MfaPolicyResponseDto>>> {
+    let policies = state.mfa_service().list_policies(&current_user).await?;
+
+    Ok(Json(policies.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/mfa/policies/{role}",
+    request_body = MfaPolicyRequestDto,
+    params(
+        ("role" = String, Path, description = "Role name")
+    ),
+    responses(
+        (status = 200, description = "Policy updated", body = MfaPolicyResponseDto),
+        (status = 400, description = "Invalid role name", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Unknown role", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "MFA"
+)]
+pub async fn set_policy(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(role): Path<String>,
+    Json(payload): Json<MfaPolicyRequestDto>,
+) -> AppResult<Json<MfaPolicyResponseDto>> {
+    let detail = sanitize_for_logging(&format!("role={role} required={}", payload.required));
+
+    let result = state
+        .mfa_service()
+        .set_role_policy(&current_user, &role, payload.required)
+        .await;
+    log_result(
+        &state,
+        "mfa.policy.update",
+        &current_user,
+        Some(detail),
+        &result,
+    );
+
+    result.map(|policy| Json(policy.into()))
+}
+
+fn log_result<T>(
+    state: &AppState,
+    action: &str,
+    user: &AuthenticatedUser,
+    detail: Option<String>,
+    result: &AppResult<T>,
+) {
+    let actor = AuditActor {
+        id: Some(user.id),
+        email: Some(sanitize_for_logging(&user.email)),
+        role: Some(user.roles_label()),
+    };
+    let target = AuditTarget::new("user", Some(user.id.to_string()));
+
+    let event = match result {
+        Ok(_) => AuditEvent::success(action, actor, target, detail, None),
+        Err(err) => AuditEvent::failure(
+            action,
+            actor,
+            target,
+            Some(sanitize_for_logging(&err.to_string())),
+            None,
+        ),
+    };
+    state.audit().log(event);
+}
@@ -1,2 +1,8 @@
 ﻿pub mod auth_controller;
+pub mod invitations_controller;
+pub mod mfa_controller;
+pub mod oauth_controller;
+pub mod personal_access_tokens_controller;
+pub mod roles_controller;
+pub mod sessions_controller;
 pub mod users_controller;
@@ -0,0 +1,651 @@
+use anyhow::anyhow;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::{Form, Json};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Utc;
+use percent_encoding::percent_decode_str;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::application::dtos::oauth::{
+    AuthorizationDecisionDto, AuthorizationRequestDto, CreateOAuthClientDto, CreatedOAuthClientDto,
+    OAuthClientDto, OAuthConsentDto, OAuthErrorDto, OAuthSignInDto, TokenRequestDto,
+    TokenResponseDto,
+};
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::application::services::oauth_service::{
+    append_query, AuthorizationOutcome, BrowserCredentials, BrowserSignIn, BrowserStep,
+    ClientCredentials, OAuthError, OAuthErrorCode,
+};
+use crate::presentation::http::auth::extractor::CurrentUser;
+use crate::presentation::http::client_ip::LoginClient;
+use crate::presentation::http::oauth_pages;
+#[allow(unused_imports)]
+use crate::shared::error::{AppError, AppResult, ErrorResponse};
+use crate::shared::validation::sanitize_for_logging;
+use crate::telemetry::{AuditActor, AuditEvent, AuditTarget};
+
+// Sessao do navegador aberta pelo login da pagina de autorizacao; guarda o access token.
+const SESSION_COOKIE: &str = "webrust_oauth_session";
+
+#[utoipa::path(
+    post,
+    path = "/oauth/clients",
+    request_body = CreateOAuthClientDto,
+    responses(
+        (status = 201, description = "Client registered; the secret is returned only once", body = CreatedOAuthClientDto),
+        (status = 400, description = "Invalid name, type, redirect URI, grant type or scope", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "OAuth"
+)]
+pub async fn create_client(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Json(payload): Json<CreateOAuthClientDto>,
+) -> AppResult<(StatusCode, Json<CreatedOAuthClientDto>)> {
+    let result = state
+        .oauth_service()
+        .register_client(&current_user, payload)
+        .await;
+
+    let (target, detail) = match &result {
+        Ok(issued) => (
+            Some(issued.client.id.to_string()),
+            Some(sanitize_for_logging(&format!(
+                "name={} type={}",
+                issued.client.name, issued.client.client_type
+            ))),
+        ),
+        Err(_) => (None, None),
+    };
+    log_result(
+        &state,
+        "oauth.client.create",
+        &current_user,
+        target,
+        detail,
+        &result,
+    );
+
+    result.map(|issued| (StatusCode::CREATED, Json(issued.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/oauth/clients",
+    responses(
+        (status = 200, description = "Registered clients that are not disabled", body = [OAuthClientDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "OAuth"
+)]
+pub async fn list_clients(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> AppResult<Json<Vec<OAuthClientDto>>> {
+    let clients = state.oauth_service().list_clients(&current_user).await?;
+
+    Ok(Json(clients.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/oauth/clients/{id}",
+    params(("id" = Uuid, Path, description = "Client identifier")),
+    responses(
+        (status = 200, description = "Client found", body = OAuthClientDto),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Client not found or disabled", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "OAuth"
+)]
+pub async fn get_client(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<OAuthClientDto>> {
+    let client = state.oauth_service().get_client(&current_user, id).await?;
+
+    Ok(Json(client.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/oauth/clients/{id}",
+    params(("id" = Uuid, Path, description = "Client identifier")),
+    responses(
+        (status = 204, description = "Client disabled and its sessions revoked"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Client not found or already disabled", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "OAuth"
+)]
+pub async fn disable_client(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let result = state
+        .oauth_service()
+        .disable_client(&current_user, id)
+        .await;
+    log_result(
+        &state,
+        "oauth.client.disable",
+        &current_user,
+        Some(id.to_string()),
+        None,
+        &result,
+    );
+
+    result.map(|()| StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/oauth/authorize",
+    params(AuthorizationRequestDto),
+    responses(
+        (status = 302, description = "Back to the client's redirect_uri with code and state, or with error when the request is invalid",
+            headers(("Location" = String, description = "Registered redirect URI of the client"))),
+        (status = 200, description = "HTML sign-in page when there is no browser session, or the consent page", content_type = "text/html"),
+        (status = 400, description = "Unknown client or unregistered redirect URI", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "OAuth"
+)]
+pub async fn authorize(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(request): Query<AuthorizationRequestDto>,
+) -> AppResult<Response> {
+    let step = state
+        .oauth_service()
+        .authorize_in_browser(session_cookie(&headers).as_deref(), &request)
+        .await?;
+
+    browser_response(&state, "oauth.authorize", &request, step)
+}
+
+#[utoipa::path(
+    post,
+    path = "/oauth/authorize",
+    request_body(content = AuthorizationDecisionDto, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 302, description = "Back to the client with the code, or with access_denied when the user refused",
+            headers(("Location" = String, description = "Registered redirect URI of the client"))),
+        (status = 200, description = "HTML sign-in page when the browser session has ended", content_type = "text/html"),
+        (status = 400, description = "Unknown client or unregistered redirect URI", body = ErrorResponse),
+        (status = 403, description = "The consent form did not come from this browser session", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "OAuth"
+)]
+pub async fn decide(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(payload): Form<AuthorizationDecisionDto>,
+) -> AppResult<Response> {
+    let step = state
+        .oauth_service()
+        .decide_in_browser(
+            session_cookie(&headers).as_deref(),
+            &payload.csrf_token,
+            &payload.request,
+            payload.decision == "approve",
+        )
+        .await?;
+
+    browser_response(&state, "oauth.consent", &payload.request, step)
+}
+
+#[utoipa::path(
+    post,
+    path = "/oauth/login",
+    request_body(content = OAuthSignInDto, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 302, description = "Signed in: sets the browser session cookie and goes back to /oauth/authorize",
+            headers(("Location" = String, description = "The authorization request being signed in for"),
+                ("Set-Cookie" = String, description = "HttpOnly browser session, valid for the access token lifetime"))),
+        (status = 200, description = "HTML page asking for the TOTP code, or the sign-in page with the reason it failed", content_type = "text/html"),
+        (status = 503, description = "Password hashing is saturated; retry after the Retry-After delay", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    tag = "OAuth"
+)]
+pub async fn sign_in(
+    State(state): State<AppState>,
+    LoginClient(client): LoginClient,
+    Form(payload): Form<OAuthSignInDto>,
+) -> AppResult<Response> {
+    let ip = client.ip_address.map(|ip| ip.to_string());
+    let email = payload.email.clone();
+    let credentials = match (payload.challenge_token, payload.code) {
+        (Some(challenge_token), Some(code)) => BrowserCredentials::SecondFactor {
+            challenge_token,
+            code,
+        },
+        _ => BrowserCredentials::Password {
+            email: payload.email.unwrap_or_default(),
+            password: payload.password.unwrap_or_default(),
+        },
+    };
+
+    let result = state
+        .oauth_service()
+        .sign_in_in_browser(credentials, &client)
+        .await;
+    log_sign_in(&state, email.as_deref(), &payload.request, &result, ip);
+
+    match result? {
+        BrowserSignIn::SignedIn {
+            session_token,
+            expires_at,
+        } => {
+            let location = append_query("/oauth/authorize", &payload.request.params());
+            let max_age = (expires_at - Utc::now()).num_seconds().max(0);
+            let cookie = format!(
+                "{SESSION_COOKIE}={session_token}; Max-Age={max_age}; Path=/oauth; HttpOnly; Secure; SameSite=Lax"
+            );
+            Ok((
+                StatusCode::FOUND,
+                [
+                    (header::LOCATION, header_value(&location)?),
+                    (header::SET_COOKIE, header_value(&cookie)?),
+                ],
+            )
+                .into_response())
+        }
+        BrowserSignIn::Retry(step) => {
+            browser_response(&state, "oauth.login", &payload.request, step)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/oauth/token",
+    request_body(content = TokenRequestDto, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Tokens issued", body = TokenResponseDto),
+        (status = 400, description = "OAuth error such as invalid_grant or invalid_scope", body = OAuthErrorDto),
+        (status = 401, description = "Client authentication failed (invalid_client)", body = OAuthErrorDto),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security((), ("clientBasicAuth" = [])),
+    tag = "OAuth"
+)]
+pub async fn token(
+    State(state): State<AppState>,
+    LoginClient(client): LoginClient,
+    headers: HeaderMap,
+    Form(request): Form<TokenRequestDto>,
+) -> Response {
+    let grant_type = request.grant_type.clone().unwrap_or_default();
+    let ip = client.ip_address.map(|ip| ip.to_string());
+
+    let result = match basic_credentials(&headers) {
+        Ok(credentials) => {
+            let client_id = credentials
+                .as_ref()
+                .map(|credentials| credentials.client_id.clone())
+                .or_else(|| request.client_id.clone());
+            let result = state
+                .oauth_service()
+                .token(request, credentials, &client)
+                .await;
+            log_token(&state, client_id, &grant_type, &result, ip);
+            result
+        }
+        Err(err) => Err(err),
+    };
+
+    let mut response = match result {
+        Ok(issued) => Json(TokenResponseDto::from(issued)).into_response(),
+        Err(err) => err.into_response(),
+    };
+    // RFC 6749, secao 5.1: respostas com tokens nunca vao para cache.
+    response
+        .headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    response
+        .headers_mut()
+        .insert(header::PRAGMA, HeaderValue::from_static("no-cache"));
+    response
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/me/oauth/consents",
+    responses(
+        (status = 200, description = "Clients the user has authorized, most recently updated first", body = [OAuthConsentDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Requires an interactive session", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "OAuth"
+)]
+pub async fn list_my_consents(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> AppResult<Json<Vec<OAuthConsentDto>>> {
+    let consents = state.oauth_service().list_consents(&current_user).await?;
+
+    Ok(Json(consents.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/me/oauth/consents/{client_id}",
+    params(("client_id" = Uuid, Path, description = "Client identifier")),
+    responses(
+        (status = 204, description = "Consent removed and the client's sessions for the user revoked"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Requires an interactive session", body = ErrorResponse),
+        (status = 404, description = "No consent for this client", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "OAuth"
+)]
+pub async fn revoke_my_consent(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(client_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let result = state
+        .oauth_service()
+        .revoke_consent(&current_user, client_id)
+        .await;
+    log_result(
+        &state,
+        "oauth.consent.revoke",
+        &current_user,
+        Some(client_id.to_string()),
+        None,
+        &result,
+    );
+
+    result.map(|()| StatusCode::NO_CONTENT)
+}
+
+// Corpo de erro da RFC 6749 (secao 5.2). `invalid_client` responde 401 com o desafio Basic,
+// como pede a especificacao.
+impl IntoResponse for OAuthError {
+    fn into_response(self) -> Response {
+        let (code, description) = match self {
+            OAuthError::Protocol { code, description } => (code, description),
+            OAuthError::Server(err) => return err.into_response(),
+        };
+        tracing::warn!(
+            error = code.as_str(),
+            detail = description.as_str(),
+            "oauth error"
+        );
+
+        let body = Json(OAuthErrorDto {
+            error: code.as_str().to_string(),
+            error_description: description,
+        });
+        match code {
+            OAuthErrorCode::InvalidClient => (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Basic realm=\"oauth\"")],
+                body,
+            )
+                .into_response(),
+            _ => (StatusCode::BAD_REQUEST, body).into_response(),
+        }
+    }
+}
+
+// `client_id` e `client_secret` vao codificados como formulario antes do base64
+// (RFC 6749, secao 2.3.1).
+fn basic_credentials(headers: &HeaderMap) -> Result<Option<ClientCredentials>, OAuthError> {
+    let Some(value) = headers.get(header::AUTHORIZATION) else {
+        return Ok(None);
+    };
+    let invalid = || OAuthError::Protocol {
+        code: OAuthErrorCode::InvalidClient,
+        description: "malformed Basic credentials".to_string(),
+    };
+
+    let encoded = value
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Basic "))
+        .ok_or_else(invalid)?;
+    let decoded = STANDARD
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(invalid)?;
+    let (client_id, client_secret) = decoded.split_once(':').ok_or_else(invalid)?;
+
+    let decode = |value: &str| {
+        percent_decode_str(&value.replace('+', " "))
+            .decode_utf8()
+            .map(|value| value.into_owned())
+            .map_err(|_| invalid())
+    };
+    Ok(Some(ClientCredentials {
+        client_id: decode(client_id)?,
+        client_secret: decode(client_secret)?,
+    }))
+}
+
+// Cookie `SameSite=Lax`: volta na navegacao de topo vinda do cliente (o GET da autorizacao),
+// mas nao em POSTs de outros sites.
+fn session_cookie(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, value)| value.to_string())
+}
+
+fn browser_response(
+    state: &AppState,
+    action: &str,
+    request: &AuthorizationRequestDto,
+    step: BrowserStep,
+) -> AppResult<Response> {
+    let (user, outcome, csrf_token) = match step {
+        BrowserStep::SignIn { error } => {
+            return Ok(oauth_pages::sign_in(request, error.as_deref()))
+        }
+        BrowserStep::SecondFactor {
+            challenge_token,
+            error,
+        } => {
+            return Ok(oauth_pages::second_factor(
+                request,
+                &challenge_token,
+                error.as_deref(),
+            ))
+        }
+        BrowserStep::Authorized {
+            user,
+            outcome,
+            csrf_token,
+        } => (user, outcome, csrf_token),
+    };
+    log_authorization(state, action, &user, request, &outcome);
+
+    match *outcome {
+        AuthorizationOutcome::Approved { redirect_to, .. }
+        | AuthorizationOutcome::Rejected { redirect_to, .. } => Ok((
+            StatusCode::FOUND,
+            [(header::LOCATION, header_value(&redirect_to)?)],
+        )
+            .into_response()),
+        AuthorizationOutcome::ConsentRequired { client, scopes } => {
+            Ok(oauth_pages::consent(request, &client, &scopes, &csrf_token))
+        }
+    }
+}
+
+fn header_value(value: &str) -> AppResult<HeaderValue> {
+    HeaderValue::from_str(value)
+        .map_err(|err| AppError::Unexpected(anyhow!("invalid header value: {err}")))
+}
+
+fn log_authorization(
+    state: &AppState,
+    action: &str,
+    user: &AuthenticatedUser,
+    request: &AuthorizationRequestDto,
+    outcome: &AuthorizationOutcome,
+) {
+    let detail = match outcome {
+        AuthorizationOutcome::Approved { .. } => "approved".to_string(),
+        AuthorizationOutcome::Rejected { error, .. } => format!("rejected error={error}"),
+        AuthorizationOutcome::ConsentRequired { .. } => "consent_required".to_string(),
+    };
+    let actor = AuditActor {
+        id: Some(user.id),
+        email: Some(sanitize_for_logging(&user.email)),
+        role: Some(user.roles_label()),
+    };
+    state.audit().log(AuditEvent::success(
+        action,
+        actor,
+        AuditTarget::new(
+            "oauth_client",
+            Some(sanitize_for_logging(&request.client_id)),
+        ),
+        Some(detail),
+        None,
+    ));
+}
+
+fn log_sign_in(
+    state: &AppState,
+    email: Option<&str>,
+    request: &AuthorizationRequestDto,
+    result: &AppResult<BrowserSignIn>,
+    ip: Option<String>,
+) {
+    let actor = AuditActor {
+        id: None,
+        email: email.map(sanitize_for_logging),
+        role: None,
+    };
+    let target = AuditTarget::new(
+        "oauth_client",
+        Some(sanitize_for_logging(&request.client_id)),
+    );
+
+    let event = match result {
+        Ok(BrowserSignIn::SignedIn { .. }) => {
+            AuditEvent::success("oauth.login", actor, target, None, ip)
+        }
+        Ok(BrowserSignIn::Retry(BrowserStep::SecondFactor { error: None, .. })) => {
+            AuditEvent::success(
+                "oauth.login",
+                actor,
+                target,
+                Some("mfa_required".to_string()),
+                ip,
+            )
+        }
+        Ok(BrowserSignIn::Retry(
+            BrowserStep::SignIn { error } | BrowserStep::SecondFactor { error, .. },
+        )) => AuditEvent::failure(
+            "oauth.login",
+            actor,
+            target,
+            error.as_deref().map(sanitize_for_logging),
+            ip,
+        ),
+        Ok(BrowserSignIn::Retry(BrowserStep::Authorized { .. })) => return,
+        Err(err) => AuditEvent::failure(
+            "oauth.login",
+            actor,
+            target,
+            Some(sanitize_for_logging(&err.to_string())),
+            ip,
+        ),
+    };
+    state.audit().log(event);
+}
+
+fn log_token<T>(
+    state: &AppState,
+    client_id: Option<String>,
+    grant_type: &str,
+    result: &Result<T, OAuthError>,
+    ip: Option<String>,
+) {
+    let target = AuditTarget::new(
+        "oauth_client",
+        client_id.map(|id| sanitize_for_logging(&id)),
+    );
+    let detail = sanitize_for_logging(&format!("grant_type={grant_type}"));
+
+    let event = match result {
+        Ok(_) => AuditEvent::success(
+            "oauth.token",
+            AuditActor::default(),
+            target,
+            Some(detail),
+            ip,
+        ),
+        Err(err) => AuditEvent::failure(
+            "oauth.token",
+            AuditActor::default(),
+            target,
+            Some(sanitize_for_logging(&format!("{detail} {err}"))),
+            ip,
+        ),
+    };
+    state.audit().log(event);
+}
+
+fn log_result<T>(
+    state: &AppState,
+    action: &str,
+    user: &AuthenticatedUser,
+    client_id: Option<String>,
+    detail: Option<String>,
+    result: &AppResult<T>,
+) {
+    let actor = AuditActor {
+        id: Some(user.id),
+        email: Some(sanitize_for_logging(&user.email)),
+        role: Some(user.roles_label()),
+    };
+    let target = AuditTarget::new("oauth_client", client_id);
+
+    let event = match result {
+        Ok(_) => AuditEvent::success(action, actor, target, detail, None),
+        Err(err) => AuditEvent::failure(
+            action,
+            actor,
+            target,
+            Some(sanitize_for_logging(&err.to_string())),
+            None,
+        ),
+    };
+    state.audit().log(event);
+}
@@ -0,0 +1,155 @@
+use axum::{extract::Path, extract::State, http::StatusCode, Json};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::application::dtos::personal_access_token::{
+    CreatePersonalAccessTokenDto, CreatedPersonalAccessTokenDto, PersonalAccessTokenDto,
+};
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::presentation::http::auth::extractor::CurrentUser;
+#[allow(unused_imports)]
+use crate::shared::error::{AppError, AppResult, ErrorResponse};
+use crate::shared::validation::sanitize_for_logging;
+use crate::telemetry::{AuditActor, AuditEvent, AuditTarget};
+
+#[utoipa::path(
+    post,
+    path = "/users/me/tokens",
+    request_body = CreatePersonalAccessTokenDto,
+    responses(
+        (status = 201, description = "Token created; the secret is returned only once", body = CreatedPersonalAccessTokenDto),
+        (status = 400, description = "Invalid name, scope or lifetime", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot create tokens", body = ErrorResponse),
+        (status = 409, description = "A token with this name already exists", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Tokens"
+)]
+pub async fn create_token(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Json(payload): Json<CreatePersonalAccessTokenDto>,
+) -> AppResult<(StatusCode, Json<CreatedPersonalAccessTokenDto>)> {
+    let result = state
+        .personal_access_token_service()
+        .create(
+            &current_user,
+            &payload.name,
+            &payload.scopes,
+            payload.expires_in_days,
+        )
+        .await;
+
+    let (target, detail) = match &result {
+        Ok(issued) => (
+            Some(issued.record.id.to_string()),
+            Some(sanitize_for_logging(&format!(
+                "name={} scopes={}",
+                issued.record.name,
+                payload.scopes.join(",")
+            ))),
+        ),
+        Err(_) => (None, None),
+    };
+    log_result(
+        &state,
+        "token.create",
+        &current_user,
+        target,
+        detail,
+        &result,
+    );
+
+    result.map(|issued| (StatusCode::CREATED, Json(issued.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/me/tokens",
+    responses(
+        (status = 200, description = "Active and expired tokens that were not revoked", body = [PersonalAccessTokenDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot list tokens", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Tokens"
+)]
+pub async fn list_tokens(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> AppResult<Json<Vec<PersonalAccessTokenDto>>> {
+    let tokens = state
+        .personal_access_token_service()
+        .list(&current_user)
+        .await?;
+
+    Ok(Json(tokens.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/me/tokens/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Token identifier")
+    ),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot revoke tokens", body = ErrorResponse),
+        (status = 404, description = "Token not found", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Tokens"
+)]
+pub async fn revoke_token(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let result = state
+        .personal_access_token_service()
+        .revoke(&current_user, id)
+        .await;
+    log_result(
+        &state,
+        "token.revoke",
+        &current_user,
+        Some(id.to_string()),
+        None,
+        &result,
+    );
+
+    result.map(|()| StatusCode::NO_CONTENT)
+}
+
+fn log_result<T>(
+    state: &AppState,
+    action: &str,
+    user: &AuthenticatedUser,
+    token_id: Option<String>,
+    detail: Option<String>,
+    result: &AppResult<T>,
+) {
+    let actor = AuditActor {
+        id: Some(user.id),
+        email: Some(sanitize_for_logging(&user.email)),
+        role: Some(user.roles_label()),
+    };
+    let target = AuditTarget::new("personal_access_token", token_id);
+
+    let event = match result {
+        Ok(_) => AuditEvent::success(action, actor, target, detail, None),
+        Err(err) => AuditEvent::failure(
+            action,
+            actor,
+            target,
+            Some(sanitize_for_logging(&err.to_string())),
+            None,
+        ),
+    };
+    state.audit().log(event);
+}
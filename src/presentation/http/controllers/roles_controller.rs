@@ -0,0 +1,204 @@
+use axum::{extract::Path, extract::State, http::StatusCode, Json};
+
+use crate::app::AppState;
+use crate::application::dtos::role::{
+    CreateRoleDto, PermissionDto, RoleResponseDto, UpdateRoleDto,
+};
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::domain::entities::permission::Permission;
+use crate::presentation::http::auth::extractor::CurrentUser;
+#[allow(unused_imports)]
+use crate::shared::error::{AppError, AppResult, ErrorResponse};
+use crate::shared::validation::sanitize_for_logging;
+use crate::telemetry::{AuditActor, AuditEvent, AuditTarget};
+
+#[utoipa::path(
+    get,
+    path = "/permissions",
+    responses(
+        (status = 200, description = "Permissions that roles can grant", body = [PermissionDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Roles"
+)]
+pub async fn list_permissions(CurrentUser(_current_user): CurrentUser) -> Json<Vec<PermissionDto>> {
+    Json(Permission::ALL.into_iter().map(Into::into).collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/roles",
+    responses(
+        (status = 200, description = "All roles", body = [RoleResponseDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Roles"
+)]
+pub async fn list_roles(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> AppResult<Json<Vec<RoleResponseDto>>> {
+    let roles = state.role_service().list_roles(&current_user).await?;
+    Ok(Json(roles))
+}
+
+#[utoipa::path(
+    get,
+    path = "/roles/{name}",
+    params(
+        ("name" = String, Path, description = "Role name")
+    ),
+    responses(
+        (status = 200, description = "Role found", body = RoleResponseDto),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Roles"
+)]
+pub async fn get_role(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(name): Path<String>,
+) -> AppResult<Json<RoleResponseDto>> {
+    let role = state.role_service().get_role(&current_user, &name).await?;
+    Ok(Json(role))
+}
+
+#[utoipa::path(
+    post,
+    path = "/roles",
+    request_body = CreateRoleDto,
+    responses(
+        (status = 201, description = "Role created", body = RoleResponseDto),
+        (status = 400, description = "Invalid name or permission", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden or granting a permission the caller lacks", body = ErrorResponse),
+        (status = 409, description = "A role with this name already exists", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Roles"
+)]
+pub async fn create_role(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Json(payload): Json<CreateRoleDto>,
+) -> AppResult<(StatusCode, Json<RoleResponseDto>)> {
+    let name = payload.name.clone();
+    let detail = sanitize_for_logging(&format!("permissions={}", payload.permissions.join(",")));
+    let result = state
+        .role_service()
+        .create_role(&current_user, payload)
+        .await;
+    log_result(
+        &state,
+        "role.create",
+        &current_user,
+        &name,
+        Some(detail),
+        &result,
+    );
+
+    result.map(|role| (StatusCode::CREATED, Json(role)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/roles/{name}",
+    request_body = UpdateRoleDto,
+    params(
+        ("name" = String, Path, description = "Role name")
+    ),
+    responses(
+        (status = 200, description = "Role updated", body = RoleResponseDto),
+        (status = 400, description = "Invalid permission", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden or granting a permission the caller lacks", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 409, description = "Built-in roles cannot be changed", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Roles"
+)]
+pub async fn update_role(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(name): Path<String>,
+    Json(payload): Json<UpdateRoleDto>,
+) -> AppResult<Json<RoleResponseDto>> {
+    let detail = payload
+        .permissions
+        .as_ref()
+        .map(|permissions| sanitize_for_logging(&format!("permissions={}", permissions.join(","))));
+    let result = state
+        .role_service()
+        .update_role(&current_user, &name, payload)
+        .await;
+    log_result(&state, "role.update", &current_user, &name, detail, &result);
+
+    result.map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/roles/{name}",
+    params(
+        ("name" = String, Path, description = "Role name")
+    ),
+    responses(
+        (status = 204, description = "Role deleted"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 409, description = "Built-in role or role still assigned to users", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Roles"
+)]
+pub async fn delete_role(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(name): Path<String>,
+) -> AppResult<StatusCode> {
+    let result = state.role_service().delete_role(&current_user, &name).await;
+    log_result(&state, "role.delete", &current_user, &name, None, &result);
+
+    result.map(|()| StatusCode::NO_CONTENT)
+}
+
+fn log_result<T>(
+    state: &AppState,
+    action: &str,
+    user: &AuthenticatedUser,
+    role: &str,
+    detail: Option<String>,
+    result: &AppResult<T>,
+) {
+    let actor = AuditActor {
+        id: Some(user.id),
+        email: Some(sanitize_for_logging(&user.email)),
+        role: Some(user.roles_label()),
+    };
+    let target = AuditTarget::new("role", Some(sanitize_for_logging(role)));
+
+    let event = match result {
+        Ok(_) => AuditEvent::success(action, actor, target, detail, None),
+        Err(err) => AuditEvent::failure(
+            action,
+            actor,
+            target,
+            Some(sanitize_for_logging(&err.to_string())),
+            None,
+        ),
+    };
+    state.audit().log(event);
+}
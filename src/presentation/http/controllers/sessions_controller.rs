@@ -0,0 +1,176 @@
+use axum::{extract::Path, extract::State, http::StatusCode, Json};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::application::dtos::session::SessionDto;
+use crate::application::services::auth_service::AuthenticatedUser;
+use crate::presentation::http::auth::extractor::CurrentUser;
+#[allow(unused_imports)]
+use crate::shared::error::{AppError, AppResult, ErrorResponse};
+use crate::shared::validation::sanitize_for_logging;
+use crate::telemetry::{AuditActor, AuditEvent, AuditTarget};
+
+#[utoipa::path(
+    get,
+    path = "/users/me/sessions",
+    responses(
+        (status = 200, description = "Active sessions, most recently used first", body = [SessionDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot list sessions", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Sessions"
+)]
+pub async fn list_my_sessions(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> AppResult<Json<Vec<SessionDto>>> {
+    let sessions = state.auth_service().sessions().list(&current_user).await?;
+
+    Ok(Json(
+        sessions
+            .into_iter()
+            .map(|session| SessionDto::new(session, current_user.session_id))
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/me/sessions/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Session identifier")
+    ),
+    responses(
+        (status = 204, description = "Session and its refresh token revoked"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot revoke sessions", body = ErrorResponse),
+        (status = 404, description = "Session not found or already revoked", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Sessions"
+)]
+pub async fn revoke_my_session(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let result = state
+        .auth_service()
+        .sessions()
+        .revoke(&current_user, id)
+        .await;
+    log_result(
+        &state,
+        "session.revoke",
+        &current_user,
+        current_user.id,
+        id,
+        &result,
+    );
+
+    result.map(|()| StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{id}/sessions",
+    params(("id" = Uuid, Path, description = "User identifier")),
+    responses(
+        (status = 200, description = "Active sessions of the user, most recently used first", body = [SessionDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Sessions"
+)]
+pub async fn list_user_sessions(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<Vec<SessionDto>>> {
+    let sessions = state
+        .auth_service()
+        .sessions()
+        .list_for_user(&current_user, id)
+        .await?;
+
+    Ok(Json(
+        sessions
+            .into_iter()
+            .map(|session| SessionDto::new(session, current_user.session_id))
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{id}/sessions/{session_id}",
+    params(
+        ("id" = Uuid, Path, description = "User identifier"),
+        ("session_id" = Uuid, Path, description = "Session identifier")
+    ),
+    responses(
+        (status = 204, description = "Session and its refresh token revoked"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "User or session not found", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Sessions"
+)]
+pub async fn revoke_user_session(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path((id, session_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<StatusCode> {
+    let result = state
+        .auth_service()
+        .sessions()
+        .revoke_for_user(&current_user, id, session_id)
+        .await;
+    log_result(
+        &state,
+        "user.session.revoke",
+        &current_user,
+        id,
+        session_id,
+        &result,
+    );
+
+    result.map(|()| StatusCode::NO_CONTENT)
+}
+
+fn log_result<T>(
+    state: &AppState,
+    action: &str,
+    user: &AuthenticatedUser,
+    owner_id: Uuid,
+    session_id: Uuid,
+    result: &AppResult<T>,
+) {
+    let actor = AuditActor {
+        id: Some(user.id),
+        email: Some(sanitize_for_logging(&user.email)),
+        role: Some(user.roles_label()),
+    };
+    let target = AuditTarget::new("session", Some(session_id.to_string()));
+    let detail = format!("user_id={owner_id}");
+
+    let event = match result {
+        Ok(_) => AuditEvent::success(action, actor, target, Some(detail), None),
+        Err(err) => AuditEvent::failure(
+            action,
+            actor,
+            target,
+            Some(sanitize_for_logging(&format!("{detail} {err}"))),
+            None,
+        ),
+    };
+    state.audit().log(event);
+}
@@ -1,12 +1,23 @@
 use std::time::Instant;
 
-use axum::{extract::Path, extract::State, http::StatusCode, Json};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
 use uuid::Uuid;
 
 use crate::app::AppState;
-use crate::application::dtos::user::{CreateUserDto, UpdateUserDto, UserResponseDto};
+use crate::application::dtos::auth::LoginResponseDto;
+use crate::application::dtos::registration::RegistrationDto;
+use crate::application::dtos::user::{
+    ChangeEmailDto, ChangePasswordDto, ChangeUserStatusDto, CreateUserDto, ListUsersQueryDto,
+    UpdateProfileDto, UpdateUserDto, UserPageDto, UserResponseDto,
+};
 use crate::application::services::auth_service::AuthenticatedUser;
-use crate::presentation::http::auth::extractor::CurrentUser;
+use crate::domain::entities::user::UserStatus;
+use crate::presentation::http::auth::extractor::{CurrentUser, PasswordChangeUser};
+use crate::presentation::http::client_ip::LoginClient;
+use crate::presentation::http::etag;
 #[allow(unused_imports)]
 use crate::shared::error::{AppError, AppResult, ErrorResponse};
 use crate::shared::validation::sanitize_for_logging;
@@ -17,6 +28,16 @@ const OP_LIST: &str = "list";
 const OP_GET: &str = "get";
 const OP_UPDATE: &str = "update";
 const OP_DELETE: &str = "delete";
+const OP_RESTORE: &str = "restore";
+const OP_SUSPEND: &str = "suspend";
+const OP_LOCK: &str = "lock";
+const OP_REACTIVATE: &str = "reactivate";
+const OP_UPDATE_PROFILE: &str = "update_profile";
+const OP_CHANGE_PASSWORD: &str = "change_password";
+const OP_CHANGE_EMAIL: &str = "change_email";
+const OP_RESEND_VERIFICATION: &str = "resend_email_verification";
+const OP_APPROVE: &str = "approve";
+const OP_REJECT: &str = "reject";
 const OUTCOME_SUCCESS: &str = "success";
 const OUTCOME_ERROR: &str = "error";
 
@@ -32,7 +53,7 @@ const OUTCOME_ERROR: &str = "error";
         (status = 409, description = "Conflict", body = ErrorResponse),
         (status = 500, description = "Unexpected error", body = ErrorResponse)
     ),
-    security(("bearerAuth" = [])),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
     tag = "Users"
 )]
 pub async fn create_user(
@@ -86,21 +107,24 @@ pub async fn create_user(
 #[utoipa::path(
     get,
     path = "/users",
+    params(ListUsersQueryDto),
     responses(
-        (status = 200, description = "List users", body = [UserResponseDto]),
+        (status = 200, description = "Page of users", body = UserPageDto),
+        (status = 400, description = "Invalid filter, sort or cursor", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 500, description = "Unexpected error", body = ErrorResponse)
     ),
-    security(("bearerAuth" = [])),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
     tag = "Users"
 )]
 pub async fn list_users(
     State(state): State<AppState>,
     CurrentUser(current_user): CurrentUser,
-) -> Result<Json<Vec<UserResponseDto>>, AppError> {
+    Query(query): Query<ListUsersQueryDto>,
+) -> Result<Json<UserPageDto>, AppError> {
     let started = Instant::now();
 
-    match state.user_service().list_users(&current_user).await {
+    match state.user_service().list_users(&current_user, query).await {
         Ok(users) => {
             state.metrics().record_user_operation(
                 OP_LIST,
@@ -121,22 +145,28 @@ pub async fn list_users(
 #[utoipa::path(
     get,
     path = "/users/{id}",
-    params(("id" = uuid::Uuid, Path, description = "user.id()entifier")),
+    params(
+        ("id" = uuid::Uuid, Path, description = "User identifier"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous read")
+    ),
     responses(
-        (status = 200, description = "User detail", body = UserResponseDto),
+        (status = 200, description = "User detail", body = UserResponseDto,
+            headers(("ETag" = String, description = "Current version of the user"))),
+        (status = 304, description = "User unchanged since the given ETag"),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Forbidden", body = ErrorResponse),
         (status = 404, description = "User not found", body = ErrorResponse),
         (status = 500, description = "Unexpected error", body = ErrorResponse)
     ),
-    security(("bearerAuth" = [])),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
     tag = "Users"
 )]
 pub async fn get_user(
     State(state): State<AppState>,
     CurrentUser(current_user): CurrentUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<UserResponseDto>, AppError> {
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
     let started = Instant::now();
 
     match state.user_service().get_user(&current_user, id).await {
@@ -144,7 +174,11 @@ pub async fn get_user(
             state
                 .metrics()
                 .record_user_operation(OP_GET, OUTCOME_SUCCESS, Some(started.elapsed()));
-            Ok(Json(user))
+            let tag = etag::from_version(user.version());
+            if etag::if_none_match(&headers, user.version()) {
+                return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, tag)]).into_response());
+            }
+            Ok(([(header::ETAG, tag)], Json(user)).into_response())
         }
         Err(err) => {
             state
@@ -159,32 +193,44 @@ pub async fn get_user(
     put,
     path = "/users/{id}",
     request_body = UpdateUserDto,
-    params(("id" = uuid::Uuid, Path, description = "user.id()entifier")),
+    params(
+        ("id" = uuid::Uuid, Path, description = "User identifier"),
+        ("If-Match" = Option<String>, Header, description = "Only update if the user still has this ETag")
+    ),
     responses(
-        (status = 200, description = "User updated", body = UserResponseDto),
+        (status = 200, description = "User updated", body = UserResponseDto,
+            headers(("ETag" = String, description = "New version of the user"))),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Forbidden", body = ErrorResponse),
         (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 412, description = "User changed since the given ETag", body = ErrorResponse),
         (status = 500, description = "Unexpected error", body = ErrorResponse)
     ),
-    security(("bearerAuth" = [])),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
     tag = "Users"
 )]
 pub async fn update_user(
     State(state): State<AppState>,
     CurrentUser(current_user): CurrentUser,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<UpdateUserDto>,
-) -> Result<Json<UserResponseDto>, AppError> {
+) -> Result<Response, AppError> {
     let actor = audit_actor(&current_user);
     let started = Instant::now();
 
-    match state
-        .user_service()
-        .update_user(&current_user, id, payload)
-        .await
-    {
+    let result = match etag::if_match(&headers) {
+        Ok(expected_versions) => {
+            state
+                .user_service()
+                .update_user(&current_user, id, payload, expected_versions)
+                .await
+        }
+        Err(err) => Err(err),
+    };
+
+    match result {
         Ok(user) => {
             state.metrics().record_user_operation(
                 OP_UPDATE,
@@ -198,7 +244,8 @@ pub async fn update_user(
                 None,
                 None,
             ));
-            Ok(Json(user))
+            let tag = etag::from_version(user.version());
+            Ok(([(header::ETAG, tag)], Json(user)).into_response())
         }
         Err(err) => {
             state.metrics().record_user_operation(
@@ -222,26 +269,41 @@ pub async fn update_user(
 #[utoipa::path(
     delete,
     path = "/users/{id}",
-    params(("id" = uuid::Uuid, Path, description = "user.id()entifier")),
+    params(
+        ("id" = uuid::Uuid, Path, description = "User identifier"),
+        ("If-Match" = Option<String>, Header, description = "Only delete if the user still has this ETag")
+    ),
     responses(
-        (status = 204, description = "User deleted"),
+        (status = 204, description = "User soft-deleted; restorable until the retention period ends"),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Forbidden", body = ErrorResponse),
         (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 412, description = "User changed since the given ETag", body = ErrorResponse),
         (status = 500, description = "Unexpected error", body = ErrorResponse)
     ),
-    security(("bearerAuth" = [])),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
     tag = "Users"
 )]
 pub async fn delete_user(
     State(state): State<AppState>,
     CurrentUser(current_user): CurrentUser,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
 ) -> AppResult<StatusCode> {
     let actor = audit_actor(&current_user);
     let started = Instant::now();
 
-    match state.user_service().delete_user(&current_user, id).await {
+    let result = match etag::if_match(&headers) {
+        Ok(expected_versions) => {
+            state
+                .user_service()
+                .delete_user(&current_user, id, expected_versions)
+                .await
+        }
+        Err(err) => Err(err),
+    };
+
+    match result {
         Ok(()) => {
             state.metrics().record_user_operation(
                 OP_DELETE,
@@ -276,10 +338,714 @@ pub async fn delete_user(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/users/{id}/restore",
+    params(("id" = uuid::Uuid, Path, description = "User identifier")),
+    responses(
+        (status = 200, description = "User restored", body = UserResponseDto),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "No soft-deleted user with this id", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn restore_user(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UserResponseDto>, AppError> {
+    let actor = audit_actor(&current_user);
+    let started = Instant::now();
+
+    match state.user_service().restore_user(&current_user, id).await {
+        Ok(user) => {
+            state.metrics().record_user_operation(
+                OP_RESTORE,
+                OUTCOME_SUCCESS,
+                Some(started.elapsed()),
+            );
+            state.audit().log(AuditEvent::success(
+                "user.restore",
+                actor,
+                AuditTarget::new("user", Some(id.to_string())),
+                None,
+                None,
+            ));
+            Ok(Json(user))
+        }
+        Err(err) => {
+            state.metrics().record_user_operation(
+                OP_RESTORE,
+                OUTCOME_ERROR,
+                Some(started.elapsed()),
+            );
+            let detail = sanitize_for_logging(&err.to_string());
+            state.audit().log(AuditEvent::failure(
+                "user.restore",
+                actor,
+                AuditTarget::new("user", Some(id.to_string())),
+                Some(detail),
+                None,
+            ));
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{id}/sessions",
+    params(("id" = uuid::Uuid, Path, description = "User identifier")),
+    responses(
+        (status = 204, description = "All sessions of the user revoked"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn revoke_user_sessions(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let actor = audit_actor(&current_user);
+
+    match state
+        .auth_service()
+        .revoke_all_sessions(&current_user, id)
+        .await
+    {
+        Ok(()) => {
+            state.audit().log(AuditEvent::success(
+                "user.sessions.revoke",
+                actor,
+                AuditTarget::new("user", Some(id.to_string())),
+                None,
+                None,
+            ));
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(err) => {
+            let detail = sanitize_for_logging(&err.to_string());
+            state.audit().log(AuditEvent::failure(
+                "user.sessions.revoke",
+                actor,
+                AuditTarget::new("user", Some(id.to_string())),
+                Some(detail),
+                None,
+            ));
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/unlock",
+    params(("id" = uuid::Uuid, Path, description = "User identifier")),
+    responses(
+        (status = 204, description = "Failed login counter cleared for the account"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn unlock_user(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let actor = audit_actor(&current_user);
+
+    match state.auth_service().unlock_account(&current_user, id).await {
+        Ok(()) => {
+            state.audit().log(AuditEvent::success(
+                "user.unlock",
+                actor,
+                AuditTarget::new("user", Some(id.to_string())),
+                None,
+                None,
+            ));
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(err) => {
+            let detail = sanitize_for_logging(&err.to_string());
+            state.audit().log(AuditEvent::failure(
+                "user.unlock",
+                actor,
+                AuditTarget::new("user", Some(id.to_string())),
+                Some(detail),
+                None,
+            ));
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/me",
+    params(
+        ("If-None-Match" = Option<String>, Header, description = "Answer 304 if the profile still has this ETag")
+    ),
+    responses(
+        (status = 200, description = "Profile of the authenticated user", body = UserResponseDto,
+            headers(("ETag" = String, description = "Current version of the user"))),
+        (status = 304, description = "Profile unchanged since the given ETag"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn get_profile(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let user = state.user_service().get_profile(&current_user).await?;
+    let tag = etag::from_version(user.version());
+    if etag::if_none_match(&headers, user.version()) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, tag)]).into_response());
+    }
+    Ok(([(header::ETAG, tag)], Json(user)).into_response())
+}
+
+#[utoipa::path(
+    patch,
+    path = "/users/me",
+    request_body = UpdateProfileDto,
+    params(
+        ("If-Match" = Option<String>, Header, description = "Only update if the profile still has this ETag")
+    ),
+    responses(
+        (status = 200, description = "Profile updated", body = UserResponseDto,
+            headers(("ETag" = String, description = "New version of the user"))),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 412, description = "Profile changed since the given ETag", body = ErrorResponse),
+        (status = 422, description = "Unknown field, e.g. `email` or `roles`"),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn update_profile(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    headers: HeaderMap,
+    Json(payload): Json<UpdateProfileDto>,
+) -> Result<Response, AppError> {
+    let actor = audit_actor(&current_user);
+    let started = Instant::now();
+
+    let result = match etag::if_match(&headers) {
+        Ok(expected_versions) => {
+            state
+                .user_service()
+                .update_profile(&current_user, payload, expected_versions)
+                .await
+        }
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(user) => {
+            state.metrics().record_user_operation(
+                OP_UPDATE_PROFILE,
+                OUTCOME_SUCCESS,
+                Some(started.elapsed()),
+            );
+            state.audit().log(AuditEvent::success(
+                "user.profile.update",
+                actor,
+                AuditTarget::new("user", Some(user.id().to_string())),
+                None,
+                None,
+            ));
+            let tag = etag::from_version(user.version());
+            Ok(([(header::ETAG, tag)], Json(user)).into_response())
+        }
+        Err(err) => {
+            state.metrics().record_user_operation(
+                OP_UPDATE_PROFILE,
+                OUTCOME_ERROR,
+                Some(started.elapsed()),
+            );
+            let detail = sanitize_for_logging(&err.to_string());
+            state.audit().log(AuditEvent::failure(
+                "user.profile.update",
+                actor,
+                AuditTarget::new("user", Some(current_user.id().to_string())),
+                Some(detail),
+                None,
+            ));
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/me/password",
+    request_body = ChangePasswordDto,
+    responses(
+        (status = 200, description = "Password changed; every other session was revoked and a new one is returned", body = LoginResponseDto),
+        (status = 400, description = "Validation error, including a recently used password", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Current password is incorrect or the token is not an interactive session", body = ErrorResponse),
+        (status = 429, description = "Too many wrong current passwords", body = ErrorResponse),
+        (status = 503, description = "Password hashing is saturated; retry after the Retry-After delay", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Users"
+)]
+pub async fn change_password(
+    State(state): State<AppState>,
+    PasswordChangeUser(current_user): PasswordChangeUser,
+    LoginClient(client): LoginClient,
+    Json(payload): Json<ChangePasswordDto>,
+) -> Result<Json<LoginResponseDto>, AppError> {
+    let actor = audit_actor(&current_user);
+    let ip = client.ip_address.map(|ip| ip.to_string());
+    let started = Instant::now();
+
+    match state
+        .auth_service()
+        .change_password(
+            &current_user,
+            &payload.current_password,
+            &payload.new_password,
+            &client,
+        )
+        .await
+    {
+        Ok(session) => {
+            state.metrics().record_user_operation(
+                OP_CHANGE_PASSWORD,
+                OUTCOME_SUCCESS,
+                Some(started.elapsed()),
+            );
+            state.audit().log(AuditEvent::success(
+                "user.password.change",
+                actor,
+                AuditTarget::new("user", Some(current_user.id().to_string())),
+                None,
+                ip,
+            ));
+            Ok(Json(LoginResponseDto::from(session)))
+        }
+        Err(err) => {
+            state.metrics().record_user_operation(
+                OP_CHANGE_PASSWORD,
+                OUTCOME_ERROR,
+                Some(started.elapsed()),
+            );
+            let detail = sanitize_for_logging(&err.to_string());
+            state.audit().log(AuditEvent::failure(
+                "user.password.change",
+                actor,
+                AuditTarget::new("user", Some(current_user.id().to_string())),
+                Some(detail),
+                ip,
+            ));
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/me/email",
+    request_body = ChangeEmailDto,
+    params(
+        ("If-Match" = Option<String>, Header, description = "Only update if the profile still has this ETag")
+    ),
+    responses(
+        (status = 202, description = "Confirmation link sent to the new address and a notice to the current one; an address already in use gets the same response and its owner a notice instead of the link", body = UserResponseDto,
+            headers(("ETag" = String, description = "New version of the user"))),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 412, description = "Profile changed since the given ETag", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn change_email(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    headers: HeaderMap,
+    Json(payload): Json<ChangeEmailDto>,
+) -> Result<Response, AppError> {
+    let actor = audit_actor(&current_user);
+    let started = Instant::now();
+
+    let result = match etag::if_match(&headers) {
+        Ok(expected_versions) => {
+            state
+                .user_service()
+                .change_email(&current_user, payload, expected_versions)
+                .await
+        }
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(user) => {
+            state.metrics().record_user_operation(
+                OP_CHANGE_EMAIL,
+                OUTCOME_SUCCESS,
+                Some(started.elapsed()),
+            );
+            state.audit().log(AuditEvent::success(
+                "user.email.change_requested",
+                actor,
+                AuditTarget::new("user", Some(user.id().to_string())),
+                user.pending_email().map(sanitize_for_logging),
+                None,
+            ));
+            let tag = etag::from_version(user.version());
+            Ok((StatusCode::ACCEPTED, [(header::ETAG, tag)], Json(user)).into_response())
+        }
+        Err(err) => {
+            state.metrics().record_user_operation(
+                OP_CHANGE_EMAIL,
+                OUTCOME_ERROR,
+                Some(started.elapsed()),
+            );
+            let detail = sanitize_for_logging(&err.to_string());
+            state.audit().log(AuditEvent::failure(
+                "user.email.change_requested",
+                actor,
+                AuditTarget::new("user", Some(current_user.id().to_string())),
+                Some(detail),
+                None,
+            ));
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/me/email/verification",
+    responses(
+        (status = 202, description = "Confirmation link sent again (to the pending address, if any)"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 409, description = "Email already verified", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn resend_email_verification(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> Result<StatusCode, AppError> {
+    let started = Instant::now();
+
+    match state
+        .user_service()
+        .resend_email_verification(&current_user)
+        .await
+    {
+        Ok(()) => {
+            state.metrics().record_user_operation(
+                OP_RESEND_VERIFICATION,
+                OUTCOME_SUCCESS,
+                Some(started.elapsed()),
+            );
+            Ok(StatusCode::ACCEPTED)
+        }
+        Err(err) => {
+            state.metrics().record_user_operation(
+                OP_RESEND_VERIFICATION,
+                OUTCOME_ERROR,
+                Some(started.elapsed()),
+            );
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/suspend",
+    params(("id" = uuid::Uuid, Path, description = "User identifier")),
+    request_body = ChangeUserStatusDto,
+    responses(
+        (status = 200, description = "User suspended and their sessions revoked", body = UserResponseDto),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 409, description = "Transition not allowed from the current status", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn suspend_user(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ChangeUserStatusDto>,
+) -> Result<Json<UserResponseDto>, AppError> {
+    change_user_status(&state, &current_user, id, UserStatus::Suspended, payload).await
+}
+
+// Bloqueio de seguranca do status da conta; nao confundir com `/unlock`, que so zera o
+// contador de falhas de login.
+#[utoipa::path(
+    post,
+    path = "/users/{id}/lock",
+    params(("id" = uuid::Uuid, Path, description = "User identifier")),
+    request_body = ChangeUserStatusDto,
+    responses(
+        (status = 200, description = "User locked and their sessions revoked", body = UserResponseDto),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 409, description = "Transition not allowed from the current status", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn lock_user(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ChangeUserStatusDto>,
+) -> Result<Json<UserResponseDto>, AppError> {
+    change_user_status(&state, &current_user, id, UserStatus::Locked, payload).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/reactivate",
+    params(("id" = uuid::Uuid, Path, description = "User identifier")),
+    request_body = ChangeUserStatusDto,
+    responses(
+        (status = 200, description = "User is active again", body = UserResponseDto),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 409, description = "Transition not allowed from the current status", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn reactivate_user(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ChangeUserStatusDto>,
+) -> Result<Json<UserResponseDto>, AppError> {
+    change_user_status(&state, &current_user, id, UserStatus::Active, payload).await
+}
+
+// Cada transicao gera seu proprio evento de auditoria, com o status anterior e o motivo.
+async fn change_user_status(
+    state: &AppState,
+    current_user: &AuthenticatedUser,
+    id: Uuid,
+    target: UserStatus,
+    payload: ChangeUserStatusDto,
+) -> Result<Json<UserResponseDto>, AppError> {
+    let (operation, action) = match target {
+        UserStatus::Suspended => (OP_SUSPEND, "user.suspend"),
+        UserStatus::Locked => (OP_LOCK, "user.lock"),
+        UserStatus::Active | UserStatus::Pending => (OP_REACTIVATE, "user.reactivate"),
+    };
+    let actor = audit_actor(current_user);
+    let started = Instant::now();
+    let reason = sanitize_for_logging(payload.reason.trim());
+
+    match state
+        .user_service()
+        .change_status(current_user, id, target, payload)
+        .await
+    {
+        Ok(change) => {
+            state.metrics().record_user_operation(
+                operation,
+                OUTCOME_SUCCESS,
+                Some(started.elapsed()),
+            );
+            state.audit().log(AuditEvent::success(
+                action,
+                actor,
+                AuditTarget::new("user", Some(id.to_string())),
+                Some(format!("{} -> {}: {reason}", change.previous, target)),
+                None,
+            ));
+            Ok(Json(change.user))
+        }
+        Err(err) => {
+            state.metrics().record_user_operation(
+                operation,
+                OUTCOME_ERROR,
+                Some(started.elapsed()),
+            );
+            let detail = sanitize_for_logging(&err.to_string());
+            state.audit().log(AuditEvent::failure(
+                action,
+                actor,
+                AuditTarget::new("user", Some(id.to_string())),
+                Some(detail),
+                None,
+            ));
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/registrations",
+    responses(
+        (status = 200, description = "Self-registrations waiting for approval, oldest first", body = [RegistrationDto]),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn list_registrations(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> Result<Json<Vec<RegistrationDto>>, AppError> {
+    let registrations = state
+        .registration_service()
+        .list_pending(&current_user)
+        .await?;
+
+    Ok(Json(registrations.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/approve",
+    params(("id" = uuid::Uuid, Path, description = "User identifier")),
+    responses(
+        (status = 200, description = "Registration approved; the user is now active", body = UserResponseDto),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "No pending registration for this user", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn approve_registration(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UserResponseDto>, AppError> {
+    let started = Instant::now();
+    let result = state
+        .registration_service()
+        .approve(&current_user, id)
+        .await;
+    log_registration_review(&state, &current_user, id, OP_APPROVE, started, &result);
+
+    result.map(|user| Json(user.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/reject",
+    params(("id" = uuid::Uuid, Path, description = "User identifier")),
+    responses(
+        (status = 204, description = "Registration rejected and the pending user deleted"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "No pending registration for this user", body = ErrorResponse),
+        (status = 500, description = "Unexpected error", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = []), ("apiKeyAuth" = [])),
+    tag = "Users"
+)]
+pub async fn reject_registration(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let started = Instant::now();
+    let result = state.registration_service().reject(&current_user, id).await;
+    log_registration_review(&state, &current_user, id, OP_REJECT, started, &result);
+
+    result.map(|()| StatusCode::NO_CONTENT)
+}
+
+fn log_registration_review<T>(
+    state: &AppState,
+    current_user: &AuthenticatedUser,
+    id: Uuid,
+    operation: &'static str,
+    started: Instant,
+    result: &AppResult<T>,
+) {
+    let action = if operation == OP_APPROVE {
+        "user.registration.approve"
+    } else {
+        "user.registration.reject"
+    };
+    let actor = audit_actor(current_user);
+    let target = AuditTarget::new("user", Some(id.to_string()));
+
+    match result {
+        Ok(_) => {
+            state.metrics().record_user_operation(
+                operation,
+                OUTCOME_SUCCESS,
+                Some(started.elapsed()),
+            );
+            state
+                .audit()
+                .log(AuditEvent::success(action, actor, target, None, None));
+        }
+        Err(err) => {
+            state.metrics().record_user_operation(
+                operation,
+                OUTCOME_ERROR,
+                Some(started.elapsed()),
+            );
+            state.audit().log(AuditEvent::failure(
+                action,
+                actor,
+                target,
+                Some(sanitize_for_logging(&err.to_string())),
+                None,
+            ));
+        }
+    }
+}
+
 fn audit_actor(user: &AuthenticatedUser) -> AuditActor {
     AuditActor {
         id: Some(user.id()),
         email: Some(sanitize_for_logging(user.email())),
-        role: Some(user.role().as_str().to_string()),
+        role: Some(user.roles_label()),
     }
 }
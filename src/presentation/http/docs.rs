@@ -1,36 +1,168 @@
 use utoipa::openapi;
-use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
-use crate::application::dtos::auth::{AuthenticatedUserDto, LoginRequestDto, LoginResponseDto};
-use crate::application::dtos::user::{CreateUserDto, UpdateUserDto, UserResponseDto};
+use crate::application::dtos::auth::{
+    AuthenticatedUserDto, ForgotPasswordRequestDto, ForgotPasswordResponseDto, LoginRequestDto,
+    LoginResponseDto, LogoutRequestDto, RefreshRequestDto, ResetPasswordRequestDto,
+    VerifyEmailRequestDto,
+};
+use crate::application::dtos::invitation::{
+    AcceptInvitationDto, CreateInvitationDto, InvitationDto,
+};
+use crate::application::dtos::mfa::{
+    MfaChallengeResponseDto, MfaPolicyRequestDto, MfaPolicyResponseDto, MfaVerifyRequestDto,
+    RecoveryCodesResponseDto, TotpConfirmRequestDto, TotpEnrollmentResponseDto,
+};
+use crate::application::dtos::oauth::{
+    AuthorizationDecisionDto, AuthorizationRequestDto, CreateOAuthClientDto, CreatedOAuthClientDto,
+    OAuthClientDto, OAuthConsentDto, OAuthErrorDto, OAuthSignInDto, TokenRequestDto,
+    TokenResponseDto,
+};
+use crate::application::dtos::personal_access_token::{
+    CreatePersonalAccessTokenDto, CreatedPersonalAccessTokenDto, PersonalAccessTokenDto,
+};
+use crate::application::dtos::registration::{
+    RegisterRequestDto, RegisterResponseDto, RegistrationDto,
+};
+use crate::application::dtos::role::{
+    CreateRoleDto, PermissionDto, RoleResponseDto, UpdateRoleDto,
+};
+use crate::application::dtos::session::SessionDto;
+use crate::application::dtos::user::{
+    ChangeEmailDto, ChangePasswordDto, ChangeUserStatusDto, CreateUserDto, UpdateProfileDto,
+    UpdateUserDto, UserPageDto, UserPreferencesDto, UserResponseDto,
+};
 use crate::shared::error::ErrorResponse;
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         crate::presentation::http::controllers::auth_controller::login,
+        crate::presentation::http::controllers::auth_controller::verify_mfa,
+        crate::presentation::http::controllers::auth_controller::refresh,
+        crate::presentation::http::controllers::auth_controller::logout,
+        crate::presentation::http::controllers::auth_controller::forgot_password,
+        crate::presentation::http::controllers::auth_controller::reset_password,
+        crate::presentation::http::controllers::auth_controller::verify_email,
+        crate::presentation::http::controllers::auth_controller::register,
+        crate::presentation::http::controllers::auth_controller::jwks,
         crate::presentation::http::controllers::users_controller::create_user,
         crate::presentation::http::controllers::users_controller::list_users,
         crate::presentation::http::controllers::users_controller::get_user,
         crate::presentation::http::controllers::users_controller::update_user,
-        crate::presentation::http::controllers::users_controller::delete_user
+        crate::presentation::http::controllers::users_controller::delete_user,
+        crate::presentation::http::controllers::users_controller::restore_user,
+        crate::presentation::http::controllers::users_controller::get_profile,
+        crate::presentation::http::controllers::users_controller::update_profile,
+        crate::presentation::http::controllers::users_controller::change_password,
+        crate::presentation::http::controllers::users_controller::change_email,
+        crate::presentation::http::controllers::users_controller::resend_email_verification,
+        crate::presentation::http::controllers::users_controller::suspend_user,
+        crate::presentation::http::controllers::users_controller::lock_user,
+        crate::presentation::http::controllers::users_controller::reactivate_user,
+        crate::presentation::http::controllers::users_controller::revoke_user_sessions,
+        crate::presentation::http::controllers::users_controller::unlock_user,
+        crate::presentation::http::controllers::users_controller::list_registrations,
+        crate::presentation::http::controllers::users_controller::approve_registration,
+        crate::presentation::http::controllers::users_controller::reject_registration,
+        crate::presentation::http::controllers::mfa_controller::enroll_totp,
+        crate::presentation::http::controllers::mfa_controller::confirm_totp,
+        crate::presentation::http::controllers::mfa_controller::list_policies,
+        crate::presentation::http::controllers::mfa_controller::set_policy,
+        crate::presentation::http::controllers::personal_access_tokens_controller::create_token,
+        crate::presentation::http::controllers::personal_access_tokens_controller::list_tokens,
+        crate::presentation::http::controllers::personal_access_tokens_controller::revoke_token,
+        crate::presentation::http::controllers::sessions_controller::list_my_sessions,
+        crate::presentation::http::controllers::sessions_controller::revoke_my_session,
+        crate::presentation::http::controllers::sessions_controller::list_user_sessions,
+        crate::presentation::http::controllers::sessions_controller::revoke_user_session,
+        crate::presentation::http::controllers::invitations_controller::create_invitation,
+        crate::presentation::http::controllers::invitations_controller::list_invitations,
+        crate::presentation::http::controllers::invitations_controller::resend_invitation,
+        crate::presentation::http::controllers::invitations_controller::revoke_invitation,
+        crate::presentation::http::controllers::invitations_controller::accept_invitation,
+        crate::presentation::http::controllers::oauth_controller::create_client,
+        crate::presentation::http::controllers::oauth_controller::list_clients,
+        crate::presentation::http::controllers::oauth_controller::get_client,
+        crate::presentation::http::controllers::oauth_controller::disable_client,
+        crate::presentation::http::controllers::oauth_controller::authorize,
+        crate::presentation::http::controllers::oauth_controller::decide,
+        crate::presentation::http::controllers::oauth_controller::sign_in,
+        crate::presentation::http::controllers::oauth_controller::token,
+        crate::presentation::http::controllers::oauth_controller::list_my_consents,
+        crate::presentation::http::controllers::oauth_controller::revoke_my_consent,
+        crate::presentation::http::controllers::roles_controller::list_permissions,
+        crate::presentation::http::controllers::roles_controller::list_roles,
+        crate::presentation::http::controllers::roles_controller::get_role,
+        crate::presentation::http::controllers::roles_controller::create_role,
+        crate::presentation::http::controllers::roles_controller::update_role,
+        crate::presentation::http::controllers::roles_controller::delete_role
     ),
     components(
         schemas(
             AuthenticatedUserDto,
             LoginRequestDto,
             LoginResponseDto,
+            LogoutRequestDto,
+            RefreshRequestDto,
+            ForgotPasswordRequestDto,
+            ForgotPasswordResponseDto,
+            ResetPasswordRequestDto,
+            VerifyEmailRequestDto,
+            MfaChallengeResponseDto,
+            MfaVerifyRequestDto,
+            TotpEnrollmentResponseDto,
+            TotpConfirmRequestDto,
+            RecoveryCodesResponseDto,
+            MfaPolicyRequestDto,
+            MfaPolicyResponseDto,
+            CreatePersonalAccessTokenDto,
+            CreatedPersonalAccessTokenDto,
+            PersonalAccessTokenDto,
+            SessionDto,
+            CreateInvitationDto,
+            AcceptInvitationDto,
+            InvitationDto,
+            RegisterRequestDto,
+            RegisterResponseDto,
+            RegistrationDto,
+            CreateOAuthClientDto,
+            OAuthClientDto,
+            CreatedOAuthClientDto,
+            AuthorizationRequestDto,
+            AuthorizationDecisionDto,
+            OAuthSignInDto,
+            TokenRequestDto,
+            TokenResponseDto,
+            OAuthErrorDto,
+            OAuthConsentDto,
+            CreateRoleDto,
+            UpdateRoleDto,
+            RoleResponseDto,
+            PermissionDto,
             CreateUserDto,
             UpdateUserDto,
             UserResponseDto,
+            UserPageDto,
+            ChangeUserStatusDto,
+            UpdateProfileDto,
+            ChangePasswordDto,
+            ChangeEmailDto,
+            UserPreferencesDto,
             ErrorResponse
         )
     ),
     modifiers(&SecurityAddon),
     tags(
         (name = "Auth", description = "Authentication operations"),
-        (name = "Users", description = "User management")
+        (name = "Users", description = "User management"),
+        (name = "MFA", description = "Multi-factor authentication"),
+        (name = "Tokens", description = "Personal access tokens for machine clients"),
+        (name = "Sessions", description = "Where an account is signed in, and revoking those sessions"),
+        (name = "Invitations", description = "Invite users who then choose their own password"),
+        (name = "Roles", description = "Roles and the permissions they grant"),
+        (name = "OAuth", description = "OAuth 2.0 authorization server: clients, consent and tokens")
     )
 )]
 pub struct ApiDoc;
@@ -49,5 +181,15 @@ impl Modify for SecurityAddon {
                     .build(),
             ),
         );
+        // Tokens pessoais tambem podem ser enviados como Bearer; o header e a alternativa.
+        components.add_security_scheme(
+            "apiKeyAuth",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+        // Clientes OAuth confidenciais em `/oauth/token`: `client_id:client_secret`.
+        components.add_security_scheme(
+            "clientBasicAuth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Basic).build()),
+        );
     }
 }
@@ -0,0 +1,53 @@
+use axum::http::{header, HeaderMap, HeaderValue};
+
+use crate::shared::error::{AppError, AppResult};
+
+// ETag forte derivado da versao do recurso: `"<versao>"`.
+pub fn from_version(version: i64) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{version}\"")).expect("quoted integer is a valid header")
+}
+
+// Versoes aceitas pelo `If-Match`, que pode listar varias tags; `None` quando o header falta
+// ou e `*`. A comparacao e forte (RFC 9110, secao 13.1.1): tags fracas ou desconhecidas nunca
+// casam, e sem nenhuma tag forte a requisicao vira 412.
+pub fn if_match(headers: &HeaderMap) -> AppResult<Option<Vec<i64>>> {
+    let mut values = headers.get_all(header::IF_MATCH).iter().peekable();
+    if values.peek().is_none() {
+        return Ok(None);
+    }
+
+    let mut versions = Vec::new();
+    for value in values {
+        let value = value.to_str().map_err(|_| precondition_failed())?;
+        for tag in value.split(',').map(str::trim) {
+            if tag == "*" {
+                return Ok(None);
+            }
+            versions.extend(parse_strong(tag));
+        }
+    }
+
+    if versions.is_empty() {
+        return Err(precondition_failed());
+    }
+    Ok(Some(versions))
+}
+
+// `If-None-Match` usa comparacao fraca e aceita lista de tags ou `*`.
+pub fn if_none_match(headers: &HeaderMap, version: i64) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || parse_strong(tag.trim_start_matches("W/")) == Some(version))
+}
+
+fn parse_strong(tag: &str) -> Option<i64> {
+    tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()
+}
+
+fn precondition_failed() -> AppError {
+    AppError::PreconditionFailed("entity tag does not match the current version".to_string())
+}
@@ -1,4 +1,7 @@
 pub mod auth;
+pub mod client_ip;
 pub mod controllers;
 pub mod docs;
+pub mod etag;
+pub mod oauth_pages;
 pub mod routes;
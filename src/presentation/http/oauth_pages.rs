@@ -0,0 +1,135 @@
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+
+use crate::application::dtos::oauth::AuthorizationRequestDto;
+use crate::domain::entities::oauth::OAuthClient;
+use crate::domain::entities::permission::Permission;
+
+// Paginas HTML do endpoint de autorizacao. Sem scripts nem recursos externos; a politica de
+// seguranca impede que outro site as carregue num frame para induzir o clique em "Allow".
+const CONTENT_SECURITY_POLICY: &str =
+    "default-src 'none'; style-src 'unsafe-inline'; frame-ancestors 'none'";
+const STYLE: &str = "body{font-family:sans-serif;max-width:24rem;margin:4rem auto;padding:0 1rem}\
+label,input,button{display:block;width:100%;margin:.5rem 0}\
+.error{color:#b00020}";
+
+pub fn sign_in(request: &AuthorizationRequestDto, error: Option<&str>) -> Response {
+    let body = format!(
+        "<h1>Sign in</h1>{error}\
+         <form method=\"post\" action=\"/oauth/login\">{fields}\
+         <label>Email<input type=\"email\" name=\"email\" autocomplete=\"username\" required></label>\
+         <label>Password<input type=\"password\" name=\"password\" autocomplete=\"current-password\" required></label>\
+         <button type=\"submit\">Sign in</button></form>",
+        error = error_message(error),
+        fields = hidden_fields(request),
+    );
+    page("Sign in", &body)
+}
+
+pub fn second_factor(
+    request: &AuthorizationRequestDto,
+    challenge_token: &str,
+    error: Option<&str>,
+) -> Response {
+    let body = format!(
+        "<h1>Two-factor authentication</h1>{error}\
+         <form method=\"post\" action=\"/oauth/login\">{fields}{challenge}\
+         <label>Code from your authenticator app or a recovery code\
+         <input name=\"code\" autocomplete=\"one-time-code\" required></label>\
+         <button type=\"submit\">Verify</button></form>",
+        error = error_message(error),
+        fields = hidden_fields(request),
+        challenge = hidden("challenge_token", challenge_token),
+    );
+    page("Two-factor authentication", &body)
+}
+
+pub fn consent(
+    request: &AuthorizationRequestDto,
+    client: &OAuthClient,
+    scopes: &[Permission],
+    csrf_token: &str,
+) -> Response {
+    let scopes = if scopes.is_empty() {
+        "<li>No permissions beyond knowing who you are</li>".to_string()
+    } else {
+        scopes
+            .iter()
+            .map(|scope| {
+                format!(
+                    "<li><code>{}</code>: {}</li>",
+                    escape(scope.as_str()),
+                    escape(scope.description())
+                )
+            })
+            .collect()
+    };
+    let body = format!(
+        "<h1>Authorize {name}</h1>\
+         <p>{name} is asking to:</p><ul>{scopes}</ul>\
+         <form method=\"post\" action=\"/oauth/authorize\">{fields}{csrf}\
+         <button type=\"submit\" name=\"decision\" value=\"approve\">Allow</button>\
+         <button type=\"submit\" name=\"decision\" value=\"deny\">Deny</button></form>",
+        name = escape(&client.name),
+        fields = hidden_fields(request),
+        csrf = hidden("csrf_token", csrf_token),
+    );
+    page("Authorize", &body)
+}
+
+fn page(title: &str, body: &str) -> Response {
+    let html = format!(
+        "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>{title}</title><style>{STYLE}</style></head><body>{body}</body></html>",
+        title = escape(title),
+    );
+
+    let mut response = (StatusCode::OK, Html(html)).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY"));
+    headers.insert(
+        header::CONTENT_SECURITY_POLICY,
+        HeaderValue::from_static(CONTENT_SECURITY_POLICY),
+    );
+    response
+}
+
+fn error_message(error: Option<&str>) -> String {
+    error
+        .map(|error| format!("<p class=\"error\">{}</p>", escape(error)))
+        .unwrap_or_default()
+}
+
+// O pedido original viaja nos formularios para continuar a autorizacao depois do login.
+fn hidden_fields(request: &AuthorizationRequestDto) -> String {
+    request
+        .params()
+        .into_iter()
+        .map(|(name, value)| hidden(name, value))
+        .collect()
+}
+
+fn hidden(name: &str, value: &str) -> String {
+    format!(
+        "<input type=\"hidden\" name=\"{}\" value=\"{}\">",
+        escape(name),
+        escape(value)
+    )
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
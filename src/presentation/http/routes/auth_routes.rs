@@ -1,9 +1,23 @@
-﻿use axum::routing::post;
+use axum::routing::{get, post};
 use axum::Router;
 
 use crate::app::AppState;
 use crate::presentation::http::controllers::auth_controller;
 
 pub fn auth_routes() -> Router<AppState> {
-    Router::new().route("/auth/login", post(auth_controller::login))
+    Router::new()
+        .route("/auth/login", post(auth_controller::login))
+        .route("/auth/mfa/verify", post(auth_controller::verify_mfa))
+        .route("/auth/refresh", post(auth_controller::refresh))
+        .route("/auth/logout", post(auth_controller::logout))
+        .route(
+            "/auth/password/forgot",
+            post(auth_controller::forgot_password),
+        )
+        .route(
+            "/auth/password/reset",
+            post(auth_controller::reset_password),
+        )
+        .route("/auth/email/verify", post(auth_controller::verify_email))
+        .route("/.well-known/jwks.json", get(auth_controller::jwks))
 }
@@ -0,0 +1,26 @@
+use axum::routing::{delete, get, post};
+use axum::Router;
+
+use crate::app::AppState;
+use crate::presentation::http::controllers::invitations_controller;
+
+pub fn invitation_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/invitations",
+            get(invitations_controller::list_invitations)
+                .post(invitations_controller::create_invitation),
+        )
+        .route(
+            "/invitations/:id",
+            delete(invitations_controller::revoke_invitation),
+        )
+        .route(
+            "/invitations/:id/resend",
+            post(invitations_controller::resend_invitation),
+        )
+        .route(
+            "/invitations/:token/accept",
+            post(invitations_controller::accept_invitation),
+        )
+}
@@ -0,0 +1,16 @@
+use axum::routing::{get, post, put};
+use axum::Router;
+
+use crate::app::AppState;
+use crate::presentation::http::controllers::mfa_controller;
+
+pub fn mfa_routes() -> Router<AppState> {
+    Router::new()
+        .route("/users/me/mfa/totp", post(mfa_controller::enroll_totp))
+        .route(
+            "/users/me/mfa/totp/confirm",
+            post(mfa_controller::confirm_totp),
+        )
+        .route("/mfa/policies", get(mfa_controller::list_policies))
+        .route("/mfa/policies/:role", put(mfa_controller::set_policy))
+}
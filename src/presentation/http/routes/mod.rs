@@ -1,5 +1,19 @@
 ﻿mod auth_routes;
+mod invitation_routes;
+mod mfa_routes;
+mod oauth_routes;
+mod registration_routes;
+mod role_routes;
+mod session_routes;
+mod token_routes;
 mod user_routes;
 
 pub use auth_routes::auth_routes;
+pub use invitation_routes::invitation_routes;
+pub use mfa_routes::mfa_routes;
+pub use oauth_routes::oauth_routes;
+pub use registration_routes::registration_routes;
+pub use role_routes::role_routes;
+pub use session_routes::session_routes;
+pub use token_routes::token_routes;
 pub use user_routes::user_routes;
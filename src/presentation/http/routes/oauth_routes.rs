@@ -0,0 +1,31 @@
+use axum::routing::{delete, get, post};
+use axum::Router;
+
+use crate::app::AppState;
+use crate::presentation::http::controllers::oauth_controller;
+
+pub fn oauth_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/oauth/clients",
+            get(oauth_controller::list_clients).post(oauth_controller::create_client),
+        )
+        .route(
+            "/oauth/clients/:id",
+            get(oauth_controller::get_client).delete(oauth_controller::disable_client),
+        )
+        .route(
+            "/oauth/authorize",
+            get(oauth_controller::authorize).post(oauth_controller::decide),
+        )
+        .route("/oauth/login", post(oauth_controller::sign_in))
+        .route("/oauth/token", post(oauth_controller::token))
+        .route(
+            "/users/me/oauth/consents",
+            get(oauth_controller::list_my_consents),
+        )
+        .route(
+            "/users/me/oauth/consents/:client_id",
+            delete(oauth_controller::revoke_my_consent),
+        )
+}
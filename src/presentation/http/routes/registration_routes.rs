@@ -0,0 +1,12 @@
+use axum::routing::post;
+use axum::Router;
+
+use crate::app::{AppState, RateLimiterLayer};
+use crate::presentation::http::controllers::auth_controller;
+
+// O autocadastro tem um balde proprio, aplicado so a esta rota e somado ao limite geral.
+pub fn registration_routes(rate_limiter_layer: RateLimiterLayer) -> Router<AppState> {
+    Router::new()
+        .route("/auth/register", post(auth_controller::register))
+        .route_layer(rate_limiter_layer)
+}
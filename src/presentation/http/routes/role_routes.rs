@@ -0,0 +1,20 @@
+use axum::routing::get;
+use axum::Router;
+
+use crate::app::AppState;
+use crate::presentation::http::controllers::roles_controller;
+
+pub fn role_routes() -> Router<AppState> {
+    Router::new()
+        .route("/permissions", get(roles_controller::list_permissions))
+        .route(
+            "/roles",
+            get(roles_controller::list_roles).post(roles_controller::create_role),
+        )
+        .route(
+            "/roles/:name",
+            get(roles_controller::get_role)
+                .put(roles_controller::update_role)
+                .delete(roles_controller::delete_role),
+        )
+}
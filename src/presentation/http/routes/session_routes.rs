@@ -0,0 +1,22 @@
+use axum::routing::{delete, get};
+use axum::Router;
+
+use crate::app::AppState;
+use crate::presentation::http::controllers::sessions_controller;
+
+// `GET /users/:id/sessions` fica em `user_routes`, junto do `DELETE` que revoga todas.
+pub fn session_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/users/me/sessions",
+            get(sessions_controller::list_my_sessions),
+        )
+        .route(
+            "/users/me/sessions/:id",
+            delete(sessions_controller::revoke_my_session),
+        )
+        .route(
+            "/users/:id/sessions/:session_id",
+            delete(sessions_controller::revoke_user_session),
+        )
+}
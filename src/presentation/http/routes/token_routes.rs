@@ -0,0 +1,18 @@
+use axum::routing::{delete, get};
+use axum::Router;
+
+use crate::app::AppState;
+use crate::presentation::http::controllers::personal_access_tokens_controller;
+
+pub fn token_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/users/me/tokens",
+            get(personal_access_tokens_controller::list_tokens)
+                .post(personal_access_tokens_controller::create_token),
+        )
+        .route(
+            "/users/me/tokens/:id",
+            delete(personal_access_tokens_controller::revoke_token),
+        )
+}
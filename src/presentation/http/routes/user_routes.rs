@@ -1,7 +1,7 @@
 use axum::{routing::get, routing::post, Router};
 
 use crate::app::AppState;
-use crate::presentation::http::controllers::users_controller;
+use crate::presentation::http::controllers::{sessions_controller, users_controller};
 
 pub fn user_routes() -> Router<AppState> {
     Router::new()
@@ -9,10 +9,49 @@ pub fn user_routes() -> Router<AppState> {
             "/users",
             post(users_controller::create_user).get(users_controller::list_users),
         )
+        // Rotas estaticas tem prioridade sobre `/users/:id`.
+        .route(
+            "/users/me",
+            get(users_controller::get_profile).patch(users_controller::update_profile),
+        )
+        .route(
+            "/users/me/password",
+            post(users_controller::change_password),
+        )
+        .route("/users/me/email", post(users_controller::change_email))
+        .route(
+            "/users/me/email/verification",
+            post(users_controller::resend_email_verification),
+        )
+        .route(
+            "/users/registrations",
+            get(users_controller::list_registrations),
+        )
         .route(
             "/users/:id",
             get(users_controller::get_user)
                 .put(users_controller::update_user)
                 .delete(users_controller::delete_user),
         )
+        .route(
+            "/users/:id/sessions",
+            get(sessions_controller::list_user_sessions)
+                .delete(users_controller::revoke_user_sessions),
+        )
+        .route("/users/:id/restore", post(users_controller::restore_user))
+        .route("/users/:id/suspend", post(users_controller::suspend_user))
+        .route("/users/:id/lock", post(users_controller::lock_user))
+        .route(
+            "/users/:id/reactivate",
+            post(users_controller::reactivate_user),
+        )
+        .route("/users/:id/unlock", post(users_controller::unlock_user))
+        .route(
+            "/users/:id/approve",
+            post(users_controller::approve_registration),
+        )
+        .route(
+            "/users/:id/reject",
+            post(users_controller::reject_registration),
+        )
 }
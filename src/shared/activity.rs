@@ -0,0 +1,10 @@
+use chrono::{DateTime, Duration, Utc};
+
+// Marcas de ultimo uso (sessoes, personal access tokens) sao gravadas no maximo uma vez por
+// minuto, para que usar um token nao custe uma escrita por requisicao.
+const ACTIVITY_RESOLUTION_SECONDS: i64 = 60;
+
+pub fn is_stale(last_recorded: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    last_recorded
+        .is_none_or(|recorded| now - recorded >= Duration::seconds(ACTIVITY_RESOLUTION_SECONDS))
+}
@@ -1,4 +1,4 @@
-use axum::http::StatusCode;
+use axum::http::{header, HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde::Serialize;
@@ -6,6 +6,8 @@ use thiserror::Error;
 use tracing::{error, warn};
 use utoipa::ToSchema;
 
+use crate::domain::errors::DomainError;
+
 pub type AppResult<T> = Result<T, AppError>;
 
 // Catálogo de erros da aplicação. Cada variante mapeia para um status HTTP e é logada de forma estruturada.
@@ -21,6 +23,18 @@ pub enum AppError {
     Unauthorized(String),
     #[error("forbidden: {0}")]
     Forbidden(String),
+    #[error("precondition failed: {0}")]
+    PreconditionFailed(String),
+    #[error("too many requests: {message}")]
+    TooManyRequests {
+        message: String,
+        retry_after_seconds: u64,
+    },
+    #[error("service unavailable: {message}")]
+    ServiceUnavailable {
+        message: String,
+        retry_after_seconds: u64,
+    },
     #[error("database error: {0}")]
     Database(sqlx::Error),
     #[error("unexpected error: {0}")]
@@ -35,6 +49,9 @@ impl AppError {
             Self::Conflict(_) => StatusCode::CONFLICT,
             Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            Self::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::ServiceUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
             Self::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -66,6 +83,15 @@ impl IntoResponse for AppError {
             (AppError::Forbidden(detail), _) => {
                 warn!(status = %status, detail = detail.as_str(), "forbidden request")
             }
+            (AppError::PreconditionFailed(detail), _) => {
+                warn!(status = %status, detail = detail.as_str(), "precondition failed")
+            }
+            (AppError::TooManyRequests { message, .. }, _) => {
+                warn!(status = %status, detail = message.as_str(), "request throttled")
+            }
+            (AppError::ServiceUnavailable { message, .. }, _) => {
+                warn!(status = %status, detail = message.as_str(), "request shed")
+            }
             (AppError::Database(err), _) => {
                 error!(status = %status, error = %err, "database error")
             }
@@ -74,11 +100,38 @@ impl IntoResponse for AppError {
             }
         }
 
+        let retry_after = match &self {
+            AppError::TooManyRequests {
+                retry_after_seconds,
+                ..
+            }
+            | AppError::ServiceUnavailable {
+                retry_after_seconds,
+                ..
+            } => Some(*retry_after_seconds),
+            _ => None,
+        };
+
         let body = Json(ErrorResponse {
             error: self.to_string(),
         });
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        if let Some(seconds) = retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+        }
+        response
+    }
+}
+
+impl From<DomainError> for AppError {
+    fn from(error: DomainError) -> Self {
+        match error {
+            DomainError::Validation(message) => AppError::Validation(message),
+            DomainError::InvalidTransition(message) => AppError::Conflict(message),
+        }
     }
 }
 
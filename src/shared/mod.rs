@@ -1,3 +1,4 @@
+pub mod activity;
 pub mod error;
 pub mod security;
 pub mod validation;
@@ -0,0 +1,99 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use sha1::{Digest, Sha1};
+
+const PREFIX_LENGTH: usize = 5;
+const HASH_LENGTH: usize = 40;
+
+// Senhas vazadas no formato k-anonymity do Have I Been Pwned: o SHA-1 da senha em hexadecimal,
+// dividido em prefixo de 5 caracteres e sufixo de 35. O arquivo pode trazer o hash completo por
+// linha (`<40 hex>[:contagem]`, como gera o PwnedPasswordsDownloader) ou respostas da API de
+// range concatenadas, em que uma linha so com o prefixo precede os sufixos (`<35 hex>:contagem`).
+// Tudo fica em memoria, entao use um recorte da base (ex.: as senhas mais frequentes).
+#[derive(Debug, Default)]
+pub struct BreachedPasswords {
+    ranges: HashMap<String, HashSet<String>>,
+    len: usize,
+}
+
+impl BreachedPasswords {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read breached password list {}", path.display()))?;
+        Self::parse(&contents)
+            .with_context(|| format!("invalid breached password list {}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> anyhow::Result<Self> {
+        let mut list = Self::default();
+        let mut prefix: Option<String> = None;
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let hash = line
+                .split_once(':')
+                .map_or(line, |(hash, _count)| hash)
+                .to_ascii_uppercase();
+            if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                bail!("line {}: expected hexadecimal SHA-1 data", index + 1);
+            }
+
+            match hash.len() {
+                HASH_LENGTH => {
+                    let (range, suffix) = hash.split_at(PREFIX_LENGTH);
+                    list.insert(range, suffix);
+                }
+                PREFIX_LENGTH => prefix = Some(hash),
+                length if length == HASH_LENGTH - PREFIX_LENGTH => match &prefix {
+                    Some(range) => list.insert(range, &hash),
+                    None => bail!("line {}: suffix without a preceding prefix", index + 1),
+                },
+                _ => bail!(
+                    "line {}: expected a SHA-1 hash, a 5-character prefix or a 35-character suffix",
+                    index + 1
+                ),
+            }
+        }
+        Ok(list)
+    }
+
+    pub fn contains(&self, password: &str) -> bool {
+        let digest = Sha1::digest(password.as_bytes());
+        let hash = digest
+            .iter()
+            .fold(String::with_capacity(HASH_LENGTH), |mut out, byte| {
+                let _ = write!(out, "{byte:02X}");
+                out
+            });
+        let (range, suffix) = hash.split_at(PREFIX_LENGTH);
+        self.ranges
+            .get(range)
+            .is_some_and(|suffixes| suffixes.contains(suffix))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn insert(&mut self, range: &str, suffix: &str) {
+        if self
+            .ranges
+            .entry(range.to_string())
+            .or_default()
+            .insert(suffix.to_string())
+        {
+            self.len += 1;
+        }
+    }
+}
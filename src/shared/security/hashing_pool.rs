@@ -0,0 +1,206 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+use anyhow::anyhow;
+use once_cell::sync::OnceCell;
+use tokio::sync::Semaphore;
+
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::security::opaque_token;
+use crate::shared::security::password::{self, Argon2Settings, PasswordError};
+use crate::telemetry::AppMetrics;
+
+#[derive(Debug, Clone)]
+pub struct HashingPoolSettings {
+    // Hashes executados ao mesmo tempo; cada um ocupa uma thread de `spawn_blocking`.
+    pub workers: usize,
+    // Pedidos que podem esperar por um worker. Com a fila cheia a chamada falha na hora.
+    pub queue_capacity: usize,
+    // Valor do `Retry-After` devolvido com o 503.
+    pub retry_after_seconds: u64,
+    pub argon2: Argon2Settings,
+}
+
+impl Default for HashingPoolSettings {
+    fn default() -> Self {
+        Self {
+            workers: thread::available_parallelism().map_or(2, |count| count.get()),
+            queue_capacity: 64,
+            retry_after_seconds: 1,
+            argon2: Argon2Settings::default(),
+        }
+    }
+}
+
+// Argon2 gasta dezenas de milissegundos de CPU por chamada; rodando dentro do handler ele prende
+// a thread do Tokio e uma rajada de logins congela todas as rotas. O pool leva o trabalho para
+// `spawn_blocking`, limita quantos rodam juntos e recusa com 503 o que nao cabe na fila.
+#[derive(Clone)]
+pub struct HashingPool {
+    // Vagas para trabalhos em execucao mais os que esperam na fila.
+    admission: Arc<Semaphore>,
+    workers: Arc<Semaphore>,
+    queued: Arc<AtomicUsize>,
+    retry_after_seconds: u64,
+    argon2: Arc<Argon2Settings>,
+    // Hash de uma senha aleatoria com os parametros e o pepper atuais, usado por `verify_dummy`.
+    dummy_hash: Arc<OnceCell<String>>,
+    metrics: AppMetrics,
+}
+
+impl Default for HashingPool {
+    fn default() -> Self {
+        Self::new(HashingPoolSettings::default(), AppMetrics::new())
+    }
+}
+
+impl HashingPool {
+    pub fn new(settings: HashingPoolSettings, metrics: AppMetrics) -> Self {
+        let workers = settings.workers.max(1);
+        Self {
+            admission: Arc::new(Semaphore::new(workers + settings.queue_capacity)),
+            workers: Arc::new(Semaphore::new(workers)),
+            queued: Arc::new(AtomicUsize::new(0)),
+            retry_after_seconds: settings.retry_after_seconds,
+            argon2: Arc::new(settings.argon2),
+            dummy_hash: Arc::new(OnceCell::new()),
+            metrics,
+        }
+    }
+
+    pub async fn hash_password(&self, password: &str) -> AppResult<Result<String, PasswordError>> {
+        let argon2 = self.argon2.clone();
+        let password = password.to_owned();
+        self.run("hash", move || password::hash_password(&argon2, &password))
+            .await
+    }
+
+    // Varios hashes em sequencia num unico worker (ex.: recovery codes).
+    pub async fn hash_passwords(
+        &self,
+        passwords: Vec<String>,
+    ) -> AppResult<Result<Vec<String>, PasswordError>> {
+        let argon2 = self.argon2.clone();
+        self.run("hash", move || {
+            passwords
+                .iter()
+                .map(|password| password::hash_password(&argon2, password))
+                .collect()
+        })
+        .await
+    }
+
+    pub async fn verify_password(
+        &self,
+        expected_hash: &str,
+        candidate: &str,
+    ) -> AppResult<Result<(), PasswordError>> {
+        let argon2 = self.argon2.clone();
+        let expected_hash = expected_hash.to_owned();
+        let candidate = candidate.to_owned();
+        self.run("verify", move || {
+            password::verify_password(&argon2, &expected_hash, &candidate)
+        })
+        .await
+    }
+
+    // Gasta o mesmo que verificar uma senha errada de uma conta existente, para que o tempo de
+    // resposta nao revele quais emails estao cadastrados. O resultado e descartado.
+    pub async fn verify_dummy(&self, candidate: &str) -> AppResult<()> {
+        let argon2 = self.argon2.clone();
+        let dummy_hash = self.dummy_hash.clone();
+        let candidate = candidate.to_owned();
+        self.run("verify", move || {
+            let hash = init_dummy_hash(&dummy_hash, &argon2)?;
+            let _ = password::verify_password(&argon2, hash, &candidate);
+            Ok::<_, PasswordError>(())
+        })
+        .await?
+        .map_err(|err| AppError::Unexpected(anyhow!("failed to prepare dummy hash: {err}")))
+    }
+
+    // Gera o hash de `verify_dummy` na subida; sem isso o primeiro login de conta inexistente
+    // custaria um hash a mais.
+    pub async fn warm_up(&self) -> AppResult<()> {
+        let argon2 = self.argon2.clone();
+        let dummy_hash = self.dummy_hash.clone();
+        self.run("hash", move || {
+            init_dummy_hash(&dummy_hash, &argon2).map(|_| ())
+        })
+        .await?
+        .map_err(|err| AppError::Unexpected(anyhow!("failed to prepare dummy hash: {err}")))
+    }
+
+    // Barato: so compara os parametros gravados no hash com os atuais.
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        password::needs_rehash(&self.argon2, hash)
+    }
+
+    // Executa `job` fora das threads do runtime. As vagas sao liberadas so quando o trabalho
+    // termina, mesmo que quem chamou desista antes: o limite vale para a CPU de fato ocupada.
+    pub async fn run<T, F>(&self, operation: &'static str, job: F) -> AppResult<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let Ok(admission) = self.admission.clone().try_acquire_owned() else {
+            self.metrics.record_password_hash_rejected(operation);
+            return Err(AppError::ServiceUnavailable {
+                message: "password hashing queue is full".to_string(),
+                retry_after_seconds: self.retry_after_seconds,
+            });
+        };
+
+        let queued_at = Instant::now();
+        let worker = {
+            let _queued = QueuedJob::enter(self);
+            self.workers
+                .clone()
+                .acquire_owned()
+                .await
+                .map_err(|err| AppError::Unexpected(anyhow!("hashing pool closed: {err}")))?
+        };
+        let waited = queued_at.elapsed();
+
+        let metrics = self.metrics.clone();
+        tokio::task::spawn_blocking(move || {
+            let started_at = Instant::now();
+            let output = job();
+            metrics.record_password_hash(operation, waited, started_at.elapsed());
+            drop(worker);
+            drop(admission);
+            output
+        })
+        .await
+        .map_err(|err| AppError::Unexpected(anyhow!("password hashing task failed: {err}")))
+    }
+}
+
+fn init_dummy_hash<'a>(
+    dummy_hash: &'a OnceCell<String>,
+    argon2: &Argon2Settings,
+) -> Result<&'a String, PasswordError> {
+    dummy_hash.get_or_try_init(|| password::hash_password(argon2, &opaque_token::generate()))
+}
+
+// Conta o pedido como enfileirado enquanto espera um worker, inclusive se a espera for cancelada.
+struct QueuedJob<'a> {
+    pool: &'a HashingPool,
+}
+
+impl<'a> QueuedJob<'a> {
+    fn enter(pool: &'a HashingPool) -> Self {
+        let depth = pool.queued.fetch_add(1, Ordering::Relaxed) + 1;
+        pool.metrics.record_password_hash_queue_depth(depth);
+        Self { pool }
+    }
+}
+
+impl Drop for QueuedJob<'_> {
+    fn drop(&mut self) {
+        let depth = self.pool.queued.fetch_sub(1, Ordering::Relaxed) - 1;
+        self.pool.metrics.record_password_hash_queue_depth(depth);
+    }
+}
@@ -0,0 +1,134 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters,
+    EllipticCurveKeyType, Jwk, KeyAlgorithm, OctetKeyPairParameters, OctetKeyPairType,
+    PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::Algorithm;
+use simple_asn1::ASN1Block;
+
+use crate::shared::security::token::TokenError;
+
+const P256_COORDINATE_BYTES: usize = 32;
+const ED25519_KEY_BYTES: usize = 32;
+
+// Converte a chave publica PEM (SPKI ou PKCS#1 para RSA) em JWK publicavel em
+// `/.well-known/jwks.json`. Nunca recebe material privado.
+pub fn public_jwk(algorithm: Algorithm, kid: &str, public_pem: &[u8]) -> Result<Jwk, TokenError> {
+    let pem = pem::parse(public_pem)
+        .map_err(|err| TokenError::Key(format!("key {kid}: invalid PEM: {err}")))?;
+
+    let parameters = match algorithm {
+        Algorithm::RS256 => rsa_parameters(kid, pem.tag(), pem.contents())?,
+        Algorithm::ES256 => ec_parameters(kid, pem.contents())?,
+        Algorithm::EdDSA => ed_parameters(kid, pem.contents())?,
+        other => {
+            return Err(TokenError::Key(format!(
+                "key {kid}: algorithm {other:?} has no public key"
+            )))
+        }
+    };
+
+    let key_algorithm = match algorithm {
+        Algorithm::RS256 => KeyAlgorithm::RS256,
+        Algorithm::ES256 => KeyAlgorithm::ES256,
+        _ => KeyAlgorithm::EdDSA,
+    };
+
+    Ok(Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(key_algorithm),
+            key_id: Some(kid.to_string()),
+            ..CommonParameters::default()
+        },
+        algorithm: parameters,
+    })
+}
+
+fn rsa_parameters(kid: &str, tag: &str, der: &[u8]) -> Result<AlgorithmParameters, TokenError> {
+    // "PUBLIC KEY" embrulha o RSAPublicKey (PKCS#1) dentro de um SubjectPublicKeyInfo.
+    let pkcs1 = match tag {
+        "RSA PUBLIC KEY" => der.to_vec(),
+        "PUBLIC KEY" => subject_public_key(kid, der)?,
+        other => {
+            return Err(TokenError::Key(format!(
+                "key {kid}: unexpected PEM block {other}"
+            )))
+        }
+    };
+
+    let blocks = decode_der(kid, &pkcs1)?;
+    match blocks.first() {
+        Some(ASN1Block::Sequence(_, items)) => match items.as_slice() {
+            [ASN1Block::Integer(_, modulus), ASN1Block::Integer(_, exponent)] => {
+                Ok(AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n: URL_SAFE_NO_PAD.encode(modulus.to_bytes_be().1),
+                    e: URL_SAFE_NO_PAD.encode(exponent.to_bytes_be().1),
+                }))
+            }
+            _ => Err(malformed(kid)),
+        },
+        _ => Err(malformed(kid)),
+    }
+}
+
+fn ec_parameters(kid: &str, der: &[u8]) -> Result<AlgorithmParameters, TokenError> {
+    let point = subject_public_key(kid, der)?;
+
+    // Ponto nao comprimido: 0x04 || X || Y.
+    if point.len() != 1 + 2 * P256_COORDINATE_BYTES || point[0] != 0x04 {
+        return Err(TokenError::Key(format!(
+            "key {kid}: expected an uncompressed P-256 public key"
+        )));
+    }
+
+    let (x, y) = point[1..].split_at(P256_COORDINATE_BYTES);
+    Ok(AlgorithmParameters::EllipticCurve(
+        EllipticCurveKeyParameters {
+            key_type: EllipticCurveKeyType::EC,
+            curve: EllipticCurve::P256,
+            x: URL_SAFE_NO_PAD.encode(x),
+            y: URL_SAFE_NO_PAD.encode(y),
+        },
+    ))
+}
+
+fn ed_parameters(kid: &str, der: &[u8]) -> Result<AlgorithmParameters, TokenError> {
+    let key = subject_public_key(kid, der)?;
+
+    if key.len() != ED25519_KEY_BYTES {
+        return Err(TokenError::Key(format!(
+            "key {kid}: expected an Ed25519 public key"
+        )));
+    }
+
+    Ok(AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+        key_type: OctetKeyPairType::OctetKeyPair,
+        curve: EllipticCurve::Ed25519,
+        x: URL_SAFE_NO_PAD.encode(key),
+    }))
+}
+
+// SubjectPublicKeyInfo ::= SEQUENCE { algorithm AlgorithmIdentifier, subjectPublicKey BIT STRING }
+fn subject_public_key(kid: &str, der: &[u8]) -> Result<Vec<u8>, TokenError> {
+    let blocks = decode_der(kid, der)?;
+    match blocks.first() {
+        Some(ASN1Block::Sequence(_, items)) => match items.as_slice() {
+            [ASN1Block::Sequence(_, _), ASN1Block::BitString(_, _, key)] => Ok(key.clone()),
+            _ => Err(malformed(kid)),
+        },
+        _ => Err(malformed(kid)),
+    }
+}
+
+fn decode_der(kid: &str, der: &[u8]) -> Result<Vec<ASN1Block>, TokenError> {
+    simple_asn1::from_der(der)
+        .map_err(|err| TokenError::Key(format!("key {kid}: invalid DER: {err}")))
+}
+
+fn malformed(kid: &str) -> TokenError {
+    TokenError::Key(format!("key {kid}: malformed public key"))
+}
@@ -1,2 +1,9 @@
-﻿pub mod password;
+pub mod breached_passwords;
+pub mod hashing_pool;
+pub mod jwks;
+pub mod opaque_token;
+pub mod password;
+pub mod password_policy;
+pub mod pkce;
 pub mod token;
+pub mod totp;
@@ -0,0 +1,27 @@
+use std::fmt::Write;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+const TOKEN_BYTES: usize = 32;
+
+// Gera um token opaco com 256 bits de entropia, seguro para URLs e headers.
+pub fn generate() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// Tokens aleatorios de alta entropia nao precisam de Argon2: SHA-256 basta para
+// que um vazamento da tabela nao permita reutilizar os valores originais.
+pub fn hash(token: &str) -> String {
+    let digest = Sha256::digest(token.as_bytes());
+    digest
+        .iter()
+        .fold(String::with_capacity(digest.len() * 2), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
+}
@@ -1,8 +1,10 @@
+use std::fmt;
+
 use argon2::password_hash::rand_core::OsRng;
 use argon2::password_hash::{
     Error as PasswordHashError, PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
 };
-use argon2::Argon2;
+use argon2::{Algorithm, Argon2, KeyId, Params, ParamsBuilder, Version};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -19,17 +21,143 @@ impl From<PasswordHashError> for PasswordError {
     }
 }
 
-pub fn hash_password(password: &str) -> Result<String, PasswordError> {
+impl From<argon2::Error> for PasswordError {
+    fn from(error: argon2::Error) -> Self {
+        Self::Hash(error.to_string())
+    }
+}
+
+// Segredo do servidor misturado ao Argon2. O `key_id` vai no hash (parametro `keyid` do formato
+// PHC), o que permite trocar o pepper sem invalidar as senhas antigas.
+#[derive(Clone)]
+pub struct Pepper {
+    pub key_id: String,
+    pub secret: Vec<u8>,
+}
+
+impl fmt::Debug for Pepper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pepper")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+// Parametros usados em hashes novos. Cada hash guarda os proprios, entao hashes antigos continuam
+// verificando e sao refeitos com estes no proximo login (`needs_rehash`).
+#[derive(Debug, Clone)]
+pub struct Argon2Settings {
+    // Memoria em KiB.
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    // Pepper de `peppers` usado em hashes novos; os demais so verificam hashes antigos.
+    pub active_pepper: Option<String>,
+    pub peppers: Vec<Pepper>,
+}
+
+impl Default for Argon2Settings {
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+            active_pepper: None,
+            peppers: Vec::new(),
+        }
+    }
+}
+
+impl Argon2Settings {
+    // Para a subida: custos aceitos pelo Argon2, ids curtos o bastante para o PHC e unicos, e o
+    // pepper ativo presente na lista.
+    pub fn validate(&self) -> Result<(), String> {
+        for (index, pepper) in self.peppers.iter().enumerate() {
+            if pepper.key_id.is_empty() || pepper.secret.is_empty() {
+                return Err("pepper ids and secrets must not be empty".to_string());
+            }
+            if pepper.key_id.len() > Params::MAX_KEYID_LEN {
+                return Err(format!(
+                    "pepper id {} is longer than {} bytes",
+                    pepper.key_id,
+                    Params::MAX_KEYID_LEN
+                ));
+            }
+            if self.peppers[..index]
+                .iter()
+                .any(|other| other.key_id == pepper.key_id)
+            {
+                return Err(format!("pepper id {} is configured twice", pepper.key_id));
+            }
+        }
+        self.params()?;
+        Ok(())
+    }
+
+    fn active(&self) -> Result<Option<&Pepper>, String> {
+        self.active_pepper
+            .as_deref()
+            .map(|key_id| {
+                self.find(key_id.as_bytes())
+                    .ok_or_else(|| format!("active pepper {key_id} is not configured"))
+            })
+            .transpose()
+    }
+
+    fn find(&self, key_id: &[u8]) -> Option<&Pepper> {
+        self.peppers
+            .iter()
+            .find(|pepper| pepper.key_id.as_bytes() == key_id)
+    }
+
+    fn params(&self) -> Result<Params, String> {
+        let mut builder = ParamsBuilder::new();
+        builder
+            .m_cost(self.m_cost)
+            .t_cost(self.t_cost)
+            .p_cost(self.p_cost);
+        if let Some(pepper) = self.active()? {
+            builder.keyid(KeyId::new(pepper.key_id.as_bytes()).map_err(|err| err.to_string())?);
+        }
+        builder.build().map_err(|err| err.to_string())
+    }
+}
+
+pub fn hash_password(settings: &Argon2Settings, password: &str) -> Result<String, PasswordError> {
     let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
+    let params = settings.params().map_err(PasswordError::Hash)?;
+    let argon2 = match settings.active().map_err(PasswordError::Hash)? {
+        Some(pepper) => {
+            Argon2::new_with_secret(&pepper.secret, Algorithm::Argon2id, Version::V0x13, params)?
+        }
+        None => Argon2::new(Algorithm::Argon2id, Version::V0x13, params),
+    };
     let hash = argon2.hash_password(password.as_bytes(), &salt)?;
 
     Ok(hash.to_string())
 }
 
-pub fn verify_password(expected_hash: &str, candidate: &str) -> Result<(), PasswordError> {
+// Algoritmo, versao e custos vem do proprio hash; daqui sai apenas o pepper indicado no `keyid`.
+pub fn verify_password(
+    settings: &Argon2Settings,
+    expected_hash: &str,
+    candidate: &str,
+) -> Result<(), PasswordError> {
     let parsed_hash = PasswordHash::new(expected_hash)?;
-    let argon2 = Argon2::default();
+    let params = Params::try_from(&parsed_hash)?;
+    let argon2 = if params.keyid().is_empty() {
+        Argon2::default()
+    } else {
+        let pepper = settings.find(params.keyid()).ok_or_else(|| {
+            PasswordError::Hash("password hash uses a pepper that is not configured".to_string())
+        })?;
+        Argon2::new_with_secret(
+            &pepper.secret,
+            Algorithm::default(),
+            Version::default(),
+            Params::default(),
+        )?
+    };
 
     match argon2.verify_password(candidate.as_bytes(), &parsed_hash) {
         Ok(()) => Ok(()),
@@ -39,3 +167,24 @@ pub fn verify_password(expected_hash: &str, candidate: &str) -> Result<(), Passw
         },
     }
 }
+
+// Verdadeiro quando o hash foi gerado com algoritmo, custos ou pepper diferentes dos atuais.
+pub fn needs_rehash(settings: &Argon2Settings, hash: &str) -> bool {
+    let Ok(parsed_hash) = PasswordHash::new(hash) else {
+        return false;
+    };
+    let Ok(params) = Params::try_from(&parsed_hash) else {
+        return false;
+    };
+    let active_key_id = match settings.active() {
+        Ok(pepper) => pepper.map_or(&[][..], |pepper| pepper.key_id.as_bytes()),
+        Err(_) => return false,
+    };
+
+    parsed_hash.algorithm != Algorithm::Argon2id.ident()
+        || parsed_hash.version != Some(Version::V0x13.into())
+        || params.m_cost() != settings.m_cost
+        || params.t_cost() != settings.t_cost
+        || params.p_cost() != settings.p_cost
+        || params.keyid() != active_key_id
+}
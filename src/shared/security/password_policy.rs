@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use zxcvbn::zxcvbn;
+
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::PlainPassword;
+use crate::shared::security::breached_passwords::BreachedPasswords;
+
+// Trechos de nome ou email mais curtos que isso (`ana`, `bob`) aparecem por acaso em senhas
+// boas; eles so pesam na estimativa de forca, nao recusam a senha sozinhos.
+const MIN_PERSONAL_TOKEN_LENGTH: usize = 4;
+
+// Regras para senhas novas. Composicao (maiuscula, digito, ...) e opcional: o que decide e a
+// estimativa de forca, que aceita frases-senha longas e recusa `Password123!`.
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    // 0 a 4, na escala do zxcvbn; 0 desliga a verificacao.
+    pub min_strength_score: u8,
+    pub reject_personal_info: bool,
+    pub breached_passwords: Option<Arc<BreachedPasswords>>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 12,
+            max_length: 128,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_digit: false,
+            require_symbol: false,
+            min_strength_score: 3,
+            reject_personal_info: true,
+            breached_passwords: None,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    // `personal_info` recebe nome e email do dono da senha. O erro lista todas as regras nao
+    // atendidas, separadas por `; `, para o usuario corrigir tudo de uma vez.
+    pub fn validate(
+        &self,
+        password: &str,
+        personal_info: &[&str],
+    ) -> Result<PlainPassword, DomainError> {
+        let plain_password = PlainPassword::parse(password)?;
+        let mut unmet = Vec::new();
+
+        let length = password.chars().count();
+        if length < self.min_length {
+            unmet.push(format!(
+                "password must be at least {} characters",
+                self.min_length
+            ));
+        }
+        if length > self.max_length {
+            unmet.push(format!(
+                "password must be at most {} characters",
+                self.max_length
+            ));
+        }
+        let composition = [
+            (
+                self.require_uppercase,
+                "an uppercase letter",
+                char::is_uppercase as fn(char) -> bool,
+            ),
+            (
+                self.require_lowercase,
+                "a lowercase letter",
+                char::is_lowercase,
+            ),
+            (self.require_digit, "a digit", |c: char| c.is_ascii_digit()),
+            (self.require_symbol, "a symbol", |c: char| {
+                !c.is_alphanumeric() && !c.is_whitespace()
+            }),
+        ];
+        for (required, what, present) in composition {
+            if required && !password.chars().any(present) {
+                unmet.push(format!("password must include {what}"));
+            }
+        }
+
+        let personal = personal_tokens(personal_info);
+        let lowered = password.to_lowercase();
+        if self.reject_personal_info
+            && personal
+                .iter()
+                .filter(|token| token.chars().count() >= MIN_PERSONAL_TOKEN_LENGTH)
+                .any(|token| lowered.contains(token.as_str()))
+        {
+            unmet.push("password must not contain your name or email".to_string());
+        }
+        // Senhas acima do maximo ja foram recusadas; nao vale a pena estima-las.
+        if self.min_strength_score > 0 && length <= self.max_length {
+            let user_inputs: Vec<&str> = personal.iter().map(String::as_str).collect();
+            let score = u8::from(zxcvbn(password, &user_inputs).score());
+            if score < self.min_strength_score {
+                unmet.push(format!(
+                    "password is too easy to guess (strength {score} of 4, at least {} required)",
+                    self.min_strength_score
+                ));
+            }
+        }
+        if self
+            .breached_passwords
+            .as_ref()
+            .is_some_and(|breached| breached.contains(password))
+        {
+            unmet.push("password appears in a known data breach".to_string());
+        }
+
+        if unmet.is_empty() {
+            Ok(plain_password)
+        } else {
+            Err(DomainError::validation(unmet.join("; ")))
+        }
+    }
+}
+
+// Nome inteiro e cada palavra dele; do email, a parte local inteira e suas partes (`ana.silva+x`).
+// O dominio fica de fora: e compartilhado por muita gente e `com` aparece em `welcome`. O zxcvbn
+// recebe todos como palavras do dicionario; so os longos recusam a senha por conterem um deles.
+fn personal_tokens(personal_info: &[&str]) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for value in personal_info {
+        let value = value.trim().to_lowercase();
+        let value = value
+            .rsplit_once('@')
+            .map_or(value.as_str(), |(local, _)| local);
+        tokens.push(value.split_whitespace().collect::<String>());
+        tokens.extend(
+            value
+                .split(|c: char| c.is_whitespace() || ".+-_".contains(c))
+                .map(str::to_string),
+        );
+    }
+    tokens.retain(|token| !token.is_empty());
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
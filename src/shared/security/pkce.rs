@@ -0,0 +1,35 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+// PKCE (RFC 7636). So aceitamos S256: com `plain` quem intercepta a requisicao de autorizacao
+// tambem conhece o verificador.
+pub const METHOD_S256: &str = "S256";
+
+const MIN_VERIFIER_CHARS: usize = 43;
+const MAX_VERIFIER_CHARS: usize = 128;
+// BASE64URL sem padding de um SHA-256.
+const CHALLENGE_CHARS: usize = 43;
+
+// 43 a 128 caracteres do conjunto "unreserved" (secao 4.1).
+pub fn is_valid_verifier(verifier: &str) -> bool {
+    (MIN_VERIFIER_CHARS..=MAX_VERIFIER_CHARS).contains(&verifier.len())
+        && verifier
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"-._~".contains(&byte))
+}
+
+pub fn is_valid_challenge(challenge: &str) -> bool {
+    challenge.len() == CHALLENGE_CHARS
+        && challenge
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+}
+
+pub fn challenge_s256(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+pub fn verify_s256(verifier: &str, challenge: &str) -> bool {
+    is_valid_verifier(verifier) && challenge_s256(verifier) == challenge
+}
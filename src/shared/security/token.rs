@@ -1,16 +1,68 @@
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
 use chrono::{DateTime, Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::config::AuthConfig;
+use crate::shared::security::jwks;
+
+// Emite e valida JWTs. Com HS256 o segredo e compartilhado; com RS256/ES256/EdDSA apenas a
+// chave ativa assina, enquanto todas as chaves configuradas continuam validando tokens
+// (rotacao sem derrubar sessoes) e sao publicadas em JWKS.
 #[derive(Clone)]
 pub struct JwtManager {
+    algorithm: Algorithm,
+    signing_kid: Option<String>,
     encoding: EncodingKey,
-    decoding: DecodingKey,
+    verification: Arc<Vec<VerificationKey>>,
+    jwks: Arc<JwkSet>,
     ttl: Duration,
 }
 
+struct VerificationKey {
+    kid: Option<String>,
+    decoding: DecodingKey,
+}
+
+// Par de chaves PEM identificado por `kid`. A chave privada so e exigida para a chave ativa.
+#[derive(Clone)]
+pub struct JwtKey {
+    pub kid: String,
+    pub public_pem: Vec<u8>,
+    pub private_pem: Option<Vec<u8>>,
+}
+
+impl JwtKey {
+    pub fn load(
+        kid: &str,
+        public_key_path: &Path,
+        private_key_path: Option<&Path>,
+    ) -> Result<Self, TokenError> {
+        let read = |path: &Path| {
+            std::fs::read(path).map_err(|err| {
+                TokenError::Key(format!(
+                    "key {kid}: failed to read {}: {err}",
+                    path.display()
+                ))
+            })
+        };
+
+        Ok(Self {
+            kid: kid.to_string(),
+            public_pem: read(public_key_path)?,
+            private_pem: private_key_path.map(read).transpose()?,
+        })
+    }
+}
+
 impl JwtManager {
     pub fn new(secret: &str, ttl_minutes: i64) -> Self {
         let encoding = EncodingKey::from_secret(secret.as_bytes());
@@ -18,58 +70,303 @@ impl JwtManager {
         let ttl = Duration::minutes(ttl_minutes);
 
         Self {
+            algorithm: Algorithm::HS256,
+            signing_kid: None,
             encoding,
-            decoding,
+            verification: Arc::new(vec![VerificationKey {
+                kid: None,
+                decoding,
+            }]),
+            jwks: Arc::new(JwkSet { keys: Vec::new() }),
             ttl,
         }
     }
 
+    pub fn with_keys(
+        algorithm: Algorithm,
+        active_kid: &str,
+        keys: &[JwtKey],
+        ttl_minutes: i64,
+    ) -> Result<Self, TokenError> {
+        let active = keys
+            .iter()
+            .find(|key| key.kid == active_kid)
+            .ok_or_else(|| TokenError::Key(format!("active key {active_kid} is not configured")))?;
+        let private_pem = active.private_pem.as_deref().ok_or_else(|| {
+            TokenError::Key(format!("active key {active_kid} has no private key"))
+        })?;
+
+        let encoding = match algorithm {
+            Algorithm::RS256 => EncodingKey::from_rsa_pem(private_pem),
+            Algorithm::ES256 => EncodingKey::from_ec_pem(private_pem),
+            Algorithm::EdDSA => EncodingKey::from_ed_pem(private_pem),
+            other => {
+                return Err(TokenError::Key(format!(
+                    "algorithm {other:?} does not use key pairs"
+                )))
+            }
+        }
+        .map_err(|err| TokenError::Key(format!("key {active_kid}: {err}")))?;
+
+        let mut verification = Vec::with_capacity(keys.len());
+        let mut jwks = JwkSet { keys: Vec::new() };
+        for key in keys {
+            let decoding = match algorithm {
+                Algorithm::RS256 => DecodingKey::from_rsa_pem(&key.public_pem),
+                Algorithm::ES256 => DecodingKey::from_ec_pem(&key.public_pem),
+                _ => DecodingKey::from_ed_pem(&key.public_pem),
+            }
+            .map_err(|err| TokenError::Key(format!("key {}: {err}", key.kid)))?;
+
+            jwks.keys
+                .push(jwks::public_jwk(algorithm, &key.kid, &key.public_pem)?);
+            verification.push(VerificationKey {
+                kid: Some(key.kid.clone()),
+                decoding,
+            });
+        }
+
+        Ok(Self {
+            algorithm,
+            signing_kid: Some(active_kid.to_string()),
+            encoding,
+            verification: Arc::new(verification),
+            jwks: Arc::new(jwks),
+            ttl: Duration::minutes(ttl_minutes),
+        })
+    }
+
+    pub fn from_config(config: &AuthConfig) -> Result<Self, TokenError> {
+        let algorithm = Algorithm::from_str(config.jwt_algorithm.trim())
+            .map_err(|_| TokenError::Key(format!("unknown algorithm {}", config.jwt_algorithm)))?;
+
+        if algorithm == Algorithm::HS256 {
+            return Ok(Self::new(&config.jwt_secret, config.jwt_ttl_minutes));
+        }
+
+        let active_kid = config
+            .jwt_active_kid
+            .as_deref()
+            .ok_or_else(|| TokenError::Key("auth.jwt_active_kid is required".to_string()))?;
+        let keys = config
+            .jwt_keys
+            .iter()
+            .map(|key| {
+                JwtKey::load(
+                    &key.kid,
+                    Path::new(&key.public_key_path),
+                    key.private_key_path.as_deref().map(Path::new),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::with_keys(algorithm, active_kid, &keys, config.jwt_ttl_minutes)
+    }
+
+    // Access token de uma sessao; `session_id` vai no claim `sid`.
     pub fn generate(
         &self,
         user_id: Uuid,
         email: &str,
-        role: &str,
+        roles: &[String],
+        session_id: Uuid,
+    ) -> Result<TokenDetails, TokenError> {
+        self.encode_claims(
+            user_id,
+            email,
+            roles,
+            TokenPurpose::Access,
+            self.ttl,
+            Binding {
+                sid: Some(session_id),
+                ..Binding::default()
+            },
+        )
+    }
+
+    // Access token emitido a um cliente OAuth: leva `client_id` e o escopo concedido. Tokens de
+    // `client_credentials` nao pertencem a nenhuma sessao.
+    pub fn generate_delegated(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        roles: &[String],
+        session_id: Option<Uuid>,
+        delegation: &Delegation,
+    ) -> Result<TokenDetails, TokenError> {
+        self.encode_claims(
+            user_id,
+            email,
+            roles,
+            TokenPurpose::Access,
+            self.ttl,
+            Binding {
+                sid: session_id,
+                client_id: Some(delegation.client_id),
+                scope: Some(delegation.scope.clone()),
+            },
+        )
+    }
+
+    // Tokens de proposito restrito (ex.: desafio MFA) usam o mesmo par de chaves, mas carregam
+    // `purpose` para que nunca sejam aceitos como access token.
+    pub fn generate_for(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        roles: &[String],
+        purpose: TokenPurpose,
+        ttl: Duration,
+    ) -> Result<TokenDetails, TokenError> {
+        self.encode_claims(user_id, email, roles, purpose, ttl, Binding::default())
+    }
+
+    fn encode_claims(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        roles: &[String],
+        purpose: TokenPurpose,
+        ttl: Duration,
+        binding: Binding,
     ) -> Result<TokenDetails, TokenError> {
         let now = Utc::now();
         let exp = now
-            .checked_add_signed(self.ttl)
+            .checked_add_signed(ttl)
             .ok_or(TokenError::InvalidTtl)?
             .timestamp();
 
+        // UUIDv7: o jti guarda o instante de emissao em milissegundos (ver `Claims::issued_at`).
+        let jti = Uuid::now_v7();
         let claims = Claims {
             sub: user_id,
             email: email.to_owned(),
-            role: role.to_owned(),
+            roles: roles.to_vec(),
+            purpose,
+            sid: binding.sid,
+            client_id: binding.client_id,
+            scope: binding.scope,
+            jti,
             iat: now.timestamp(),
             exp,
         };
 
-        let token = encode(&Header::default(), &claims, &self.encoding)?;
+        let mut header = Header::new(self.algorithm);
+        header.kid = self.signing_kid.clone();
+
+        let token = encode(&header, &claims, &self.encoding)?;
         let expires_at = DateTime::from_timestamp(exp, 0).ok_or(TokenError::InvalidTtl)?;
 
-        Ok(TokenDetails { token, expires_at })
+        Ok(TokenDetails {
+            token,
+            jti,
+            expires_at,
+        })
     }
 
     pub fn verify(&self, token: &str) -> Result<Claims, TokenError> {
-        let validation = Validation::default();
-        let token = decode::<Claims>(token, &self.decoding, &validation)?;
+        let header = decode_header(token)?;
+        if header.alg != self.algorithm {
+            return Err(TokenError::Invalid(
+                "unexpected signing algorithm".to_string(),
+            ));
+        }
+
+        // Sem `kid` so aceitamos quando ha uma unica chave (HS256 ou deploy sem rotacao).
+        let key = match header.kid.as_deref() {
+            Some(kid) => self
+                .verification
+                .iter()
+                .find(|key| key.kid.as_deref() == Some(kid)),
+            None if self.verification.len() == 1 => self.verification.first(),
+            None => None,
+        }
+        .ok_or_else(|| TokenError::Invalid("unknown signing key".to_string()))?;
+
+        let validation = Validation::new(self.algorithm);
+        let token = decode::<Claims>(token, &key.decoding, &validation)?;
 
         Ok(token.claims)
     }
+
+    pub fn jwks(&self) -> &JwkSet {
+        &self.jwks
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: Uuid,
     pub email: String,
-    pub role: String,
+    // Informativo para clientes; a autorizacao usa os papeis atuais do cadastro.
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub purpose: TokenPurpose,
+    // Sessao de origem; ausente nos tokens de desafio e nos emitidos antes do inventario.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
+    // Cliente OAuth que recebeu o token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<Uuid>,
+    // Escopos concedidos ao cliente OAuth, separados por espaco; limitam as permissoes dos papeis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    pub jti: Uuid,
     pub iat: i64,
     pub exp: i64,
 }
 
+impl Claims {
+    // Instante de emissao em milissegundos, tirado do jti; o `iat` so tem segundos. Tokens
+    // emitidos antes do jti UUIDv7 ficam com o `iat`.
+    pub fn issued_at(&self) -> Option<DateTime<Utc>> {
+        match self.jti.get_timestamp() {
+            Some(timestamp) => {
+                let (seconds, nanos) = timestamp.to_unix();
+                DateTime::from_timestamp(i64::try_from(seconds).ok()?, nanos)
+            }
+            None => DateTime::from_timestamp(self.iat, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenPurpose {
+    #[default]
+    Access,
+    MfaChallenge,
+    MfaEnrollment,
+    // Emitido no login quando a senha passou da validade maxima; so serve para troca-la.
+    PasswordChange,
+    // Link de confirmacao enviado por email; `email` no token e o endereco sendo confirmado.
+    EmailVerification,
+}
+
+// Cliente OAuth e escopo (ja formatado) de um token delegado.
+#[derive(Debug, Clone)]
+pub struct Delegation {
+    pub client_id: Uuid,
+    pub scope: String,
+}
+
+// Claims que ligam o token a uma sessao ou a um cliente OAuth.
+#[derive(Debug, Default)]
+struct Binding {
+    sid: Option<Uuid>,
+    client_id: Option<Uuid>,
+    scope: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenDetails {
     pub token: String,
+    pub jti: Uuid,
     pub expires_at: DateTime<Utc>,
 }
 
@@ -81,6 +378,8 @@ pub enum TokenError {
     Expired,
     #[error("invalid token ttl")]
     InvalidTtl,
+    #[error("invalid signing key configuration: {0}")]
+    Key(String),
 }
 
 impl From<jsonwebtoken::errors::Error> for TokenError {
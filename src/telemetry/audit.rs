@@ -2,7 +2,7 @@
 
 use crate::shared::validation::sanitize_for_logging;
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct AuditLogger;
 
 impl AuditLogger {
@@ -1,10 +1,10 @@
 ﻿use axum_prometheus::{metrics_exporter_prometheus::PrometheusHandle, PrometheusMetricLayer};
-use metrics::counter;
+use metrics::{counter, gauge, histogram};
 
 pub type MetricsHandle = PrometheusHandle;
 pub type MetricsLayer = PrometheusMetricLayer<'static>;
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct AppMetrics;
 
 impl AppMetrics {
@@ -21,6 +21,11 @@ impl AppMetrics {
     const USER_DELETE_SUCCESS: &'static str = "app_user_delete_success_total";
     const USER_DELETE_ERROR: &'static str = "app_user_delete_error_total";
 
+    const PASSWORD_HASH_QUEUE_DEPTH: &'static str = "app_password_hash_queue_depth";
+    const PASSWORD_HASH_WAIT: &'static str = "app_password_hash_wait_seconds";
+    const PASSWORD_HASH_DURATION: &'static str = "app_password_hash_duration_seconds";
+    const PASSWORD_HASH_REJECTED: &'static str = "app_password_hash_rejected_total";
+
     pub fn new() -> Self {
         Self
     }
@@ -39,6 +44,26 @@ impl AppMetrics {
         }
     }
 
+    pub fn record_password_hash_queue_depth(&self, depth: usize) {
+        gauge!(Self::PASSWORD_HASH_QUEUE_DEPTH).set(depth as f64);
+    }
+
+    // `operation` e `hash` ou `verify`; espera na fila e tempo de Argon2 ficam separados.
+    pub fn record_password_hash(
+        &self,
+        operation: &'static str,
+        waited: std::time::Duration,
+        duration: std::time::Duration,
+    ) {
+        histogram!(Self::PASSWORD_HASH_WAIT, "operation" => operation).record(waited.as_secs_f64());
+        histogram!(Self::PASSWORD_HASH_DURATION, "operation" => operation)
+            .record(duration.as_secs_f64());
+    }
+
+    pub fn record_password_hash_rejected(&self, operation: &'static str) {
+        counter!(Self::PASSWORD_HASH_REJECTED, "operation" => operation).increment(1);
+    }
+
     fn counter_metric(operation: &str, outcome: &str) -> Option<&'static str> {
         match (operation, outcome) {
             ("create", "success") => Some(Self::USER_CREATE_SUCCESS),
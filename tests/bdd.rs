@@ -1,26 +1,308 @@
 mod support;
 
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 
+use axum::extract::{ConnectInfo, Query};
+use axum::http::{header, Extensions, HeaderMap, HeaderValue};
+use chrono::Utc;
 use cucumber::{given, then, when, World as _};
-use webrust::application::services::auth_service::{AuthService, AuthSession};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use sha1::{Digest, Sha1};
+use uuid::Uuid;
+use webrust::application::dtos::invitation::CreateInvitationDto;
+use webrust::application::dtos::oauth::{
+    AuthorizationRequestDto, CreateOAuthClientDto, TokenRequestDto,
+};
+use webrust::application::dtos::registration::RegisterRequestDto;
+use webrust::application::dtos::role::{CreateRoleDto, UpdateRoleDto};
+use webrust::application::dtos::session::SessionDto;
+use webrust::application::dtos::user::{
+    ChangeEmailDto, ChangeUserStatusDto, CreateUserDto, ListUsersQueryDto, UpdateProfileDto,
+    UpdateUserDto, UserPageDto,
+};
+use webrust::application::services::auth_service::{
+    AuthService, AuthSession, AuthSettings, AuthenticatedUser, LoginOutcome,
+};
+use webrust::application::services::email_outbox_service::EmailOutboxService;
+use webrust::application::services::email_verification_service::{
+    EmailVerificationService, EmailVerificationSettings,
+};
+use webrust::application::services::invitation_service::{InvitationService, InvitationSettings};
+use webrust::application::services::login_throttle_service::{LockoutPolicy, LoginThrottleService};
+use webrust::application::services::mfa_service::MfaService;
+use webrust::application::services::oauth_service::{
+    AuthorizationOutcome, BrowserCredentials, BrowserSignIn, BrowserStep, ClientCredentials,
+    IssuedOAuthTokens, OAuthError, OAuthService, OAuthSettings,
+};
+use webrust::application::services::password_history_service::PasswordHistoryService;
+use webrust::application::services::password_reset_service::{
+    PasswordResetService, PasswordResetSettings,
+};
+use webrust::application::services::personal_access_token_service::{
+    PersonalAccessTokenService, PersonalAccessTokenSettings,
+};
+use webrust::application::services::registration_service::{
+    RegistrationMode, RegistrationService, RegistrationSettings,
+};
+use webrust::application::services::role_service::RoleService;
+use webrust::application::services::session_service::SessionService;
+use webrust::application::services::token_revocation_service::TokenRevocationService;
 use webrust::application::services::user_service::UserService;
+use webrust::domain::entities::session::SessionClient;
+use webrust::domain::entities::user::{User, UserStatus};
+use webrust::domain::mailer::EmailMessage;
+use webrust::domain::repositories::login_throttle_repository::LoginThrottleRepository;
+use webrust::domain::repositories::mfa_repository::MfaRepository;
+use webrust::domain::repositories::oauth_authorization_code_repository::AuthorizationCodeRepository;
+use webrust::domain::repositories::oauth_client_repository::OAuthClientRepository;
+use webrust::domain::repositories::oauth_consent_repository::OAuthConsentRepository;
+use webrust::domain::repositories::password_history_repository::PasswordHistoryRepository;
+use webrust::domain::repositories::password_reset_repository::PasswordResetRepository;
+use webrust::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+use webrust::domain::repositories::registration_repository::RegistrationRepository;
+use webrust::domain::repositories::role_repository::RoleRepository;
+use webrust::domain::repositories::session_repository::SessionRepository;
+use webrust::domain::repositories::token_revocation_repository::TokenRevocationRepository;
 use webrust::domain::repositories::user_repository::UserRepository;
-use webrust::shared::error::AppError;
-use webrust::shared::security::token::JwtManager;
+use webrust::domain::value_objects::EmailAddress;
+use webrust::presentation::http::{client_ip, etag};
+use webrust::shared::error::{AppError, AppResult};
+use webrust::shared::security::breached_passwords::BreachedPasswords;
+use webrust::shared::security::hashing_pool::{HashingPool, HashingPoolSettings};
+use webrust::shared::security::password::Pepper;
+use webrust::shared::security::password_policy::PasswordPolicy;
+use webrust::shared::security::token::{Claims, JwtKey, JwtManager};
+use webrust::shared::security::totp;
+use webrust::shared::security::{opaque_token, pkce};
+use webrust::telemetry::AppMetrics;
 
-use support::InMemoryUserRepository;
+use support::{
+    InMemoryAuthorizationCodeRepository, InMemoryEmailOutboxRepository,
+    InMemoryInvitationRepository, InMemoryLoginThrottleRepository, InMemoryMailer,
+    InMemoryMfaRepository, InMemoryOAuthClientRepository, InMemoryOAuthConsentRepository,
+    InMemoryPasswordHistoryRepository, InMemoryPasswordResetRepository,
+    InMemoryPersonalAccessTokenRepository, InMemoryRefreshTokenRepository,
+    InMemoryRegistrationRepository, InMemoryRoleRepository, InMemorySessionRepository,
+    InMemoryTokenRevocationRepository, InMemoryUserRepository,
+};
+
+// Repositorios em memoria compartilhados entre reconstrucoes dos servicos (ex.: rotacao de chaves).
+#[derive(Clone)]
+struct Backends {
+    users: Arc<dyn UserRepository>,
+    // O mesmo repositorio de `users`, para os passos que manipulam o cadastro diretamente.
+    user_records: InMemoryUserRepository,
+    refresh_tokens: Arc<dyn RefreshTokenRepository>,
+    sessions: SessionService,
+    // O mesmo repositorio de `sessions`, para simular a passagem do tempo.
+    session_records: InMemorySessionRepository,
+    revocations: TokenRevocationService,
+    mfa: MfaService,
+    roles: RoleService,
+    login_throttles: Arc<dyn LoginThrottleRepository>,
+    password_history: Arc<dyn PasswordHistoryRepository>,
+    hasher: HashingPool,
+    password_resets: Arc<dyn PasswordResetRepository>,
+    outbox: EmailOutboxService,
+    mailer: InMemoryMailer,
+    personal_access_tokens: PersonalAccessTokenService,
+    invitations: InvitationService,
+    registrations: Arc<dyn RegistrationRepository>,
+    oauth_clients: Arc<dyn OAuthClientRepository>,
+    oauth_codes: Arc<dyn AuthorizationCodeRepository>,
+    oauth_consents: Arc<dyn OAuthConsentRepository>,
+}
+
+impl Backends {
+    fn in_memory() -> Self {
+        let revocation_repository: Arc<dyn TokenRevocationRepository> =
+            Arc::new(InMemoryTokenRevocationRepository::new());
+        let mfa_repository: Arc<dyn MfaRepository> = Arc::new(InMemoryMfaRepository::new());
+        let mailer = InMemoryMailer::new();
+        let user_records = InMemoryUserRepository::new();
+        let users: Arc<dyn UserRepository> = Arc::new(user_records.clone());
+        let role_repository: Arc<dyn RoleRepository> = Arc::new(InMemoryRoleRepository::new());
+        let roles = RoleService::new(role_repository.clone(), users.clone());
+        let hasher = HashingPool::default();
+        let refresh_tokens: Arc<dyn RefreshTokenRepository> =
+            Arc::new(InMemoryRefreshTokenRepository::new());
+        let session_records = InMemorySessionRepository::new();
+        let sessions = SessionService::new(
+            Arc::new(session_records.clone()),
+            refresh_tokens.clone(),
+            users.clone(),
+            roles.clone(),
+        );
+        let registrations: Arc<dyn RegistrationRepository> =
+            Arc::new(InMemoryRegistrationRepository::new(&user_records));
+        let invitations = InMemoryInvitationRepository::new(&user_records);
+        let outbox = EmailOutboxService::new(
+            Arc::new(InMemoryEmailOutboxRepository::new()),
+            Arc::new(mailer.clone()),
+            5,
+        );
+
+        Self {
+            users: users.clone(),
+            user_records,
+            refresh_tokens,
+            sessions: sessions.clone(),
+            session_records,
+            revocations: TokenRevocationService::new(revocation_repository, sessions),
+            mfa: MfaService::new(mfa_repository, role_repository, hasher.clone(), "WebRust"),
+            roles: roles.clone(),
+            login_throttles: Arc::new(InMemoryLoginThrottleRepository::new()),
+            password_history: Arc::new(InMemoryPasswordHistoryRepository::new()),
+            hasher: hasher.clone(),
+            password_resets: Arc::new(InMemoryPasswordResetRepository::new()),
+            outbox: outbox.clone(),
+            mailer,
+            personal_access_tokens: PersonalAccessTokenService::new(
+                Arc::new(InMemoryPersonalAccessTokenRepository::new()),
+                users.clone(),
+                roles.clone(),
+                PersonalAccessTokenSettings::default(),
+            ),
+            invitations: InvitationService::new(
+                users,
+                Arc::new(invitations),
+                roles,
+                outbox,
+                hasher,
+                PasswordPolicy::default(),
+                InvitationSettings::default(),
+            ),
+            registrations,
+            oauth_clients: Arc::new(InMemoryOAuthClientRepository::new()),
+            oauth_codes: Arc::new(InMemoryAuthorizationCodeRepository::new()),
+            oauth_consents: Arc::new(InMemoryOAuthConsentRepository::new()),
+        }
+    }
+}
+
+// Latencias e erros de logins recusados, medidos para uma conta existente e uma inexistente.
+#[derive(Debug)]
+struct LoginTimings {
+    known: Vec<std::time::Duration>,
+    unknown: Vec<std::time::Duration>,
+    known_error: String,
+    unknown_error: String,
+}
 
 #[derive(Default, cucumber::World)]
 pub struct AppWorld {
+    #[world(skip)]
+    backends: Option<Backends>,
+    #[world(skip)]
+    jwt_manager: Option<JwtManager>,
     #[world(skip)]
     user_service: Option<UserService>,
     #[world(skip)]
     auth_service: Option<AuthService>,
     #[world(skip)]
+    password_reset_service: Option<PasswordResetService>,
+    #[world(skip)]
     last_auth_session: Option<AuthSession>,
     #[world(skip)]
     last_error: Option<AppError>,
+    #[world(skip)]
+    access_token: Option<String>,
+    #[world(skip)]
+    refresh_token: Option<String>,
+    #[world(skip)]
+    previous_refresh_token: Option<String>,
+    #[world(skip)]
+    mfa_challenge: Option<(String, String)>,
+    #[world(skip)]
+    totp_secret: Option<String>,
+    #[world(skip)]
+    otpauth_uri: Option<String>,
+    #[world(skip)]
+    last_totp_code: Option<String>,
+    #[world(skip)]
+    recovery_codes: Vec<String>,
+    #[world(skip)]
+    lockout_policy: Option<LockoutPolicy>,
+    // Endereco e `User-Agent` enviados nos logins.
+    #[world(skip)]
+    client: SessionClient,
+    #[world(skip)]
+    password_reset_ttl: Option<chrono::Duration>,
+    #[world(skip)]
+    personal_access_token: Option<String>,
+    // Ultimo access token emitido por email, para alternar entre usuarios no mesmo cenario.
+    #[world(skip)]
+    sessions: HashMap<String, String>,
+    #[world(skip)]
+    listed_users: Option<UserPageDto>,
+    #[world(skip)]
+    user_query: String,
+    #[world(skip)]
+    fetched_etag: Option<HeaderValue>,
+    // Ids dos usuarios excluidos, que deixam de ser encontrados por email.
+    #[world(skip)]
+    deleted_users: HashMap<String, Uuid>,
+    #[world(skip)]
+    purged_users: Option<u64>,
+    #[world(skip)]
+    require_verified_email: bool,
+    #[world(skip)]
+    registration_service: Option<RegistrationService>,
+    #[world(skip)]
+    registration_settings: RegistrationSettings,
+    #[world(skip)]
+    password_policy: PasswordPolicy,
+    #[world(skip)]
+    password_history_size: usize,
+    #[world(skip)]
+    password_max_age: Option<chrono::Duration>,
+    // Substitui o pool de hashing dos servicos reconstruidos, montado a partir de
+    // `hashing_settings`.
+    #[world(skip)]
+    hashing_pool: Option<HashingPool>,
+    #[world(skip)]
+    hashing_settings: Option<HashingPoolSettings>,
+    // Trabalhos que ocupam os workers ate o canal ser fechado.
+    #[world(skip)]
+    busy_hashing_jobs: Vec<(
+        std::sync::mpsc::Sender<()>,
+        tokio::task::JoinHandle<AppResult<()>>,
+    )>,
+    #[world(skip)]
+    login_timings: Option<LoginTimings>,
+    #[world(skip)]
+    listed_sessions: Option<Vec<SessionDto>>,
+    // Clientes OAuth registrados no cenario, por nome, com o segredo dos confidenciais.
+    #[world(skip)]
+    oauth_clients: HashMap<String, (Uuid, Option<String>)>,
+    #[world(skip)]
+    authorization_code_ttl: Option<chrono::Duration>,
+    // Ultimo pedido de autorizacao e o verificador PKCE correspondente.
+    #[world(skip)]
+    authorization_request: Option<AuthorizationRequestDto>,
+    #[world(skip)]
+    code_verifier: Option<String>,
+    #[world(skip)]
+    authorization_outcome: Option<AuthorizationOutcome>,
+    #[world(skip)]
+    authorization_code: Option<String>,
+    #[world(skip)]
+    oauth_tokens: Option<IssuedOAuthTokens>,
+    #[world(skip)]
+    previous_oauth_tokens: Option<IssuedOAuthTokens>,
+    #[world(skip)]
+    oauth_error: Option<OAuthError>,
+    // Cookie de sessao da pagina de autorizacao e o ultimo passo mostrado ao navegador.
+    #[world(skip)]
+    browser_session: Option<String>,
+    #[world(skip)]
+    browser_step: Option<BrowserStep>,
+    #[world(skip)]
+    csrf_token: Option<String>,
 }
 
 impl std::fmt::Debug for AppWorld {
@@ -30,23 +312,99 @@ impl std::fmt::Debug for AppWorld {
             .field("has_auth_service", &self.auth_service.is_some())
             .field("last_auth_session", &self.last_auth_session)
             .field("last_error", &self.last_error)
+            .field("has_refresh_token", &self.refresh_token.is_some())
             .finish()
     }
 }
 
 impl AppWorld {
     fn ensure_services(&mut self) {
-        if self.user_service.is_some() && self.auth_service.is_some() {
+        if self.user_service.is_some()
+            && self.auth_service.is_some()
+            && self.password_reset_service.is_some()
+            && self.registration_service.is_some()
+        {
             return;
         }
 
-        let repository: Arc<dyn UserRepository> = Arc::new(InMemoryUserRepository::new());
-        let user_service = UserService::new(repository.clone());
-        let jwt_manager = JwtManager::new("test-secret", 60);
-        let auth_service = AuthService::new(repository, jwt_manager);
+        let backends = self
+            .backends
+            .get_or_insert_with(Backends::in_memory)
+            .clone();
+        let jwt_manager = self
+            .jwt_manager
+            .clone()
+            .unwrap_or_else(|| JwtManager::new("test-secret", 60));
+        // Sem back-off por padrao para que os cenarios possam repetir tentativas imediatamente.
+        let lockout_policy = self.lockout_policy.clone().unwrap_or(LockoutPolicy {
+            backoff_base: chrono::Duration::zero(),
+            backoff_max: chrono::Duration::zero(),
+            ..LockoutPolicy::default()
+        });
+        let hasher = self
+            .hashing_pool
+            .clone()
+            .unwrap_or_else(|| backends.hasher.clone());
+        let auth_service = AuthService::new(
+            backends.users.clone(),
+            backends.refresh_tokens,
+            backends.sessions,
+            backends.revocations,
+            backends.mfa,
+            backends.roles.clone(),
+            LoginThrottleService::new(backends.login_throttles, lockout_policy),
+            PasswordHistoryService::new(
+                backends.password_history,
+                hasher.clone(),
+                self.password_history_size,
+            ),
+            hasher.clone(),
+            jwt_manager.clone(),
+            AuthSettings {
+                require_verified_email: self.require_verified_email,
+                password_policy: self.password_policy.clone(),
+                password_max_age: self.password_max_age,
+                ..AuthSettings::default()
+            },
+        );
+        let email_verification_service = EmailVerificationService::new(
+            backends.users.clone(),
+            backends.outbox.clone(),
+            jwt_manager,
+            EmailVerificationSettings::default(),
+        );
+        let user_service = UserService::new(
+            backends.users.clone(),
+            backends.roles,
+            auth_service.clone(),
+            email_verification_service.clone(),
+        );
+        let registration_service = RegistrationService::new(
+            backends.users.clone(),
+            backends.registrations,
+            email_verification_service,
+            backends.outbox.clone(),
+            hasher,
+            self.password_policy.clone(),
+            self.registration_settings.clone(),
+        );
+        let password_reset_service = PasswordResetService::new(
+            backends.users,
+            backends.password_resets,
+            backends.outbox,
+            auth_service.clone(),
+            PasswordResetSettings {
+                token_ttl: self
+                    .password_reset_ttl
+                    .unwrap_or_else(|| PasswordResetSettings::default().token_ttl),
+                ..PasswordResetSettings::default()
+            },
+        );
 
         self.user_service = Some(user_service);
         self.auth_service = Some(auth_service);
+        self.password_reset_service = Some(password_reset_service);
+        self.registration_service = Some(registration_service);
     }
 
     fn user_service(&mut self) -> &mut UserService {
@@ -63,10 +421,215 @@ impl AppWorld {
             .expect("auth service should be initialised")
     }
 
+    async fn current_user(&mut self) -> AuthenticatedUser {
+        let token = self
+            .access_token
+            .clone()
+            .expect("an access token should have been issued");
+        self.auth_service()
+            .verify(&token)
+            .await
+            .expect("access token should be valid")
+    }
+
+    fn mfa_service(&mut self) -> MfaService {
+        self.ensure_services();
+        self.backends
+            .as_ref()
+            .expect("backends should be initialised")
+            .mfa
+            .clone()
+    }
+
+    fn password_reset_service(&mut self) -> PasswordResetService {
+        self.ensure_services();
+        self.password_reset_service
+            .clone()
+            .expect("password reset service should be initialised")
+    }
+
+    fn backends(&mut self) -> Backends {
+        self.ensure_services();
+        self.backends
+            .clone()
+            .expect("backends should be initialised")
+    }
+
+    // O job de despacho roda em segundo plano na aplicacao; aqui o outbox e esvaziado na hora.
+    async fn dispatch_emails(&mut self) {
+        self.backends()
+            .outbox
+            .dispatch_pending()
+            .await
+            .expect("outbox dispatch should succeed");
+    }
+
+    async fn emails_sent(&mut self, recipient: &str, subject: &str) -> Vec<EmailMessage> {
+        self.dispatch_emails().await;
+        self.backends()
+            .mailer
+            .sent_to(recipient)
+            .await
+            .into_iter()
+            .filter(|message| message.subject == subject)
+            .collect()
+    }
+
+    // Le o token do link enviado por email (o `n`-esimo email com o assunto dado).
+    async fn emailed_token(
+        &mut self,
+        recipient: &str,
+        subject: &str,
+        index: Option<usize>,
+    ) -> String {
+        let sent = self.emails_sent(recipient, subject).await;
+        let message = match index {
+            Some(index) => sent.get(index),
+            None => sent.last(),
+        }
+        .unwrap_or_else(|| panic!("an email \"{subject}\" should have been delivered"));
+        message
+            .body
+            .split_whitespace()
+            .find_map(|word| {
+                word.split_once("token=")
+                    .map(|(_, token)| token.to_string())
+            })
+            .expect("the email should contain a link with a token")
+    }
+
+    fn registration_service(&mut self) -> RegistrationService {
+        self.ensure_services();
+        self.registration_service
+            .clone()
+            .expect("registration service should be initialised")
+    }
+
+    fn invitations(&mut self) -> InvitationService {
+        self.backends().invitations
+    }
+
+    fn personal_access_tokens(&mut self) -> PersonalAccessTokenService {
+        self.backends().personal_access_tokens
+    }
+
+    async fn personal_access_token_user(&mut self) -> AppResult<AuthenticatedUser> {
+        let token = self
+            .personal_access_token
+            .clone()
+            .expect("a personal access token should have been created");
+        self.personal_access_tokens().authenticate(&token).await
+    }
+
+    fn lockout_policy(&mut self) -> &mut LockoutPolicy {
+        self.auth_service = None;
+        self.lockout_policy.get_or_insert_with(|| LockoutPolicy {
+            backoff_base: chrono::Duration::zero(),
+            backoff_max: chrono::Duration::zero(),
+            ..LockoutPolicy::default()
+        })
+    }
+
     fn clear_results(&mut self) {
         self.last_auth_session = None;
         self.last_error = None;
+        self.mfa_challenge = None;
+    }
+
+    // O passo atual ja foi consumido na confirmacao; o seguinte ainda cabe na janela de tolerancia.
+    fn next_totp_code(&self) -> String {
+        let secret = self
+            .totp_secret
+            .as_deref()
+            .expect("a TOTP secret should have been enrolled");
+        totp::code_at(secret, totp::step_at(Utc::now()) + 1).expect("secret should be valid")
+    }
+
+    fn challenge_token(&self) -> String {
+        self.mfa_challenge
+            .as_ref()
+            .map(|(_, token)| token.clone())
+            .expect("an MFA challenge should have been issued")
+    }
+
+    fn record_login(&mut self, result: AppResult<LoginOutcome>) {
+        self.mfa_challenge = None;
+        self.record_outcome(result);
     }
+
+    // Nao descarta o desafio atual, para que os cenarios possam reapresenta-lo.
+    fn record_outcome(&mut self, result: AppResult<LoginOutcome>) {
+        match result {
+            Ok(LoginOutcome::Authenticated(session)) => self.record_session(Ok(session)),
+            Ok(LoginOutcome::MfaRequired(challenge)) => {
+                self.last_auth_session = None;
+                self.last_error = None;
+                self.mfa_challenge = Some(("mfa_required".to_string(), challenge.token));
+            }
+            Ok(LoginOutcome::MfaEnrollmentRequired(challenge)) => {
+                self.last_auth_session = None;
+                self.last_error = None;
+                self.mfa_challenge = Some(("mfa_enrollment_required".to_string(), challenge.token));
+            }
+            Ok(LoginOutcome::PasswordChangeRequired(challenge)) => {
+                self.last_auth_session = None;
+                self.last_error = None;
+                self.mfa_challenge =
+                    Some(("password_change_required".to_string(), challenge.token));
+            }
+            Err(err) => self.record_session(Err(err)),
+        }
+    }
+
+    fn record_session(&mut self, result: AppResult<AuthSession>) {
+        match result {
+            Ok(session) => {
+                self.previous_refresh_token = self.refresh_token.take();
+                self.refresh_token = Some(session.refresh_token.clone());
+                self.access_token = Some(session.token.clone());
+                self.sessions
+                    .insert(session.user.email.clone(), session.token.clone());
+                self.last_auth_session = Some(session);
+                self.last_error = None;
+            }
+            Err(err) => {
+                self.last_auth_session = None;
+                self.last_error = Some(err);
+            }
+        }
+    }
+}
+
+fn fixture_key(kid: &str, with_private_key: bool) -> JwtKey {
+    let dir = PathBuf::from("tests/fixtures/jwt");
+    let private_key = dir.join(format!("{kid}.key.pem"));
+    JwtKey::load(
+        kid,
+        &dir.join(format!("{kid}.pub.pem")),
+        with_private_key.then_some(private_key.as_path()),
+    )
+    .expect("fixture key should load")
+}
+
+#[given(regex = r#"access tokens are signed with "(?P<algorithm>[^"]+)" key "(?P<kid>[^"]+)""#)]
+async fn access_tokens_signed_with(world: &mut AppWorld, algorithm: String, kid: String) {
+    let algorithm = Algorithm::from_str(&algorithm).expect("unknown algorithm");
+    let manager = JwtManager::with_keys(algorithm, &kid, &[fixture_key(&kid, true)], 60)
+        .expect("signing key should be valid");
+    world.jwt_manager = Some(manager);
+    world.auth_service = None;
+}
+
+#[when(
+    regex = r#"the "(?P<algorithm>[^"]+)" signing key rotates from "(?P<old>[^"]+)" to "(?P<new>[^"]+)""#
+)]
+async fn signing_key_rotates(world: &mut AppWorld, algorithm: String, old: String, new: String) {
+    let algorithm = Algorithm::from_str(&algorithm).expect("unknown algorithm");
+    let keys = [fixture_key(&old, false), fixture_key(&new, true)];
+    let manager =
+        JwtManager::with_keys(algorithm, &new, &keys, 60).expect("signing keys should be valid");
+    world.jwt_manager = Some(manager);
+    world.auth_service = None;
 }
 
 #[given(
@@ -85,16 +648,113 @@ async fn an_admin_account(world: &mut AppWorld, name: String, email: String, pas
     regex = r#"I authenticate with email "(?P<email>[^"]+)" and password "(?P<password>[^"]+)""#
 )]
 async fn i_authenticate(world: &mut AppWorld, email: String, password: String) {
-    match world.auth_service().authenticate(&email, &password).await {
-        Ok(session) => {
-            world.last_auth_session = Some(session);
-            world.last_error = None;
-        }
-        Err(err) => {
-            world.last_auth_session = None;
-            world.last_error = Some(err);
-        }
-    }
+    let client = world.client.clone();
+    let result = world
+        .auth_service()
+        .authenticate(&email, &password, &client)
+        .await;
+    world.record_login(result);
+}
+
+#[when("I refresh the session")]
+async fn i_refresh_the_session(world: &mut AppWorld) {
+    let token = world
+        .refresh_token
+        .clone()
+        .expect("a refresh token should have been issued");
+    let result = world.auth_service().refresh(&token).await;
+    world.record_session(result);
+}
+
+#[when("I replay the previous refresh token")]
+async fn i_replay_the_previous_refresh_token(world: &mut AppWorld) {
+    let token = world
+        .previous_refresh_token
+        .clone()
+        .expect("a rotated refresh token should be available");
+    let result = world.auth_service().refresh(&token).await;
+    world.record_session(result);
+}
+
+#[when("I log out")]
+async fn i_log_out(world: &mut AppWorld) {
+    let actor = world.current_user().await;
+    world
+        .auth_service()
+        .logout(&actor, None)
+        .await
+        .expect("logout should succeed");
+}
+
+#[when("I log out including the refresh token")]
+async fn i_log_out_including_refresh_token(world: &mut AppWorld) {
+    let actor = world.current_user().await;
+    let refresh_token = world.refresh_token.clone();
+    world
+        .auth_service()
+        .logout(&actor, refresh_token.as_deref())
+        .await
+        .expect("logout should succeed");
+}
+
+#[when("I revoke all of my sessions as admin")]
+async fn i_revoke_all_my_sessions(world: &mut AppWorld) {
+    let actor = world.current_user().await;
+    world
+        .auth_service()
+        .revoke_all_sessions(&actor, actor.id)
+        .await
+        .expect("session revocation should succeed");
+}
+
+#[then(regex = r#"the access token is rejected with message "(?P<message>[^"]+)""#)]
+async fn access_token_rejected(world: &mut AppWorld, message: String) {
+    let token = world
+        .access_token
+        .clone()
+        .expect("an access token should have been issued");
+    let err = world
+        .auth_service()
+        .verify(&token)
+        .await
+        .expect_err("expected access token to be rejected");
+    assert!(
+        err.to_string().contains(&message),
+        "expected error to contain '{message}', got '{}'",
+        err
+    );
+}
+
+#[then(regex = r#"the access token header names key "(?P<kid>[^"]+)""#)]
+async fn access_token_header_names_key(world: &mut AppWorld, kid: String) {
+    let token = world
+        .access_token
+        .clone()
+        .expect("an access token should have been issued");
+    let header = decode_header(&token).expect("token header should decode");
+    assert_eq!(header.kid.as_deref(), Some(kid.as_str()));
+}
+
+#[then("the access token is accepted")]
+async fn access_token_accepted(world: &mut AppWorld) {
+    let user = world.current_user().await;
+    assert!(!user.email.is_empty());
+}
+
+#[then(regex = r#"the published JWKS verifies the access token with key "(?P<kid>[^"]+)""#)]
+async fn jwks_verifies_access_token(world: &mut AppWorld, kid: String) {
+    let token = world
+        .access_token
+        .clone()
+        .expect("an access token should have been issued");
+    let jwks = world.auth_service().jwks().clone();
+    let jwk = jwks.find(&kid).expect("kid should be published");
+    let algorithm = decode_header(&token)
+        .expect("token header should decode")
+        .alg;
+    let key = DecodingKey::from_jwk(jwk).expect("published JWK should be usable");
+    decode::<Claims>(&token, &key, &Validation::new(algorithm))
+        .expect("token should verify with the published key");
 }
 
 #[then("the authentication succeeds")]
@@ -106,13 +766,17 @@ async fn authentication_succeeds(world: &mut AppWorld) {
     );
 }
 
-#[then(regex = r#"the returned user role is "(?P<role>[^"]+)""#)]
+#[then(regex = r#"the returned user has role "(?P<role>[^"]+)""#)]
 async fn returned_user_role(world: &mut AppWorld, role: String) {
     let session = world
         .last_auth_session
         .as_ref()
         .expect("authentication session should be present");
-    assert_eq!(session.user.role.as_str(), role);
+    assert!(
+        session.user.roles.iter().any(|name| name.as_str() == role),
+        "expected role {role}, got {:?}",
+        session.user.roles
+    );
 }
 
 #[then("the access token is issued")]
@@ -124,6 +788,23 @@ async fn access_token_issued(world: &mut AppWorld) {
     assert!(!session.token.is_empty(), "token must not be empty");
 }
 
+#[then("a new refresh token is issued")]
+async fn new_refresh_token_issued(world: &mut AppWorld) {
+    let session = world
+        .last_auth_session
+        .as_ref()
+        .expect("expected session to be present");
+    assert!(
+        !session.refresh_token.is_empty(),
+        "refresh token must not be empty"
+    );
+    assert_ne!(
+        Some(&session.refresh_token),
+        world.previous_refresh_token.as_ref(),
+        "refresh token must change on rotation"
+    );
+}
+
 #[then(regex = r#"the authentication fails with message "(?P<message>[^"]+)""#)]
 async fn authentication_fails(world: &mut AppWorld, message: String) {
     let err = world
@@ -145,7 +826,2394 @@ async fn no_access_token_issued(world: &mut AppWorld) {
     );
 }
 
+#[when("I enroll in TOTP")]
+async fn i_enroll_in_totp(world: &mut AppWorld) {
+    let actor = world.current_user().await;
+    let enrollment = world
+        .mfa_service()
+        .begin_totp_enrollment(&actor)
+        .await
+        .expect("TOTP enrollment should start");
+    world.totp_secret = Some(enrollment.secret);
+    world.otpauth_uri = Some(enrollment.otpauth_uri);
+}
+
+#[when("I enroll in TOTP using the challenge token")]
+async fn i_enroll_in_totp_with_challenge(world: &mut AppWorld) {
+    let token = world.challenge_token();
+    let actor = world
+        .auth_service()
+        .verify_for_mfa_enrollment(&token)
+        .await
+        .expect("challenge token should allow enrollment");
+    let enrollment = world
+        .mfa_service()
+        .begin_totp_enrollment(&actor)
+        .await
+        .expect("TOTP enrollment should start");
+    world.totp_secret = Some(enrollment.secret);
+    world.otpauth_uri = Some(enrollment.otpauth_uri);
+}
+
+#[when("I confirm the TOTP enrollment with the current code")]
+async fn i_confirm_totp_enrollment(world: &mut AppWorld) {
+    let secret = world
+        .totp_secret
+        .clone()
+        .expect("a TOTP secret should have been enrolled");
+    let code = totp::code_at(&secret, totp::step_at(Utc::now())).expect("secret should be valid");
+    let token = world
+        .access_token
+        .clone()
+        .or_else(|| world.mfa_challenge.as_ref().map(|(_, token)| token.clone()))
+        .expect("a token should be available");
+    let actor = world
+        .auth_service()
+        .verify_for_mfa_enrollment(&token)
+        .await
+        .expect("token should allow enrollment");
+    world.recovery_codes = world
+        .mfa_service()
+        .confirm_totp_enrollment(actor.id, &code)
+        .await
+        .expect("TOTP enrollment should be confirmed");
+}
+
+#[when(regex = r#"MFA is required for role "(?P<role>[^"]+)""#)]
+async fn mfa_required_for_role(world: &mut AppWorld, role: String) {
+    let actor = world.current_user().await;
+    world
+        .mfa_service()
+        .set_role_policy(&actor, &role, true)
+        .await
+        .expect("policy update should succeed");
+}
+
+#[when("I verify the MFA challenge with the next code")]
+async fn i_verify_mfa_with_next_code(world: &mut AppWorld) {
+    let code = world.next_totp_code();
+    world.last_totp_code = Some(code.clone());
+    let token = world.challenge_token();
+    let client = world.client.clone();
+    let result = world
+        .auth_service()
+        .verify_mfa(&token, &code, &client)
+        .await;
+    world.record_outcome(result);
+}
+
+#[when("I verify the MFA challenge with the last code again")]
+async fn i_verify_mfa_with_last_code(world: &mut AppWorld) {
+    let code = world
+        .last_totp_code
+        .clone()
+        .expect("a TOTP code should have been used");
+    let token = world.challenge_token();
+    let client = world.client.clone();
+    let result = world
+        .auth_service()
+        .verify_mfa(&token, &code, &client)
+        .await;
+    world.record_outcome(result);
+}
+
+#[when(regex = r#"I verify the MFA challenge with code "(?P<code>[^"]+)""#)]
+async fn i_verify_mfa_with_code(world: &mut AppWorld, code: String) {
+    let token = world.challenge_token();
+    let client = world.client.clone();
+    let result = world
+        .auth_service()
+        .verify_mfa(&token, &code, &client)
+        .await;
+    world.record_outcome(result);
+}
+
+#[when("I verify the MFA challenge with the first recovery code")]
+async fn i_verify_mfa_with_recovery_code(world: &mut AppWorld) {
+    let code = world
+        .recovery_codes
+        .first()
+        .cloned()
+        .expect("recovery codes should have been issued");
+    let token = world.challenge_token();
+    let client = world.client.clone();
+    let result = world
+        .auth_service()
+        .verify_mfa(&token, &code, &client)
+        .await;
+    world.record_outcome(result);
+}
+
+#[then(regex = r#"the login requires "(?P<status>[^"]+)""#)]
+async fn login_requires(world: &mut AppWorld, status: String) {
+    let (actual, _) = world
+        .mfa_challenge
+        .as_ref()
+        .expect("expected an MFA challenge");
+    assert_eq!(actual, &status);
+    assert!(world.last_auth_session.is_none());
+}
+
+#[then("the challenge token is not accepted as an access token")]
+async fn challenge_token_not_access_token(world: &mut AppWorld) {
+    let token = world.challenge_token();
+    world
+        .auth_service()
+        .verify(&token)
+        .await
+        .expect_err("challenge token must not grant API access");
+}
+
+#[then(regex = r#"the provisioning URI names issuer "(?P<issuer>[^"]+)""#)]
+async fn provisioning_uri_names_issuer(world: &mut AppWorld, issuer: String) {
+    let uri = world
+        .otpauth_uri
+        .as_deref()
+        .expect("an otpauth URI should have been issued");
+    assert!(uri.starts_with("otpauth://totp/"), "unexpected URI {uri}");
+    assert!(
+        uri.contains(&format!("issuer={issuer}")),
+        "unexpected URI {uri}"
+    );
+}
+
+#[then(regex = r#"I receive (?P<count>[0-9]+) recovery codes"#)]
+async fn i_receive_recovery_codes(world: &mut AppWorld, count: usize) {
+    assert_eq!(world.recovery_codes.len(), count);
+}
+
+#[given(regex = r#"accounts lock after (?P<count>[0-9]+) failed logins"#)]
+async fn accounts_lock_after(world: &mut AppWorld, count: u32) {
+    world.lockout_policy().max_account_failures = count;
+}
+
+#[given(regex = r#"addresses lock after (?P<count>[0-9]+) failed logins"#)]
+async fn addresses_lock_after(world: &mut AppWorld, count: u32) {
+    world.lockout_policy().max_ip_failures = count;
+}
+
+#[given(regex = r#"^accounts lock for the longest representable duration$"#)]
+async fn accounts_lock_for_longest_duration(world: &mut AppWorld) {
+    world.lockout_policy().lockout_duration = chrono::Duration::MAX;
+}
+
+#[given(regex = r#"failed logins back off starting at (?P<seconds>[0-9]+) seconds"#)]
+async fn failed_logins_back_off(world: &mut AppWorld, seconds: i64) {
+    let policy = world.lockout_policy();
+    policy.backoff_base = chrono::Duration::seconds(seconds);
+    policy.backoff_max = chrono::Duration::seconds(seconds * 8);
+}
+
+#[given(regex = r#"requests come from address "(?P<ip>[^"]+)""#)]
+async fn requests_come_from(world: &mut AppWorld, ip: String) {
+    world.client.ip_address = Some(ip.parse().expect("invalid IP address"));
+}
+
+#[when(
+    regex = r#"^requests from address "(?P<ip>[^"]+)" reach a trusted proxy claiming X-Forwarded-For "(?P<claimed>[^"]+)"$"#
+)]
+async fn requests_through_trusted_proxy(world: &mut AppWorld, ip: String, claimed: String) {
+    // O proxy acrescenta o endereco que o conectou ao que o cliente enviou.
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "x-forwarded-for",
+        HeaderValue::from_str(&format!("{claimed}, {ip}")).expect("invalid header value"),
+    );
+    let mut extensions = Extensions::new();
+    extensions.insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 8443))));
+    world.client.ip_address = client_ip::resolve(&headers, &extensions, true);
+}
+
+#[when(regex = r#"I fail to authenticate (?P<count>[0-9]+) times as "(?P<email>[^"]+)""#)]
+async fn i_fail_to_authenticate(world: &mut AppWorld, count: u32, email: String) {
+    let client = world.client.clone();
+    for _ in 0..count {
+        let result = world
+            .auth_service()
+            .authenticate(&email, "WrongPass123!", &client)
+            .await;
+        world.record_login(result);
+    }
+}
+
+#[when(regex = r#"I unlock the account "(?P<email>[^"]+)" as admin"#)]
+async fn i_unlock_the_account(world: &mut AppWorld, email: String) {
+    let actor = world.current_user().await;
+    let user = world
+        .backends
+        .as_ref()
+        .expect("backends should be initialised")
+        .users
+        .find_by_email(&EmailAddress::parse(&email).expect("email should be valid"))
+        .await
+        .expect("user lookup should succeed")
+        .expect("user should exist");
+    world
+        .auth_service()
+        .unlock_account(&actor, user.id())
+        .await
+        .expect("unlock should succeed");
+}
+
+#[then(regex = r#"the authentication is throttled for at least (?P<seconds>[0-9]+) seconds"#)]
+async fn authentication_is_throttled(world: &mut AppWorld, seconds: u64) {
+    match world.last_error.as_ref() {
+        Some(AppError::TooManyRequests {
+            retry_after_seconds,
+            ..
+        }) => assert!(
+            *retry_after_seconds >= seconds,
+            "expected retry after at least {seconds}s, got {retry_after_seconds}s"
+        ),
+        other => panic!("expected a throttled login, got {other:?}"),
+    }
+}
+
+const RESET_SUBJECT: &str = "Reset your password";
+
+#[given("password reset links expire immediately")]
+async fn password_reset_links_expire_immediately(world: &mut AppWorld) {
+    world.password_reset_ttl = Some(chrono::Duration::zero());
+    world.password_reset_service = None;
+}
+
+#[when(regex = r#"I request a password reset for "(?P<email>[^"]+)""#)]
+async fn i_request_a_password_reset(world: &mut AppWorld, email: String) {
+    let result = world.password_reset_service().request_reset(&email).await;
+    world.last_error = result.err();
+    world.dispatch_emails().await;
+}
+
+#[when(
+    regex = r#"I reset the password of "(?P<email>[^"]+)" to "(?P<password>[^"]+)" using the (?P<which>first|latest) emailed link"#
+)]
+async fn i_reset_the_password(
+    world: &mut AppWorld,
+    email: String,
+    password: String,
+    which: String,
+) {
+    let index = (which == "first").then_some(0);
+    let token = world.emailed_token(&email, RESET_SUBJECT, index).await;
+    let result = world
+        .password_reset_service()
+        .reset_password(&token, &password)
+        .await;
+    world.last_error = result.err();
+}
+
+#[then("the password reset request is accepted")]
+async fn password_reset_request_accepted(world: &mut AppWorld) {
+    assert!(
+        world.last_error.is_none(),
+        "expected the reset request to be accepted, got {:?}",
+        world.last_error
+    );
+}
+
+#[then(regex = r#"(?P<count>[0-9]+) reset emails? (?:is|are) sent to "(?P<email>[^"]+)""#)]
+async fn reset_emails_sent(world: &mut AppWorld, count: usize, email: String) {
+    let sent = world.emails_sent(&email, RESET_SUBJECT).await;
+    assert_eq!(sent.len(), count, "unexpected number of emails to {email}");
+}
+
+#[then("the password reset succeeds")]
+async fn password_reset_succeeds(world: &mut AppWorld) {
+    assert!(
+        world.last_error.is_none(),
+        "expected the password reset to succeed, got {:?}",
+        world.last_error
+    );
+}
+
+#[then(regex = r#"the password reset fails with message "(?P<message>[^"]+)""#)]
+async fn password_reset_fails(world: &mut AppWorld, message: String) {
+    let err = world
+        .last_error
+        .as_ref()
+        .expect("expected the password reset to fail");
+    assert!(
+        err.to_string().contains(&message),
+        "expected error to contain '{message}', got '{}'",
+        err
+    );
+}
+
+#[when(
+    regex = r#"^I create a personal access token "(?P<name>[^"]+)" with scopes "(?P<scopes>[^"]*)"(?: valid for (?P<days>[0-9]+) days)?$"#
+)]
+async fn i_create_a_personal_access_token(
+    world: &mut AppWorld,
+    name: String,
+    scopes: String,
+    days: String,
+) {
+    let actor = world.current_user().await;
+    create_personal_access_token(world, actor, name, scopes, days).await;
+}
+
+#[when(
+    regex = r#"I create a personal access token "(?P<name>[^"]+)" with scopes "(?P<scopes>[^"]*)" using the personal access token"#
+)]
+async fn i_create_a_token_with_a_token(world: &mut AppWorld, name: String, scopes: String) {
+    let actor = world
+        .personal_access_token_user()
+        .await
+        .expect("personal access token should authenticate");
+    create_personal_access_token(world, actor, name, scopes, String::new()).await;
+}
+
+async fn create_personal_access_token(
+    world: &mut AppWorld,
+    actor: AuthenticatedUser,
+    name: String,
+    scopes: String,
+    days: String,
+) {
+    let scopes: Vec<String> = scopes.split(',').map(|scope| scope.to_string()).collect();
+    let days = days.parse::<i64>().ok();
+    match world
+        .personal_access_tokens()
+        .create(&actor, &name, &scopes, days)
+        .await
+    {
+        Ok(issued) => {
+            world.personal_access_token = Some(issued.token);
+            world.last_error = None;
+        }
+        Err(err) => world.last_error = Some(err),
+    }
+}
+
+#[when(regex = r#"I revoke the personal access token "(?P<name>[^"]+)""#)]
+async fn i_revoke_the_personal_access_token(world: &mut AppWorld, name: String) {
+    let actor = world.current_user().await;
+    let service = world.personal_access_tokens();
+    let token = service
+        .list(&actor)
+        .await
+        .expect("listing tokens should succeed")
+        .into_iter()
+        .find(|token| token.name == name)
+        .expect("token should be listed");
+    service
+        .revoke(&actor, token.id)
+        .await
+        .expect("revoking the token should succeed");
+}
+
+#[when("I list users using the personal access token")]
+async fn i_list_users_with_a_token(world: &mut AppWorld) {
+    let result = match world.personal_access_token_user().await {
+        Ok(actor) => world
+            .user_service()
+            .list_users(&actor, ListUsersQueryDto::default())
+            .await
+            .map(|_| ()),
+        Err(err) => Err(err),
+    };
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"I create the user "(?P<email>[^"]+)" using the personal access token"#)]
+async fn i_create_a_user_with_a_token(world: &mut AppWorld, email: String) {
+    let result = match world.personal_access_token_user().await {
+        Ok(actor) => world
+            .user_service()
+            .create_user(
+                &actor,
+                CreateUserDto {
+                    name: "Automation User".to_string(),
+                    email,
+                    password: "Pylon-Ledger-Quill-58".to_string(),
+                    roles: vec!["viewer".to_string()],
+                },
+            )
+            .await
+            .map(|_| ()),
+        Err(err) => Err(err),
+    };
+    world.last_error = result.err();
+}
+
+#[when("I log out using the personal access token")]
+async fn i_log_out_with_a_token(world: &mut AppWorld) {
+    let actor = world
+        .personal_access_token_user()
+        .await
+        .expect("personal access token should authenticate");
+    world.last_error = world.auth_service().logout(&actor, None).await.err();
+}
+
+#[then("the API call succeeds")]
+async fn api_call_succeeds(world: &mut AppWorld) {
+    assert!(
+        world.last_error.is_none(),
+        "expected the call to succeed, got {:?}",
+        world.last_error
+    );
+}
+
+#[then(regex = r#"the API call fails with message "(?P<message>[^"]+)""#)]
+async fn api_call_fails(world: &mut AppWorld, message: String) {
+    let err = world
+        .last_error
+        .as_ref()
+        .expect("expected the call to fail");
+    assert!(
+        err.to_string().contains(&message),
+        "expected error to contain '{message}', got '{}'",
+        err
+    );
+}
+
+#[then(regex = r#"my personal access tokens are listed as "(?P<names>[^"]*)""#)]
+async fn my_tokens_are_listed(world: &mut AppWorld, names: String) {
+    let actor = world.current_user().await;
+    let listed: Vec<String> = world
+        .personal_access_tokens()
+        .list(&actor)
+        .await
+        .expect("listing tokens should succeed")
+        .into_iter()
+        .map(|token| token.name)
+        .collect();
+    let expected: Vec<String> = names
+        .split(',')
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    assert_eq!(listed, expected);
+}
+
+#[then(regex = r#"the personal access token "(?P<name>[^"]+)" (?P<state>was|was not) used"#)]
+async fn personal_access_token_usage(world: &mut AppWorld, name: String, state: String) {
+    let actor = world.current_user().await;
+    let token = world
+        .personal_access_tokens()
+        .list(&actor)
+        .await
+        .expect("listing tokens should succeed")
+        .into_iter()
+        .find(|token| token.name == name)
+        .expect("token should be listed");
+    let secret = world
+        .personal_access_token
+        .clone()
+        .expect("a token should have been created");
+    assert!(secret.starts_with(&token.token_prefix));
+    assert_eq!(token.last_used_at.is_some(), state == "was");
+}
+
+fn split_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[when(regex = r#"I act as "(?P<email>[^"]+)""#)]
+async fn i_act_as(world: &mut AppWorld, email: String) {
+    let token = world
+        .sessions
+        .get(&email)
+        .cloned()
+        .expect("the user should have authenticated before");
+    world.access_token = Some(token);
+}
+
+#[when(regex = r#"I create the role "(?P<name>[^"]+)" with permissions "(?P<permissions>[^"]*)""#)]
+async fn i_create_a_role(world: &mut AppWorld, name: String, permissions: String) {
+    let actor = world.current_user().await;
+    let result = world
+        .backends()
+        .roles
+        .create_role(
+            &actor,
+            CreateRoleDto {
+                name,
+                description: None,
+                permissions: split_list(&permissions),
+            },
+        )
+        .await;
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"I change the permissions of role "(?P<name>[^"]+)" to "(?P<permissions>[^"]*)""#)]
+async fn i_update_a_role(world: &mut AppWorld, name: String, permissions: String) {
+    let actor = world.current_user().await;
+    let result = world
+        .backends()
+        .roles
+        .update_role(
+            &actor,
+            &name,
+            UpdateRoleDto {
+                description: None,
+                permissions: Some(split_list(&permissions)),
+            },
+        )
+        .await;
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"I delete the role "(?P<name>[^"]+)""#)]
+async fn i_delete_a_role(world: &mut AppWorld, name: String) {
+    let actor = world.current_user().await;
+    let result = world.backends().roles.delete_role(&actor, &name).await;
+    world.last_error = result.err();
+}
+
+#[when(
+    regex = r#"I create the user "(?P<email>[^"]+)" with password "(?P<password>[^"]+)" and roles "(?P<roles>[^"]*)""#
+)]
+async fn i_create_a_user_with_roles(
+    world: &mut AppWorld,
+    email: String,
+    password: String,
+    roles: String,
+) {
+    let actor = world.current_user().await;
+    let result = world
+        .user_service()
+        .create_user(
+            &actor,
+            CreateUserDto {
+                name: "Team Member".to_string(),
+                email,
+                password,
+                roles: split_list(&roles),
+            },
+        )
+        .await;
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"^I rename the user "(?P<email>[^"]+)" to "(?P<name>[^"]+)"$"#)]
+async fn i_rename_a_user(world: &mut AppWorld, email: String, name: String) {
+    world.rename_user(&email, name, None).await;
+}
+
+#[when(
+    regex = r#"I rename the user "(?P<email>[^"]+)" to "(?P<name>[^"]+)" with If-Match (?P<tag>.+)"#
+)]
+async fn i_rename_a_user_if_match(world: &mut AppWorld, email: String, name: String, tag: String) {
+    let tag = world.expand_etag(&tag);
+    world.rename_user(&email, name, Some(&tag)).await;
+}
+
+#[when(regex = r#"I delete the user "(?P<email>[^"]+)" with If-Match (?P<tag>.+)"#)]
+async fn i_delete_a_user_if_match(world: &mut AppWorld, email: String, tag: String) {
+    let tag = world.expand_etag(&tag);
+    let target = world.user_by_email(&email).await;
+    let actor = world.current_user().await;
+    let result = match etag::if_match(&if_match_headers(&tag)) {
+        Ok(expected) => {
+            world
+                .user_service()
+                .delete_user(&actor, target.id(), expected)
+                .await
+        }
+        Err(err) => Err(err),
+    };
+    if result.is_ok() {
+        world.deleted_users.insert(email, target.id());
+    }
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"^I delete the user "(?P<email>[^"]+)"$"#)]
+async fn i_delete_a_user(world: &mut AppWorld, email: String) {
+    let target = world.user_by_email(&email).await;
+    let actor = world.current_user().await;
+    let result = world
+        .user_service()
+        .delete_user(&actor, target.id(), None)
+        .await;
+    if result.is_ok() {
+        world.deleted_users.insert(email, target.id());
+    }
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"I restore the user "(?P<email>[^"]+)""#)]
+async fn i_restore_a_user(world: &mut AppWorld, email: String) {
+    let id = world
+        .deleted_users
+        .get(&email)
+        .copied()
+        .expect("the user should have been deleted in this scenario");
+    let actor = world.current_user().await;
+    world.last_error = world.user_service().restore_user(&actor, id).await.err();
+}
+
+#[when(regex = r#"deleted users older than (?P<days>[0-9]+) days? are purged"#)]
+async fn deleted_users_are_purged(world: &mut AppWorld, days: i64) {
+    let purged = world
+        .user_service()
+        .purge_deleted_users(chrono::Duration::days(days))
+        .await
+        .expect("purge should succeed");
+    world.purged_users = Some(purged);
+}
+
+#[then(regex = r#"(?P<count>[0-9]+) deleted users? (?:was|were) purged"#)]
+async fn deleted_users_were_purged(world: &mut AppWorld, count: u64) {
+    assert_eq!(world.purged_users, Some(count));
+}
+
+#[when(
+    regex = r#"I (?P<action>suspend|lock|reactivate) the user "(?P<email>[^"]+)" because "(?P<reason>[^"]*)""#
+)]
+async fn i_change_the_user_status(
+    world: &mut AppWorld,
+    action: String,
+    email: String,
+    reason: String,
+) {
+    let target = match action.as_str() {
+        "suspend" => UserStatus::Suspended,
+        "lock" => UserStatus::Locked,
+        _ => UserStatus::Active,
+    };
+    let user = world.user_by_email(&email).await;
+    let actor = world.current_user().await;
+    let result = world
+        .user_service()
+        .change_status(&actor, user.id(), target, ChangeUserStatusDto { reason })
+        .await;
+    world.last_error = result.err();
+}
+
+#[then(regex = r#"the user "(?P<email>[^"]+)" has status "(?P<status>[^"]+)""#)]
+async fn the_user_has_status(world: &mut AppWorld, email: String, status: String) {
+    let user = world.user_by_email(&email).await;
+    assert_eq!(user.status().as_str(), status);
+}
+
+#[when(regex = r#"^I keep this session as "(?P<name>[^"]+)"$"#)]
+async fn i_keep_this_session(world: &mut AppWorld, name: String) {
+    let token = world
+        .access_token
+        .clone()
+        .expect("an access token should have been issued");
+    world.sessions.insert(name, token);
+}
+
+#[when(regex = r#"I update my profile with (?P<body>\{.*\})"#)]
+async fn i_update_my_profile(world: &mut AppWorld, body: String) {
+    let dto = match serde_json::from_str::<UpdateProfileDto>(&body) {
+        Ok(dto) => dto,
+        Err(err) => {
+            world.last_error = Some(AppError::Validation(err.to_string()));
+            return;
+        }
+    };
+    let actor = world.current_user().await;
+    let result = world.user_service().update_profile(&actor, dto, None).await;
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"^I change my password from "(?P<current>[^"]+)" to "(?P<new_password>[^"]+)"$"#)]
+async fn i_change_my_password(world: &mut AppWorld, current: String, new_password: String) {
+    let actor = world.current_user().await;
+    let client = world.client.clone();
+    let result = world
+        .auth_service()
+        .change_password(&actor, &current, &new_password, &client)
+        .await;
+    world.record_session(result);
+}
+
+#[when(
+    regex = r#"^I change my password from "(?P<current>[^"]+)" to "(?P<new_password>[^"]+)" using the challenge token$"#
+)]
+async fn i_change_my_password_with_the_challenge(
+    world: &mut AppWorld,
+    current: String,
+    new_password: String,
+) {
+    let token = world.challenge_token();
+    let actor = world
+        .auth_service()
+        .verify_for_password_change(&token)
+        .await
+        .expect("challenge token should allow a password change");
+    let result = world
+        .auth_service()
+        .change_password(&actor, &current, &new_password, &SessionClient::default())
+        .await;
+    world.record_session(result);
+}
+
+#[when("I change my password using the personal access token")]
+async fn i_change_my_password_with_a_token(world: &mut AppWorld) {
+    let actor = world
+        .personal_access_token_user()
+        .await
+        .expect("the personal access token should authenticate");
+    let result = world
+        .auth_service()
+        .change_password(
+            &actor,
+            "Listing#Pass1",
+            "Brand#NewPass2",
+            &SessionClient::default(),
+        )
+        .await;
+    world.record_session(result);
+}
+
+#[then(regex = r#"my profile shows (?P<field>name|locale|timezone) "(?P<value>[^"]*)""#)]
+async fn my_profile_shows(world: &mut AppWorld, field: String, value: String) {
+    let actor = world.current_user().await;
+    let profile = world
+        .user_service()
+        .get_profile(&actor)
+        .await
+        .expect("reading the profile should succeed");
+    let actual = match field.as_str() {
+        "name" => Some(profile.name().to_string()),
+        "locale" => profile.preferences().locale.clone(),
+        _ => profile.preferences().timezone.clone(),
+    };
+    assert_eq!(actual.unwrap_or_default(), value);
+}
+
+const VERIFY_SUBJECT: &str = "Confirm your email address";
+const VERIFY_NEW_SUBJECT: &str = "Confirm your new email address";
+
+#[given("login requires a verified email address")]
+async fn login_requires_a_verified_email(world: &mut AppWorld) {
+    world.require_verified_email = true;
+    world.auth_service = None;
+}
+
+#[when(regex = r#"I change my email to "(?P<email>[^"]+)""#)]
+async fn i_change_my_email(world: &mut AppWorld, email: String) {
+    let actor = world.current_user().await;
+    let result = world
+        .user_service()
+        .change_email(&actor, ChangeEmailDto { email }, None)
+        .await;
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"I change the email of the user "(?P<email>[^"]+)" to "(?P<new_email>[^"]+)""#)]
+async fn i_change_the_email_of_a_user(world: &mut AppWorld, email: String, new_email: String) {
+    let target = world.user_by_email(&email).await;
+    let actor = world.current_user().await;
+    let result = world
+        .user_service()
+        .update_user(
+            &actor,
+            target.id(),
+            UpdateUserDto {
+                name: None,
+                email: Some(new_email),
+                password: None,
+                roles: None,
+            },
+            None,
+        )
+        .await;
+    world.last_error = result.err();
+}
+
+#[when("I ask for a new verification email")]
+async fn i_ask_for_a_new_verification_email(world: &mut AppWorld) {
+    let actor = world.current_user().await;
+    let result = world.user_service().resend_email_verification(&actor).await;
+    world.last_error = result.err();
+}
+
+#[when(
+    regex = r#"I open the (?P<which>first|latest) (?P<kind>verification|email change) link sent to "(?P<email>[^"]+)""#
+)]
+async fn i_open_the_verification_link(
+    world: &mut AppWorld,
+    which: String,
+    kind: String,
+    email: String,
+) {
+    let index = (which == "first").then_some(0);
+    let subject = match kind.as_str() {
+        "verification" => VERIFY_SUBJECT,
+        _ => VERIFY_NEW_SUBJECT,
+    };
+    let token = world.emailed_token(&email, subject, index).await;
+    let result = world.user_service().verify_email(&token).await;
+    world.last_error = result.err();
+}
+
+#[when("I try to verify my email with my access token")]
+async fn i_verify_my_email_with_an_access_token(world: &mut AppWorld) {
+    let token = world
+        .access_token
+        .clone()
+        .expect("an access token should have been issued");
+    let result = world.user_service().verify_email(&token).await;
+    world.last_error = result.err();
+}
+
+#[then(
+    regex = r#"(?P<count>[0-9]+) emails? "(?P<subject>[^"]+)" (?:is|are) sent to "(?P<email>[^"]+)""#
+)]
+async fn emails_with_subject_sent(
+    world: &mut AppWorld,
+    count: usize,
+    subject: String,
+    email: String,
+) {
+    let sent = world.emails_sent(&email, &subject).await;
+    assert_eq!(
+        sent.len(),
+        count,
+        "unexpected number of \"{subject}\" emails to {email}"
+    );
+}
+
+#[then(regex = r#"the user "(?P<email>[^"]+)" has (?P<state>a verified|an unverified) email"#)]
+async fn the_user_has_verified_email(world: &mut AppWorld, email: String, state: String) {
+    let user = world.user_by_email(&email).await;
+    assert_eq!(user.is_email_verified(), state == "a verified");
+}
+
+#[then(regex = r#"the user "(?P<email>[^"]+)" has pending email "(?P<pending>[^"]*)""#)]
+async fn the_user_has_pending_email(world: &mut AppWorld, email: String, pending: String) {
+    let user = world.user_by_email(&email).await;
+    assert_eq!(
+        user.pending_email()
+            .map(|email| email.as_str())
+            .unwrap_or_default(),
+        pending
+    );
+}
+
+const INVITATION_SUBJECT: &str = "You have been invited";
+
+#[when(
+    regex = r#"I invite "(?P<name>[^"]+)" with email "(?P<email>[^"]+)" and roles "(?P<roles>[^"]*)""#
+)]
+async fn i_invite_a_user(world: &mut AppWorld, name: String, email: String, roles: String) {
+    let actor = world.current_user().await;
+    let result = world
+        .invitations()
+        .invite(
+            &actor,
+            CreateInvitationDto {
+                name,
+                email,
+                roles: split_list(&roles),
+            },
+        )
+        .await;
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"I (?P<action>resend|revoke) the invitation for "(?P<email>[^"]+)""#)]
+async fn i_manage_an_invitation(world: &mut AppWorld, action: String, email: String) {
+    let actor = world.current_user().await;
+    let service = world.invitations();
+    let invitation = service
+        .list_pending(&actor)
+        .await
+        .expect("listing invitations should succeed")
+        .into_iter()
+        .find(|invitation| invitation.email == email)
+        .expect("invitation should be pending");
+    let result = match action.as_str() {
+        "resend" => service.resend(&actor, invitation.id).await,
+        _ => service.revoke(&actor, invitation.id).await,
+    };
+    world.last_error = result.err();
+}
+
+#[when(
+    regex = r#"I accept the (?P<which>first|latest) invitation sent to "(?P<email>[^"]+)" with password "(?P<password>[^"]+)""#
+)]
+async fn i_accept_an_invitation(
+    world: &mut AppWorld,
+    which: String,
+    email: String,
+    password: String,
+) {
+    let index = (which == "first").then_some(0);
+    let token = world.emailed_token(&email, INVITATION_SUBJECT, index).await;
+    let result = world.invitations().accept(&token, &password).await;
+    world.last_error = result.err();
+}
+
+#[then(regex = r#"the pending invitations are "(?P<emails>[^"]*)""#)]
+async fn the_pending_invitations_are(world: &mut AppWorld, emails: String) {
+    let actor = world.current_user().await;
+    let listed: Vec<String> = world
+        .invitations()
+        .list_pending(&actor)
+        .await
+        .expect("listing invitations should succeed")
+        .into_iter()
+        .map(|invitation| invitation.email)
+        .collect();
+    assert_eq!(listed, split_list(&emails));
+}
+
+#[given(
+    regex = r#"^self-registration is "(?P<mode>[^"]+)"(?: for domains "(?P<domains>[^"]*)")?$"#
+)]
+async fn self_registration_is(world: &mut AppWorld, mode: String, domains: String) {
+    world.registration_settings = RegistrationSettings {
+        mode: RegistrationMode::parse(&mode).expect("registration mode should be valid"),
+        allowed_domains: split_list(&domains),
+    };
+    world.registration_service = None;
+}
+
+#[when(
+    regex = r#"I register as "(?P<name>[^"]+)" with email "(?P<email>[^"]+)" and password "(?P<password>[^"]+)""#
+)]
+async fn i_register(world: &mut AppWorld, name: String, email: String, password: String) {
+    let result = world
+        .registration_service()
+        .register(RegisterRequestDto {
+            name,
+            email,
+            password,
+        })
+        .await;
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"I (?P<action>approve|reject) the registration of "(?P<email>[^"]+)""#)]
+async fn i_review_a_registration(world: &mut AppWorld, action: String, email: String) {
+    let user = world.user_by_email(&email).await;
+    let actor = world.current_user().await;
+    let service = world.registration_service();
+    let result = match action.as_str() {
+        "approve" => service.approve(&actor, user.id()).await.map(|_| ()),
+        _ => service.reject(&actor, user.id()).await,
+    };
+    world.last_error = result.err();
+}
+
+#[then(regex = r#"the pending registrations are "(?P<emails>[^"]*)""#)]
+async fn the_pending_registrations_are(world: &mut AppWorld, emails: String) {
+    let actor = world.current_user().await;
+    let listed: Vec<String> = world
+        .registration_service()
+        .list_pending(&actor)
+        .await
+        .expect("listing registrations should succeed")
+        .into_iter()
+        .map(|registration| registration.email)
+        .collect();
+    assert_eq!(listed, split_list(&emails));
+}
+
+#[given(regex = r#"^the password policy requires "(?P<rules>[^"]*)"$"#)]
+async fn the_password_policy_requires(world: &mut AppWorld, rules: String) {
+    let policy = &mut world.password_policy;
+    for rule in split_list(&rules) {
+        match rule.as_str() {
+            "uppercase" => policy.require_uppercase = true,
+            "lowercase" => policy.require_lowercase = true,
+            "digit" => policy.require_digit = true,
+            "symbol" => policy.require_symbol = true,
+            other => panic!("unknown password rule {other}"),
+        }
+    }
+    world.auth_service = None;
+}
+
+#[given(regex = r#"^the password policy requires a strength score of (?P<score>[0-4])$"#)]
+async fn the_password_policy_requires_a_score(world: &mut AppWorld, score: u8) {
+    world.password_policy.min_strength_score = score;
+    world.auth_service = None;
+}
+
+// Monta a lista no formato das respostas de range do HIBP: prefixo numa linha, sufixos abaixo.
+#[given(regex = r#"^the breached password list contains "(?P<passwords>[^"]*)"$"#)]
+async fn the_breached_password_list_contains(world: &mut AppWorld, passwords: String) {
+    let contents: String = split_list(&passwords)
+        .iter()
+        .map(|password| {
+            let hash: String = Sha1::digest(password.as_bytes())
+                .iter()
+                .map(|byte| format!("{byte:02X}"))
+                .collect();
+            format!("{}\n{}:42\n", &hash[..5], &hash[5..])
+        })
+        .collect();
+    let list = BreachedPasswords::parse(&contents).expect("breached list should parse");
+    world.password_policy.breached_passwords = Some(Arc::new(list));
+    world.auth_service = None;
+}
+
+#[when(regex = r#"I fetch the user "(?P<email>[^"]+)""#)]
+async fn i_fetch_a_user(world: &mut AppWorld, email: String) {
+    let target = world.user_by_email(&email).await;
+    let actor = world.current_user().await;
+    let user = world
+        .user_service()
+        .get_user(&actor, target.id())
+        .await
+        .expect("fetching the user should succeed");
+    world.fetched_etag = Some(etag::from_version(user.version()));
+}
+
+#[then(
+    regex = r#"a conditional read of the user "(?P<email>[^"]+)" is (?P<state>not modified|modified)"#
+)]
+async fn conditional_read(world: &mut AppWorld, email: String, state: String) {
+    let tag = world
+        .fetched_etag
+        .clone()
+        .expect("a user should have been fetched");
+    let mut headers = HeaderMap::new();
+    headers.insert(header::IF_NONE_MATCH, tag);
+    let target = world.user_by_email(&email).await;
+    assert_eq!(
+        etag::if_none_match(&headers, target.version()),
+        state == "not modified"
+    );
+}
+
+#[then(regex = r#"the user "(?P<email>[^"]+)" is named "(?P<name>[^"]+)""#)]
+async fn the_user_is_named(world: &mut AppWorld, email: String, name: String) {
+    let user = world.user_by_email(&email).await;
+    assert_eq!(user.name().as_str(), name);
+}
+
+fn if_match_headers(tag: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::IF_MATCH,
+        HeaderValue::from_str(tag).expect("tag should be a valid header value"),
+    );
+    headers
+}
+
+impl AppWorld {
+    fn configure_hashing(&mut self, change: impl FnOnce(&mut HashingPoolSettings)) {
+        let settings = self
+            .hashing_settings
+            .get_or_insert_with(HashingPoolSettings::default);
+        change(settings);
+        self.hashing_pool = Some(HashingPool::new(settings.clone(), AppMetrics::new()));
+        self.auth_service = None;
+    }
+
+    async fn stored_password_hash(&mut self, email: &str) -> argon2::Params {
+        let user = self.user_by_email(email).await;
+        let hash = argon2::password_hash::PasswordHash::new(user.password_hash().as_str())
+            .expect("stored hash should be in PHC format");
+        argon2::Params::try_from(&hash).expect("stored hash should carry Argon2 parameters")
+    }
+
+    async fn user_by_email(&mut self, email: &str) -> User {
+        self.backends()
+            .users
+            .find_by_email(&EmailAddress::parse(email).expect("email should be valid"))
+            .await
+            .expect("lookup should succeed")
+            .expect("user should exist")
+    }
+
+    // `{fetched}` e substituido pelo ETag da ultima leitura feita com "I fetch the user".
+    fn expand_etag(&self, tag: &str) -> String {
+        match &self.fetched_etag {
+            Some(fetched) => {
+                tag.replace("{fetched}", fetched.to_str().expect("etag should be ascii"))
+            }
+            None => tag.to_string(),
+        }
+    }
+
+    async fn rename_user(&mut self, email: &str, name: String, if_match: Option<&str>) {
+        let target = self.user_by_email(email).await;
+        let actor = self.current_user().await;
+        let expected = match if_match.map(|tag| etag::if_match(&if_match_headers(tag))) {
+            Some(Ok(expected)) => expected,
+            Some(Err(err)) => {
+                self.last_error = Some(err);
+                return;
+            }
+            None => None,
+        };
+        let result = self
+            .user_service()
+            .update_user(
+                &actor,
+                target.id(),
+                UpdateUserDto {
+                    name: Some(name),
+                    email: None,
+                    password: None,
+                    roles: None,
+                },
+                expected,
+            )
+            .await;
+        self.last_error = result.err();
+    }
+}
+
+impl AppWorld {
+    // Interpreta a query string como o extractor `Query` do Axum faria em `GET /users`.
+    async fn list_users_with(&mut self, query: &str) {
+        let uri: axum::http::Uri = format!("/users?{query}")
+            .parse()
+            .expect("query should form a valid URI");
+        let Query(dto) =
+            Query::<ListUsersQueryDto>::try_from_uri(&uri).expect("query should deserialize");
+        let actor = self.current_user().await;
+
+        match self.user_service().list_users(&actor, dto).await {
+            Ok(page) => {
+                self.listed_users = Some(page);
+                self.last_error = None;
+            }
+            Err(err) => {
+                self.listed_users = None;
+                self.last_error = Some(err);
+            }
+        }
+    }
+
+    async fn list_next_page_with(&mut self, query: &str) {
+        let cursor = self
+            .listed_page()
+            .next_cursor
+            .clone()
+            .expect("the previous page should have a next cursor");
+        self.list_users_with(&format!("{query}&cursor={cursor}"))
+            .await;
+    }
+
+    fn listed_page(&self) -> &UserPageDto {
+        self.listed_users
+            .as_ref()
+            .unwrap_or_else(|| panic!("listing failed with {:?}", self.last_error))
+    }
+}
+
+#[given(
+    regex = r#"a user named "(?P<name>[^"]+)" with email "(?P<email>[^"]+)" and roles "(?P<roles>[^"]+)""#
+)]
+async fn a_user_exists(world: &mut AppWorld, name: String, email: String, roles: String) {
+    let actor = world.current_user().await;
+    world
+        .user_service()
+        .create_user(
+            &actor,
+            CreateUserDto {
+                name,
+                email,
+                password: "Listing#Pass1".to_string(),
+                roles: split_list(&roles),
+            },
+        )
+        .await
+        .expect("user creation should succeed");
+}
+
+#[when("I list users")]
+async fn i_list_users(world: &mut AppWorld) {
+    world.user_query.clear();
+    world.list_users_with("").await;
+}
+
+#[when(regex = r#"I list users with query "(?P<query>[^"]*)""#)]
+async fn i_list_users_with_query(world: &mut AppWorld, query: String) {
+    world.list_users_with(&query).await;
+    world.user_query = query;
+}
+
+#[when("I request the next page of users")]
+async fn i_request_the_next_page(world: &mut AppWorld) {
+    let query = world.user_query.clone();
+    world.list_next_page_with(&query).await;
+}
+
+#[when(regex = r#"I request the next page of users with query "(?P<query>[^"]*)""#)]
+async fn i_request_the_next_page_with_query(world: &mut AppWorld, query: String) {
+    world.list_next_page_with(&query).await;
+}
+
+#[then(regex = r#"I see (?P<count>[0-9]+) users?"#)]
+async fn i_see_users(world: &mut AppWorld, count: usize) {
+    assert_eq!(world.listed_page().items.len(), count);
+}
+
+#[then(regex = r#"the listed users are "(?P<emails>[^"]*)""#)]
+async fn the_listed_users_are(world: &mut AppWorld, emails: String) {
+    let listed: Vec<String> = world
+        .listed_page()
+        .items
+        .iter()
+        .map(|user| user.email().to_string())
+        .collect();
+    assert_eq!(listed, split_list(&emails));
+}
+
+#[then("there are no more users")]
+async fn there_are_no_more_users(world: &mut AppWorld) {
+    assert!(world.listed_page().next_cursor.is_none());
+}
+
+#[then(regex = r#"the total user count is (?P<count>[0-9]+)"#)]
+async fn the_total_user_count_is(world: &mut AppWorld, count: u64) {
+    assert_eq!(world.listed_page().total_count, Some(count));
+}
+
+#[given(regex = r#"^passwords cannot repeat the last (?P<count>[0-9]+)$"#)]
+async fn passwords_cannot_repeat(world: &mut AppWorld, count: usize) {
+    world.password_history_size = count;
+    world.auth_service = None;
+}
+
+#[given(regex = r#"^passwords expire after (?P<days>[0-9]+) days$"#)]
+async fn passwords_expire_after(world: &mut AppWorld, days: i64) {
+    world.password_max_age = Some(chrono::Duration::days(days));
+    world.auth_service = None;
+}
+
+#[given(
+    regex = r#"^the password of "(?P<email>[^"]+)" was last changed (?P<days>[0-9]+) days ago$"#
+)]
+async fn password_last_changed(world: &mut AppWorld, email: String, days: i64) {
+    let user = world.user_by_email(&email).await;
+    world
+        .backends()
+        .user_records
+        .backdate_password_change(user.id(), chrono::Duration::days(days))
+        .await;
+}
+
+#[when(
+    regex = r#"^I set the password of the user "(?P<email>[^"]+)" to "(?P<password>[^"]+)"(?: with If-Match (?P<tag>.+))?$"#
+)]
+async fn i_set_the_password_of_a_user(
+    world: &mut AppWorld,
+    email: String,
+    password: String,
+    tag: String,
+) {
+    let target = world.user_by_email(&email).await;
+    let actor = world.current_user().await;
+    let expected = if tag.is_empty() {
+        None
+    } else {
+        match etag::if_match(&if_match_headers(&world.expand_etag(&tag))) {
+            Ok(expected) => expected,
+            Err(err) => {
+                world.last_error = Some(err);
+                return;
+            }
+        }
+    };
+    let result = world
+        .user_service()
+        .update_user(
+            &actor,
+            target.id(),
+            UpdateUserDto {
+                name: None,
+                email: None,
+                password: Some(password),
+                roles: None,
+            },
+            expected,
+        )
+        .await;
+    world.last_error = result.err();
+}
+
+#[given(
+    regex = r#"^password hashing runs on (?P<workers>[0-9]+) workers? with a queue of (?P<queue>[0-9]+)$"#
+)]
+async fn password_hashing_runs_on(world: &mut AppWorld, workers: usize, queue: usize) {
+    world.configure_hashing(|settings| {
+        settings.workers = workers;
+        settings.queue_capacity = queue;
+        settings.retry_after_seconds = 1;
+    });
+}
+
+#[given("every password hashing worker is busy")]
+async fn every_hashing_worker_is_busy(world: &mut AppWorld) {
+    let pool = world
+        .hashing_pool
+        .clone()
+        .expect("a hashing pool should have been configured");
+    let workers = world
+        .hashing_settings
+        .as_ref()
+        .map(|settings| settings.workers)
+        .expect("a hashing pool should have been configured");
+    let (started_tx, started_rx) = std::sync::mpsc::channel();
+    for _ in 0..workers {
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let started_tx = started_tx.clone();
+        let pool = pool.clone();
+        let job = tokio::spawn(async move {
+            pool.run("hash", move || {
+                started_tx.send(()).expect("the step should be waiting");
+                let _ = release_rx.recv();
+            })
+            .await
+        });
+        world.busy_hashing_jobs.push((release_tx, job));
+    }
+    tokio::task::spawn_blocking(move || {
+        for _ in 0..workers {
+            started_rx.recv().expect("the hashing job should start");
+        }
+    })
+    .await
+    .expect("waiting for the hashing jobs should succeed");
+}
+
+#[when("the password hashing workers are released")]
+async fn hashing_workers_are_released(world: &mut AppWorld) {
+    for (release, job) in world.busy_hashing_jobs.drain(..) {
+        drop(release);
+        job.await
+            .expect("the hashing job should not panic")
+            .expect("the hashing job should have run");
+    }
+}
+
+#[then(regex = r#"^the request is shed with retry after (?P<seconds>[0-9]+) seconds?$"#)]
+async fn the_request_is_shed(world: &mut AppWorld, seconds: u64) {
+    match world.last_error.as_ref() {
+        Some(AppError::ServiceUnavailable {
+            retry_after_seconds,
+            ..
+        }) => assert_eq!(*retry_after_seconds, seconds),
+        other => panic!("expected the request to be shed, got {other:?}"),
+    }
+}
+
+#[given(
+    regex = r#"^passwords are hashed with m_cost (?P<m>[0-9]+), t_cost (?P<t>[0-9]+) and p_cost (?P<p>[0-9]+)$"#
+)]
+async fn passwords_are_hashed_with(world: &mut AppWorld, m: u32, t: u32, p: u32) {
+    world.configure_hashing(|settings| {
+        settings.argon2.m_cost = m;
+        settings.argon2.t_cost = t;
+        settings.argon2.p_cost = p;
+    });
+}
+
+// Peppers anteriores continuam configurados para verificar os hashes que ainda os usam.
+#[given(regex = r#"^passwords are peppered with key "(?P<key_id>[^"]+)"$"#)]
+async fn passwords_are_peppered_with(world: &mut AppWorld, key_id: String) {
+    world.configure_hashing(|settings| {
+        let argon2 = &mut settings.argon2;
+        if !argon2.peppers.iter().any(|pepper| pepper.key_id == key_id) {
+            argon2.peppers.push(Pepper {
+                key_id: key_id.clone(),
+                secret: format!("bdd-pepper-secret-{key_id}").into_bytes(),
+            });
+        }
+        argon2.active_pepper = Some(key_id);
+    });
+}
+
+#[given(regex = r#"^the pepper "(?P<key_id>[^"]+)" is no longer configured$"#)]
+async fn the_pepper_is_removed(world: &mut AppWorld, key_id: String) {
+    world.configure_hashing(|settings| {
+        let argon2 = &mut settings.argon2;
+        argon2.peppers.retain(|pepper| pepper.key_id != key_id);
+        if argon2.active_pepper.as_deref() == Some(key_id.as_str()) {
+            argon2.active_pepper = None;
+        }
+    });
+}
+
+#[then(
+    regex = r#"^the stored password hash of "(?P<email>[^"]+)" uses m_cost (?P<m>[0-9]+), t_cost (?P<t>[0-9]+) and p_cost (?P<p>[0-9]+)$"#
+)]
+async fn stored_hash_uses_costs(world: &mut AppWorld, email: String, m: u32, t: u32, p: u32) {
+    let params = world.stored_password_hash(&email).await;
+    assert_eq!(
+        (params.m_cost(), params.t_cost(), params.p_cost()),
+        (m, t, p)
+    );
+}
+
+#[then(
+    regex = r#"^the stored password hash of "(?P<email>[^"]+)" uses pepper "(?P<key_id>[^"]*)"$"#
+)]
+async fn stored_hash_uses_pepper(world: &mut AppWorld, email: String, key_id: String) {
+    let params = world.stored_password_hash(&email).await;
+    assert_eq!(params.keyid(), key_id.as_bytes());
+}
+
+#[then(regex = r#"^the password age of "(?P<email>[^"]+)" is (?P<days>[0-9]+) days$"#)]
+async fn the_password_age_is(world: &mut AppWorld, email: String, days: i64) {
+    let user = world.user_by_email(&email).await;
+    assert_eq!((Utc::now() - user.password_changed_at()).num_days(), days);
+}
+
+// Alterna as duas contas a cada rodada para que ruido do ambiente afete ambas por igual.
+#[when(
+    regex = r#"^I time (?P<count>[0-9]+) failed logins each for "(?P<known>[^"]+)" and "(?P<unknown>[^"]+)"$"#
+)]
+async fn i_time_failed_logins(world: &mut AppWorld, count: usize, known: String, unknown: String) {
+    let auth = world.auth_service().clone();
+    let client = world.client.clone();
+    let mut timings = LoginTimings {
+        known: Vec::with_capacity(count),
+        unknown: Vec::with_capacity(count),
+        known_error: String::new(),
+        unknown_error: String::new(),
+    };
+    // A primeira rodada aquece caches e o hash descartavel e nao entra na amostra.
+    for round in 0..=count {
+        let order = if round % 2 == 0 {
+            [true, false]
+        } else {
+            [false, true]
+        };
+        for is_known in order {
+            let email = if is_known { &known } else { &unknown };
+            let started = std::time::Instant::now();
+            let result = auth.authenticate(email, "Wrong-Password-99", &client).await;
+            let elapsed = started.elapsed();
+            let error = match result {
+                Err(err) => err.to_string(),
+                Ok(_) => panic!("login for {email} should have failed"),
+            };
+            let (samples, last_error) = if is_known {
+                (&mut timings.known, &mut timings.known_error)
+            } else {
+                (&mut timings.unknown, &mut timings.unknown_error)
+            };
+            if round > 0 {
+                samples.push(elapsed);
+            }
+            *last_error = error;
+        }
+    }
+    world.login_timings = Some(timings);
+}
+
+#[then("unknown and known accounts are rejected with the same error")]
+async fn rejected_with_the_same_error(world: &mut AppWorld) {
+    let timings = world
+        .login_timings
+        .as_ref()
+        .expect("login timings should have been measured");
+    assert_eq!(timings.known_error, timings.unknown_error);
+}
+
+// Medianas proximas e faixas interquartis sobrepostas: as duas distribuicoes se confundem.
+#[then(regex = r#"^the login latencies overlap within (?P<percent>[0-9]+) percent$"#)]
+async fn login_latencies_overlap(world: &mut AppWorld, percent: u32) {
+    let timings = world
+        .login_timings
+        .as_mut()
+        .expect("login timings should have been measured");
+    timings.known.sort();
+    timings.unknown.sort();
+    let quantile = |samples: &[std::time::Duration], q: f64| {
+        samples[((samples.len() - 1) as f64 * q).round() as usize].as_secs_f64()
+    };
+    let (known, unknown) = (&timings.known, &timings.unknown);
+    let (known_median, unknown_median) = (quantile(known, 0.5), quantile(unknown, 0.5));
+    let difference = (known_median - unknown_median).abs() / known_median.max(unknown_median);
+    assert!(
+        difference <= f64::from(percent) / 100.0,
+        "median login latency differs by {:.0}% (known {known_median:.4}s, unknown {unknown_median:.4}s)",
+        difference * 100.0
+    );
+    assert!(
+        quantile(known, 0.25) <= quantile(unknown, 0.75)
+            && quantile(unknown, 0.25) <= quantile(known, 0.75),
+        "interquartile ranges do not overlap: known {known:?}, unknown {unknown:?}"
+    );
+}
+
+// Le o `sid` sem validar a assinatura nem tocar na sessao (verificar registraria atividade).
+fn session_id_of(token: &str) -> Uuid {
+    let mut validation = Validation::default();
+    validation.insecure_disable_signature_validation();
+    decode::<Claims>(token, &DecodingKey::from_secret(&[]), &validation)
+        .expect("access token should decode")
+        .claims
+        .sid
+        .expect("access token should name its session")
+}
+
+impl AppWorld {
+    fn kept_session_id(&self, name: &str) -> Uuid {
+        let token = self
+            .sessions
+            .get(name)
+            .unwrap_or_else(|| panic!("session {name} should have been kept"));
+        session_id_of(token)
+    }
+
+    fn current_session_id(&self) -> Uuid {
+        session_id_of(
+            self.access_token
+                .as_deref()
+                .expect("an access token should have been issued"),
+        )
+    }
+
+    fn record_sessions(&mut self, result: AppResult<Vec<SessionDto>>) {
+        match result {
+            Ok(sessions) => {
+                self.listed_sessions = Some(sessions);
+                self.last_error = None;
+            }
+            Err(err) => {
+                self.listed_sessions = None;
+                self.last_error = Some(err);
+            }
+        }
+    }
+
+    fn listed_session(&self, name: &str) -> &SessionDto {
+        let id = self.kept_session_id(name);
+        self.listed_sessions
+            .as_ref()
+            .expect("sessions should have been listed")
+            .iter()
+            .find(|session| session.id == id)
+            .unwrap_or_else(|| panic!("session {name} should be listed"))
+    }
+}
+
+#[given(regex = r#"^logins use the user agent "(?P<agent>[^"]+)"$"#)]
+async fn logins_use_the_user_agent(world: &mut AppWorld, agent: String) {
+    world.client.user_agent = Some(agent);
+}
+
+#[when("I list my sessions")]
+async fn i_list_my_sessions(world: &mut AppWorld) {
+    let actor = world.current_user().await;
+    let result = world
+        .auth_service()
+        .sessions()
+        .list(&actor)
+        .await
+        .map(|sessions| {
+            sessions
+                .into_iter()
+                .map(|session| SessionDto::new(session, actor.session_id))
+                .collect()
+        });
+    world.record_sessions(result);
+}
+
+#[when(regex = r#"^I list the sessions of "(?P<email>[^"]+)"$"#)]
+async fn i_list_the_sessions_of(world: &mut AppWorld, email: String) {
+    let actor = world.current_user().await;
+    let user = world.user_by_email(&email).await;
+    let result = world
+        .auth_service()
+        .sessions()
+        .list_for_user(&actor, user.id())
+        .await
+        .map(|sessions| {
+            sessions
+                .into_iter()
+                .map(|session| SessionDto::new(session, actor.session_id))
+                .collect()
+        });
+    world.record_sessions(result);
+}
+
+#[when(regex = r#"^I revoke my session "(?P<name>[^"]+)"$"#)]
+async fn i_revoke_my_session(world: &mut AppWorld, name: String) {
+    let id = world.kept_session_id(&name);
+    let actor = world.current_user().await;
+    let result = world.auth_service().sessions().revoke(&actor, id).await;
+    world.last_error = result.err();
+}
+
+#[when("I revoke my current session")]
+async fn i_revoke_my_current_session(world: &mut AppWorld) {
+    let id = world.current_session_id();
+    let actor = world.current_user().await;
+    let result = world.auth_service().sessions().revoke(&actor, id).await;
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"^I revoke the session "(?P<name>[^"]+)" of "(?P<email>[^"]+)"$"#)]
+async fn i_revoke_the_session_of(world: &mut AppWorld, name: String, email: String) {
+    let id = world.kept_session_id(&name);
+    let actor = world.current_user().await;
+    let user = world.user_by_email(&email).await;
+    let result = world
+        .auth_service()
+        .sessions()
+        .revoke_for_user(&actor, user.id(), id)
+        .await;
+    world.last_error = result.err();
+}
+
+// Vai direto na lista de revogacao, sem passar pelo `AuthService`.
+#[when(regex = r#"^every token of "(?P<email>[^"]+)" is cut off by the revocation list$"#)]
+async fn every_token_is_cut_off(world: &mut AppWorld, email: String) {
+    let user = world.user_by_email(&email).await;
+    let now = Utc::now();
+    world
+        .backends()
+        .revocations
+        .revoke_user(user.id(), now, now + chrono::Duration::minutes(5))
+        .await
+        .expect("revocation should succeed");
+}
+
+#[when("I use my access token")]
+async fn i_use_my_access_token(world: &mut AppWorld) {
+    world.current_user().await;
+}
+
+#[given(regex = r#"^my current session was last seen (?P<seconds>[0-9]+) seconds ago$"#)]
+async fn my_session_was_last_seen(world: &mut AppWorld, seconds: i64) {
+    let id = world.current_session_id();
+    world
+        .backends()
+        .session_records
+        .set_last_seen(id, Utc::now() - chrono::Duration::seconds(seconds))
+        .await;
+}
+
+#[then(regex = r#"^I see (?P<count>[0-9]+) active sessions?$"#)]
+async fn i_see_active_sessions(world: &mut AppWorld, count: usize) {
+    let sessions = world
+        .listed_sessions
+        .as_ref()
+        .expect("sessions should have been listed");
+    assert_eq!(sessions.len(), count, "listed sessions: {sessions:?}");
+}
+
+#[then(
+    regex = r#"^the listed session "(?P<name>[^"]+)" shows user agent "(?P<agent>[^"]+)", address "(?P<ip>[^"]+)" and method "(?P<method>[^"]+)"$"#
+)]
+async fn the_listed_session_shows(
+    world: &mut AppWorld,
+    name: String,
+    agent: String,
+    ip: String,
+    method: String,
+) {
+    let session = world.listed_session(&name);
+    assert_eq!(session.user_agent.as_deref(), Some(agent.as_str()));
+    assert_eq!(session.ip_address.as_deref(), Some(ip.as_str()));
+    assert_eq!(session.auth_method, method);
+}
+
+#[then(regex = r#"^only the listed session "(?P<name>[^"]+)" is marked as current$"#)]
+async fn only_the_listed_session_is_current(world: &mut AppWorld, name: String) {
+    let id = world.kept_session_id(&name);
+    let current: Vec<Uuid> = world
+        .listed_sessions
+        .as_ref()
+        .expect("sessions should have been listed")
+        .iter()
+        .filter(|session| session.current)
+        .map(|session| session.id)
+        .collect();
+    assert_eq!(current, vec![id]);
+}
+
+#[then(
+    regex = r#"^my current session was last seen (?P<bound>less|more) than (?P<seconds>[0-9]+) seconds ago$"#
+)]
+async fn my_session_was_last_seen_within(world: &mut AppWorld, bound: String, seconds: i64) {
+    let id = world.current_session_id();
+    let session = world
+        .backends()
+        .session_records
+        .find_by_id(id)
+        .await
+        .expect("lookup should succeed")
+        .expect("session should exist");
+    let elapsed = Utc::now() - session.last_seen_at;
+    let limit = chrono::Duration::seconds(seconds);
+    match bound.as_str() {
+        "less" => assert!(elapsed < limit, "last seen {elapsed} ago"),
+        _ => assert!(elapsed > limit, "last seen {elapsed} ago"),
+    }
+}
+
+const OAUTH_STATE: &str = "af0ifjsldkj";
+
+impl AppWorld {
+    fn oauth_service(&mut self) -> OAuthService {
+        let auth = self.auth_service().clone();
+        let backends = self.backends();
+        OAuthService::new(
+            backends.oauth_clients,
+            backends.oauth_codes,
+            backends.oauth_consents,
+            auth,
+            OAuthSettings {
+                authorization_code_ttl: self
+                    .authorization_code_ttl
+                    .unwrap_or_else(|| OAuthSettings::default().authorization_code_ttl),
+            },
+        )
+    }
+
+    fn oauth_client(&self, name: &str) -> (Uuid, Option<String>) {
+        self.oauth_clients
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| panic!("the OAuth client \"{name}\" should have been registered"))
+    }
+
+    // Clientes confidenciais se autenticam pelo header Basic; publicos so informam o id.
+    fn oauth_credentials(&self, name: &str) -> (TokenRequestDto, Option<ClientCredentials>) {
+        match self.oauth_client(name) {
+            (id, Some(secret)) => (
+                TokenRequestDto::default(),
+                Some(ClientCredentials {
+                    client_id: id.to_string(),
+                    client_secret: secret,
+                }),
+            ),
+            (id, None) => (
+                TokenRequestDto {
+                    client_id: Some(id.to_string()),
+                    ..TokenRequestDto::default()
+                },
+                None,
+            ),
+        }
+    }
+
+    fn authorization_client_name(&self) -> String {
+        let request = self
+            .authorization_request
+            .as_ref()
+            .expect("an authorization should have been started");
+        self.oauth_clients
+            .iter()
+            .find(|(_, (id, _))| id.to_string() == request.client_id)
+            .map(|(name, _)| name.clone())
+            .expect("the authorization should target a registered client")
+    }
+
+    fn start_authorization(
+        &mut self,
+        name: &str,
+        scopes: &str,
+        redirect_uri: Option<String>,
+        pkce: bool,
+    ) -> AuthorizationRequestDto {
+        let (id, _) = self.oauth_client(name);
+        let verifier = opaque_token::generate();
+        let request = AuthorizationRequestDto {
+            response_type: "code".to_string(),
+            client_id: id.to_string(),
+            redirect_uri,
+            scope: Some(split_list(scopes).join(" ")),
+            state: Some(OAUTH_STATE.to_string()),
+            code_challenge: pkce.then(|| pkce::challenge_s256(&verifier)),
+            code_challenge_method: pkce.then(|| pkce::METHOD_S256.to_string()),
+        };
+
+        self.authorization_request = Some(request.clone());
+        self.code_verifier = Some(verifier);
+        self.authorization_code = None;
+        request
+    }
+
+    fn started_authorization(&self) -> AuthorizationRequestDto {
+        self.authorization_request
+            .clone()
+            .expect("an authorization should have been started")
+    }
+
+    // Com sessao, o resultado e o mesmo da API; sem ela, o passo fica para as verificacoes da pagina.
+    fn record_browser_step(&mut self, result: AppResult<BrowserStep>) {
+        match result {
+            Ok(BrowserStep::Authorized {
+                outcome,
+                csrf_token,
+                ..
+            }) => {
+                self.csrf_token = Some(csrf_token);
+                self.browser_step = None;
+                self.record_authorization(Ok(*outcome));
+            }
+            Ok(step) => {
+                self.authorization_outcome = None;
+                self.last_error = None;
+                self.browser_step = Some(step);
+            }
+            Err(err) => {
+                self.browser_step = None;
+                self.record_authorization(Err(err));
+            }
+        }
+    }
+
+    // Depois do login o navegador volta para `GET /oauth/authorize` com o cookie.
+    async fn sign_in_in_browser(&mut self, credentials: BrowserCredentials) {
+        let client = self.client.clone();
+        let result = self
+            .oauth_service()
+            .sign_in_in_browser(credentials, &client)
+            .await;
+        match result {
+            Ok(BrowserSignIn::SignedIn { session_token, .. }) => {
+                self.browser_session = Some(session_token);
+                let request = self.started_authorization();
+                let result = self
+                    .oauth_service()
+                    .authorize_in_browser(self.browser_session.as_deref(), &request)
+                    .await;
+                self.record_browser_step(result);
+            }
+            Ok(BrowserSignIn::Retry(step)) => self.record_browser_step(Ok(step)),
+            Err(err) => self.record_browser_step(Err(err)),
+        }
+    }
+
+    fn record_authorization(&mut self, result: AppResult<AuthorizationOutcome>) {
+        match result {
+            Ok(outcome) => {
+                if let AuthorizationOutcome::Approved { redirect_to, .. } = &outcome {
+                    self.authorization_code = redirect_to
+                        .split(['?', '&'])
+                        .find_map(|param| param.strip_prefix("code="))
+                        .map(str::to_string);
+                }
+                self.authorization_outcome = Some(outcome);
+                self.last_error = None;
+            }
+            Err(err) => {
+                self.authorization_outcome = None;
+                self.last_error = Some(err);
+            }
+        }
+    }
+
+    fn record_tokens(&mut self, result: Result<IssuedOAuthTokens, OAuthError>) {
+        match result {
+            Ok(tokens) => {
+                self.previous_oauth_tokens = self.oauth_tokens.replace(tokens);
+                self.oauth_error = None;
+            }
+            Err(err) => self.oauth_error = Some(err),
+        }
+    }
+
+    fn oauth_tokens(&self) -> &IssuedOAuthTokens {
+        self.oauth_tokens
+            .as_ref()
+            .expect("OAuth tokens should have been issued")
+    }
+
+    async fn oauth_user(&mut self) -> AppResult<AuthenticatedUser> {
+        let token = self.oauth_tokens().access_token.clone();
+        self.auth_service().verify(&token).await
+    }
+
+    async fn exchange_code(&mut self, verifier: String, send_redirect_uri: bool) {
+        let name = self.authorization_client_name();
+        let (mut request, credentials) = self.oauth_credentials(&name);
+        request.grant_type = Some("authorization_code".to_string());
+        request.code = self.authorization_code.clone();
+        request.redirect_uri = self
+            .authorization_request
+            .as_ref()
+            .and_then(|authorization| authorization.redirect_uri.clone())
+            .filter(|_| send_redirect_uri);
+        request.code_verifier = Some(verifier);
+
+        let client = self.client.clone();
+        let result = self
+            .oauth_service()
+            .token(request, credentials, &client)
+            .await;
+        self.record_tokens(result);
+    }
+
+    async fn refresh_oauth_tokens(&mut self, refresh_token: String, scopes: Option<String>) {
+        let name = self.authorization_client_name();
+        let (mut request, credentials) = self.oauth_credentials(&name);
+        request.grant_type = Some("refresh_token".to_string());
+        request.refresh_token = Some(refresh_token);
+        request.scope = scopes.map(|scopes| split_list(&scopes).join(" "));
+
+        let client = self.client.clone();
+        let result = self
+            .oauth_service()
+            .token(request, credentials, &client)
+            .await;
+        self.record_tokens(result);
+    }
+}
+
+#[given("authorization codes expire immediately")]
+async fn authorization_codes_expire_immediately(world: &mut AppWorld) {
+    world.authorization_code_ttl = Some(chrono::Duration::zero());
+}
+
+#[when(
+    regex = r#"^I register the (?P<kind>public|confidential) OAuth client "(?P<name>[^"]+)" with redirect URIs? "(?P<uris>[^"]*)", grants "(?P<grants>[^"]*)" and scopes "(?P<scopes>[^"]*)"$"#
+)]
+async fn i_register_an_oauth_client(
+    world: &mut AppWorld,
+    kind: String,
+    name: String,
+    uris: String,
+    grants: String,
+    scopes: String,
+) {
+    let actor = world.current_user().await;
+    let result = world
+        .oauth_service()
+        .register_client(
+            &actor,
+            CreateOAuthClientDto {
+                name: name.clone(),
+                client_type: kind,
+                redirect_uris: split_list(&uris),
+                grant_types: split_list(&grants),
+                scopes: split_list(&scopes),
+            },
+        )
+        .await;
+    match result {
+        Ok(issued) => {
+            world
+                .oauth_clients
+                .insert(name, (issued.client.id, issued.secret));
+            world.last_error = None;
+        }
+        Err(err) => world.last_error = Some(err),
+    }
+}
+
+#[when(regex = r#"^I disable the OAuth client "(?P<name>[^"]+)"$"#)]
+async fn i_disable_an_oauth_client(world: &mut AppWorld, name: String) {
+    let actor = world.current_user().await;
+    let (id, _) = world.oauth_client(&name);
+    world.last_error = world.oauth_service().disable_client(&actor, id).await.err();
+}
+
+#[then(regex = r#"^the active OAuth clients are "(?P<names>[^"]*)"$"#)]
+async fn the_active_oauth_clients_are(world: &mut AppWorld, names: String) {
+    let actor = world.current_user().await;
+    let mut listed: Vec<String> = world
+        .oauth_service()
+        .list_clients(&actor)
+        .await
+        .expect("listing clients should succeed")
+        .into_iter()
+        .map(|client| client.name)
+        .collect();
+    listed.sort();
+    assert_eq!(listed, split_list(&names));
+}
+
+#[when(
+    regex = r#"^I start an authorization for "(?P<name>[^"]+)" with scopes "(?P<scopes>[^"]*)"(?: redirecting to "(?P<uri>[^"]+)")?(?P<plain> without PKCE)?$"#
+)]
+async fn i_start_an_authorization(
+    world: &mut AppWorld,
+    name: String,
+    scopes: String,
+    uri: String,
+    plain: String,
+) {
+    let request = world.start_authorization(
+        &name,
+        &scopes,
+        (!uri.is_empty()).then_some(uri),
+        plain.is_empty(),
+    );
+    let actor = world.current_user().await;
+    let result = world.oauth_service().authorize(&actor, &request).await;
+    world.record_authorization(result);
+}
+
+#[when(regex = r#"^I (?P<decision>approve|deny) the authorization$"#)]
+async fn i_decide_the_authorization(world: &mut AppWorld, decision: String) {
+    let request = world.started_authorization();
+    let actor = world.current_user().await;
+    let result = world
+        .oauth_service()
+        .decide(&actor, &request, decision == "approve")
+        .await;
+    world.record_authorization(result);
+}
+
+#[when(
+    regex = r#"^I open the authorization page for "(?P<name>[^"]+)" with scopes "(?P<scopes>[^"]*)"$"#
+)]
+async fn i_open_the_authorization_page(world: &mut AppWorld, name: String, scopes: String) {
+    let request = world.start_authorization(&name, &scopes, None, true);
+    let result = world
+        .oauth_service()
+        .authorize_in_browser(world.browser_session.as_deref(), &request)
+        .await;
+    world.record_browser_step(result);
+}
+
+#[when(
+    regex = r#"^I sign in on the authorization page with email "(?P<email>[^"]+)" and password "(?P<password>[^"]+)"$"#
+)]
+async fn i_sign_in_on_the_authorization_page(
+    world: &mut AppWorld,
+    email: String,
+    password: String,
+) {
+    world
+        .sign_in_in_browser(BrowserCredentials::Password { email, password })
+        .await;
+}
+
+#[when("I enter the next code on the authorization page")]
+async fn i_enter_the_next_code_on_the_authorization_page(world: &mut AppWorld) {
+    let challenge_token = match &world.browser_step {
+        Some(BrowserStep::SecondFactor {
+            challenge_token, ..
+        }) => challenge_token.clone(),
+        other => panic!("expected the second factor page, got {other:?}"),
+    };
+    let code = world.next_totp_code();
+    world
+        .sign_in_in_browser(BrowserCredentials::SecondFactor {
+            challenge_token,
+            code,
+        })
+        .await;
+}
+
+#[when(
+    regex = r#"^I (?P<decision>approve|deny) the authorization on the consent page(?P<forged> with a forged csrf token)?$"#
+)]
+async fn i_decide_on_the_consent_page(world: &mut AppWorld, decision: String, forged: String) {
+    let request = world.started_authorization();
+    let csrf = if forged.is_empty() {
+        world
+            .csrf_token
+            .clone()
+            .expect("the consent page should have been shown")
+    } else {
+        opaque_token::generate()
+    };
+    let result = world
+        .oauth_service()
+        .decide_in_browser(
+            world.browser_session.as_deref(),
+            &csrf,
+            &request,
+            decision == "approve",
+        )
+        .await;
+    world.record_browser_step(result);
+}
+
+#[then(regex = r#"^the authorization page asks me to sign in(?: with error "(?P<error>[^"]+)")?$"#)]
+async fn the_authorization_page_asks_to_sign_in(world: &mut AppWorld, error: String) {
+    match &world.browser_step {
+        Some(BrowserStep::SignIn { error: shown }) => {
+            assert_eq!(
+                shown.as_deref(),
+                (!error.is_empty()).then_some(error.as_str())
+            )
+        }
+        other => panic!(
+            "expected the sign-in page, got {other:?} ({:?})",
+            world.last_error
+        ),
+    }
+}
+
+#[then("the authorization page asks for the second factor")]
+async fn the_authorization_page_asks_for_the_second_factor(world: &mut AppWorld) {
+    assert!(
+        matches!(
+            world.browser_step,
+            Some(BrowserStep::SecondFactor { error: None, .. })
+        ),
+        "expected the second factor page, got {:?}",
+        world.browser_step
+    );
+}
+
+#[then(regex = r#"^consent is required for scopes "(?P<scopes>[^"]*)"$"#)]
+async fn consent_is_required(world: &mut AppWorld, scopes: String) {
+    match &world.authorization_outcome {
+        Some(AuthorizationOutcome::ConsentRequired { scopes: asked, .. }) => {
+            let asked: Vec<String> = asked.iter().map(|scope| scope.to_string()).collect();
+            assert_eq!(asked, split_list(&scopes));
+        }
+        other => panic!(
+            "expected consent to be required, got {other:?} ({:?})",
+            world.last_error
+        ),
+    }
+}
+
+#[then(regex = r#"^the authorization redirects to "(?P<uri>[^"]+)" with a code and the state$"#)]
+async fn the_authorization_redirects_with_a_code(world: &mut AppWorld, uri: String) {
+    match &world.authorization_outcome {
+        Some(AuthorizationOutcome::Approved { redirect_to, .. }) => {
+            assert!(
+                redirect_to.starts_with(&format!("{uri}?code=")),
+                "{redirect_to}"
+            );
+            assert!(
+                redirect_to.ends_with(&format!("&state={OAUTH_STATE}")),
+                "{redirect_to}"
+            );
+        }
+        other => panic!(
+            "expected an approved authorization, got {other:?} ({:?})",
+            world.last_error
+        ),
+    }
+    assert!(world.authorization_code.is_some());
+}
+
+#[then(regex = r#"^the authorization is rejected with error "(?P<error>[^"]+)"$"#)]
+async fn the_authorization_is_rejected(world: &mut AppWorld, error: String) {
+    match &world.authorization_outcome {
+        Some(AuthorizationOutcome::Rejected {
+            redirect_to,
+            error: code,
+        }) => {
+            assert_eq!(code.as_str(), error);
+            assert!(
+                redirect_to.contains(&format!("error={error}")),
+                "{redirect_to}"
+            );
+            assert!(
+                redirect_to.ends_with(&format!("&state={OAUTH_STATE}")),
+                "{redirect_to}"
+            );
+        }
+        other => panic!(
+            "expected a rejected authorization, got {other:?} ({:?})",
+            world.last_error
+        ),
+    }
+}
+
+#[when(
+    regex = r#"^I exchange the code for tokens(?P<wrong> with a different code verifier)?(?P<bare> without the redirect URI)?$"#
+)]
+async fn i_exchange_the_code(world: &mut AppWorld, wrong: String, bare: String) {
+    let verifier = if wrong.is_empty() {
+        world
+            .code_verifier
+            .clone()
+            .expect("an authorization should have been started")
+    } else {
+        opaque_token::generate()
+    };
+    world.exchange_code(verifier, bare.is_empty()).await;
+}
+
+#[when(regex = r#"^I refresh the OAuth tokens(?: with scopes "(?P<scopes>[^"]*)")?$"#)]
+async fn i_refresh_the_oauth_tokens(world: &mut AppWorld, scopes: String) {
+    let refresh_token = world
+        .oauth_tokens()
+        .refresh_token
+        .clone()
+        .expect("a refresh token should have been issued");
+    let scopes = (!scopes.is_empty()).then_some(scopes);
+    world.refresh_oauth_tokens(refresh_token, scopes).await;
+}
+
+#[when("I replay the previous OAuth refresh token")]
+async fn i_replay_the_previous_oauth_refresh_token(world: &mut AppWorld) {
+    let refresh_token = world
+        .previous_oauth_tokens
+        .as_ref()
+        .and_then(|tokens| tokens.refresh_token.clone())
+        .expect("a rotated refresh token should be available");
+    world.refresh_oauth_tokens(refresh_token, None).await;
+}
+
+#[when("I refresh the session using the OAuth refresh token")]
+async fn i_refresh_the_session_with_the_oauth_refresh_token(world: &mut AppWorld) {
+    let refresh_token = world
+        .oauth_tokens()
+        .refresh_token
+        .clone()
+        .expect("a refresh token should have been issued");
+    let result = world.auth_service().refresh(&refresh_token).await;
+    world.record_session(result);
+}
+
+#[when(
+    regex = r#"^I request a client credentials token for "(?P<name>[^"]+)"(?: with scopes "(?P<scopes>[^"]*)")?(?P<wrong> with a wrong secret)?$"#
+)]
+async fn i_request_a_client_credentials_token(
+    world: &mut AppWorld,
+    name: String,
+    scopes: String,
+    wrong: String,
+) {
+    let (mut request, mut credentials) = world.oauth_credentials(&name);
+    request.grant_type = Some("client_credentials".to_string());
+    request.scope = (!scopes.is_empty()).then(|| split_list(&scopes).join(" "));
+    if !wrong.is_empty() {
+        if let Some(credentials) = credentials.as_mut() {
+            credentials.client_secret = "wr_cs_not-the-secret".to_string();
+        }
+    }
+
+    let client = world.client.clone();
+    let result = world
+        .oauth_service()
+        .token(request, credentials, &client)
+        .await;
+    world.record_tokens(result);
+}
+
+#[then(
+    regex = r#"^the token response grants scopes "(?P<scopes>[^"]*)" (?P<refresh>with|without) a refresh token$"#
+)]
+async fn the_token_response_grants(world: &mut AppWorld, scopes: String, refresh: String) {
+    assert!(
+        world.oauth_error.is_none(),
+        "expected the token request to succeed, got {:?}",
+        world.oauth_error
+    );
+    let tokens = world.oauth_tokens();
+    let granted: Vec<String> = tokens
+        .scopes
+        .iter()
+        .map(|scope| scope.to_string())
+        .collect();
+    assert_eq!(granted, split_list(&scopes));
+    assert_eq!(tokens.refresh_token.is_some(), refresh == "with");
+}
+
+#[then(
+    regex = r#"^the token request fails with error "(?P<error>[^"]+)"(?: and description "(?P<description>[^"]+)")?$"#
+)]
+async fn the_token_request_fails(world: &mut AppWorld, error: String, description: String) {
+    match &world.oauth_error {
+        Some(OAuthError::Protocol {
+            code,
+            description: actual,
+        }) => {
+            assert_eq!(code.as_str(), error);
+            assert!(
+                actual.contains(&description),
+                "expected description to contain '{description}', got '{actual}'"
+            );
+        }
+        other => panic!("expected the token request to fail with {error}, got {other:?}"),
+    }
+}
+
+#[when("I list users using the OAuth access token")]
+async fn i_list_users_with_the_oauth_token(world: &mut AppWorld) {
+    let result = match world.oauth_user().await {
+        Ok(actor) => world
+            .user_service()
+            .list_users(&actor, ListUsersQueryDto::default())
+            .await
+            .map(|_| ()),
+        Err(err) => Err(err),
+    };
+    world.last_error = result.err();
+}
+
+#[when(regex = r#"^I create the user "(?P<email>[^"]+)" using the OAuth access token$"#)]
+async fn i_create_a_user_with_the_oauth_token(world: &mut AppWorld, email: String) {
+    let result = match world.oauth_user().await {
+        Ok(actor) => world
+            .user_service()
+            .create_user(
+                &actor,
+                CreateUserDto {
+                    name: "Delegated User".to_string(),
+                    email,
+                    password: "Pylon-Ledger-Quill-58".to_string(),
+                    roles: vec!["viewer".to_string()],
+                },
+            )
+            .await
+            .map(|_| ()),
+        Err(err) => Err(err),
+    };
+    world.last_error = result.err();
+}
+
+#[when("I create a personal access token using the OAuth access token")]
+async fn i_create_a_token_with_the_oauth_token(world: &mut AppWorld) {
+    let result = match world.oauth_user().await {
+        Ok(actor) => world
+            .personal_access_tokens()
+            .create(&actor, "delegated", &["users:read".to_string()], None)
+            .await
+            .map(|_| ()),
+        Err(err) => Err(err),
+    };
+    world.last_error = result.err();
+}
+
+#[then(regex = r#"^the OAuth access token is rejected with message "(?P<message>[^"]+)"$"#)]
+async fn the_oauth_access_token_is_rejected(world: &mut AppWorld, message: String) {
+    let err = world
+        .oauth_user()
+        .await
+        .expect_err("expected the OAuth access token to be rejected");
+    assert!(
+        err.to_string().contains(&message),
+        "expected error to contain '{message}', got '{err}'"
+    );
+}
+
+#[when(regex = r#"^I revoke my OAuth consent for "(?P<name>[^"]+)"$"#)]
+async fn i_revoke_my_oauth_consent(world: &mut AppWorld, name: String) {
+    let actor = world.current_user().await;
+    let (id, _) = world.oauth_client(&name);
+    world.last_error = world.oauth_service().revoke_consent(&actor, id).await.err();
+}
+
+#[then(regex = r#"^my OAuth consents are "(?P<entries>[^"]*)"$"#)]
+async fn my_oauth_consents_are(world: &mut AppWorld, entries: String) {
+    let actor = world.current_user().await;
+    let listed: Vec<String> = world
+        .oauth_service()
+        .list_consents(&actor)
+        .await
+        .expect("listing consents should succeed")
+        .into_iter()
+        .map(|entry| {
+            let scopes: Vec<String> = entry
+                .consent
+                .scopes
+                .iter()
+                .map(|scope| scope.to_string())
+                .collect();
+            format!("{} ({})", entry.client.name, scopes.join(" "))
+        })
+        .collect();
+    assert_eq!(listed, split_list(&entries));
+}
+
+// Cada cenario monta os proprios servicos e calcula hashes Argon2 com os custos padrao (19 MiB
+// cada); com o paralelismo padrao do cucumber (64) a suite passa de 5 GB de memoria.
+const MAX_CONCURRENT_SCENARIOS: usize = 4;
+
 #[tokio::main(flavor = "multi_thread")]
 async fn main() {
-    AppWorld::run("tests/features").await;
+    AppWorld::cucumber()
+        .fail_on_skipped()
+        .max_concurrent_scenarios(MAX_CONCURRENT_SCENARIOS)
+        .run_and_exit("tests/features")
+        .await;
 }
@@ -0,0 +1,182 @@
+use proptest::prelude::*;
+use unicode_normalization::UnicodeNormalization;
+use webrust::domain::value_objects::{normalize_email_domain, EmailAddress};
+
+// Atomos da parte local: ASCII `atext` e letras acentuadas (RFC 6531).
+fn atom() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9!#$%&'*+/=?^_`{|}~-]{1,8}|[a-zà-ÿ]{1,4}"
+}
+
+fn local_part() -> impl Strategy<Value = String> {
+    prop::collection::vec(atom(), 1..4).prop_map(|atoms| atoms.join("."))
+}
+
+fn label() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9]([a-zA-Z0-9-]{0,20}[a-zA-Z0-9])?|[a-zü]{1,6}"
+}
+
+fn domain() -> impl Strategy<Value = String> {
+    (
+        prop::collection::vec(label(), 1..4),
+        "[a-zA-Z]{2,6}|xn--p1ai",
+    )
+        .prop_map(|(labels, tld)| format!("{}.{tld}", labels.join(".")))
+}
+
+fn address() -> impl Strategy<Value = String> {
+    (local_part(), domain()).prop_map(|(local, domain)| format!("{local}@{domain}"))
+}
+
+fn message(raw: &str) -> String {
+    EmailAddress::parse(raw)
+        .expect_err("address should be rejected")
+        .message()
+        .to_string()
+}
+
+proptest! {
+    #[test]
+    fn well_formed_addresses_parse(raw in address()) {
+        let email = EmailAddress::parse(&raw).expect("address should parse");
+        prop_assert_eq!(email.as_str(), raw.as_str());
+        prop_assert!(email.domain().is_ascii());
+        prop_assert_eq!(email.canonical(), email.canonical().to_lowercase());
+    }
+
+    #[test]
+    fn canonical_form_is_a_fixed_point(raw in address()) {
+        let email = EmailAddress::parse(&raw).expect("address should parse");
+        let reparsed = EmailAddress::parse(email.canonical()).expect("canonical form should parse");
+        prop_assert_eq!(reparsed.canonical(), email.canonical());
+    }
+
+    #[test]
+    fn case_and_normalization_do_not_change_identity(raw in address()) {
+        let email = EmailAddress::parse(&raw).expect("address should parse");
+        let upper = EmailAddress::parse(raw.to_uppercase()).expect("uppercase should parse");
+        let decomposed: String = raw.nfd().collect();
+        let decomposed = EmailAddress::parse(decomposed).expect("NFD should parse");
+        prop_assert_eq!(&upper, &email);
+        prop_assert_eq!(&decomposed, &email);
+    }
+
+    #[test]
+    fn needlessly_quoted_local_parts_are_unquoted(local in local_part(), domain in domain()) {
+        let plain = EmailAddress::parse(format!("{local}@{domain}")).expect("plain should parse");
+        let quoted = EmailAddress::parse(format!("\"{local}\"@{domain}")).expect("quoted should parse");
+        prop_assert_eq!(quoted, plain);
+    }
+
+    #[test]
+    fn consecutive_dots_in_the_local_part_are_rejected(
+        left in local_part(),
+        right in local_part(),
+        domain in domain(),
+    ) {
+        prop_assert_eq!(
+            message(&format!("{left}..{right}@{domain}")),
+            "email local part cannot contain consecutive dots"
+        );
+    }
+
+    #[test]
+    fn labels_with_edge_hyphens_are_rejected(
+        local in local_part(),
+        label in "[a-z0-9]{1,10}",
+        leading in any::<bool>(),
+    ) {
+        let label = if leading { format!("-{label}") } else { format!("{label}-") };
+        prop_assert_eq!(
+            message(&format!("{local}@{label}.example")),
+            format!("email domain label cannot start or end with a hyphen: {label}")
+        );
+    }
+
+    #[test]
+    fn labels_longer_than_63_characters_are_rejected(local in local_part(), label in "[a-z]{64,80}") {
+        prop_assert_eq!(
+            message(&format!("{local}@{label}.example")),
+            format!("email domain label must be at most 63 characters: {label}")
+        );
+    }
+
+    #[test]
+    fn local_parts_longer_than_64_bytes_are_rejected(local in "[a-z]{65,120}", domain in domain()) {
+        prop_assert_eq!(
+            message(&format!("{local}@{domain}")),
+            "email local part must be at most 64 bytes"
+        );
+    }
+
+    #[test]
+    fn parser_never_panics(raw in "\\PC{0,80}") {
+        if let Ok(email) = EmailAddress::parse(&raw) {
+            prop_assert!(email.canonical().len() <= 254);
+        }
+    }
+
+    #[test]
+    fn domain_filters_match_parsed_domains(raw in address()) {
+        let email = EmailAddress::parse(&raw).expect("address should parse");
+        let (_, domain) = raw.rsplit_once('@').expect("address has a domain");
+        prop_assert_eq!(normalize_email_domain(domain).expect("domain should parse"), email.domain());
+    }
+}
+
+#[test]
+fn accepts_rfc_5321_and_6531_forms() {
+    let cases = [
+        ("\"john doe\"@example.com", "\"john doe\"@example.com"),
+        ("\"a@b\\\"c\"@example.com", "\"a@b\\\"c\"@example.com"),
+        ("\"Ada\"@Example.com", "ada@example.com"),
+        ("user@[192.0.2.1]", "user@[192.0.2.1]"),
+        ("user@[IPv6:2001:DB8::1]", "user@[IPv6:2001:db8::1]"),
+        ("иван@пример.рф", "иван@xn--e1afmkfd.xn--p1ai"),
+        ("user@example.xn--p1ai", "user@example.xn--p1ai"),
+        ("o'brien+tag@example.com", "o'brien+tag@example.com"),
+        ("用户@例子.广告", "用户@xn--fsqu00a.xn--4rr70v"),
+    ];
+    for (raw, canonical) in cases {
+        let email = EmailAddress::parse(raw).unwrap_or_else(|err| panic!("{raw}: {err}"));
+        assert_eq!(email.canonical(), canonical, "{raw}");
+    }
+}
+
+#[test]
+fn reports_the_specific_failure() {
+    let cases = [
+        ("", "email is required"),
+        ("user.example.com", "email must contain @"),
+        ("@example.com", "email local part is required"),
+        (".user@example.com", "email local part cannot start or end with a dot"),
+        ("us er@example.com", "email local part contains an invalid character: ' '"),
+        ("\"user@example.com", "email quoted local part is not closed"),
+        ("\"us\"er@example.com", "email quoted local part must be followed by @"),
+        ("user@", "email domain is required"),
+        ("user@example..com", "email domain cannot contain empty labels (check for leading, trailing or consecutive dots)"),
+        ("user@localhost", "email domain must include a top-level domain"),
+        ("user@exa_mple.com", "email domain contains an invalid character: '_'"),
+        ("user@example.123", "email top-level domain cannot be numeric"),
+        ("user@[300.0.0.1]", "email domain address literal is not a valid IP address"),
+        ("user@[192.0.2.1", "email domain address literal is not closed"),
+        ("user@xn--zz.com", "email domain is not a valid internationalized domain name"),
+    ];
+    for (raw, expected) in cases {
+        assert_eq!(message(raw), expected, "{raw:?}");
+    }
+}
+
+#[test]
+fn length_limit_counts_utf8_bytes() {
+    // 32 caracteres acentuados ocupam os 64 bytes da parte local; o total passa de 254 bytes
+    // com bem menos de 254 caracteres.
+    let raw = format!(
+        "{}@{}.{}.{}",
+        "é".repeat(32),
+        "a".repeat(63),
+        "b".repeat(63),
+        "c".repeat(62)
+    );
+    assert!(raw.chars().count() < 254);
+    assert_eq!(message(&raw), "email must be at most 254 bytes");
+}
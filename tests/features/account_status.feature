@@ -0,0 +1,86 @@
+Feature: Account status lifecycle
+  As an administrator
+  I want to suspend, lock and reactivate accounts with a reason
+  So that I can block access without deleting the user
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+
+  Scenario: New users start active
+    Then the user "carol@example.com" has status "active"
+
+  Scenario: Suspended users cannot sign in until reactivated
+    When I suspend the user "carol@example.com" because "Chargeback under investigation"
+    Then the API call succeeds
+    And the user "carol@example.com" has status "suspended"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication fails with message "account is suspended"
+    When I reactivate the user "carol@example.com" because "Chargeback resolved"
+    Then the API call succeeds
+    And the user "carol@example.com" has status "active"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+
+  Scenario: A wrong password does not reveal the account status
+    When I suspend the user "carol@example.com" because "Policy violation"
+    And I authenticate with email "carol@example.com" and password "Wrong#Pass1"
+    Then the authentication fails with message "invalid credentials"
+
+  Scenario: Tokens issued before the suspension stop working
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I act as "admin@webrust.dev"
+    When I lock the user "carol@example.com" because "Credentials leaked"
+    And I act as "carol@example.com"
+    Then the access token is rejected with message "session revoked"
+    When I refresh the session
+    Then the authentication fails with message "invalid refresh token"
+
+  Scenario: Personal access tokens only work while the account is active
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I create a personal access token "ci" with scopes "users:read"
+    And I act as "admin@webrust.dev"
+    When I suspend the user "carol@example.com" because "Policy violation"
+    And I list users using the personal access token
+    Then the API call fails with message "account is suspended"
+    When I reactivate the user "carol@example.com" because "Appeal accepted"
+    And I list users using the personal access token
+    Then the API call succeeds
+
+  Scenario: Reactivation does not revive sessions opened before the suspension
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I act as "admin@webrust.dev"
+    When I suspend the user "carol@example.com" because "Policy violation"
+    And I reactivate the user "carol@example.com" because "Appeal accepted"
+    And I act as "carol@example.com"
+    Then the access token is rejected with message "session revoked"
+
+  Scenario: Transitions are enforced
+    When I reactivate the user "carol@example.com" because "Nothing to do"
+    Then the API call fails with message "user is already active"
+    When I suspend the user "carol@example.com" because "Policy violation"
+    And I suspend the user "carol@example.com" because "Again"
+    Then the API call fails with message "user is already suspended"
+
+  Scenario: A reason is required
+    When I suspend the user "carol@example.com" because "   "
+    Then the API call fails with message "reason is required"
+    And the user "carol@example.com" has status "active"
+
+  Scenario: Administrators cannot change their own status
+    When I suspend the user "admin@webrust.dev" because "Testing"
+    Then the API call fails with message "cannot change the status of your own account"
+
+  Scenario: Users without write permission cannot change statuses
+    Given a user named "Alan Turing" with email "alan@example.org" and roles "viewer"
+    When I authenticate with email "alan@example.org" and password "Listing#Pass1"
+    When I suspend the user "carol@example.com" because "Policy violation"
+    Then the API call fails with message "permission users:write required"
+
+  Scenario: Listing can be filtered by status
+    When I suspend the user "carol@example.com" because "Policy violation"
+    And I list users with query "status=suspended"
+    Then the listed users are "carol@example.com"
+    When I list users with query "status=active"
+    Then the listed users are "admin@webrust.dev"
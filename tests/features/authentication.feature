@@ -9,7 +9,7 @@ Feature: Authentication
   Scenario: Successful authentication
     When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
     Then the authentication succeeds
-    And the returned user role is "admin"
+    And the returned user has role "admin"
     And the access token is issued
 
   Scenario: Authentication fails with wrong password
@@ -0,0 +1,50 @@
+Feature: Optimistic concurrency on user updates
+  As an administrator
+  I want updates to fail when someone else changed the user first
+  So that concurrent edits never silently overwrite each other
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+
+  Scenario: An update with the current ETag succeeds
+    When I fetch the user "carol@example.com"
+    And I rename the user "carol@example.com" to "Captain Marvel" with If-Match {fetched}
+    Then the API call succeeds
+    And the user "carol@example.com" is named "Captain Marvel"
+
+  Scenario: A stale ETag is rejected instead of overwriting
+    When I fetch the user "carol@example.com"
+    And I rename the user "carol@example.com" to "Captain Marvel"
+    And I rename the user "carol@example.com" to "Binary" with If-Match {fetched}
+    Then the API call fails with message "has been modified since version 1"
+    And the user "carol@example.com" is named "Captain Marvel"
+
+  Scenario: Deletes honour If-Match as well
+    When I fetch the user "carol@example.com"
+    And I rename the user "carol@example.com" to "Captain Marvel"
+    And I delete the user "carol@example.com" with If-Match {fetched}
+    Then the API call fails with message "has been modified since version 1"
+    When I delete the user "carol@example.com" with If-Match *
+    Then the API call succeeds
+
+  Scenario: Weak or malformed tags never match on writes
+    When I rename the user "carol@example.com" to "Captain Marvel" with If-Match W/"1"
+    Then the API call fails with message "entity tag does not match the current version"
+    When I rename the user "carol@example.com" to "Captain Marvel" with If-Match W/"1", "bogus"
+    Then the API call fails with message "entity tag does not match the current version"
+
+  Scenario: A list of entity tags matches when any strong tag is current
+    When I fetch the user "carol@example.com"
+    And I rename the user "carol@example.com" to "Captain Marvel" with If-Match "41", {fetched}
+    Then the API call succeeds
+    And the user "carol@example.com" is named "Captain Marvel"
+    When I rename the user "carol@example.com" to "Binary" with If-Match "41", W/"2"
+    Then the API call fails with message "has been modified since version 41"
+
+  Scenario: Conditional reads detect changes
+    When I fetch the user "carol@example.com"
+    Then a conditional read of the user "carol@example.com" is not modified
+    When I rename the user "carol@example.com" to "Captain Marvel"
+    Then a conditional read of the user "carol@example.com" is modified
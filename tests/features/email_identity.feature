@@ -0,0 +1,43 @@
+Feature: Email identity
+  As an administrator
+  I want each email address to identify a single account regardless of how it is typed
+  So that case or Unicode variants cannot create duplicate accounts or dodge lookups
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: Emails differing only in case belong to the same account
+    When I create the user "carol@example.com" with password "Ledger-Harbor-Quill-73" and roles "viewer"
+    Then the API call succeeds
+    When I create the user "Carol@EXAMPLE.com" with password "Ledger-Harbor-Quill-73" and roles "viewer"
+    Then the API call fails with message "already exists"
+
+  Scenario: Login accepts any casing of the stored email
+    When I create the user "Dave.Smith@Example.com" with password "Ledger-Harbor-Quill-73" and roles "viewer"
+    And I authenticate with email "dave.smith@example.COM" and password "Ledger-Harbor-Quill-73"
+    Then the authentication succeeds
+
+  Scenario: Composed and decomposed accents are the same address
+    When I create the user "josé@example.com" with password "Ledger-Harbor-Quill-73" and roles "viewer"
+    And I create the user "josé@example.com" with password "Ledger-Harbor-Quill-73" and roles "viewer"
+    Then the API call fails with message "already exists"
+    When I authenticate with email "josé@example.com" and password "Ledger-Harbor-Quill-73"
+    Then the authentication succeeds
+
+  Scenario: Internationalized domains match their ASCII form
+    Given a user named "Ana" with email "ana@bücher.example" and roles "viewer"
+    When I create the user "ana@xn--bcher-kva.example" with password "Ledger-Harbor-Quill-73" and roles "viewer"
+    Then the API call fails with message "already exists"
+    When I list users with query "email_domain=xn--bcher-kva.example"
+    Then the listed users are "ana@bücher.example"
+    When I list users with query "email_domain=BÜCHER.example"
+    Then the listed users are "ana@bücher.example"
+
+  Scenario: Malformed addresses are rejected
+    When I create the user "eve@exa_mple.com" with password "Ledger-Harbor-Quill-73" and roles "viewer"
+    Then the API call fails with message "email domain contains an invalid character: '_'"
+    When I create the user "eve..smith@example.com" with password "Ledger-Harbor-Quill-73" and roles "viewer"
+    Then the API call fails with message "email local part cannot contain consecutive dots"
+    When I create the user "eve@-example.com" with password "Ledger-Harbor-Quill-73" and roles "viewer"
+    Then the API call fails with message "email domain label cannot start or end with a hyphen: -example"
@@ -0,0 +1,79 @@
+Feature: Email verification and email changes
+  As an account owner
+  I want my email address confirmed before it is trusted
+  So that nobody can take over an account by pointing it at an inbox they control
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+
+  Scenario: New users receive a link that confirms their address
+    Then the user "admin@webrust.dev" has a verified email
+    And the user "carol@example.com" has an unverified email
+    And 1 email "Confirm your email address" is sent to "carol@example.com"
+    When I open the latest verification link sent to "carol@example.com"
+    Then the API call succeeds
+    And the user "carol@example.com" has a verified email
+
+  Scenario: Login can be restricted to verified accounts
+    Given login requires a verified email address
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication fails with message "email address is not verified"
+    When I open the latest verification link sent to "carol@example.com"
+    And I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+
+  Scenario: A new address only takes effect once confirmed from its inbox
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I change my email to "carol@new.example"
+    Then the API call succeeds
+    And the user "carol@example.com" has pending email "carol@new.example"
+    And 1 email "Your email address is being changed" is sent to "carol@example.com"
+    And 1 email "Confirm your new email address" is sent to "carol@new.example"
+    When I open the latest email change link sent to "carol@new.example"
+    Then the API call succeeds
+    And the user "carol@new.example" has a verified email
+    And the user "carol@new.example" has pending email ""
+    When I authenticate with email "carol@new.example" and password "Listing#Pass1"
+    Then the authentication succeeds
+
+  Scenario: A superseded email change link is rejected
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I change my email to "carol@first.example"
+    And I change my email to "carol@second.example"
+    And I open the latest email change link sent to "carol@first.example"
+    Then the API call fails with message "invalid or expired verification link"
+    And the user "carol@example.com" has pending email "carol@second.example"
+
+  Scenario: An address that belongs to someone else cannot be claimed
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I change my email to "admin@webrust.dev"
+    Then the API call succeeds
+    And the user "carol@example.com" has pending email "admin@webrust.dev"
+    And 1 email "Your email address is being changed" is sent to "carol@example.com"
+    And 0 emails "Confirm your new email address" are sent to "admin@webrust.dev"
+    And 1 email "Someone tried to use your email address" is sent to "admin@webrust.dev"
+    When I ask for a new verification email
+    Then 0 emails "Confirm your new email address" are sent to "admin@webrust.dev"
+    And 2 emails "Someone tried to use your email address" are sent to "admin@webrust.dev"
+
+  Scenario: Administrators go through the same confirmation
+    When I change the email of the user "carol@example.com" to "carol@corp.example"
+    Then the API call succeeds
+    And the user "carol@example.com" has pending email "carol@corp.example"
+    And 1 email "Your email address is being changed" is sent to "carol@example.com"
+
+  Scenario: Resending a link
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I ask for a new verification email
+    Then 2 emails "Confirm your email address" are sent to "carol@example.com"
+    When I open the first verification link sent to "carol@example.com"
+    And I ask for a new verification email
+    Then the API call fails with message "email already verified"
+
+  Scenario: Access tokens are not verification links
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I try to verify my email with my access token
+    Then the API call fails with message "invalid or expired verification link"
+    And the user "carol@example.com" has an unverified email
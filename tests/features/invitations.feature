@@ -0,0 +1,73 @@
+Feature: User invitations
+  As an administrator
+  I want to invite people instead of choosing their passwords
+  So that nobody but the invitee ever knows their credentials
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+
+  Scenario: The invitee chooses a password and activates the account
+    When I invite "Peter Parker" with email "peter@example.com" and roles "viewer"
+    Then the API call succeeds
+    And the user "peter@example.com" has status "pending"
+    And 1 email "You have been invited" is sent to "peter@example.com"
+    And the pending invitations are "peter@example.com"
+    When I accept the latest invitation sent to "peter@example.com" with password "Spider#Sense1"
+    Then the API call succeeds
+    And the user "peter@example.com" has status "active"
+    And the user "peter@example.com" has a verified email
+    And the pending invitations are ""
+    When I authenticate with email "peter@example.com" and password "Spider#Sense1"
+    Then the authentication succeeds
+
+  Scenario: A rejected password leaves the link usable
+    When I invite "Peter Parker" with email "peter@example.com" and roles "viewer"
+    And I accept the latest invitation sent to "peter@example.com" with password "short"
+    Then the API call fails with message "password must be at least 12 characters"
+    And the user "peter@example.com" has status "pending"
+    When I accept the latest invitation sent to "peter@example.com" with password "Spider#Sense1"
+    Then the API call succeeds
+
+  Scenario: A link can only be used once
+    When I invite "Peter Parker" with email "peter@example.com" and roles "viewer"
+    And I accept the latest invitation sent to "peter@example.com" with password "Spider#Sense1"
+    And I accept the latest invitation sent to "peter@example.com" with password "Other#Sense22"
+    Then the API call fails with message "invalid or expired invitation"
+
+  Scenario: Resending replaces the previous link
+    When I invite "Peter Parker" with email "peter@example.com" and roles "viewer"
+    And I resend the invitation for "peter@example.com"
+    Then the API call succeeds
+    And 2 emails "You have been invited" are sent to "peter@example.com"
+    When I accept the first invitation sent to "peter@example.com" with password "Spider#Sense1"
+    Then the API call fails with message "invalid or expired invitation"
+    When I accept the latest invitation sent to "peter@example.com" with password "Spider#Sense1"
+    Then the API call succeeds
+
+  Scenario: Revoking removes the pending user
+    When I invite "Peter Parker" with email "peter@example.com" and roles "viewer"
+    And I revoke the invitation for "peter@example.com"
+    Then the API call succeeds
+    And the pending invitations are ""
+    When I list users
+    Then the listed users are "carol@example.com,admin@webrust.dev"
+    When I accept the latest invitation sent to "peter@example.com" with password "Spider#Sense1"
+    Then the API call fails with message "invalid or expired invitation"
+
+  Scenario: A revoked address can be invited again
+    When I invite "Peter Parker" with email "peter@example.com" and roles "viewer"
+    And I revoke the invitation for "peter@example.com"
+    And I invite "Peter Parker" with email "peter@example.com" and roles "viewer"
+    Then the API call succeeds
+    And the pending invitations are "peter@example.com"
+
+  Scenario: Existing addresses cannot be invited
+    When I invite "Carol Again" with email "carol@example.com" and roles "viewer"
+    Then the API call fails with message "user carol@example.com already exists"
+
+  Scenario: Inviting requires permission to manage users
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    When I invite "Peter Parker" with email "peter@example.com" and roles "viewer"
+    Then the API call fails with message "permission users:write required"
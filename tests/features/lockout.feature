@@ -0,0 +1,70 @@
+Feature: Login lockout
+  As an operator
+  I want repeated login failures to lock the targeted account or source address
+  So that passwords cannot be brute-forced without locking out everyone else
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    And accounts lock after 3 failed logins
+    And requests come from address "203.0.113.10"
+
+  Scenario: An account is locked after repeated failures
+    When I fail to authenticate 3 times as "admin@webrust.dev"
+    Then the authentication fails with message "too many login attempts"
+    And the authentication is throttled for at least 60 seconds
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication fails with message "too many login attempts"
+    And no access token is issued
+
+  Scenario: Unknown emails are throttled exactly like real accounts
+    When I fail to authenticate 3 times as "ghost@webrust.dev"
+    Then the authentication fails with message "too many login attempts"
+    And the authentication is throttled for at least 60 seconds
+
+  Scenario: An oversized lockout duration locks the account instead of failing the request
+    Given accounts lock for the longest representable duration
+    When I fail to authenticate 3 times as "admin@webrust.dev"
+    Then the authentication fails with message "too many login attempts"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication fails with message "too many login attempts"
+
+  Scenario: Failures against one account do not lock another
+    When I fail to authenticate 3 times as "ghost@webrust.dev"
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication succeeds
+
+  Scenario: A successful login resets the failure counter
+    When I fail to authenticate 2 times as "admin@webrust.dev"
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I fail to authenticate 2 times as "admin@webrust.dev"
+    Then the authentication fails with message "invalid credentials"
+
+  Scenario: Spraying many accounts from one address locks the address
+    Given addresses lock after 4 failed logins
+    When I fail to authenticate 2 times as "alice@webrust.dev"
+    And I fail to authenticate 2 times as "bob@webrust.dev"
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication fails with message "too many login attempts"
+
+  Scenario: A spoofed X-Forwarded-For entry does not change the locked address
+    Given addresses lock after 4 failed logins
+    When requests from address "198.51.100.7" reach a trusted proxy claiming X-Forwarded-For "10.0.0.1"
+    And I fail to authenticate 2 times as "alice@webrust.dev"
+    And requests from address "198.51.100.7" reach a trusted proxy claiming X-Forwarded-For "10.0.0.2"
+    And I fail to authenticate 2 times as "bob@webrust.dev"
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication fails with message "too many login attempts"
+
+  Scenario: Failed attempts impose an exponential back-off
+    Given failed logins back off starting at 30 seconds
+    When I fail to authenticate 1 times as "admin@webrust.dev"
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication fails with message "too many login attempts"
+    And the authentication is throttled for at least 29 seconds
+
+  Scenario: An admin can unlock a locked account
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I fail to authenticate 3 times as "admin@webrust.dev"
+    And I unlock the account "admin@webrust.dev" as admin
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication succeeds
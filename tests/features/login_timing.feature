@@ -0,0 +1,18 @@
+Feature: Timing-uniform login
+  As a security officer
+  I want failed logins to cost the same whether or not the account exists
+  So that response times do not reveal which emails are registered
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    And passwords are hashed with m_cost 4096, t_cost 2 and p_cost 1
+    And accounts lock after 1000 failed logins
+    And addresses lock after 1000 failed logins
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  @serial
+  Scenario: Unknown accounts take as long to reject as wrong passwords
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    When I time 40 failed logins each for "carol@example.com" and "nobody@example.com"
+    Then unknown and known accounts are rejected with the same error
+    And the login latencies overlap within 25 percent
@@ -0,0 +1,28 @@
+Feature: Logout and token revocation
+  As an API consumer
+  I want to invalidate my tokens before they expire
+  So that a leaked token cannot be used after I sign out
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: Logging out revokes the access token
+    When I log out
+    Then the access token is rejected with message "session revoked"
+
+  Scenario: Logging out with the refresh token ends the refresh chain
+    When I log out including the refresh token
+    And I refresh the session
+    Then the authentication fails with message "invalid refresh token"
+
+  Scenario: Revoking every session of a user
+    When I revoke all of my sessions as admin
+    Then the access token is rejected with message "session revoked"
+    When I refresh the session
+    Then the authentication fails with message "invalid refresh token"
+
+  Scenario: Signing in right after revoking every session works
+    When I revoke all of my sessions as admin
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the access token is accepted
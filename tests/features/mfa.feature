@@ -0,0 +1,49 @@
+Feature: TOTP multi-factor authentication
+  As a security-conscious operator
+  I want logins to require a second factor once TOTP is enabled
+  So that a leaked password alone does not grant access
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I enroll in TOTP
+    And I confirm the TOTP enrollment with the current code
+
+  Scenario: Enrollment returns a provisioning URI and recovery codes
+    Then the provisioning URI names issuer "WebRust"
+    And I receive 10 recovery codes
+
+  Scenario: Login requires the second factor after enrollment
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the login requires "mfa_required"
+    And the challenge token is not accepted as an access token
+    When I verify the MFA challenge with the next code
+    Then the authentication succeeds
+    And the access token is accepted
+
+  Scenario: Wrong codes are rejected
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I verify the MFA challenge with code "abcdef"
+    Then the authentication fails with message "invalid mfa code"
+    And no access token is issued
+
+  Scenario: A TOTP code cannot be replayed
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I verify the MFA challenge with the next code
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I verify the MFA challenge with the last code again
+    Then the authentication fails with message "mfa code already used"
+
+  Scenario: A challenge token is single use
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I verify the MFA challenge with the next code
+    And I verify the MFA challenge with the first recovery code
+    Then the authentication fails with message "invalid mfa challenge"
+
+  Scenario: Recovery codes work exactly once
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I verify the MFA challenge with the first recovery code
+    Then the authentication succeeds
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I verify the MFA challenge with the first recovery code
+    Then the authentication fails with message "invalid mfa code"
@@ -0,0 +1,18 @@
+Feature: Role-based MFA policy
+  As an administrator
+  I want to require MFA for privileged roles
+  So that admin accounts cannot sign in with a password alone
+
+  Scenario: Admins without TOTP must enroll before receiving tokens
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    When MFA is required for role "admin"
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the login requires "mfa_enrollment_required"
+    And the challenge token is not accepted as an access token
+    When I enroll in TOTP using the challenge token
+    And I confirm the TOTP enrollment with the current code
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the login requires "mfa_required"
+    When I verify the MFA challenge with the next code
+    Then the authentication succeeds
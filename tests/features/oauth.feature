@@ -0,0 +1,202 @@
+Feature: OAuth 2.0 authorization server
+  As a user of third-party applications
+  I want to grant them limited, revocable access without sharing my password
+  So that an integration can only do what I consented to and my roles allow
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I register the public OAuth client "Dashboard" with redirect URI "https://dashboard.example.com/callback", grants "authorization_code,refresh_token" and scopes "users:read,users:write"
+    Then the API call succeeds
+
+  Scenario: Authorization code with PKCE yields tokens limited to the granted scopes
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    Then consent is required for scopes "users:read"
+    When I approve the authorization
+    Then the authorization redirects to "https://dashboard.example.com/callback" with a code and the state
+    When I exchange the code for tokens
+    Then the token response grants scopes "users:read" with a refresh token
+    When I list users using the OAuth access token
+    Then the API call succeeds
+    When I create the user "bot@webrust.dev" using the OAuth access token
+    Then the API call fails with message "token scope users:write required"
+    When I create a personal access token using the OAuth access token
+    Then the API call fails with message "operation requires an interactive session"
+
+  Scenario: The authorization page signs the user in and redirects back with a code
+    When I open the authorization page for "Dashboard" with scopes "users:read"
+    Then the authorization page asks me to sign in
+    When I sign in on the authorization page with email "admin@webrust.dev" and password "wrong-password"
+    Then the authorization page asks me to sign in with error "invalid credentials"
+    When I sign in on the authorization page with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then consent is required for scopes "users:read"
+    When I approve the authorization on the consent page
+    Then the authorization redirects to "https://dashboard.example.com/callback" with a code and the state
+    When I exchange the code for tokens
+    Then the token response grants scopes "users:read" with a refresh token
+    When I open the authorization page for "Dashboard" with scopes "users:read"
+    Then the authorization redirects to "https://dashboard.example.com/callback" with a code and the state
+
+  Scenario: The authorization page asks for the second factor of TOTP users
+    When I enroll in TOTP
+    And I confirm the TOTP enrollment with the current code
+    And I open the authorization page for "Dashboard" with scopes "users:read"
+    And I sign in on the authorization page with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authorization page asks for the second factor
+    When I enter the next code on the authorization page
+    Then consent is required for scopes "users:read"
+
+  Scenario: The consent page only accepts decisions carrying its csrf token
+    When I open the authorization page for "Dashboard" with scopes "users:read"
+    And I sign in on the authorization page with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I approve the authorization on the consent page with a forged csrf token
+    Then the API call fails with message "invalid csrf token"
+    When I deny the authorization on the consent page
+    Then the authorization is rejected with error "access_denied"
+
+  Scenario: A redirect URI sent with the authorization must be repeated in the exchange
+    When I start an authorization for "Dashboard" with scopes "users:read" redirecting to "https://dashboard.example.com/callback"
+    And I approve the authorization
+    Then the authorization redirects to "https://dashboard.example.com/callback" with a code and the state
+    When I exchange the code for tokens without the redirect URI
+    Then the token request fails with error "invalid_request" and description "redirect_uri is required"
+    When I exchange the code for tokens
+    Then the token response grants scopes "users:read" with a refresh token
+
+  Scenario: Consent is remembered for the scopes already granted
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    And I approve the authorization
+    And I start an authorization for "Dashboard" with scopes "users:read"
+    Then the authorization redirects to "https://dashboard.example.com/callback" with a code and the state
+    When I start an authorization for "Dashboard" with scopes "users:read,users:write"
+    Then consent is required for scopes "users:read,users:write"
+
+  Scenario: Denying the request sends access_denied back to the client
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    And I deny the authorization
+    Then the authorization is rejected with error "access_denied"
+    And my OAuth consents are ""
+
+  Scenario: The code verifier must match the challenge
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    And I approve the authorization
+    And I exchange the code for tokens with a different code verifier
+    Then the token request fails with error "invalid_grant" and description "code_verifier does not match the code_challenge"
+
+  Scenario: Authorization requests without PKCE are refused
+    When I start an authorization for "Dashboard" with scopes "users:read" without PKCE
+    Then the authorization is rejected with error "invalid_request"
+
+  Scenario: Replaying an authorization code revokes the tokens it produced
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    And I approve the authorization
+    And I exchange the code for tokens
+    Then the token response grants scopes "users:read" with a refresh token
+    When I exchange the code for tokens
+    Then the token request fails with error "invalid_grant" and description "authorization code already used"
+    And the OAuth access token is rejected with message "session revoked"
+
+  Scenario: Expired authorization codes are refused
+    Given authorization codes expire immediately
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    And I approve the authorization
+    And I exchange the code for tokens
+    Then the token request fails with error "invalid_grant" and description "authorization code expired"
+
+  Scenario: Refresh tokens rotate and can only narrow the granted scopes
+    When I start an authorization for "Dashboard" with scopes "users:read,users:write"
+    And I approve the authorization
+    And I exchange the code for tokens
+    And I refresh the OAuth tokens with scopes "users:read"
+    Then the token response grants scopes "users:read" with a refresh token
+    When I refresh the OAuth tokens
+    Then the token response grants scopes "users:read,users:write" with a refresh token
+    When I refresh the OAuth tokens with scopes "roles:read"
+    Then the token request fails with error "invalid_scope"
+    When I replay the previous OAuth refresh token
+    Then the token request fails with error "invalid_grant" and description "refresh token reuse detected"
+
+  Scenario: OAuth refresh tokens are not accepted by the session refresh endpoint
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    And I approve the authorization
+    And I exchange the code for tokens
+    And I refresh the session using the OAuth refresh token
+    Then the authentication fails with message "invalid refresh token"
+
+  Scenario: Users can only grant what their roles allow
+    When I create the role "support" with permissions "users:read"
+    And I create the user "support@webrust.dev" with password "Harbor-Quill-Ledger-42" and roles "support"
+    And I authenticate with email "support@webrust.dev" and password "Harbor-Quill-Ledger-42"
+    And I start an authorization for "Dashboard" with scopes "users:read,users:write"
+    Then consent is required for scopes "users:read"
+    When I approve the authorization
+    And I exchange the code for tokens
+    Then the token response grants scopes "users:read" with a refresh token
+
+  Scenario: Clients cannot ask for more than they were registered with
+    When I start an authorization for "Dashboard" with scopes "roles:write"
+    Then the authorization is rejected with error "invalid_scope"
+
+  Scenario: Unregistered redirect URIs are never redirected to
+    When I start an authorization for "Dashboard" with scopes "users:read" redirecting to "https://evil.example.com/callback"
+    Then the API call fails with message "redirect_uri is not registered for this client"
+
+  Scenario: Confidential clients obtain tokens with client credentials
+    When I register the confidential OAuth client "Reporting" with redirect URIs "", grants "client_credentials" and scopes "users:read"
+    And I request a client credentials token for "Reporting"
+    Then the token response grants scopes "users:read" without a refresh token
+    When I list users using the OAuth access token
+    Then the API call succeeds
+    When I request a client credentials token for "Reporting" with a wrong secret
+    Then the token request fails with error "invalid_client"
+    When I request a client credentials token for "Reporting" with scopes "users:write"
+    Then the token request fails with error "invalid_scope"
+
+  Scenario: Revoking every session of the owner rejects client credentials tokens issued just before
+    When I register the confidential OAuth client "Reporting" with redirect URIs "", grants "client_credentials" and scopes "users:read"
+    And I request a client credentials token for "Reporting"
+    And I revoke all of my sessions as admin
+    And I list users using the OAuth access token
+    Then the API call fails with message "token revoked"
+
+  Scenario: A client credentials token issued right after revoking every session is accepted
+    When I register the confidential OAuth client "Reporting" with redirect URIs "", grants "client_credentials" and scopes "users:read"
+    And I revoke all of my sessions as admin
+    And I request a client credentials token for "Reporting"
+    And I list users using the OAuth access token
+    Then the API call succeeds
+
+  Scenario: Public clients cannot use client credentials
+    When I register the public OAuth client "Widget" with redirect URIs "", grants "client_credentials" and scopes "users:read"
+    Then the API call fails with message "public clients cannot use the client_credentials grant"
+    When I request a client credentials token for "Dashboard"
+    Then the token request fails with error "unauthorized_client"
+
+  Scenario: Redirect URIs must be https or loopback
+    When I register the public OAuth client "Insecure" with redirect URI "http://app.example.com/callback", grants "authorization_code" and scopes "users:read"
+    Then the API call fails with message "invalid redirect URI: http://app.example.com/callback"
+    When I register the public OAuth client "Desktop" with redirect URI "http://127.0.0.1:8080/callback", grants "authorization_code" and scopes "users:read"
+    Then the API call succeeds
+
+  Scenario: Revoking consent ends the sessions of that client
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    And I approve the authorization
+    And I exchange the code for tokens
+    Then my OAuth consents are "Dashboard (users:read)"
+    When I revoke my OAuth consent for "Dashboard"
+    Then the API call succeeds
+    And my OAuth consents are ""
+    And the OAuth access token is rejected with message "session revoked"
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    Then consent is required for scopes "users:read"
+
+  Scenario: Disabling a client ends its sessions and stops new authorizations
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    And I approve the authorization
+    And I exchange the code for tokens
+    And I disable the OAuth client "Dashboard"
+    Then the API call succeeds
+    And the active OAuth clients are ""
+    And the OAuth access token is rejected with message "session revoked"
+    When I start an authorization for "Dashboard" with scopes "users:read"
+    Then the API call fails with message "unknown client_id"
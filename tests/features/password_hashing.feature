@@ -0,0 +1,36 @@
+Feature: Bounded password hashing
+  As an operator
+  I want Argon2 to run on a bounded pool that sheds load when full
+  So that a burst of logins cannot freeze the rest of the API
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    And password hashing runs on 1 worker with a queue of 0
+
+  Scenario: Logins are refused with a retry delay while the pool is saturated
+    Given every password hashing worker is busy
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the request is shed with retry after 1 second
+    When the password hashing workers are released
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication succeeds
+
+  Scenario: A shed login does not count as a failed attempt
+    Given accounts lock after 2 failed logins
+    And every password hashing worker is busy
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the request is shed with retry after 1 second
+    When the password hashing workers are released
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication succeeds
+
+  Scenario: Creating a user is refused while the pool is saturated
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Given every password hashing worker is busy
+    When I create the user "carol@example.com" with password "Quartz-Meadow-Lynx-42" and roles "viewer"
+    Then the API call fails with message "password hashing queue is full"
+    When the password hashing workers are released
+    And I create the user "carol@example.com" with password "Quartz-Meadow-Lynx-42" and roles "viewer"
+    Then the API call succeeds
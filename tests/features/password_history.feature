@@ -0,0 +1,100 @@
+Feature: Password history and expiry
+  As a security officer
+  I want recent passwords refused and old passwords to expire
+  So that a password cannot be recycled forever
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: Changing my own password cannot bring back a recent one
+    Given passwords cannot repeat the last 3
+    When I create the user "carol@example.com" with password "Quartz-Meadow-Lynx-42" and roles "viewer"
+    And I authenticate with email "carol@example.com" and password "Quartz-Meadow-Lynx-42"
+    And I change my password from "Quartz-Meadow-Lynx-42" to "Harbor-Thistle-Comet-17"
+    Then the API call succeeds
+    When I change my password from "Harbor-Thistle-Comet-17" to "Quartz-Meadow-Lynx-42"
+    Then the API call fails with message "new password must differ from the last 3 passwords"
+    When I change my password from "Harbor-Thistle-Comet-17" to "Pebble-Orchard-Violin-63"
+    And I change my password from "Pebble-Orchard-Violin-63" to "Quartz-Meadow-Lynx-42"
+    Then the API call fails with message "new password must differ from the last 3 passwords"
+    When I change my password from "Pebble-Orchard-Violin-63" to "Riddle-Canyon-Ember-08"
+    And I change my password from "Riddle-Canyon-Ember-08" to "Quartz-Meadow-Lynx-42"
+    Then the API call succeeds
+
+  Scenario: Administrators cannot reset a user to a recent password
+    Given passwords cannot repeat the last 3
+    When I create the user "carol@example.com" with password "Quartz-Meadow-Lynx-42" and roles "viewer"
+    And I set the password of the user "carol@example.com" to "Quartz-Meadow-Lynx-42"
+    Then the API call fails with message "new password must differ from the last 3 passwords"
+    When I set the password of the user "carol@example.com" to "Harbor-Thistle-Comet-17"
+    Then the API call succeeds
+    When I set the password of the user "carol@example.com" to "Quartz-Meadow-Lynx-42"
+    Then the API call fails with message "new password must differ from the last 3 passwords"
+
+  Scenario: An administrator password reset signs the user out everywhere
+    When I create the user "carol@example.com" with password "Quartz-Meadow-Lynx-42" and roles "viewer"
+    And I authenticate with email "carol@example.com" and password "Quartz-Meadow-Lynx-42"
+    And I act as "admin@webrust.dev"
+    And I set the password of the user "carol@example.com" to "Harbor-Thistle-Comet-17"
+    Then the API call succeeds
+    When I act as "carol@example.com"
+    Then the access token is rejected with message "session revoked"
+    When I refresh the session
+    Then the authentication fails with message "invalid refresh token"
+
+  Scenario: A rejected password change is not remembered
+    Given passwords cannot repeat the last 3
+    When I create the user "carol@example.com" with password "Quartz-Meadow-Lynx-42" and roles "viewer"
+    And I set the password of the user "carol@example.com" to "Harbor-Thistle-Comet-17"
+    And I set the password of the user "carol@example.com" to "Pebble-Orchard-Violin-63"
+    Then the API call succeeds
+    When I set the password of the user "carol@example.com" to "Riddle-Canyon-Ember-08" with If-Match "41"
+    Then the API call fails with message "has been modified since version 41"
+    When I set the password of the user "carol@example.com" to "Quartz-Meadow-Lynx-42"
+    Then the API call fails with message "new password must differ from the last 3 passwords"
+
+  Scenario: A reset link cannot bring back a recent password
+    Given passwords cannot repeat the last 3
+    When I create the user "carol@example.com" with password "Quartz-Meadow-Lynx-42" and roles "viewer"
+    And I request a password reset for "carol@example.com"
+    And I reset the password of "carol@example.com" to "Quartz-Meadow-Lynx-42" using the latest emailed link
+    Then the password reset fails with message "new password must differ from the last 3 passwords"
+    When I reset the password of "carol@example.com" to "Harbor-Thistle-Comet-17" using the latest emailed link
+    Then the password reset succeeds
+
+  Scenario: Without a history earlier passwords can be reused
+    When I create the user "carol@example.com" with password "Quartz-Meadow-Lynx-42" and roles "viewer"
+    And I set the password of the user "carol@example.com" to "Harbor-Thistle-Comet-17"
+    And I set the password of the user "carol@example.com" to "Quartz-Meadow-Lynx-42"
+    Then the API call succeeds
+
+  Scenario: An expired password only allows changing it
+    Given passwords expire after 90 days
+    And a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    And the password of "carol@example.com" was last changed 91 days ago
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the login requires "password_change_required"
+    And the challenge token is not accepted as an access token
+    When I change my password from "Listing#Pass1" to "Harbor-Thistle-Comet-17" using the challenge token
+    Then the API call succeeds
+    And the access token is accepted
+    When I authenticate with email "carol@example.com" and password "Harbor-Thistle-Comet-17"
+    Then the authentication succeeds
+
+  Scenario: Passwords younger than the maximum age sign in normally
+    Given passwords expire after 90 days
+    And a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    And the password of "carol@example.com" was last changed 89 days ago
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+
+  Scenario: The second factor is still required before an expired password can be changed
+    When I enroll in TOTP
+    And I confirm the TOTP enrollment with the current code
+    Given passwords expire after 90 days
+    And the password of "admin@webrust.dev" was last changed 120 days ago
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the login requires "mfa_required"
+    When I verify the MFA challenge with the next code
+    Then the login requires "password_change_required"
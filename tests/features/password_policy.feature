@@ -0,0 +1,62 @@
+Feature: Password policy
+  As a security officer
+  I want new passwords judged by how hard they are to guess
+  So that strong passphrases are welcome and predictable passwords are refused
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: Predictable passwords are refused even when they tick every box
+    When I create the user "weak@example.com" with password "Password123!" and roles "viewer"
+    Then the API call fails with message "password is too easy to guess"
+    When I create the user "weak@example.com" with password "qwertyuiop1234" and roles "viewer"
+    Then the API call fails with message "password is too easy to guess"
+
+  Scenario: Long passphrases need no digits or symbols
+    When I create the user "phrase@example.com" with password "correct horse battery staple" and roles "viewer"
+    Then the API call succeeds
+    When I authenticate with email "phrase@example.com" and password "correct horse battery staple"
+    Then the authentication succeeds
+
+  Scenario: Every unmet rule is reported at once
+    Given the password policy requires "uppercase, digit, symbol"
+    When I create the user "short@example.com" with password "sunshine" and roles "viewer"
+    Then the API call fails with message "password must be at least 12 characters; password must include an uppercase letter; password must include a digit; password must include a symbol; password is too easy to guess"
+
+  Scenario: Composition rules apply only when configured
+    When I create the user "lower@example.com" with password "velvet orbit lantern quiz" and roles "viewer"
+    Then the API call succeeds
+    Given the password policy requires "uppercase"
+    When I create the user "lower2@example.com" with password "velvet orbit lantern quiz" and roles "viewer"
+    Then the API call fails with message "password must include an uppercase letter"
+
+  Scenario: Passwords built from the account's name or email are refused
+    When I create the user "marguerite.dupont@example.com" with password "Marguerite-Vault-8841" and roles "viewer"
+    Then the API call fails with message "password must not contain your name or email"
+    Given self-registration is "open"
+    When I register as "Oswaldo Pereira" with email "ozzy@example.com" and password "pereira gosta de cafe"
+    Then the API call fails with message "password must not contain your name or email"
+
+  Scenario: Short names do not rule out unrelated passwords
+    Given self-registration is "open"
+    When I register as "Bob Ana" with email "bob@example.com" and password "Kebob-Banana-Lantern-73"
+    Then the API call succeeds
+
+  Scenario: Breached passwords are refused
+    Given the breached password list contains "blue-velvet-harbor-99, Tr0ub4dor&3x"
+    When I create the user "leaked@example.com" with password "blue-velvet-harbor-99" and roles "viewer"
+    Then the API call fails with message "password appears in a known data breach"
+    When I create the user "leaked@example.com" with password "blue-velvet-harbor-98" and roles "viewer"
+    Then the API call succeeds
+
+  Scenario: The strength threshold is configurable
+    Given the password policy requires a strength score of 0
+    When I create the user "legacy@example.com" with password "Password123!" and roles "viewer"
+    Then the API call succeeds
+
+  Scenario: Changing my own password follows the policy
+    When I create the user "member@example.com" with password "Quartz-Meadow-Lynx-42" and roles "viewer"
+    And I authenticate with email "member@example.com" and password "Quartz-Meadow-Lynx-42"
+    And I change my password from "Quartz-Meadow-Lynx-42" to "Password123!"
+    Then the API call fails with message "password is too easy to guess"
@@ -0,0 +1,62 @@
+Feature: Argon2 parameter upgrades
+  As an operator
+  I want to raise the Argon2 costs or rotate the pepper without resetting passwords
+  So that stored hashes catch up on each user's next login
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: Hashes with outdated costs are upgraded on the next successful login
+    Given passwords are hashed with m_cost 8192, t_cost 1 and p_cost 1
+    And a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    Then the stored password hash of "carol@example.com" uses m_cost 8192, t_cost 1 and p_cost 1
+    Given passwords are hashed with m_cost 12288, t_cost 2 and p_cost 1
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+    And the stored password hash of "carol@example.com" uses m_cost 12288, t_cost 2 and p_cost 1
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+
+  Scenario: A failed login leaves the stored hash untouched
+    Given passwords are hashed with m_cost 8192, t_cost 1 and p_cost 1
+    And a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    And passwords are hashed with m_cost 12288, t_cost 2 and p_cost 1
+    When I authenticate with email "carol@example.com" and password "Wrong-Password-99"
+    Then the stored password hash of "carol@example.com" uses m_cost 8192, t_cost 1 and p_cost 1
+
+  Scenario: Upgrading a hash does not restart the password age
+    Given passwords expire after 90 days
+    And passwords are hashed with m_cost 8192, t_cost 1 and p_cost 1
+    And a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    And the password of "carol@example.com" was last changed 60 days ago
+    And passwords are hashed with m_cost 12288, t_cost 2 and p_cost 1
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+    And the stored password hash of "carol@example.com" uses m_cost 12288, t_cost 2 and p_cost 1
+    And the password age of "carol@example.com" is 60 days
+
+  Scenario: Adding a pepper upgrades hashes made without one
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    Then the stored password hash of "carol@example.com" uses pepper ""
+    Given passwords are peppered with key "p2025"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+    And the stored password hash of "carol@example.com" uses pepper "p2025"
+
+  Scenario: Rotating the pepper moves hashes to the new key on login
+    Given passwords are peppered with key "p2025"
+    And a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    And passwords are peppered with key "p2026"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+    And the stored password hash of "carol@example.com" uses pepper "p2026"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+
+  Scenario: A hash made with a pepper that is no longer configured cannot be verified
+    Given passwords are peppered with key "p2025"
+    And a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    And the pepper "p2025" is no longer configured
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the API call fails with message "failed to verify stored password hash"
@@ -0,0 +1,55 @@
+Feature: Self-service password reset
+  As a user who forgot my password
+  I want to receive a single-use reset link by email
+  So that I can choose a new password without revealing which emails are registered
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: Requesting a reset for an unknown email looks like any other request
+    When I request a password reset for "ghost@webrust.dev"
+    Then the password reset request is accepted
+    And 0 reset emails are sent to "ghost@webrust.dev"
+    When I request a password reset for "admin@webrust.dev"
+    Then the password reset request is accepted
+    And 1 reset email is sent to "admin@webrust.dev"
+
+  Scenario: A reset link changes the password exactly once
+    When I request a password reset for "admin@webrust.dev"
+    And I reset the password of "admin@webrust.dev" to "N3wSecret!42" using the latest emailed link
+    Then the password reset succeeds
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the authentication fails with message "invalid credentials"
+    When I authenticate with email "admin@webrust.dev" and password "N3wSecret!42"
+    Then the authentication succeeds
+    When I reset the password of "admin@webrust.dev" to "An0therSecret!" using the latest emailed link
+    Then the password reset fails with message "invalid or expired reset token"
+
+  Scenario: Resetting the password ends existing sessions
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I request a password reset for "admin@webrust.dev"
+    And I reset the password of "admin@webrust.dev" to "N3wSecret!42" using the latest emailed link
+    Then the access token is rejected with message "session revoked"
+    When I refresh the session
+    Then the authentication fails with message "invalid refresh token"
+
+  Scenario: An expired reset link is rejected
+    Given password reset links expire immediately
+    When I request a password reset for "admin@webrust.dev"
+    And I reset the password of "admin@webrust.dev" to "N3wSecret!42" using the latest emailed link
+    Then the password reset fails with message "invalid or expired reset token"
+
+  Scenario: Requesting a new link invalidates the previous one
+    When I request a password reset for "admin@webrust.dev"
+    And I request a password reset for "admin@webrust.dev"
+    And I reset the password of "admin@webrust.dev" to "N3wSecret!42" using the first emailed link
+    Then the password reset fails with message "invalid or expired reset token"
+    When I reset the password of "admin@webrust.dev" to "N3wSecret!42" using the latest emailed link
+    Then the password reset succeeds
+
+  Scenario: A weak new password does not burn the reset link
+    When I request a password reset for "admin@webrust.dev"
+    And I reset the password of "admin@webrust.dev" to "short" using the latest emailed link
+    Then the password reset fails with message "password"
+    When I reset the password of "admin@webrust.dev" to "N3wSecret!42" using the latest emailed link
+    Then the password reset succeeds
@@ -0,0 +1,54 @@
+Feature: Personal access tokens
+  As an automation owner
+  I want long-lived tokens limited to the scopes my scripts need
+  So that machine clients never hold my password or an interactive session
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: A token can only do what its scopes allow
+    When I create a personal access token "reporting" with scopes "users:read"
+    And I list users using the personal access token
+    Then the API call succeeds
+    When I create the user "bot@webrust.dev" using the personal access token
+    Then the API call fails with message "token scope users:write required"
+
+  Scenario: A write-scoped token can manage users
+    When I create a personal access token "provisioning" with scopes "users:read,users:write"
+    And I create the user "bot@webrust.dev" using the personal access token
+    Then the API call succeeds
+
+  Scenario: Tokens are listed without their secret and track their last use
+    When I create a personal access token "reporting" with scopes "users:read"
+    Then my personal access tokens are listed as "reporting"
+    And the personal access token "reporting" was not used
+    When I list users using the personal access token
+    Then the personal access token "reporting" was used
+
+  Scenario: A revoked token stops working immediately
+    When I create a personal access token "reporting" with scopes "users:read"
+    And I revoke the personal access token "reporting"
+    Then my personal access tokens are listed as ""
+    When I list users using the personal access token
+    Then the API call fails with message "invalid token"
+
+  Scenario: Tokens cannot manage credentials
+    When I create a personal access token "provisioning" with scopes "users:read,users:write"
+    And I create a personal access token "escalation" with scopes "users:read" using the personal access token
+    Then the API call fails with message "operation requires an interactive session"
+    When I log out using the personal access token
+    Then the API call fails with message "operation requires an interactive session"
+
+  Scenario: Token names are unique per user
+    When I create a personal access token "reporting" with scopes "users:read"
+    And I create a personal access token "reporting" with scopes "users:read"
+    Then the API call fails with message "a token named 'reporting' already exists"
+
+  Scenario: Unknown scopes and excessive lifetimes are rejected
+    When I create a personal access token "reporting" with scopes "users:admin"
+    Then the API call fails with message "invalid scope: users:admin"
+    When I create a personal access token "reporting" with scopes "users:read" valid for 400 days
+    Then the API call fails with message "expires_in_days must be at most 365"
+    When I create a personal access token "reporting" with scopes "users:read" valid for 9223372036854775807 days
+    Then the API call fails with message "expires_in_days must be at most 365"
@@ -0,0 +1,20 @@
+Feature: Refresh token rotation
+  As an API consumer
+  I want to renew my access token without re-sending my password
+  So that stolen refresh tokens cannot be replayed
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: Refresh token is rotated on use
+    When I refresh the session
+    Then the authentication succeeds
+    And a new refresh token is issued
+
+  Scenario: Reusing a rotated refresh token revokes the whole family
+    When I refresh the session
+    And I replay the previous refresh token
+    Then the authentication fails with message "refresh token reuse detected"
+    When I refresh the session
+    Then the authentication fails with message "invalid refresh token"
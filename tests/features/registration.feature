@@ -0,0 +1,93 @@
+Feature: Self-registration
+  As a visitor
+  I want to create my own account when the operator allows it
+  So that administrators do not have to create every account by hand
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: Registration is disabled by default
+    When I register as "Peter Parker" with email "peter@example.com" and password "Spider#Sense1"
+    Then the API call fails with message "self-registration is disabled"
+
+  Scenario: Open registration creates an active account
+    Given self-registration is "open"
+    When I register as "Peter Parker" with email "peter@example.com" and password "Spider#Sense1"
+    Then the API call succeeds
+    And the user "peter@example.com" has status "active"
+    And 1 email "Confirm your email address" is sent to "peter@example.com"
+    And the pending registrations are ""
+    When I authenticate with email "peter@example.com" and password "Spider#Sense1"
+    Then the authentication succeeds
+
+  Scenario: Registered accounts get no permissions
+    Given self-registration is "open"
+    When I register as "Peter Parker" with email "peter@example.com" and password "Spider#Sense1"
+    And I authenticate with email "peter@example.com" and password "Spider#Sense1"
+    And I list users
+    Then the listed users are "peter@example.com"
+
+  Scenario: Only allowed email domains can register
+    Given self-registration is "domains" for domains "example.com, corp.example"
+    When I register as "Mallory" with email "mallory@evil.example" and password "Spider#Sense1"
+    Then the API call fails with message "registration is not open to this email domain"
+    When I register as "Peter Parker" with email "peter@Corp.Example" and password "Spider#Sense1"
+    Then the API call succeeds
+
+  Scenario: Registering an existing address looks like a new registration and warns the owner
+    Given self-registration is "open"
+    When I register as "Impostor" with email "Admin@WebRust.dev" and password "Spider#Sense1"
+    Then the API call succeeds
+    And 1 email "Someone tried to register with your email" is sent to "admin@webrust.dev"
+    And 0 emails "Confirm your email address" are sent to "admin@webrust.dev"
+    When I list users
+    Then the listed users are "admin@webrust.dev"
+
+  Scenario: Weak passwords are refused
+    Given self-registration is "open"
+    When I register as "Peter Parker" with email "peter@example.com" and password "short"
+    Then the API call fails with message "password must be at least 12 characters"
+
+  Scenario: Approval keeps the account pending until an admin approves it
+    Given self-registration is "approval"
+    When I register as "Peter Parker" with email "peter@example.com" and password "Spider#Sense1"
+    Then the API call succeeds
+    And the user "peter@example.com" has status "pending"
+    And the pending registrations are "peter@example.com"
+    When I authenticate with email "peter@example.com" and password "Spider#Sense1"
+    Then the authentication fails with message "account is pending"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I approve the registration of "peter@example.com"
+    Then the API call succeeds
+    And the user "peter@example.com" has status "active"
+    And the pending registrations are ""
+    And 1 email "Your account has been approved" is sent to "peter@example.com"
+    When I authenticate with email "peter@example.com" and password "Spider#Sense1"
+    Then the authentication succeeds
+
+  Scenario: Rejecting removes the pending account
+    Given self-registration is "approval"
+    When I register as "Peter Parker" with email "peter@example.com" and password "Spider#Sense1"
+    And I reject the registration of "peter@example.com"
+    Then the API call succeeds
+    And the pending registrations are ""
+    And 1 email "Your registration was not approved" is sent to "peter@example.com"
+    When I list users
+    Then the listed users are "admin@webrust.dev"
+    When I register as "Peter Parker" with email "peter@example.com" and password "Spider#Sense1"
+    Then the pending registrations are "peter@example.com"
+
+  Scenario: Only pending registrations can be reviewed
+    Given self-registration is "open"
+    When I register as "Peter Parker" with email "peter@example.com" and password "Spider#Sense1"
+    And I approve the registration of "peter@example.com"
+    Then the API call fails with message "no pending registration for user"
+
+  Scenario: Reviewing requires permission to manage users
+    Given self-registration is "approval"
+    And a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    When I register as "Peter Parker" with email "peter@example.com" and password "Spider#Sense1"
+    And I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I approve the registration of "peter@example.com"
+    Then the API call fails with message "permission users:write required"
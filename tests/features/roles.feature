@@ -0,0 +1,70 @@
+Feature: Permission-based roles
+  As an administrator
+  I want to define custom roles from the permission catalog
+  So that each account receives exactly the access it needs
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+
+  Scenario: A custom role grants its permissions to every assigned user
+    When I create the role "support" with permissions "users:read,users:write"
+    Then the API call succeeds
+    When I create the user "agent@webrust.dev" with password "Support#Pass1" and roles "support"
+    Then the API call succeeds
+    When I authenticate with email "agent@webrust.dev" and password "Support#Pass1"
+    Then the returned user has role "support"
+    When I list users
+    Then I see 2 users
+
+  Scenario: Role changes apply to tokens that were already issued
+    When I create the role "support" with permissions "users:read"
+    And I create the user "agent@webrust.dev" with password "Support#Pass1" and roles "support"
+    And I authenticate with email "agent@webrust.dev" and password "Support#Pass1"
+    And I act as "admin@webrust.dev"
+    And I change the permissions of role "support" to ""
+    And I act as "agent@webrust.dev"
+    And I list users
+    Then I see 1 user
+
+  Scenario: Users cannot grant or manage permissions they do not hold
+    When I create the role "support" with permissions "users:read,users:write,roles:write"
+    And I create the user "agent@webrust.dev" with password "Support#Pass1" and roles "support"
+    And I authenticate with email "agent@webrust.dev" and password "Support#Pass1"
+    When I create the role "deleter" with permissions "users:delete"
+    Then the API call fails with message "cannot grant permission users:delete"
+    When I rename the user "admin@webrust.dev" to "Hijacked"
+    Then the API call fails with message "cannot manage a user holding permission users:delete"
+    When I create the user "other@webrust.dev" with password "Support#Pass1" and roles "admin"
+    Then the API call fails with message "cannot grant permission users:delete"
+
+  Scenario: Built-in roles cannot be changed or removed
+    When I change the permissions of role "admin" to "users:read"
+    Then the API call fails with message "built-in role admin cannot be changed"
+    When I delete the role "viewer"
+    Then the API call fails with message "built-in role viewer cannot be changed"
+
+  Scenario: Roles still assigned to users cannot be deleted
+    When I create the role "support" with permissions "users:read"
+    And I create the user "agent@webrust.dev" with password "Support#Pass1" and roles "support"
+    And I delete the role "support"
+    Then the API call fails with message "role support is still assigned to 1 user(s)"
+
+  Scenario: Unknown permissions and roles are rejected
+    When I create the role "support" with permissions "users:everything"
+    Then the API call fails with message "invalid permission: users:everything"
+    When I create the user "agent@webrust.dev" with password "Support#Pass1" and roles "ghost"
+    Then the API call fails with message "unknown role: ghost"
+
+  Scenario: MFA policies apply to custom roles
+    When I create the role "support" with permissions "users:read"
+    And I create the user "agent@webrust.dev" with password "Support#Pass1" and roles "viewer,support"
+    And MFA is required for role "support"
+    And I authenticate with email "agent@webrust.dev" and password "Support#Pass1"
+    Then the login requires "mfa_enrollment_required"
+
+  Scenario: Viewers only see their own account
+    When I create the user "viewer@webrust.dev" with password "Viewer#Pass1" and roles "viewer"
+    And I authenticate with email "viewer@webrust.dev" and password "Viewer#Pass1"
+    And I list users
+    Then I see 1 user
@@ -0,0 +1,76 @@
+Feature: Self-service profile and password
+  As a signed-in user
+  I want to manage my own profile and password
+  So that I do not depend on an administrator for everyday changes
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+
+  Scenario: A viewer reads and renames their own profile
+    Then my profile shows name "Carol Danvers"
+    When I update my profile with {"name": "Captain Marvel"}
+    Then the API call succeeds
+    And my profile shows name "Captain Marvel"
+
+  Scenario: Preferences are normalized and can be cleared
+    When I update my profile with {"locale": "pt_br", "timezone": "America/Sao_Paulo"}
+    Then the API call succeeds
+    And my profile shows locale "pt-BR"
+    And my profile shows timezone "America/Sao_Paulo"
+    When I update my profile with {"locale": ""}
+    Then my profile shows locale ""
+    And my profile shows timezone "America/Sao_Paulo"
+
+  Scenario: Invalid preferences are rejected
+    When I update my profile with {"timezone": "not a zone"}
+    Then the API call fails with message "invalid timezone"
+    When I update my profile with {"locale": "portuguese-brazil-please"}
+    Then the API call fails with message "invalid locale"
+
+  Scenario: Email and roles cannot be changed through the profile
+    When I update my profile with {"email": "carol@evil.example"}
+    Then the API call fails with message "unknown field `email`"
+    When I update my profile with {"roles": ["admin"]}
+    Then the API call fails with message "unknown field `roles`"
+    When I update my profile with {}
+    Then the API call fails with message "at least one field must be provided"
+
+  Scenario: Changing the password revokes the other sessions
+    When I keep this session as "laptop"
+    And I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    When I change my password from "Listing#Pass1" to "Brand#NewPass2"
+    Then the API call succeeds
+    And the access token is accepted
+    When I act as "laptop"
+    Then the access token is rejected with message "session revoked"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication fails with message "invalid credentials"
+    When I authenticate with email "carol@example.com" and password "Brand#NewPass2"
+    Then the authentication succeeds
+
+  Scenario: The current password is required
+    When I change my password from "Wrong#Pass1" to "Brand#NewPass2"
+    Then the API call fails with message "current password is incorrect"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+
+  Scenario: Wrong current passwords count towards the login lockout
+    Given accounts lock after 2 failed logins
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    When I change my password from "Wrong#Pass1" to "Brand#NewPass2"
+    And I change my password from "Wrong#Pass2" to "Brand#NewPass2"
+    Then the authentication is throttled for at least 60 seconds
+
+  Scenario: The new password must satisfy the password rules
+    When I change my password from "Listing#Pass1" to "short"
+    Then the API call fails with message "password must be at least 12 characters"
+    When I change my password from "Listing#Pass1" to "Listing#Pass1"
+    Then the API call fails with message "new password must differ from the current one"
+
+  Scenario: Personal access tokens cannot change the password
+    When I create a personal access token "ci" with scopes "users:read,users:write"
+    When I change my password using the personal access token
+    Then the API call fails with message "operation requires an interactive session"
@@ -0,0 +1,125 @@
+Feature: Session inventory
+  As a user signed in on several devices
+  I want to see where my account is signed in and end any of those sessions
+  So that a lost laptop or a shared computer does not keep access to my account
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    And requests come from address "203.0.113.7"
+    And logins use the user agent "Firefox on Linux"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I keep this session as "laptop"
+    Given requests come from address "198.51.100.20"
+    And logins use the user agent "Safari on iPhone"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I keep this session as "phone"
+
+  Scenario: Each login is listed with the device it came from
+    When I list my sessions
+    Then I see 2 active sessions
+    And the listed session "laptop" shows user agent "Firefox on Linux", address "203.0.113.7" and method "password"
+    And the listed session "phone" shows user agent "Safari on iPhone", address "198.51.100.20" and method "password"
+    And only the listed session "phone" is marked as current
+
+  Scenario: Revoking another session signs that device out at once
+    When I revoke my session "laptop"
+    Then the API call succeeds
+    When I act as "laptop"
+    Then the access token is rejected with message "session revoked"
+    When I act as "phone"
+    And I list my sessions
+    Then I see 1 active session
+
+  Scenario: Revoking the current session also ends its refresh token
+    When I revoke my current session
+    Then the API call succeeds
+    And the access token is rejected with message "session revoked"
+    When I refresh the session
+    Then the authentication fails with message "invalid refresh token"
+
+  Scenario: Cutting off every token through the revocation list also ends the sessions
+    When every token of "admin@webrust.dev" is cut off by the revocation list
+    And I act as "laptop"
+    Then the access token is rejected with message "session revoked"
+    When I refresh the session
+    Then the authentication fails with message "invalid refresh token"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the access token is accepted
+
+  Scenario: Refreshing keeps the same session
+    When I refresh the session
+    Then the authentication succeeds
+    When I list my sessions
+    Then I see 2 active sessions
+    And only the listed session "phone" is marked as current
+
+  Scenario: Logging out removes the session from the list
+    When I log out
+    And I act as "laptop"
+    And I list my sessions
+    Then I see 1 active session
+    And only the listed session "laptop" is marked as current
+
+  Scenario: Sessions opened with a second factor say so
+    When I enroll in TOTP
+    And I confirm the TOTP enrollment with the current code
+    And I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And I verify the MFA challenge with the next code
+    And I keep this session as "tablet"
+    And I list my sessions
+    Then I see 3 active sessions
+    And the listed session "tablet" shows user agent "Safari on iPhone", address "198.51.100.20" and method "password+totp"
+
+  Scenario: Changing the password leaves only the new session
+    When I change my password from "ChangeMe123!" to "Harbor-Thistle-Comet-17"
+    Then the authentication succeeds
+    When I list my sessions
+    Then I see 1 active session
+
+  Scenario: Activity is recorded at most once a minute
+    Given my current session was last seen 300 seconds ago
+    When I use my access token
+    Then my current session was last seen less than 60 seconds ago
+    Given my current session was last seen 30 seconds ago
+    When I use my access token
+    Then my current session was last seen more than 29 seconds ago
+
+  Scenario: Sessions of other users cannot be revoked as mine
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I keep this session as "carol"
+    And I act as "phone"
+    And I revoke my session "carol"
+    Then the API call fails with message "not found"
+    When I act as "carol"
+    Then the access token is accepted
+
+  Scenario: Admins list and revoke the sessions of a user
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I keep this session as "carol"
+    And I act as "phone"
+    And I list the sessions of "carol@example.com"
+    Then I see 1 active session
+    When I revoke the session "carol" of "carol@example.com"
+    Then the API call succeeds
+    When I act as "carol"
+    Then the access token is rejected with message "session revoked"
+
+  Scenario: Viewers cannot see the sessions of other users
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I list the sessions of "admin@webrust.dev"
+    Then the API call fails with message "permission users:read required"
+
+  Scenario: Admins cannot touch the sessions of a user with more permissions
+    When I act as "phone"
+    And I create the role "support" with permissions "users:read,users:write"
+    And I create the user "agent@webrust.dev" with password "Support#Pass1" and roles "support"
+    And I authenticate with email "agent@webrust.dev" and password "Support#Pass1"
+    And I list the sessions of "admin@webrust.dev"
+    Then the API call fails with message "cannot manage a user holding permission"
+    When I revoke the session "phone" of "admin@webrust.dev"
+    Then the API call fails with message "cannot manage a user holding permission"
+    When I act as "phone"
+    Then the access token is accepted
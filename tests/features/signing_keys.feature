@@ -0,0 +1,36 @@
+Feature: Asymmetric token signing
+  As a downstream service
+  I want to verify access tokens with published public keys
+  So that I never need the signing secret
+
+  Scenario: RS256 tokens carry a kid and verify against the JWKS
+    Given access tokens are signed with "RS256" key "rsa-2025"
+    And an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the access token header names key "rsa-2025"
+    And the access token is accepted
+    And the published JWKS verifies the access token with key "rsa-2025"
+
+  Scenario: ES256 tokens verify against the JWKS
+    Given access tokens are signed with "ES256" key "ec-2025"
+    And an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the access token is accepted
+    And the published JWKS verifies the access token with key "ec-2025"
+
+  Scenario: EdDSA tokens verify against the JWKS
+    Given access tokens are signed with "EdDSA" key "ed-2025"
+    And an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the access token is accepted
+    And the published JWKS verifies the access token with key "ed-2025"
+
+  Scenario: Tokens signed before a key rotation stay valid
+    Given access tokens are signed with "RS256" key "rsa-2025"
+    And an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    And the "RS256" signing key rotates from "rsa-2025" to "rsa-2026"
+    Then the access token is accepted
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Then the access token header names key "rsa-2026"
+    And the published JWKS verifies the access token with key "rsa-2026"
@@ -0,0 +1,58 @@
+Feature: Soft delete and restore of users
+  As an administrator
+  I want deleted users to stay restorable for a while
+  So that an accidental deletion can be undone
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+
+  Scenario: Deleted users cannot sign in and disappear from listings
+    When I delete the user "carol@example.com"
+    Then the API call succeeds
+    When I list users
+    Then the listed users are "admin@webrust.dev"
+    When I list users with query "include_deleted=true"
+    Then the listed users are "carol@example.com,admin@webrust.dev"
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication fails with message "invalid credentials"
+
+  Scenario: Restoring brings the user back
+    When I delete the user "carol@example.com"
+    And I restore the user "carol@example.com"
+    Then the API call succeeds
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    Then the authentication succeeds
+
+  Scenario: Existing sessions stop working once the user is deleted
+    When I authenticate with email "carol@example.com" and password "Listing#Pass1"
+    And I act as "admin@webrust.dev"
+    When I delete the user "carol@example.com"
+    And I act as "carol@example.com"
+    Then the access token is rejected with message "invalid token"
+
+  Scenario: Only users allowed to delete can see or restore deleted users
+    When I delete the user "carol@example.com"
+    Given a user named "Alan Turing" with email "alan@example.org" and roles "viewer"
+    When I authenticate with email "alan@example.org" and password "Listing#Pass1"
+    When I list users with query "include_deleted=true"
+    Then the API call fails with message "permission users:delete required"
+    When I restore the user "carol@example.com"
+    Then the API call fails with message "permission users:delete required"
+
+  Scenario: Purge removes users past the retention period
+    When I delete the user "carol@example.com"
+    And deleted users older than 30 days are purged
+    Then 0 deleted users were purged
+    When deleted users older than 0 days are purged
+    Then 1 deleted user was purged
+    When I restore the user "carol@example.com"
+    Then the API call fails with message "deleted user"
+
+  Scenario: A deleted user's email can be reused, and restoring then conflicts
+    When I delete the user "carol@example.com"
+    And I create the user "carol@example.com" with password "Listing#Pass2" and roles "viewer"
+    Then the API call succeeds
+    When I restore the user "carol@example.com"
+    Then the API call fails with message "user carol@example.com already exists"
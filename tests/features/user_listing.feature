@@ -0,0 +1,59 @@
+Feature: Paginated user listing
+  As an administrator
+  I want to page through users with filters and sorting
+  So that the listing stays fast as the directory grows
+
+  Background:
+    Given an admin account "Bootstrap Admin" with email "admin@webrust.dev" and password "ChangeMe123!"
+    When I authenticate with email "admin@webrust.dev" and password "ChangeMe123!"
+    Given a user named "Carol Danvers" with email "carol@example.com" and roles "viewer"
+    And a user named "Alan Turing" with email "alan@example.org" and roles "viewer"
+    And a user named "Barbara Liskov" with email "barbara@Example.com" and roles "admin"
+
+  Scenario: Newest users come first by default
+    When I list users
+    Then the listed users are "barbara@Example.com,alan@example.org,carol@example.com,admin@webrust.dev"
+    And there are no more users
+
+  Scenario: Cursors walk through every page exactly once
+    When I list users with query "sort=name&limit=2&include_total=true"
+    Then the listed users are "alan@example.org,barbara@Example.com"
+    And the total user count is 4
+    When I request the next page of users
+    Then the listed users are "admin@webrust.dev,carol@example.com"
+    And there are no more users
+
+  Scenario: Descending sort by email
+    When I list users with query "sort=email&order=desc&limit=3"
+    Then the listed users are "carol@example.com,barbara@Example.com,alan@example.org"
+    When I request the next page of users
+    Then the listed users are "admin@webrust.dev"
+    And there are no more users
+
+  Scenario: Filters by role and email domain
+    When I list users with query "role=viewer&sort=email"
+    Then the listed users are "alan@example.org,carol@example.com"
+    When I list users with query "email_domain=EXAMPLE.com&sort=email&include_total=true"
+    Then the listed users are "barbara@Example.com,carol@example.com"
+    And the total user count is 2
+    When I list users with query "created_after=2000-01-01T00:00:00Z&created_before=2001-01-01T00:00:00Z"
+    Then I see 0 users
+
+  Scenario: Invalid paging parameters are rejected
+    When I list users with query "limit=0"
+    Then the API call fails with message "limit must be between 1 and 100"
+    When I list users with query "sort=password"
+    Then the API call fails with message "invalid sort field: password"
+    When I list users with query "cursor=not-a-cursor"
+    Then the API call fails with message "invalid cursor"
+    When I list users with query "sort=name&limit=1"
+    And I request the next page of users with query "sort=email"
+    Then the API call fails with message "cursor does not match the requested sort"
+
+  Scenario: Users without users:read only list themselves
+    When I authenticate with email "alan@example.org" and password "Listing#Pass1"
+    When I list users with query "include_total=true"
+    Then the listed users are "alan@example.org"
+    And the total user count is 1
+    When I list users with query "email_domain=example.com"
+    Then I see 0 users
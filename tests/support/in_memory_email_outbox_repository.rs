@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::email_outbox::OutboundEmail;
+use webrust::domain::mailer::EmailMessage;
+use webrust::domain::repositories::email_outbox_repository::EmailOutboxRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone, Default)]
+pub struct InMemoryEmailOutboxRepository {
+    emails: Arc<RwLock<Vec<OutboundEmail>>>,
+}
+
+impl InMemoryEmailOutboxRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl EmailOutboxRepository for InMemoryEmailOutboxRepository {
+    async fn enqueue(&self, message: &EmailMessage) -> RepositoryResult<OutboundEmail> {
+        let now = Utc::now();
+        let email = OutboundEmail {
+            id: Uuid::new_v4(),
+            recipient: message.to.clone(),
+            subject: message.subject.clone(),
+            body: message.body.clone(),
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            created_at: now,
+            sent_at: None,
+        };
+        self.emails.write().await.push(email.clone());
+        Ok(email)
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        max_attempts: u32,
+        limit: u32,
+    ) -> RepositoryResult<Vec<OutboundEmail>> {
+        let mut emails = self.emails.write().await;
+        let claimed = emails
+            .iter_mut()
+            .filter(|email| {
+                email.sent_at.is_none()
+                    && email.next_attempt_at <= now
+                    && email.attempts < max_attempts
+            })
+            .take(limit as usize)
+            .map(|email| {
+                email.attempts += 1;
+                email.next_attempt_at = lease_until;
+                email.clone()
+            })
+            .collect();
+        Ok(claimed)
+    }
+
+    async fn mark_sent(&self, id: Uuid, sent_at: DateTime<Utc>) -> RepositoryResult<()> {
+        if let Some(email) = self.emails.write().await.iter_mut().find(|e| e.id == id) {
+            email.sent_at = Some(sent_at);
+            email.last_error = None;
+        }
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        if let Some(email) = self.emails.write().await.iter_mut().find(|e| e.id == id) {
+            email.last_error = Some(error.to_string());
+            email.next_attempt_at = retry_at;
+        }
+        Ok(())
+    }
+}
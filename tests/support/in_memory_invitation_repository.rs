@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::invitation::Invitation;
+use webrust::domain::repositories::invitation_repository::InvitationRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+use super::in_memory_user_repository::InMemoryUserRepository;
+
+// Le os convites que o repositorio de usuarios grava ao criar o usuario, como a tabela
+// compartilhada no Postgres.
+#[derive(Clone)]
+pub struct InMemoryInvitationRepository {
+    invitations: Arc<RwLock<HashMap<Uuid, Invitation>>>,
+}
+
+impl InMemoryInvitationRepository {
+    pub fn new(users: &InMemoryUserRepository) -> Self {
+        Self {
+            invitations: users.invitations(),
+        }
+    }
+}
+
+#[async_trait]
+impl InvitationRepository for InMemoryInvitationRepository {
+    async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<Invitation>> {
+        Ok(self.invitations.read().await.get(&id).cloned())
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<Invitation>> {
+        Ok(self
+            .invitations
+            .read()
+            .await
+            .values()
+            .find(|invitation| invitation.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn find_pending(&self) -> RepositoryResult<Vec<Invitation>> {
+        let mut pending: Vec<Invitation> = self
+            .invitations
+            .read()
+            .await
+            .values()
+            .filter(|invitation| invitation.is_pending())
+            .cloned()
+            .collect();
+        pending.sort_by_key(|invitation| (invitation.created_at, invitation.id));
+        Ok(pending)
+    }
+
+    async fn replace_token(
+        &self,
+        id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<Option<Invitation>> {
+        let mut invitations = self.invitations.write().await;
+        match invitations.get_mut(&id) {
+            Some(invitation) if invitation.is_pending() => {
+                invitation.token_hash = token_hash.to_string();
+                invitation.expires_at = expires_at;
+                Ok(Some(invitation.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn accept(&self, id: Uuid, accepted_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        let mut invitations = self.invitations.write().await;
+        match invitations.get_mut(&id) {
+            Some(invitation) if invitation.is_usable(accepted_at) => {
+                invitation.accepted_at = Some(accepted_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn revoke(&self, id: Uuid, revoked_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        let mut invitations = self.invitations.write().await;
+        match invitations.get_mut(&id) {
+            Some(invitation) if invitation.is_pending() => {
+                invitation.revoked_at = Some(revoked_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+
+use webrust::domain::entities::login_throttle::LoginThrottle;
+use webrust::domain::repositories::login_throttle_repository::LoginThrottleRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone, Default)]
+pub struct InMemoryLoginThrottleRepository {
+    throttles: Arc<RwLock<HashMap<String, LoginThrottle>>>,
+}
+
+impl InMemoryLoginThrottleRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LoginThrottleRepository for InMemoryLoginThrottleRepository {
+    async fn find(&self, key_hash: &str) -> RepositoryResult<Option<LoginThrottle>> {
+        Ok(self.throttles.read().await.get(key_hash).cloned())
+    }
+
+    async fn record_failure(
+        &self,
+        key_hash: &str,
+        now: DateTime<Utc>,
+        window_start: DateTime<Utc>,
+    ) -> RepositoryResult<LoginThrottle> {
+        let mut throttles = self.throttles.write().await;
+        let throttle = throttles
+            .entry(key_hash.to_string())
+            .or_insert_with(|| LoginThrottle {
+                key_hash: key_hash.to_string(),
+                failure_count: 0,
+                window_started_at: now,
+                last_failure_at: now,
+                locked_until: None,
+            });
+
+        let last_activity = throttle
+            .locked_until
+            .map_or(throttle.last_failure_at, |until| {
+                until.max(throttle.last_failure_at)
+            });
+        if last_activity < window_start {
+            throttle.failure_count = 0;
+            throttle.window_started_at = now;
+            throttle.locked_until = None;
+        }
+
+        throttle.failure_count += 1;
+        throttle.last_failure_at = now;
+        Ok(throttle.clone())
+    }
+
+    async fn lock(&self, key_hash: &str, until: DateTime<Utc>) -> RepositoryResult<()> {
+        if let Some(throttle) = self.throttles.write().await.get_mut(key_hash) {
+            throttle.locked_until = Some(until);
+        }
+        Ok(())
+    }
+
+    async fn clear(&self, key_hash: &str) -> RepositoryResult<()> {
+        self.throttles.write().await.remove(key_hash);
+        Ok(())
+    }
+
+    async fn purge_stale(&self, before: DateTime<Utc>) -> RepositoryResult<u64> {
+        let mut throttles = self.throttles.write().await;
+        let count = throttles.len();
+        throttles.retain(|_, throttle| {
+            throttle.last_failure_at >= before
+                || throttle.locked_until.is_some_and(|until| until >= before)
+        });
+        Ok((count - throttles.len()) as u64)
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use webrust::domain::mailer::{EmailMessage, Mailer};
+use webrust::shared::error::AppResult;
+
+// Guarda as mensagens entregues para que os cenarios possam ler o link enviado.
+#[derive(Clone, Default)]
+pub struct InMemoryMailer {
+    sent: Arc<RwLock<Vec<EmailMessage>>>,
+}
+
+impl InMemoryMailer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn sent_to(&self, recipient: &str) -> Vec<EmailMessage> {
+        self.sent
+            .read()
+            .await
+            .iter()
+            .filter(|message| message.to == recipient)
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Mailer for InMemoryMailer {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        self.sent.write().await.push(message.clone());
+        Ok(())
+    }
+}
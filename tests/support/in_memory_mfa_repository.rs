@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::mfa::{MfaPolicy, RecoveryCode, TotpCredential};
+use webrust::domain::repositories::mfa_repository::MfaRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+use webrust::domain::value_objects::RoleName;
+
+#[derive(Clone, Default)]
+pub struct InMemoryMfaRepository {
+    credentials: Arc<RwLock<HashMap<Uuid, TotpCredential>>>,
+    recovery_codes: Arc<RwLock<Vec<RecoveryCode>>>,
+    policies: Arc<RwLock<HashMap<String, MfaPolicy>>>,
+}
+
+impl InMemoryMfaRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl MfaRepository for InMemoryMfaRepository {
+    async fn find_totp(&self, user_id: Uuid) -> RepositoryResult<Option<TotpCredential>> {
+        Ok(self.credentials.read().await.get(&user_id).cloned())
+    }
+
+    async fn save_pending_totp(&self, user_id: Uuid, secret: &str) -> RepositoryResult<bool> {
+        let mut credentials = self.credentials.write().await;
+        if credentials
+            .get(&user_id)
+            .is_some_and(|credential| credential.is_confirmed())
+        {
+            return Ok(false);
+        }
+
+        credentials.insert(
+            user_id,
+            TotpCredential {
+                user_id,
+                secret: secret.to_string(),
+                confirmed_at: None,
+                last_used_step: None,
+                created_at: Utc::now(),
+            },
+        );
+        Ok(true)
+    }
+
+    async fn confirm_totp(
+        &self,
+        user_id: Uuid,
+        step: i64,
+        confirmed_at: DateTime<Utc>,
+        recovery_code_hashes: Vec<String>,
+    ) -> RepositoryResult<()> {
+        if let Some(credential) = self.credentials.write().await.get_mut(&user_id) {
+            credential.confirmed_at = Some(confirmed_at);
+            credential.last_used_step = Some(step);
+        }
+
+        let mut recovery_codes = self.recovery_codes.write().await;
+        recovery_codes.retain(|code| code.user_id != user_id);
+        recovery_codes.extend(
+            recovery_code_hashes
+                .into_iter()
+                .map(|code_hash| RecoveryCode {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    code_hash,
+                    used_at: None,
+                }),
+        );
+        Ok(())
+    }
+
+    async fn advance_totp_step(&self, user_id: Uuid, step: i64) -> RepositoryResult<bool> {
+        let mut credentials = self.credentials.write().await;
+        match credentials.get_mut(&user_id) {
+            Some(credential) if credential.last_used_step.is_none_or(|last| last < step) => {
+                credential.last_used_step = Some(step);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn find_unused_recovery_codes(
+        &self,
+        user_id: Uuid,
+    ) -> RepositoryResult<Vec<RecoveryCode>> {
+        Ok(self
+            .recovery_codes
+            .read()
+            .await
+            .iter()
+            .filter(|code| code.user_id == user_id && code.used_at.is_none())
+            .cloned()
+            .collect())
+    }
+
+    async fn mark_recovery_code_used(
+        &self,
+        id: Uuid,
+        used_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        let mut recovery_codes = self.recovery_codes.write().await;
+        match recovery_codes
+            .iter_mut()
+            .find(|code| code.id == id && code.used_at.is_none())
+        {
+            Some(code) => {
+                code.used_at = Some(used_at);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn find_policies(&self) -> RepositoryResult<Vec<MfaPolicy>> {
+        Ok(self.policies.read().await.values().cloned().collect())
+    }
+
+    async fn set_policy(&self, role: &RoleName, required: bool) -> RepositoryResult<MfaPolicy> {
+        let policy = MfaPolicy {
+            role: role.clone(),
+            required,
+            updated_at: Utc::now(),
+        };
+        self.policies
+            .write()
+            .await
+            .insert(role.as_str().to_string(), policy.clone());
+        Ok(policy)
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::oauth::{AuthorizationCode, NewAuthorizationCode};
+use webrust::domain::repositories::oauth_authorization_code_repository::AuthorizationCodeRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone, Default)]
+pub struct InMemoryAuthorizationCodeRepository {
+    codes: Arc<RwLock<HashMap<Uuid, AuthorizationCode>>>,
+}
+
+impl InMemoryAuthorizationCodeRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AuthorizationCodeRepository for InMemoryAuthorizationCodeRepository {
+    async fn create(&self, code: NewAuthorizationCode) -> RepositoryResult<AuthorizationCode> {
+        let record = AuthorizationCode {
+            id: Uuid::new_v4(),
+            code_hash: code.code_hash,
+            client_id: code.client_id,
+            user_id: code.user_id,
+            session_id: code.session_id,
+            redirect_uri: code.redirect_uri,
+            redirect_uri_supplied: code.redirect_uri_supplied,
+            scopes: code.scopes,
+            code_challenge: code.code_challenge,
+            expires_at: code.expires_at,
+            created_at: Utc::now(),
+            consumed_at: None,
+        };
+        self.codes.write().await.insert(record.id, record.clone());
+        Ok(record)
+    }
+
+    async fn find_by_hash(&self, code_hash: &str) -> RepositoryResult<Option<AuthorizationCode>> {
+        Ok(self
+            .codes
+            .read()
+            .await
+            .values()
+            .find(|code| code.code_hash == code_hash)
+            .cloned())
+    }
+
+    async fn mark_consumed(&self, id: Uuid, consumed_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        match self.codes.write().await.get_mut(&id) {
+            Some(code) if code.consumed_at.is_none() => {
+                code.consumed_at = Some(consumed_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn purge_expired(&self, before: DateTime<Utc>) -> RepositoryResult<u64> {
+        let mut codes = self.codes.write().await;
+        let total = codes.len();
+        codes.retain(|_, code| code.expires_at >= before);
+        Ok((total - codes.len()) as u64)
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::oauth::{NewOAuthClient, OAuthClient};
+use webrust::domain::repositories::oauth_client_repository::OAuthClientRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone, Default)]
+pub struct InMemoryOAuthClientRepository {
+    clients: Arc<RwLock<HashMap<Uuid, OAuthClient>>>,
+}
+
+impl InMemoryOAuthClientRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl OAuthClientRepository for InMemoryOAuthClientRepository {
+    async fn create(&self, client: NewOAuthClient) -> RepositoryResult<OAuthClient> {
+        let record = OAuthClient {
+            id: Uuid::new_v4(),
+            name: client.name,
+            client_type: client.client_type,
+            secret_hash: client.secret_hash,
+            redirect_uris: client.redirect_uris,
+            grant_types: client.grant_types,
+            scopes: client.scopes,
+            owner_id: client.owner_id,
+            created_at: Utc::now(),
+            disabled_at: None,
+        };
+        self.clients.write().await.insert(record.id, record.clone());
+        Ok(record)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<OAuthClient>> {
+        Ok(self.clients.read().await.get(&id).cloned())
+    }
+
+    async fn list_active(&self) -> RepositoryResult<Vec<OAuthClient>> {
+        let mut clients: Vec<_> = self
+            .clients
+            .read()
+            .await
+            .values()
+            .filter(|client| client.is_active())
+            .cloned()
+            .collect();
+        clients.sort_by_key(|client| std::cmp::Reverse(client.created_at));
+        Ok(clients)
+    }
+
+    async fn disable(&self, id: Uuid, disabled_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        match self.clients.write().await.get_mut(&id) {
+            Some(client) if client.is_active() => {
+                client.disabled_at = Some(disabled_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
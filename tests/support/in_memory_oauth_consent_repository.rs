@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::oauth::OAuthConsent;
+use webrust::domain::entities::permission::Permission;
+use webrust::domain::repositories::oauth_consent_repository::OAuthConsentRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone, Default)]
+pub struct InMemoryOAuthConsentRepository {
+    consents: Arc<RwLock<HashMap<(Uuid, Uuid), OAuthConsent>>>,
+}
+
+impl InMemoryOAuthConsentRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl OAuthConsentRepository for InMemoryOAuthConsentRepository {
+    async fn find(&self, user_id: Uuid, client_id: Uuid) -> RepositoryResult<Option<OAuthConsent>> {
+        Ok(self
+            .consents
+            .read()
+            .await
+            .get(&(user_id, client_id))
+            .cloned())
+    }
+
+    async fn grant(
+        &self,
+        user_id: Uuid,
+        client_id: Uuid,
+        scopes: &[Permission],
+    ) -> RepositoryResult<OAuthConsent> {
+        let now = Utc::now();
+        let mut consents = self.consents.write().await;
+        let consent = consents
+            .entry((user_id, client_id))
+            .or_insert_with(|| OAuthConsent {
+                user_id,
+                client_id,
+                scopes: Vec::new(),
+                granted_at: now,
+                updated_at: now,
+            });
+        consent.scopes.extend_from_slice(scopes);
+        consent.scopes.sort();
+        consent.scopes.dedup();
+        consent.updated_at = now;
+        Ok(consent.clone())
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<OAuthConsent>> {
+        let mut consents: Vec<_> = self
+            .consents
+            .read()
+            .await
+            .values()
+            .filter(|consent| consent.user_id == user_id)
+            .cloned()
+            .collect();
+        consents.sort_by_key(|consent| std::cmp::Reverse(consent.updated_at));
+        Ok(consents)
+    }
+
+    async fn revoke(&self, user_id: Uuid, client_id: Uuid) -> RepositoryResult<bool> {
+        Ok(self
+            .consents
+            .write()
+            .await
+            .remove(&(user_id, client_id))
+            .is_some())
+    }
+}
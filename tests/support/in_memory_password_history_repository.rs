@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::repositories::password_history_repository::PasswordHistoryRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+use webrust::domain::value_objects::PasswordHash;
+
+// Hashes por usuario, do mais recente para o mais antigo.
+#[derive(Clone, Default)]
+pub struct InMemoryPasswordHistoryRepository {
+    entries: Arc<RwLock<HashMap<Uuid, Vec<PasswordHash>>>>,
+}
+
+impl InMemoryPasswordHistoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl PasswordHistoryRepository for InMemoryPasswordHistoryRepository {
+    async fn record(
+        &self,
+        user_id: Uuid,
+        password_hash: &PasswordHash,
+        keep: usize,
+    ) -> RepositoryResult<()> {
+        let mut entries = self.entries.write().await;
+        let history = entries.entry(user_id).or_default();
+        history.insert(0, password_hash.clone());
+        history.truncate(keep);
+        Ok(())
+    }
+
+    async fn recent(&self, user_id: Uuid, limit: usize) -> RepositoryResult<Vec<PasswordHash>> {
+        Ok(self
+            .entries
+            .read()
+            .await
+            .get(&user_id)
+            .map(|history| history.iter().take(limit).cloned().collect())
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::password_reset::PasswordResetToken;
+use webrust::domain::repositories::password_reset_repository::PasswordResetRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone, Default)]
+pub struct InMemoryPasswordResetRepository {
+    tokens: Arc<RwLock<HashMap<Uuid, PasswordResetToken>>>,
+}
+
+impl InMemoryPasswordResetRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl PasswordResetRepository for InMemoryPasswordResetRepository {
+    async fn replace_for_user(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<PasswordResetToken> {
+        let now = Utc::now();
+        let mut tokens = self.tokens.write().await;
+
+        for token in tokens.values_mut() {
+            if token.user_id == user_id && token.used_at.is_none() {
+                token.used_at = Some(now);
+            }
+        }
+
+        let token = PasswordResetToken {
+            id: Uuid::new_v4(),
+            user_id,
+            token_hash: token_hash.to_string(),
+            expires_at,
+            created_at: now,
+            used_at: None,
+        };
+        tokens.insert(token.id, token.clone());
+        Ok(token)
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<PasswordResetToken>> {
+        Ok(self
+            .tokens
+            .read()
+            .await
+            .values()
+            .find(|token| token.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn consume(&self, id: Uuid, used_at: DateTime<Utc>) -> RepositoryResult<bool> {
+        let mut tokens = self.tokens.write().await;
+        match tokens.get_mut(&id) {
+            Some(token) if token.used_at.is_none() && token.expires_at > used_at => {
+                token.used_at = Some(used_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn purge_expired(&self, before: DateTime<Utc>) -> RepositoryResult<u64> {
+        let mut tokens = self.tokens.write().await;
+        let count = tokens.len();
+        tokens.retain(|_, token| {
+            token.expires_at >= before && token.used_at.is_none_or(|used_at| used_at >= before)
+        });
+        Ok((count - tokens.len()) as u64)
+    }
+}
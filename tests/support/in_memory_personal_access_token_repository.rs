@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::personal_access_token::{
+    NewPersonalAccessToken, PersonalAccessToken,
+};
+use webrust::domain::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+use webrust::shared::error::AppError;
+
+#[derive(Clone, Default)]
+pub struct InMemoryPersonalAccessTokenRepository {
+    tokens: Arc<RwLock<HashMap<Uuid, PersonalAccessToken>>>,
+}
+
+impl InMemoryPersonalAccessTokenRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl PersonalAccessTokenRepository for InMemoryPersonalAccessTokenRepository {
+    async fn create(&self, token: NewPersonalAccessToken) -> RepositoryResult<PersonalAccessToken> {
+        let mut tokens = self.tokens.write().await;
+        if tokens.values().any(|existing| {
+            existing.user_id == token.user_id
+                && existing.name == token.name
+                && existing.revoked_at.is_none()
+        }) {
+            return Err(AppError::Conflict(
+                "duplicate key value violates unique constraint".to_string(),
+            ));
+        }
+
+        let record = PersonalAccessToken {
+            id: Uuid::new_v4(),
+            user_id: token.user_id,
+            name: token.name,
+            token_prefix: token.token_prefix,
+            token_hash: token.token_hash,
+            scopes: token.scopes,
+            expires_at: token.expires_at,
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        tokens.insert(record.id, record.clone());
+        Ok(record)
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<PersonalAccessToken>> {
+        Ok(self
+            .tokens
+            .read()
+            .await
+            .values()
+            .find(|token| token.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<PersonalAccessToken>> {
+        let mut tokens: Vec<_> = self
+            .tokens
+            .read()
+            .await
+            .values()
+            .filter(|token| token.user_id == user_id && token.revoked_at.is_none())
+            .cloned()
+            .collect();
+        tokens.sort_by_key(|token| std::cmp::Reverse(token.created_at));
+        Ok(tokens)
+    }
+
+    async fn revoke(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        let mut tokens = self.tokens.write().await;
+        match tokens.get_mut(&id) {
+            Some(token) if token.user_id == user_id && token.revoked_at.is_none() => {
+                token.revoked_at = Some(revoked_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn record_usage(&self, id: Uuid, used_at: DateTime<Utc>) -> RepositoryResult<()> {
+        if let Some(token) = self.tokens.write().await.get_mut(&id) {
+            if token.last_used_at.is_none_or(|last| last < used_at) {
+                token.last_used_at = Some(used_at);
+            }
+        }
+        Ok(())
+    }
+}
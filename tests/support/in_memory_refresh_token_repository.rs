@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::refresh_token::{NewRefreshToken, RefreshToken};
+use webrust::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone, Default)]
+pub struct InMemoryRefreshTokenRepository {
+    store: Arc<RwLock<HashMap<Uuid, RefreshToken>>>,
+}
+
+impl InMemoryRefreshTokenRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for InMemoryRefreshTokenRepository {
+    async fn create(&self, token: NewRefreshToken) -> RepositoryResult<RefreshToken> {
+        let id = Uuid::new_v4();
+        let stored = RefreshToken::new(
+            id,
+            token.user_id,
+            token.family_id,
+            token.token_hash,
+            token.expires_at,
+            Utc::now(),
+            None,
+            None,
+        );
+
+        let mut store = self.store.write().await;
+        store.insert(id, stored.clone());
+        Ok(stored)
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<RefreshToken>> {
+        let store = self.store.read().await;
+        Ok(store
+            .values()
+            .find(|token| token.token_hash() == token_hash)
+            .cloned())
+    }
+
+    async fn mark_rotated(&self, id: Uuid) -> RepositoryResult<bool> {
+        let mut store = self.store.write().await;
+        match store.get(&id).cloned() {
+            Some(token) if !token.is_rotated() && !token.is_revoked() => {
+                store.insert(id, with_timestamps(&token, Some(Utc::now()), None));
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn revoke_family(&self, family_id: Uuid) -> RepositoryResult<u64> {
+        let mut store = self.store.write().await;
+        let mut revoked = 0;
+        for token in store.values_mut() {
+            if token.family_id() == family_id && !token.is_revoked() {
+                *token = with_timestamps(token, token.rotated_at(), Some(Utc::now()));
+                revoked += 1;
+            }
+        }
+        Ok(revoked)
+    }
+
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> RepositoryResult<u64> {
+        let mut store = self.store.write().await;
+        let mut revoked = 0;
+        for token in store.values_mut() {
+            if token.user_id() == user_id && !token.is_revoked() {
+                *token = with_timestamps(token, token.rotated_at(), Some(Utc::now()));
+                revoked += 1;
+            }
+        }
+        Ok(revoked)
+    }
+}
+
+fn with_timestamps(
+    token: &RefreshToken,
+    rotated_at: Option<chrono::DateTime<Utc>>,
+    revoked_at: Option<chrono::DateTime<Utc>>,
+) -> RefreshToken {
+    RefreshToken::new(
+        token.id(),
+        token.user_id(),
+        token.family_id(),
+        token.token_hash().to_string(),
+        token.expires_at(),
+        token.created_at(),
+        rotated_at.or(token.rotated_at()),
+        revoked_at.or(token.revoked_at()),
+    )
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::registration::Registration;
+use webrust::domain::repositories::registration_repository::RegistrationRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+use super::in_memory_user_repository::InMemoryUserRepository;
+
+// Le os pedidos que o repositorio de usuarios grava ao criar o usuario, como a tabela
+// compartilhada no Postgres.
+#[derive(Clone)]
+pub struct InMemoryRegistrationRepository {
+    registrations: Arc<RwLock<HashMap<Uuid, Registration>>>,
+}
+
+impl InMemoryRegistrationRepository {
+    pub fn new(users: &InMemoryUserRepository) -> Self {
+        Self {
+            registrations: users.registrations(),
+        }
+    }
+
+    async fn review(
+        &self,
+        user_id: Uuid,
+        reviewer: Uuid,
+        approved: bool,
+        at: DateTime<Utc>,
+    ) -> bool {
+        let mut registrations = self.registrations.write().await;
+        match registrations.get_mut(&user_id) {
+            Some(registration) if registration.is_pending() => {
+                registration.reviewed_by = Some(reviewer);
+                if approved {
+                    registration.approved_at = Some(at);
+                } else {
+                    registration.rejected_at = Some(at);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[async_trait]
+impl RegistrationRepository for InMemoryRegistrationRepository {
+    async fn find_by_user(&self, user_id: Uuid) -> RepositoryResult<Option<Registration>> {
+        Ok(self.registrations.read().await.get(&user_id).cloned())
+    }
+
+    async fn find_pending(&self) -> RepositoryResult<Vec<Registration>> {
+        let mut pending: Vec<Registration> = self
+            .registrations
+            .read()
+            .await
+            .values()
+            .filter(|registration| registration.is_pending())
+            .cloned()
+            .collect();
+        pending.sort_by_key(|registration| (registration.created_at, registration.user_id));
+        Ok(pending)
+    }
+
+    async fn approve(
+        &self,
+        user_id: Uuid,
+        reviewer: Uuid,
+        at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        Ok(self.review(user_id, reviewer, true, at).await)
+    }
+
+    async fn reject(
+        &self,
+        user_id: Uuid,
+        reviewer: Uuid,
+        at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        Ok(self.review(user_id, reviewer, false, at).await)
+    }
+}
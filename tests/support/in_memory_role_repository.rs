@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::permission::Permission;
+use webrust::domain::entities::role::{NewRole, Role, UpdateRole, ADMIN_ROLE, VIEWER_ROLE};
+use webrust::domain::repositories::role_repository::RoleRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+use webrust::domain::value_objects::RoleName;
+use webrust::shared::error::AppError;
+
+#[derive(Clone)]
+pub struct InMemoryRoleRepository {
+    roles: Arc<RwLock<BTreeMap<RoleName, Role>>>,
+}
+
+impl InMemoryRoleRepository {
+    // Mesmos papeis semeados pela migration.
+    pub fn new() -> Self {
+        let now = Utc::now();
+        let built_in = |name: &str, permissions: Vec<Permission>| Role {
+            id: Uuid::new_v4(),
+            name: RoleName::parse(name).expect("built-in role name should be valid"),
+            description: None,
+            permissions,
+            built_in: true,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let roles = [
+            built_in(ADMIN_ROLE, Permission::ALL.to_vec()),
+            built_in(VIEWER_ROLE, Vec::new()),
+        ]
+        .into_iter()
+        .map(|role| (role.name.clone(), role))
+        .collect();
+
+        Self {
+            roles: Arc::new(RwLock::new(roles)),
+        }
+    }
+}
+
+impl Default for InMemoryRoleRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RoleRepository for InMemoryRoleRepository {
+    async fn create(&self, role: NewRole) -> RepositoryResult<Role> {
+        let mut roles = self.roles.write().await;
+        if roles.contains_key(&role.name) {
+            return Err(AppError::Conflict(
+                "duplicate key value violates unique constraint".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let record = Role {
+            id: Uuid::new_v4(),
+            name: role.name,
+            description: role.description,
+            permissions: role.permissions,
+            built_in: false,
+            created_at: now,
+            updated_at: now,
+        };
+        roles.insert(record.name.clone(), record.clone());
+        Ok(record)
+    }
+
+    async fn find_all(&self) -> RepositoryResult<Vec<Role>> {
+        Ok(self.roles.read().await.values().cloned().collect())
+    }
+
+    async fn find_by_name(&self, name: &RoleName) -> RepositoryResult<Option<Role>> {
+        Ok(self.roles.read().await.get(name).cloned())
+    }
+
+    async fn find_by_names(&self, names: &[RoleName]) -> RepositoryResult<Vec<Role>> {
+        let roles = self.roles.read().await;
+        Ok(roles
+            .values()
+            .filter(|role| names.contains(&role.name))
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, name: &RoleName, update: UpdateRole) -> RepositoryResult<Option<Role>> {
+        let mut roles = self.roles.write().await;
+        let Some(role) = roles.get_mut(name) else {
+            return Ok(None);
+        };
+
+        if let Some(description) = update.description {
+            role.description = Some(description);
+        }
+        if let Some(permissions) = update.permissions {
+            role.permissions = permissions;
+        }
+        role.updated_at = Utc::now();
+        Ok(Some(role.clone()))
+    }
+
+    async fn delete(&self, name: &RoleName) -> RepositoryResult<bool> {
+        Ok(self.roles.write().await.remove(name).is_some())
+    }
+}
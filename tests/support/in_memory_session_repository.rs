@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::session::{NewSession, Session};
+use webrust::domain::repositories::session_repository::SessionRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone, Default)]
+pub struct InMemorySessionRepository {
+    sessions: Arc<RwLock<HashMap<Uuid, Session>>>,
+}
+
+impl InMemorySessionRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Simula a passagem do tempo desde o ultimo uso registrado.
+    pub async fn set_last_seen(&self, id: Uuid, last_seen_at: DateTime<Utc>) {
+        if let Some(session) = self.sessions.write().await.get_mut(&id) {
+            session.last_seen_at = last_seen_at;
+        }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for InMemorySessionRepository {
+    async fn create(&self, session: NewSession) -> RepositoryResult<Session> {
+        let now = Utc::now();
+        let record = Session {
+            id: session.id,
+            user_id: session.user_id,
+            auth_method: session.auth_method,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            created_at: now,
+            last_seen_at: now,
+            expires_at: session.expires_at,
+            revoked_at: None,
+            grant: session.grant,
+        };
+        self.sessions
+            .write()
+            .await
+            .insert(record.id, record.clone());
+        Ok(record)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<Session>> {
+        Ok(self.sessions.read().await.get(&id).cloned())
+    }
+
+    async fn find_active_by_user(
+        &self,
+        user_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> RepositoryResult<Vec<Session>> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .read()
+            .await
+            .values()
+            .filter(|session| session.user_id == user_id && session.is_active(now))
+            .cloned()
+            .collect();
+        sessions.sort_by_key(|session| std::cmp::Reverse(session.last_seen_at));
+        Ok(sessions)
+    }
+
+    async fn record_activity(&self, id: Uuid, seen_at: DateTime<Utc>) -> RepositoryResult<()> {
+        if let Some(session) = self.sessions.write().await.get_mut(&id) {
+            if session.last_seen_at < seen_at {
+                session.last_seen_at = seen_at;
+            }
+        }
+        Ok(())
+    }
+
+    async fn renew(
+        &self,
+        id: Uuid,
+        seen_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        match self.sessions.write().await.get_mut(&id) {
+            Some(session) if session.revoked_at.is_none() => {
+                session.last_seen_at = session.last_seen_at.max(seen_at);
+                session.expires_at = expires_at;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn revoke(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<bool> {
+        match self.sessions.write().await.get_mut(&id) {
+            Some(session) if session.user_id == user_id && session.revoked_at.is_none() => {
+                session.revoked_at = Some(revoked_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn revoke_all_for_user(
+        &self,
+        user_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<u64> {
+        let mut revoked = 0;
+        for session in self.sessions.write().await.values_mut() {
+            if session.user_id == user_id && session.revoked_at.is_none() {
+                session.revoked_at = Some(revoked_at);
+                revoked += 1;
+            }
+        }
+        Ok(revoked)
+    }
+
+    async fn revoke_all_for_client(
+        &self,
+        client_id: Uuid,
+        user_id: Option<Uuid>,
+        revoked_at: DateTime<Utc>,
+    ) -> RepositoryResult<u64> {
+        let mut revoked = 0;
+        for session in self.sessions.write().await.values_mut() {
+            let matches = session
+                .grant
+                .as_ref()
+                .is_some_and(|grant| grant.client_id == client_id)
+                && user_id.is_none_or(|user_id| session.user_id == user_id);
+            if matches && session.revoked_at.is_none() {
+                session.revoked_at = Some(revoked_at);
+                revoked += 1;
+            }
+        }
+        Ok(revoked)
+    }
+}
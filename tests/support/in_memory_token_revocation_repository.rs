@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use webrust::domain::entities::token_revocation::{
+    RevocationSnapshot, RevokedToken, UserTokenRevocation,
+};
+use webrust::domain::repositories::token_revocation_repository::TokenRevocationRepository;
+use webrust::domain::repositories::user_repository::RepositoryResult;
+
+#[derive(Clone, Default)]
+pub struct InMemoryTokenRevocationRepository {
+    tokens: Arc<RwLock<HashMap<Uuid, RevokedToken>>>,
+    users: Arc<RwLock<HashMap<Uuid, UserTokenRevocation>>>,
+}
+
+impl InMemoryTokenRevocationRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenRevocationRepository for InMemoryTokenRevocationRepository {
+    async fn revoke_token(&self, token: RevokedToken) -> RepositoryResult<()> {
+        let mut tokens = self.tokens.write().await;
+        tokens.entry(token.jti).or_insert(token);
+        Ok(())
+    }
+
+    async fn revoke_user(&self, revocation: UserTokenRevocation) -> RepositoryResult<()> {
+        let mut users = self.users.write().await;
+        let entry = users
+            .entry(revocation.user_id)
+            .or_insert_with(|| revocation.clone());
+        entry.revoked_before = entry.revoked_before.max(revocation.revoked_before);
+        entry.expires_at = entry.expires_at.max(revocation.expires_at);
+        Ok(())
+    }
+
+    async fn find_active(&self, now: DateTime<Utc>) -> RepositoryResult<RevocationSnapshot> {
+        let tokens = self.tokens.read().await;
+        let users = self.users.read().await;
+        Ok(RevocationSnapshot {
+            tokens: tokens
+                .values()
+                .filter(|token| token.expires_at > now)
+                .cloned()
+                .collect(),
+            users: users
+                .values()
+                .filter(|revocation| revocation.expires_at > now)
+                .cloned()
+                .collect(),
+        })
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> RepositoryResult<u64> {
+        let mut tokens = self.tokens.write().await;
+        let mut users = self.users.write().await;
+        let before = tokens.len() + users.len();
+        tokens.retain(|_, token| token.expires_at > now);
+        users.retain(|_, revocation| revocation.expires_at > now);
+        Ok((before - tokens.len() - users.len()) as u64)
+    }
+}
@@ -2,17 +2,26 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use webrust::domain::entities::user::{NewUser, UpdateUser, User};
+use webrust::domain::entities::invitation::Invitation;
+use webrust::domain::entities::registration::Registration;
+use webrust::domain::entities::user::{
+    NewUser, SortDirection, UpdateUser, User, UserPage, UserQuery, UserSortKey,
+};
 use webrust::domain::repositories::user_repository::{RepositoryResult, UserRepository};
+use webrust::domain::value_objects::{EmailAddress, PasswordHash, RoleName};
 use webrust::shared::error::AppError;
 
 #[derive(Clone, Default)]
 pub struct InMemoryUserRepository {
     store: Arc<RwLock<HashMap<Uuid, User>>>,
+    // Pedidos de autocadastro, gravados junto com o usuario como no Postgres.
+    registrations: Arc<RwLock<HashMap<Uuid, Registration>>>,
+    // Convites, gravados do mesmo jeito.
+    invitations: Arc<RwLock<HashMap<Uuid, Invitation>>>,
 }
 
 impl InMemoryUserRepository {
@@ -20,12 +29,30 @@ impl InMemoryUserRepository {
         Self::default()
     }
 
-    async fn email_exists(&self, email: &str, ignore_id: Option<Uuid>) -> bool {
+    pub fn registrations(&self) -> Arc<RwLock<HashMap<Uuid, Registration>>> {
+        self.registrations.clone()
+    }
+
+    pub fn invitations(&self) -> Arc<RwLock<HashMap<Uuid, Invitation>>> {
+        self.invitations.clone()
+    }
+
+    // Simula uma senha definida ha tempos, para exercitar a validade maxima.
+    pub async fn backdate_password_change(&self, id: Uuid, by: Duration) {
+        let mut store = self.store.write().await;
+        if let Some(user) = store.get_mut(&id) {
+            let changed_at = user.password_changed_at() - by;
+            *user = user.clone().with_password_changed_at(changed_at);
+        }
+    }
+
+    // Mesma regra do indice unico do Postgres: forma canonica, apenas entre nao excluidos.
+    async fn email_exists(&self, email: &EmailAddress, ignore_id: Option<Uuid>) -> bool {
         let store = self.store.read().await;
         store
             .values()
-            .filter(|user| Some(user.id()) != ignore_id)
-            .any(|user| user.email().as_str().eq_ignore_ascii_case(email))
+            .filter(|user| Some(user.id()) != ignore_id && !user.is_deleted())
+            .any(|user| user.email() == email)
     }
 }
 
@@ -38,13 +65,16 @@ impl UserRepository for InMemoryUserRepository {
             id,
             new_user.name().clone(),
             new_user.email().clone(),
-            new_user.role(),
+            new_user.roles().to_vec(),
             new_user.password_hash().clone(),
+            1,
             now,
             now,
-        );
+            None,
+        )
+        .with_status(new_user.status(), None);
 
-        if self.email_exists(user.email().as_str(), None).await {
+        if self.email_exists(user.email(), None).await {
             return Err(AppError::Conflict(format!(
                 "user {} already exists",
                 user.email().as_str()
@@ -53,33 +83,100 @@ impl UserRepository for InMemoryUserRepository {
 
         let mut store = self.store.write().await;
         store.insert(id, user.clone());
+        if new_user.has_registration() {
+            self.registrations.write().await.insert(
+                id,
+                Registration {
+                    user_id: id,
+                    email: user.email().as_str().to_string(),
+                    name: user.name().as_str().to_string(),
+                    created_at: now,
+                    reviewed_by: None,
+                    approved_at: None,
+                    rejected_at: None,
+                },
+            );
+        }
+        if let Some(invitation) = new_user.invitation() {
+            let invitation = Invitation {
+                id: Uuid::new_v4(),
+                user_id: id,
+                email: user.email().as_str().to_string(),
+                invited_by: invitation.invited_by,
+                token_hash: invitation.token_hash.clone(),
+                expires_at: invitation.expires_at,
+                created_at: now,
+                accepted_at: None,
+                revoked_at: None,
+            };
+            self.invitations
+                .write()
+                .await
+                .insert(invitation.id, invitation);
+        }
         Ok(user)
     }
 
-    async fn find_all(&self) -> RepositoryResult<Vec<User>> {
+    async fn find_page(&self, query: &UserQuery) -> RepositoryResult<UserPage> {
         let store = self.store.read().await;
-        let mut users: Vec<User> = store.values().cloned().collect();
-        users.sort_by_key(|user| user.created_at());
-        users.reverse();
-        Ok(users)
+        let mut users: Vec<User> = store
+            .values()
+            .filter(|user| query.filter.matches(user))
+            .cloned()
+            .collect();
+        let total = query.include_total.then_some(users.len() as u64);
+
+        let position = |user: &User| (UserSortKey::of(user, query.sort.field), user.id());
+        users.sort_by_key(position);
+        if query.sort.direction == SortDirection::Desc {
+            users.reverse();
+        }
+
+        if let Some(cursor) = &query.after {
+            let last = (cursor.key.clone(), cursor.id);
+            users.retain(|user| match query.sort.direction {
+                SortDirection::Asc => position(user) > last,
+                SortDirection::Desc => position(user) < last,
+            });
+        }
+
+        let has_more = users.len() > query.limit as usize;
+        users.truncate(query.limit as usize);
+
+        Ok(UserPage {
+            users,
+            has_more,
+            total,
+        })
     }
 
     async fn find_by_id(&self, id: Uuid) -> RepositoryResult<Option<User>> {
         let store = self.store.read().await;
-        Ok(store.get(&id).cloned())
+        Ok(store.get(&id).filter(|user| !user.is_deleted()).cloned())
     }
 
-    async fn find_by_email(&self, email: &str) -> RepositoryResult<Option<User>> {
+    async fn find_by_email(&self, email: &EmailAddress) -> RepositoryResult<Option<User>> {
         let store = self.store.read().await;
         Ok(store
             .values()
-            .find(|user| user.email().as_str().eq_ignore_ascii_case(email))
+            .filter(|user| !user.is_deleted())
+            .find(|user| user.email() == email)
             .cloned())
     }
 
-    async fn update(&self, id: Uuid, update: UpdateUser) -> RepositoryResult<User> {
+    async fn find_deleted_by_id(&self, id: Uuid) -> RepositoryResult<Option<User>> {
+        let store = self.store.read().await;
+        Ok(store.get(&id).filter(|user| user.is_deleted()).cloned())
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        update: UpdateUser,
+        expected_versions: Option<Vec<i64>>,
+    ) -> RepositoryResult<User> {
         if let Some(ref email) = update.email {
-            if self.email_exists(email.as_str(), Some(id)).await {
+            if self.email_exists(email, Some(id)).await {
                 return Err(AppError::Conflict(format!(
                     "user {} already exists",
                     email.as_str()
@@ -90,8 +187,10 @@ impl UserRepository for InMemoryUserRepository {
         let mut store = self.store.write().await;
         let existing = store
             .get(&id)
+            .filter(|user| !user.is_deleted())
             .cloned()
             .ok_or_else(|| AppError::NotFound(format!("user {id} not found")))?;
+        ensure_version(&existing, expected_versions)?;
 
         let name = update
             .name
@@ -105,28 +204,157 @@ impl UserRepository for InMemoryUserRepository {
             .password_hash
             .clone()
             .unwrap_or_else(|| existing.password_hash().clone());
-        let role = update.role.clone().unwrap_or_else(|| existing.role());
+        let roles = update
+            .roles
+            .clone()
+            .unwrap_or_else(|| existing.roles().to_vec());
+        let (status, status_reason) = update.status.clone().unwrap_or_else(|| {
+            (
+                existing.status(),
+                existing.status_reason().map(str::to_string),
+            )
+        });
+        let preferences = update
+            .preferences
+            .clone()
+            .unwrap_or_else(|| existing.preferences().clone());
+        let email_verified_at = match update.email_verified_at {
+            Some(at) => Some(at),
+            None if email != *existing.email() => None,
+            None => existing.email_verified_at(),
+        };
+        let pending_email = update
+            .pending_email
+            .clone()
+            .unwrap_or_else(|| existing.pending_email().cloned());
         let updated_at = Utc::now();
+        let password_changed_at = if update.password_hash.is_some() {
+            updated_at
+        } else {
+            existing.password_changed_at()
+        };
 
         let updated = User::new(
             existing.id(),
             name,
             email,
-            role,
+            roles,
             password_hash,
+            existing.version() + 1,
             existing.created_at(),
             updated_at,
-        );
+            None,
+        )
+        .with_status(status, status_reason)
+        .with_preferences(preferences)
+        .with_email_verification(email_verified_at, pending_email)
+        .with_password_changed_at(password_changed_at);
 
         store.insert(id, updated.clone());
         Ok(updated)
     }
 
-    async fn delete(&self, id: Uuid) -> RepositoryResult<()> {
+    async fn replace_password_hash(
+        &self,
+        id: Uuid,
+        current: &PasswordHash,
+        replacement: &PasswordHash,
+    ) -> RepositoryResult<bool> {
         let mut store = self.store.write().await;
-        match store.remove(&id) {
-            Some(_) => Ok(()),
-            None => Err(AppError::NotFound(format!("user {id} not found"))),
+        match store.get_mut(&id) {
+            Some(user) if !user.is_deleted() && user.password_hash() == current => {
+                *user = user.clone().with_password_hash(replacement.clone());
+                Ok(true)
+            }
+            _ => Ok(false),
         }
     }
+
+    async fn delete(&self, id: Uuid, expected_versions: Option<Vec<i64>>) -> RepositoryResult<()> {
+        let mut store = self.store.write().await;
+        let existing = store
+            .get(&id)
+            .filter(|user| !user.is_deleted())
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("user {id} not found")))?;
+        ensure_version(&existing, expected_versions)?;
+
+        let now = Utc::now();
+        store.insert(id, with_deletion(&existing, Some(now), now));
+        Ok(())
+    }
+
+    async fn restore(&self, id: Uuid) -> RepositoryResult<User> {
+        let existing = self
+            .store
+            .read()
+            .await
+            .get(&id)
+            .filter(|user| user.is_deleted())
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("deleted user {id} not found")))?;
+        if self.email_exists(existing.email(), Some(id)).await {
+            return Err(AppError::Conflict(format!(
+                "user {} already exists",
+                existing.email().as_str()
+            )));
+        }
+
+        let mut store = self.store.write().await;
+
+        let restored = with_deletion(&existing, None, Utc::now());
+        store.insert(id, restored.clone());
+        Ok(restored)
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> RepositoryResult<u64> {
+        let mut store = self.store.write().await;
+        let before = store.len();
+        store.retain(|_, user| user.deleted_at().is_none_or(|at| at >= deleted_before));
+        Ok((before - store.len()) as u64)
+    }
+
+    async fn count_with_role(&self, role: &RoleName) -> RepositoryResult<u64> {
+        let store = self.store.read().await;
+        Ok(store
+            .values()
+            .filter(|user| user.roles().contains(role))
+            .count() as u64)
+    }
+}
+
+fn ensure_version(user: &User, expected_versions: Option<Vec<i64>>) -> RepositoryResult<()> {
+    match expected_versions {
+        Some(expected) if !expected.contains(&user.version()) => {
+            let expected: Vec<String> = expected.iter().map(i64::to_string).collect();
+            Err(AppError::PreconditionFailed(format!(
+                "user {} has been modified since version {}",
+                user.id(),
+                expected.join(", ")
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn with_deletion(
+    user: &User,
+    deleted_at: Option<DateTime<Utc>>,
+    updated_at: DateTime<Utc>,
+) -> User {
+    User::new(
+        user.id(),
+        user.name().clone(),
+        user.email().clone(),
+        user.roles().to_vec(),
+        user.password_hash().clone(),
+        user.version() + 1,
+        user.created_at(),
+        updated_at,
+        deleted_at,
+    )
+    .with_status(user.status(), user.status_reason().map(str::to_string))
+    .with_preferences(user.preferences().clone())
+    .with_email_verification(user.email_verified_at(), user.pending_email().cloned())
+    .with_password_changed_at(user.password_changed_at())
 }
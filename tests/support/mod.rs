@@ -1,3 +1,35 @@
+pub mod in_memory_email_outbox_repository;
+pub mod in_memory_invitation_repository;
+pub mod in_memory_login_throttle_repository;
+pub mod in_memory_mailer;
+pub mod in_memory_mfa_repository;
+pub mod in_memory_oauth_authorization_code_repository;
+pub mod in_memory_oauth_client_repository;
+pub mod in_memory_oauth_consent_repository;
+pub mod in_memory_password_history_repository;
+pub mod in_memory_password_reset_repository;
+pub mod in_memory_personal_access_token_repository;
+pub mod in_memory_refresh_token_repository;
+pub mod in_memory_registration_repository;
+pub mod in_memory_role_repository;
+pub mod in_memory_session_repository;
+pub mod in_memory_token_revocation_repository;
 pub mod in_memory_user_repository;
 
+pub use in_memory_email_outbox_repository::InMemoryEmailOutboxRepository;
+pub use in_memory_invitation_repository::InMemoryInvitationRepository;
+pub use in_memory_login_throttle_repository::InMemoryLoginThrottleRepository;
+pub use in_memory_mailer::InMemoryMailer;
+pub use in_memory_mfa_repository::InMemoryMfaRepository;
+pub use in_memory_oauth_authorization_code_repository::InMemoryAuthorizationCodeRepository;
+pub use in_memory_oauth_client_repository::InMemoryOAuthClientRepository;
+pub use in_memory_oauth_consent_repository::InMemoryOAuthConsentRepository;
+pub use in_memory_password_history_repository::InMemoryPasswordHistoryRepository;
+pub use in_memory_password_reset_repository::InMemoryPasswordResetRepository;
+pub use in_memory_personal_access_token_repository::InMemoryPersonalAccessTokenRepository;
+pub use in_memory_refresh_token_repository::InMemoryRefreshTokenRepository;
+pub use in_memory_registration_repository::InMemoryRegistrationRepository;
+pub use in_memory_role_repository::InMemoryRoleRepository;
+pub use in_memory_session_repository::InMemorySessionRepository;
+pub use in_memory_token_revocation_repository::InMemoryTokenRevocationRepository;
 pub use in_memory_user_repository::InMemoryUserRepository;